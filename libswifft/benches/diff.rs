@@ -0,0 +1,65 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::{compute_diff, compute_multiple};
+
+/// Counts the bytes allocated, to compare the memory traffic of the two approaches
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const NUM_BLOCKS: usize = 1024;
+
+/// Hashes everything into a freshly allocated buffer, then compares it against the baseline
+fn hash_then_diff(inputs: &Inputs<NUM_BLOCKS>, baseline: &Outputs<NUM_BLOCKS>) -> Vec<usize> {
+    let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+    compute_multiple(inputs, &mut outputs);
+    (0..NUM_BLOCKS).filter(|&i| outputs.0[i] != baseline.0[i]).collect()
+}
+
+fn allocated_by(f: impl FnOnce()) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    f();
+    ALLOCATED.load(Ordering::Relaxed) - before
+}
+
+fn diff(c: &mut Criterion) {
+    let mut inputs = Box::<Inputs<NUM_BLOCKS>>::default();
+    for (i, byte) in inputs.0.iter_mut().flatten().enumerate() {
+        *byte = (i * 131 % 251) as u8;
+    }
+    let mut baseline = Box::<Outputs<NUM_BLOCKS>>::default();
+    compute_multiple(&inputs, &mut baseline);
+    // 1% of the blocks change between runs
+    for block in inputs.0.iter_mut().step_by(100) {
+        block[0] ^= 0x01;
+    }
+
+    println!("bytes allocated: compute_diff {}, hash_then_diff {}",
+        allocated_by(|| { black_box(compute_diff(&inputs, &baseline)); }),
+        allocated_by(|| { black_box(hash_then_diff(&inputs, &baseline)); }));
+
+    let mut group = c.benchmark_group("diff");
+    group.bench_function("compute_diff/1024", |b| b.iter(|| compute_diff(black_box(&inputs), black_box(&baseline))));
+    group.bench_function("hash_then_diff/1024", |b| b.iter(|| hash_then_diff(black_box(&inputs), black_box(&baseline))));
+    group.finish();
+}
+
+criterion_group!(benches, diff);
+criterion_main!(benches);
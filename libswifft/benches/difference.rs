@@ -0,0 +1,33 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use libswifft::arithmetic::sub;
+use libswifft::buffer::{Input, Output};
+use libswifft::hash::{compute, compute_difference};
+
+/// Hashes both inputs and subtracts their hash values, the baseline that `compute_difference` replaces
+fn compute_then_sub(a: &Input, b: &Input) -> Output {
+    let (mut output, mut subtrahend) = (Output::default(), Output::default());
+    compute(a, &mut output);
+    compute(b, &mut subtrahend);
+    sub(&mut output, &subtrahend);
+    output
+}
+
+fn difference(c: &mut Criterion) {
+    let mut a = Input::default();
+    for (i, byte) in a.0[0].iter_mut().enumerate() {
+        *byte = (i * 131 % 251) as u8;
+    }
+    let mut b = a.clone();
+    for byte in b.0[0].iter_mut().step_by(7) {
+        *byte ^= 0x5a;
+    }
+    assert_eq!(compute_difference(&a, &b), compute_then_sub(&a, &b));
+
+    let mut group = c.benchmark_group("difference");
+    group.bench_function("compute_difference", |bencher| bencher.iter(|| compute_difference(black_box(&a), black_box(&b))));
+    group.bench_function("compute_then_sub", |bencher| bencher.iter(|| compute_then_sub(black_box(&a), black_box(&b))));
+    group.finish();
+}
+
+criterion_group!(benches, difference);
+criterion_main!(benches);
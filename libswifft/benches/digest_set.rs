@@ -0,0 +1,43 @@
+use std::collections::BTreeSet;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use libswifft::buffer::{CompactOutput, Input, Output};
+use libswifft::digest_set::DigestSet;
+use libswifft::hash::{compact_slice, compute_slice};
+
+const NUM_DIGESTS: usize = 1 << 14;
+
+fn digests() -> Vec<CompactOutput> {
+    let inputs: Vec<Input> = (0..NUM_DIGESTS).map(|i| {
+        // every 16th block repeats the one before it, so that there are duplicates to collapse
+        let mut input = Input::default();
+        input.0[0][..8].copy_from_slice(&((i - i % 16 / 15) as u64).to_le_bytes());
+        input
+    }).collect();
+    let mut outputs: Vec<Output> = (0..NUM_DIGESTS).map(|_| Output::default()).collect();
+    compute_slice(&inputs, &mut outputs).unwrap();
+    let mut compact_outputs: Vec<CompactOutput> = (0..NUM_DIGESTS).map(|_| CompactOutput::default()).collect();
+    compact_slice(&outputs, &mut compact_outputs).unwrap();
+    compact_outputs
+}
+
+fn digest_set(c: &mut Criterion) {
+    let digests = digests();
+    let keys: Vec<[u8; 64]> = digests.iter().map(|digest| digest.0[0]).collect();
+    let set = DigestSet::from_unsorted(digests.clone());
+    let tree: BTreeSet<[u8; 64]> = keys.iter().copied().collect();
+
+    let mut group = c.benchmark_group("digest_set");
+    group.bench_function("build/DigestSet", |b| b.iter_batched(
+        || digests.clone(), DigestSet::from_unsorted, BatchSize::LargeInput));
+    group.bench_function("build/BTreeSet", |b| b.iter_batched(
+        || keys.clone(), |keys| keys.into_iter().collect::<BTreeSet<_>>(), BatchSize::LargeInput));
+    group.bench_function("contains/DigestSet", |b| b.iter(
+        || digests.iter().filter(|digest| set.contains(black_box(digest))).count()));
+    group.bench_function("contains/BTreeSet", |b| b.iter(
+        || keys.iter().filter(|key| tree.contains(black_box(*key))).count()));
+    group.finish();
+}
+
+criterion_group!(benches, digest_set);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use libswifft::buffer::Inputs;
+use libswifft::interop::{inputs_to_swifft, swifft_to_inputs};
+
+const NUM_BLOCKS: usize = 1024;
+
+fn interop(c: &mut Criterion) {
+    let mut inputs = Box::<Inputs<NUM_BLOCKS>>::default();
+    for (i, byte) in inputs.0.iter_mut().flatten().enumerate() {
+        *byte = (i * 131 % 251) as u8;
+    }
+    let swifft_inputs = inputs_to_swifft(&inputs);
+
+    let mut output = Box::<Inputs<NUM_BLOCKS>>::default();
+
+    let mut group = c.benchmark_group("interop");
+    group.bench_function("inputs_to_swifft/1024", |b| b.iter(|| inputs_to_swifft(black_box(&inputs))));
+    group.bench_function("swifft_to_inputs/1024", |b| b.iter(|| {
+        swifft_to_inputs(black_box(&swifft_inputs), &mut output).unwrap()
+    }));
+    group.finish();
+}
+
+criterion_group!(benches, interop);
+criterion_main!(benches);
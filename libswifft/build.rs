@@ -0,0 +1,24 @@
+//! Records the widest instruction set that the native library is compiled for, as `LIBSWIFFT_NATIVE_ISA`.
+//!
+//! `libswifft-sys` compiles the native library with `-march=native`, so unless cross compiling this is the widest
+//! instruction set of the build machine; when cross compiling it is not known, so the least that the native library
+//! compiles with, AVX, is assumed
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    let native = std::env::var("HOST").ok() == std::env::var("TARGET").ok();
+    println!("cargo:rustc-env=LIBSWIFFT_NATIVE_ISA={}", if native { detect() } else { "avx" });
+}
+
+fn detect() -> &'static str {
+    #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+    {
+        if std::is_x86_feature_detected!("avx512f") {
+            return "avx512"
+        }
+        if std::is_x86_feature_detected!("avx2") {
+            return "avx2"
+        }
+    }
+    "avx"
+}
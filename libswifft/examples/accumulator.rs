@@ -0,0 +1,92 @@
+//! Keeps a homomorphic accumulator of the lines of a file, inserting and removing lines by adding and subtracting
+//! their SWIFFT hash values, and checks that the order of insertion does not matter and that removal undoes insertion
+//!
+//! Usage: `cargo run -p libswifft --example accumulator -- <file>`
+
+use std::process::ExitCode;
+
+use libswifft::arithmetic::{add, sub};
+use libswifft::buffer::{Input, Output};
+use libswifft::constant::INPUT_BLOCK_SIZE;
+use libswifft::hash::compute;
+
+/// The longest line that fits in an input block after its length
+const MAX_ITEM_LEN: usize = INPUT_BLOCK_SIZE - 1;
+
+/// The sum of the hash values of a multiset of lines
+#[derive(Default)]
+struct Accumulator(Output);
+
+impl Accumulator {
+    fn insert(&mut self, item: &str) {
+        add(&mut self.0, &hash_item(item))
+    }
+
+    fn remove(&mut self, item: &str) {
+        sub(&mut self.0, &hash_item(item))
+    }
+
+    /// Whether the accumulator holds no lines, i.e. its sum is zero modulo 257
+    fn is_empty(&self) -> bool {
+        self.0.is_difference_zero()
+    }
+}
+
+/// Hashes a line as the input block `[len, line...]`, so that lines differing only by trailing zero bytes differ
+fn hash_item(item: &str) -> Output {
+    let mut input = Input::default();
+    input.0[0][0] = item.len() as u8;
+    input.0[0][1..1 + item.len()].copy_from_slice(item.as_bytes());
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    output
+}
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: accumulator <file>");
+        return ExitCode::FAILURE
+    };
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE
+        }
+    };
+    let items: Vec<&str> = contents.lines().collect();
+    if let Some(item) = items.iter().find(|item| item.len() > MAX_ITEM_LEN) {
+        eprintln!("lines must be at most {} bytes, but one has {}", MAX_ITEM_LEN, item.len());
+        return ExitCode::FAILURE
+    }
+
+    let mut accumulator = Accumulator::default();
+    items.iter().for_each(|item| accumulator.insert(item));
+    println!("inserted {} lines: {:x}", items.len(), accumulator.0);
+
+    let mut reversed = Accumulator::default();
+    items.iter().rev().for_each(|item| reversed.insert(item));
+    if reversed.0.0 != accumulator.0.0 {
+        eprintln!("inserting in reverse order gave a different accumulator");
+        return ExitCode::FAILURE
+    }
+    println!("insertion order does not matter");
+
+    let (kept, removed) = items.split_at(items.len() / 2);
+    removed.iter().for_each(|item| accumulator.remove(item));
+    let mut expected = Accumulator::default();
+    kept.iter().for_each(|item| expected.insert(item));
+    if accumulator.0.0 != expected.0.0 {
+        eprintln!("removing lines did not undo inserting them");
+        return ExitCode::FAILURE
+    }
+    println!("removed {} lines: {:x}", removed.len(), accumulator.0);
+
+    kept.iter().for_each(|item| accumulator.remove(item));
+    if !accumulator.is_empty() {
+        eprintln!("removing every line did not empty the accumulator");
+        return ExitCode::FAILURE
+    }
+    println!("removed every line: empty");
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,67 @@
+//! Hashes every block of a file with each backend and path, and checks that they agree
+//!
+//! The native library hashes whole blocks of 256 bytes, both in one batch and block by block in place,
+//! and the pure Rust implementation hashes each half of a block with both its FFT and its naive reference path.
+//! The backends use different parameters, so each prints its own digests, but every path of a backend must agree.
+//! The last block of the file is zero-padded.
+//!
+//! Usage: `cargo run -p libswifft --example cross_check -- <file>`
+
+use std::process::ExitCode;
+
+use libswifft::buffer::{Input, Output};
+use libswifft::constant::INPUT_BLOCK_SIZE;
+use libswifft::hash::{compute_ref, compute_slice};
+use libswifft::interop::inputs_to_swifft;
+use libswifft::view::{InputRef, OutputMut};
+use swifft::hash::{swifft_hash, swifft_hash_const, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE};
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: cross_check <file>");
+        return ExitCode::FAILURE
+    };
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE
+        }
+    };
+    let inputs: Vec<Input> = bytes.chunks(INPUT_BLOCK_SIZE).map(|chunk| {
+        let mut input = Input::default();
+        input.0[0][..chunk.len()].copy_from_slice(chunk);
+        input
+    }).collect();
+
+    let mut batch: Vec<Output> = inputs.iter().map(|_| Output::default()).collect();
+    compute_slice(&inputs, &mut batch).expect("one output per input");
+
+    let mut agree = true;
+    for (i, (input, batched)) in inputs.iter().zip(&batch).enumerate() {
+        // hash the block again in place through a view, as a caller with a foreign aligned buffer would
+        let mut in_place = Output::default();
+        compute_ref(
+            InputRef::new(&input.0[0]).expect("an `Input` is aligned"),
+            OutputMut::new(&mut in_place.0[0]).expect("an `Output` is aligned"),
+        );
+        let native_agrees = in_place.0 == batched.0;
+        println!("block {} native: {:x}", i, batched);
+
+        let halves = inputs_to_swifft(input);
+        for (half, (swifft_input, bytes)) in halves.iter().zip(input.0[0].chunks_exact(SWIFFT_INPUT_BLOCK_SIZE)).enumerate() {
+            let digest = swifft_hash(swifft_input);
+            let rust_agrees = digest == swifft_hash_const(bytes.try_into().unwrap());
+            println!("block {} half {} pure Rust: {}", i, half, digest);
+            agree &= rust_agrees;
+        }
+        agree &= native_agrees;
+    }
+
+    if !agree {
+        eprintln!("the hashing paths disagree");
+        return ExitCode::FAILURE
+    }
+    println!("{} blocks agree across all paths", inputs.len());
+    ExitCode::SUCCESS
+}
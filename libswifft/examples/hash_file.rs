@@ -0,0 +1,36 @@
+//! Hashes a file by streaming it through a [`Hasher`], printing the hex digest
+//!
+//! The digest is that of `hash_bytes` over the whole file, which absorbs the length of the file,
+//! so that files differing only by trailing zero bytes get different digests.
+//!
+//! Usage: `cargo run -p libswifft --example hash_file -- <file>`
+
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::process::ExitCode;
+
+use libswifft::buffer::Digest512;
+use libswifft::hash::Hasher;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: hash_file <file>");
+        return ExitCode::FAILURE
+    };
+    match hash_file(&path) {
+        Ok(digest) => {
+            println!("{:x}  {}", digest, path);
+            ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn hash_file(path: &str) -> io::Result<Digest512> {
+    let mut hasher = Hasher::new();
+    io::copy(&mut BufReader::new(File::open(path)?), &mut hasher)?;
+    Ok(hasher.finalize().compact())
+}
@@ -0,0 +1,61 @@
+//! Builds a Merkle tree over the lines of a file, then proves and verifies the inclusion of one line
+//!
+//! The tree is a [`MerkleTree`], whose leaves are hashed by `hash_bytes` and whose nodes are compressions of
+//! their two children, so lines may be of any length.
+//!
+//! Usage: `cargo run -p libswifft --example merkle -- <file> <line index>`
+
+use std::process::ExitCode;
+
+use libswifft::buffer::Digest512;
+use libswifft::merkle::{MerkleProof, MerkleTree};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [path, index] = &args[..] else {
+        eprintln!("usage: merkle <file> <line index>");
+        return ExitCode::FAILURE
+    };
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{}: {}", path, err);
+            return ExitCode::FAILURE
+        }
+    };
+    let leaves: Vec<&str> = contents.lines().collect();
+    let index = match index.parse::<usize>() {
+        Ok(index) if index < leaves.len() => index,
+        _ => {
+            eprintln!("the line index must be less than {}", leaves.len());
+            return ExitCode::FAILURE
+        }
+    };
+
+    let tree = MerkleTree::build(&leaves);
+    let root = tree.root();
+    let proof = tree.prove(index);
+    println!("leaves: {}", tree.len());
+    println!("root: {:x}", root);
+    println!("proof of line {}: {} siblings", index, proof.siblings().len());
+
+    if !proof.verify(&root, leaves[index].as_bytes()) {
+        eprintln!("the proof of line {} does not verify", index);
+        return ExitCode::FAILURE
+    }
+    println!("proof verified");
+
+    let mut siblings = proof.siblings().to_vec();
+    if let Some(sibling) = siblings.first_mut() {
+        let mut bytes = *sibling.as_bytes();
+        bytes[0] ^= 1;
+        *sibling = Digest512::from_bytes(bytes);
+    }
+    let tampered = MerkleProof::new(proof.index(), proof.num_leaves(), siblings);
+    if tampered.verify(&root, leaves[index].as_bytes()) || proof.verify(&root, b"not a line of the file") {
+        eprintln!("a tampered proof verified");
+        return ExitCode::FAILURE
+    }
+    println!("tampered proofs rejected");
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,80 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Statistical helpers for sanity-checking the distribution of SWIFFT hash values,
+//! and for estimating collisions between compact hash values
+
+use std::collections::HashMap;
+
+use crate::buffer::{CompactOutput, Output};
+use crate::constant::{COMPACT_OUTPUT_BLOCK_SIZE, N, Q};
+
+/// Counts, for each of the [`N`] elements of a SWIFFT hash value,
+/// how many times each value in `Z_{257}` occurs across all `outputs`.
+///
+/// Elements are read as little endian `16`-bit values and reduced modulo [`Q`].
+///
+/// # Arguments
+/// * `outputs` - the hash values of SWIFFT to count the elements of
+pub fn element_histogram(outputs: &[Output]) -> [[u32; Q]; N] {
+    let mut histogram = [[0u32; Q]; N];
+    for output in outputs {
+        for (element, counts) in histogram.iter_mut().enumerate() {
+            let value = u16::from_le_bytes([output.0[0][2*element], output.0[0][2*element + 1]]);
+            counts[value as usize % Q] += 1;
+        }
+    }
+    histogram
+}
+
+/// Computes, for each of the [`N`] elements of a SWIFFT hash value,
+/// the chi-squared statistic of its value counts against the uniform distribution over `Z_{257}`.
+///
+/// Each statistic has `256` degrees of freedom, so values far above `256`
+/// indicate that the corresponding element is biased.
+///
+/// # Arguments
+/// * `histogram` - the per-element value counts, as produced by [`element_histogram`]
+pub fn chi_squared_per_element(histogram: &[[u32; Q]; N]) -> [f64; N] {
+    let mut chi_squared = [0f64; N];
+    for (statistic, counts) in chi_squared.iter_mut().zip(histogram.iter()) {
+        let total: u64 = counts.iter().map(|&count| count as u64).sum();
+        if total == 0 {
+            continue
+        }
+        let expected = total as f64 / Q as f64;
+        *statistic = counts.iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+    }
+    chi_squared
+}
+
+/// Estimates the expected number of colliding pairs among `num_items` compact hash values,
+/// using the birthday bound on the `2^512` compact output space.
+///
+/// # Arguments
+/// * `num_items` - the number of compact hash values stored
+pub fn expected_collisions(num_items: u64) -> f64 {
+    let num_items = num_items as f64;
+    let space = 2f64.powi(8 * COMPACT_OUTPUT_BLOCK_SIZE as i32);
+    num_items * (num_items - 1.0) / 2.0 / space
+}
+
+/// Finds the first pair of equal compact hash values in `iter`,
+/// returning their positions, or `None` if all values are distinct.
+///
+/// Values are consumed one at a time, so the iterator is only read up to the second occurrence.
+///
+/// # Arguments
+/// * `iter` - the compact hash values to search for a duplicate
+pub fn find_duplicate<I: Iterator<Item = CompactOutput>>(iter: I) -> Option<(usize, usize)> {
+    // the size hint is only a hint, so a large or wrong one must not decide how much is allocated up front
+    let mut seen: HashMap<[u8; COMPACT_OUTPUT_BLOCK_SIZE], usize> = HashMap::with_capacity(iter.size_hint().0.min(1 << 16));
+    for (index, output) in iter.enumerate() {
+        if let Some(&first) = seen.get(&output.0[0]) {
+            return Some((first, index))
+        }
+        seen.insert(output.0[0], index);
+    }
+    None
+}
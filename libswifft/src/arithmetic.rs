@@ -1,4 +1,15 @@
 //! Parameters: n=64, m=32, q=257
+//!
+//! The element-wise wrappers never panic, see [`hash`](crate::hash#panics)
+//!
+//! # Aliasing
+//! The borrow rules prevent passing one hash value as both `output` and `operand` of the two-operand wrappers,
+//! so the operations of a hash value with itself have wrappers of their own, e.g. [`add_assign_self`].
+//! These pass the same pointer as both arguments to the native library, which is sound for its addition,
+//! subtraction and multiplication: each reads every vector of elements before writing it, at the same position.
+//! Its `SWIFFT_Set` is a `memcpy`, for which identical pointers are undefined behaviour in C,
+//! so it must never be called with aliasing pointers, e.g. from other FFI code through [`raw`](crate::buffer::raw);
+//! partially overlapping blocks are not supported by any of the operations
 
 use crate::sys::{
     SWIFFT_Set, SWIFFT_SetMultiple, SWIFFT_Add, SWIFFT_AddMultiple, SWIFFT_ConstAdd,
@@ -6,17 +17,30 @@ use crate::sys::{
     SWIFFT_ConstSetMultiple, SWIFFT_ConstSub, SWIFFT_ConstSubMultiple, SWIFFT_Mul,
     SWIFFT_MulMultiple, SWIFFT_Sub, SWIFFT_SubMultiple
 };
-use crate::buffer::{Output, Outputs};
+use std::ops::{Mul, MulAssign};
+
+use swifft::z257::Z257;
+
+use crate::batch::for_each_chunk;
+use crate::buffer::{AlignedBuffer, Output, Outputs};
+use crate::constant::OUTPUT_BLOCK_SIZE;
+use crate::paranoid::{debug_assert_canonical, debug_assert_canonical_operand, debug_assert_ffi_buffer};
+use crate::error::LenMismatch;
 
 /// Sets a SWIFFT hash value to another, element-wise.
 /// 
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to set to
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn set(output: &mut Output, operand: &Output) {
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
+    debug_assert_canonical_operand(operand, "operand");
     unsafe {
         SWIFFT_Set(output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
     }
+    debug_assert_canonical(output);
 }
 
 /// Sets a SWIFFT hash value to another, element-wise, for multiple blocks.
@@ -25,10 +49,15 @@ pub fn set(output: &mut Output, operand: &Output) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to set to
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn set_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &Outputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_SetMultiple(NUM_BLOCKS.try_into().unwrap(), output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
+    debug_assert_canonical_operand(operand, "operand");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_SetMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand.0[first..].as_ptr().cast())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Adds a SWIFFT hash value to another, element-wise.
@@ -36,10 +65,14 @@ pub fn set_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, o
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to add
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn add(output: &mut Output, operand: &Output) {
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
     unsafe {
         SWIFFT_Add(output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
     }
+    debug_assert_canonical(output);
 }
 
 /// Adds a SWIFFT hash value to another, element-wise, for multiple blocks.
@@ -48,10 +81,14 @@ pub fn add(output: &mut Output, operand: &Output) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to add
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn add_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &Outputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_AddMultiple(NUM_BLOCKS.try_into().unwrap(), output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_AddMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand.0[first..].as_ptr().cast())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Subtracts a SWIFFT hash value from another, element-wise.
@@ -59,10 +96,14 @@ pub fn add_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, o
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to subtract
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn sub(output: &mut Output, operand: &Output) {
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
     unsafe {
         SWIFFT_Sub(output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
     }
+    debug_assert_canonical(output);
 }
 /// Subtracts a SWIFFT hash value from another, element-wise, for multiple blocks.
 /// 
@@ -70,10 +111,14 @@ pub fn sub(output: &mut Output, operand: &Output) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to subtract
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn sub_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &Outputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_SubMultiple(NUM_BLOCKS.try_into().unwrap(), output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(operand, "operand");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_SubMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand.0[first..].as_ptr().cast())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Multiplies a SWIFFT hash value from another, element-wise.
@@ -81,22 +126,182 @@ pub fn sub_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, o
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to multiply by
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn mul(output: &mut Output, operand: &Output) {
-    unsafe {
-        SWIFFT_Mul(output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    mul_block(&mut output.0[0], &operand.0[0], &mut Output::default());
+    debug_assert_canonical(output);
 }
 
 /// Multiplies a SWIFFT hash value from another, element-wise, for multiple blocks.
+///
+/// The blocks are multiplied one at a time, each block of `operand` centered in a single scratch block,
+/// so that no copy of the whole batch is made on the stack
 /// 
 /// # Arguments
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the hash value to multiply by
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn mul_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &Outputs<NUM_BLOCKS>) {
+    debug_assert_ffi_buffer(output, "output");
+    let mut scratch = Output::default();
+    for (output, operand) in output.0.iter_mut().zip(operand.0.iter()) {
+        mul_block(output, operand, &mut scratch);
+    }
+    debug_assert_canonical(output);
+}
+
+/// Like [`set_multiple`], but with the number of blocks given at runtime by the lengths of the slices.
+/// 
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operand` - the hash value to set to, per block
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `operand` does not hold exactly one block per block of `output`,
+/// leaving `output` unmodified
+pub fn set_slice(output: &mut [Output], operand: &[Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(output.len(), operand.len())?;
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    debug_assert_ffi_buffer(operand.as_ptr(), "operand");
+    operand.iter().for_each(|operand| debug_assert_canonical_operand(operand, "operand"));
+    for_each_chunk(output.len(), |first, num_blocks| unsafe {
+        SWIFFT_SetMultiple(num_blocks, output[first..].as_mut_ptr().cast(), operand[first..].as_ptr().cast())
+    });
+    Ok(())
+}
+
+/// Like [`add_multiple`], but with the number of blocks given at runtime by the lengths of the slices.
+/// 
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operand` - the hash value to add, per block
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `operand` does not hold exactly one block per block of `output`,
+/// leaving `output` unmodified
+pub fn add_slice(output: &mut [Output], operand: &[Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(output.len(), operand.len())?;
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    debug_assert_ffi_buffer(operand.as_ptr(), "operand");
+    for_each_chunk(output.len(), |first, num_blocks| unsafe {
+        SWIFFT_AddMultiple(num_blocks, output[first..].as_mut_ptr().cast(), operand[first..].as_ptr().cast())
+    });
+    output.iter().for_each(debug_assert_canonical);
+    Ok(())
+}
+
+/// Like [`sub_multiple`], but with the number of blocks given at runtime by the lengths of the slices.
+/// 
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operand` - the hash value to subtract, per block
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `operand` does not hold exactly one block per block of `output`,
+/// leaving `output` unmodified
+pub fn sub_slice(output: &mut [Output], operand: &[Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(output.len(), operand.len())?;
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    debug_assert_ffi_buffer(operand.as_ptr(), "operand");
+    for_each_chunk(output.len(), |first, num_blocks| unsafe {
+        SWIFFT_SubMultiple(num_blocks, output[first..].as_mut_ptr().cast(), operand[first..].as_ptr().cast())
+    });
+    output.iter().for_each(debug_assert_canonical);
+    Ok(())
+}
+
+/// Like [`mul_multiple`], but with the number of blocks given at runtime by the lengths of the slices.
+/// 
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operand` - the hash value to multiply by, per block
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `operand` does not hold exactly one block per block of `output`,
+/// leaving `output` unmodified
+pub fn mul_slice(output: &mut [Output], operand: &[Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(output.len(), operand.len())?;
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    let mut scratch = Output::default();
+    for (output, operand) in output.iter_mut().zip(operand) {
+        mul_block(&mut output.0[0], &operand.0[0], &mut scratch);
+    }
+    output.iter().for_each(debug_assert_canonical);
+    Ok(())
+}
+
+/// Adds a SWIFFT hash value to itself, element-wise, i.e. doubles it.
+///
+/// The same as [`add`] with a copy of `output` as the operand, without the copy
+///
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn add_assign_self(output: &mut Output) {
+    debug_assert_ffi_buffer(output, "output");
+    let ptr = output.0[0].as_mut_ptr();
+    // SAFETY: the native addition reads every vector of elements before writing it, see the module documentation
+    unsafe {
+        SWIFFT_Add(ptr, ptr)
+    }
+    debug_assert_canonical(output);
+}
+
+/// Adds a SWIFFT hash value to itself, element-wise, for multiple blocks, i.e. doubles them.
+///
+/// The same as [`add_multiple`] with a copy of `output` as the operand, without the copy
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn add_assign_self_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>) {
+    debug_assert_ffi_buffer(output, "output");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        // SAFETY: as for `add_assign_self`, block by block
+        let ptr = output.0[first..].as_mut_ptr().cast();
+        SWIFFT_AddMultiple(num_blocks, ptr, ptr)
+    });
+    debug_assert_canonical(output);
+}
+
+/// Multiplies a SWIFFT hash value by itself, element-wise, i.e. squares every element.
+///
+/// The same as [`mul`] with a copy of `output` as the operand, without the copy
+///
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn square_elements(output: &mut Output) {
+    debug_assert_ffi_buffer(output, "output");
+    center_elements(output);
+    let ptr = output.0[0].as_mut_ptr();
+    // SAFETY: the native multiplication reads every vector of elements before writing it, see the module documentation
     unsafe {
-        SWIFFT_MulMultiple(NUM_BLOCKS.try_into().unwrap(), output.0[0].as_mut_ptr(), operand.0[0].as_ptr())
+        SWIFFT_Mul(ptr, ptr)
     }
+    debug_assert_canonical(output);
+}
+
+/// Multiplies a SWIFFT hash value by itself, element-wise, for multiple blocks, i.e. squares every element.
+///
+/// The same as [`mul_multiple`] with a copy of `output` as the operand, without the copy
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn square_elements_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>) {
+    debug_assert_ffi_buffer(output, "output");
+    center_elements(output);
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        // SAFETY: as for `square_elements`, block by block
+        let ptr = output.0[first..].as_mut_ptr().cast();
+        SWIFFT_MulMultiple(num_blocks, ptr, ptr)
+    });
+    debug_assert_canonical(output);
 }
 
 /// Sets a constant value at each SWIFFT hash value element.
@@ -104,10 +309,13 @@ pub fn mul_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, o
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - operand the constant value to set
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_set(output: &mut Output, operand: i16) {
+    debug_assert_ffi_buffer(output, "output");
     unsafe {
         SWIFFT_ConstSet(output.0[0].as_mut_ptr(), operand.rem_euclid(257));
     }
+    debug_assert_canonical(output);
 }
 
 /// Sets a constant value at each SWIFFT hash value element for multiple blocks.
@@ -116,11 +324,14 @@ pub fn const_set(output: &mut Output, operand: i16) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify, per block
 /// * `operand` - the constant value to set, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_set_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) {
-    unsafe {
-        SWIFFT_ConstSetMultiple(NUM_BLOCKS.try_into().unwrap(), 
-            output.0[0].as_mut_ptr(), operand.map(|i| { i.rem_euclid(257) }).as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    let operand = operand.map(|i| { i.rem_euclid(257) });
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ConstSetMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand[first..].as_ptr())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Adds a constant value to each SWIFFT hash value element.
@@ -128,10 +339,13 @@ pub fn const_set_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOC
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the constant value to add
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_add(output: &mut Output, operand: i16) {
+    debug_assert_ffi_buffer(output, "output");
     unsafe {
-        SWIFFT_ConstAdd(output.0[0].as_mut_ptr(), operand)
+        SWIFFT_ConstAdd(output.0[0].as_mut_ptr(), operand.rem_euclid(257))
     }
+    debug_assert_canonical(output);
 }
 
 /// Adds a constant value to each SWIFFT hash value element for multiple blocks.
@@ -140,11 +354,14 @@ pub fn const_add(output: &mut Output, operand: i16) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify, per block
 /// * `operand` - the constant value to add, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_add_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) {
-    unsafe {
-        SWIFFT_ConstAddMultiple(NUM_BLOCKS.try_into().unwrap(), 
-            output.0[0].as_mut_ptr(), operand.map(|i| { i.rem_euclid(257) }).as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    let operand = operand.map(|i| { i.rem_euclid(257) });
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ConstAddMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand[first..].as_ptr())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Subtracts a constant value from each SWIFFT hash value element.
@@ -152,10 +369,13 @@ pub fn const_add_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOC
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the constant value to subtract
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_sub(output: &mut Output, operand: i16) {
+    debug_assert_ffi_buffer(output, "output");
     unsafe {
-        SWIFFT_ConstSub(output.0[0].as_mut_ptr(), operand)
+        SWIFFT_ConstSub(output.0[0].as_mut_ptr(), operand.rem_euclid(257))
     }
+    debug_assert_canonical(output);
 }
 
 /// Subtracts a constant value from each SWIFFT hash value element for multiple blocks.
@@ -164,11 +384,14 @@ pub fn const_sub(output: &mut Output, operand: i16) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify, per block
 /// * `operand` - the constant value to subtract, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_sub_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) {
-    unsafe {
-        SWIFFT_ConstSubMultiple(NUM_BLOCKS.try_into().unwrap(), 
-            output.0[0].as_mut_ptr(), operand.map(|i| { i.rem_euclid(257) }).as_ptr())
-    }
+    debug_assert_ffi_buffer(output, "output");
+    let operand = operand.map(|i| { i.rem_euclid(257) });
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ConstSubMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand[first..].as_ptr())
+    });
+    debug_assert_canonical(output);
 }
 
 /// Multiply a constant value into each SWIFFT hash value element.
@@ -176,10 +399,14 @@ pub fn const_sub_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOC
 /// # Arguments
 /// * `output` - the hash value of SWIFFT to modify
 /// * `operand` - the constant value to multiply by
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_mul(output: &mut Output, operand: i16) {
+    debug_assert_ffi_buffer(output, "output");
+    center_elements(output);
     unsafe {
-        SWIFFT_ConstMul(output.0[0].as_mut_ptr(), operand)
+        SWIFFT_ConstMul(output.0[0].as_mut_ptr(), center(operand))
     }
+    debug_assert_canonical(output);
 }
 
 /// Multiply a constant value into each SWIFFT hash value element for multiple blocks.
@@ -188,9 +415,387 @@ pub fn const_mul(output: &mut Output, operand: i16) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `output` - the hash value of SWIFFT to modify, per block
 /// * `operand` - the constant value to multiply by, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn const_mul_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) {
+    debug_assert_ffi_buffer(output, "output");
+    let operand = operand.map(center);
+    center_elements(output);
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ConstMulMultiple(num_blocks, output.0[first..].as_mut_ptr().cast(), operand[first..].as_ptr())
+    });
+    debug_assert_canonical(output);
+}
+
+/// Like [`const_set_multiple`], but takes the per-block constants as a slice,
+/// checking at runtime that there is exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to set, per block
+pub fn const_set_batch<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: &[i16]) -> Result<(), LenMismatch> {
+    const_set_multiple(output, &slice_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_set_multiple`], but takes the per-block constants from an iterator,
+/// checking at runtime that it yields exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to set, per block
+pub fn const_set_iter<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: impl IntoIterator<Item = i16>) -> Result<(), LenMismatch> {
+    const_set_multiple(output, &collect_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_add_multiple`], but takes the per-block constants as a slice,
+/// checking at runtime that there is exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to add, per block
+pub fn const_add_batch<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: &[i16]) -> Result<(), LenMismatch> {
+    const_add_multiple(output, &slice_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_add_multiple`], but takes the per-block constants from an iterator,
+/// checking at runtime that it yields exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to add, per block
+pub fn const_add_iter<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: impl IntoIterator<Item = i16>) -> Result<(), LenMismatch> {
+    const_add_multiple(output, &collect_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_sub_multiple`], but takes the per-block constants as a slice,
+/// checking at runtime that there is exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to subtract, per block
+pub fn const_sub_batch<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: &[i16]) -> Result<(), LenMismatch> {
+    const_sub_multiple(output, &slice_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_sub_multiple`], but takes the per-block constants from an iterator,
+/// checking at runtime that it yields exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to subtract, per block
+pub fn const_sub_iter<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: impl IntoIterator<Item = i16>) -> Result<(), LenMismatch> {
+    const_sub_multiple(output, &collect_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_mul_multiple`], but takes the per-block constants as a slice,
+/// checking at runtime that there is exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to multiply by, per block
+pub fn const_mul_batch<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: &[i16]) -> Result<(), LenMismatch> {
+    const_mul_multiple(output, &slice_operands(operands)?);
+    Ok(())
+}
+
+/// Like [`const_mul_multiple`], but takes the per-block constants from an iterator,
+/// checking at runtime that it yields exactly one per block.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `operands` - the constant value to multiply by, per block
+pub fn const_mul_iter<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, operands: impl IntoIterator<Item = i16>) -> Result<(), LenMismatch> {
+    const_mul_multiple(output, &collect_operands(operands)?);
+    Ok(())
+}
+
+/// Copies exactly `NUM_BLOCKS` per-block constants into an array
+fn slice_operands<const NUM_BLOCKS: usize>(operands: &[i16]) -> Result<[i16; NUM_BLOCKS], LenMismatch> {
+    operands.try_into().map_err(|_| LenMismatch { expected: NUM_BLOCKS, actual: operands.len() })
+}
+
+/// Collects exactly `NUM_BLOCKS` per-block constants into an array, without consuming more than one surplus constant,
+/// so that an endless iterator is rejected too. The [`LenMismatch`] of a surplus counts only that one constant
+fn collect_operands<const NUM_BLOCKS: usize>(operands: impl IntoIterator<Item = i16>) -> Result<[i16; NUM_BLOCKS], LenMismatch> {
+    let mut operands = operands.into_iter();
+    let mut collected = [0i16; NUM_BLOCKS];
+    for (i, slot) in collected.iter_mut().enumerate() {
+        *slot = operands.next().ok_or(LenMismatch { expected: NUM_BLOCKS, actual: i })?;
+    }
+    if operands.next().is_some() {
+        return Err(LenMismatch { expected: NUM_BLOCKS, actual: NUM_BLOCKS + 1 })
+    }
+    Ok(collected)
+}
+
+/// Scales a SWIFFT hash value by an element of `Z_{257}`.
+/// 
+/// # Arguments
+/// * `output` - the hash value of SWIFFT to modify
+/// * `scalar` - the scalar to multiply by
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn scale(output: &mut Output, scalar: Z257) {
+    const_mul(output, scalar.value() as i16)
+}
+
+/// Scales a SWIFFT hash value by an element of `Z_{257}` for multiple blocks.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash value of SWIFFT to modify, per block
+/// * `scalar` - the scalar to multiply by, per block
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn scale_multiple<const NUM_BLOCKS: usize>(output: &mut Outputs<NUM_BLOCKS>, scalar: &[Z257; NUM_BLOCKS]) {
+    const_mul_multiple(output, &scalar.map(|scalar| { scalar.value() as i16 }))
+}
+
+/// Returns a copy of a SWIFFT hash value, as [`set`] does in place.
+/// 
+/// # Arguments
+/// * `operand` - the hash value to copy
+pub fn set_owned(operand: &Output) -> Output {
+    let mut output = Output::default();
+    set(&mut output, operand);
+    output
+}
+
+/// Returns a copy of multiple SWIFFT hash values, as [`set_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `operand` - the hash values to copy
+pub fn set_multiple_owned<const NUM_BLOCKS: usize>(operand: &Outputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut output = Outputs::default();
+    set_multiple(&mut output, operand);
+    output
+}
+
+/// Adds `b` to `a`, element-wise, returning the result, as [`add`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT on the left
+/// * `b` - the hash value to add
+pub fn add_owned(a: &Output, b: &Output) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    add(&mut output, b);
+    output
+}
+
+/// Adds `b` to `a`, element-wise, for multiple blocks, returning the result, as [`add_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT on the left
+/// * `b` - the hash values to add
+pub fn add_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, b: &Outputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    add_multiple(&mut output, b);
+    output
+}
+
+/// Subtracts `b` from `a`, element-wise, returning the result, as [`sub`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT on the left
+/// * `b` - the hash value to subtract
+pub fn sub_owned(a: &Output, b: &Output) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    sub(&mut output, b);
+    output
+}
+
+/// Subtracts `b` from `a`, element-wise, for multiple blocks, returning the result, as [`sub_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT on the left
+/// * `b` - the hash values to subtract
+pub fn sub_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, b: &Outputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    sub_multiple(&mut output, b);
+    output
+}
+
+/// Multiplies `a` by `b`, element-wise, returning the result, as [`mul`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT on the left
+/// * `b` - the hash value to multiply by
+pub fn mul_owned(a: &Output, b: &Output) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    mul(&mut output, b);
+    output
+}
+
+/// Multiplies `a` by `b`, element-wise, for multiple blocks, returning the result, as [`mul_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT on the left
+/// * `b` - the hash values to multiply by
+pub fn mul_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, b: &Outputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    mul_multiple(&mut output, b);
+    output
+}
+
+/// Returns a hash value with a constant value at each element, as [`const_set`] does in place.
+/// 
+/// # Arguments
+/// * `operand` - the constant value to set
+pub fn const_set_owned(operand: i16) -> Output {
+    let mut output = Output::default();
+    const_set(&mut output, operand);
+    output
+}
+
+/// Returns hash values with a constant value at each element for multiple blocks, as [`const_set_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `operand` - the constant value to set, per block
+pub fn const_set_multiple_owned<const NUM_BLOCKS: usize>(operand: &[i16; NUM_BLOCKS]) -> Outputs<NUM_BLOCKS> {
+    let mut output = Outputs::default();
+    const_set_multiple(&mut output, operand);
+    output
+}
+
+/// Adds a constant value to each element of `a`, returning the result, as [`const_add`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT
+/// * `operand` - the constant value
+pub fn const_add_owned(a: &Output, operand: i16) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    const_add(&mut output, operand);
+    output
+}
+
+/// Adds a constant value to each element of `a` for multiple blocks, returning the result, as [`const_add_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT
+/// * `operand` - the constant value, per block
+pub fn const_add_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    const_add_multiple(&mut output, operand);
+    output
+}
+
+/// Subtracts a constant value from each element of `a`, returning the result, as [`const_sub`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT
+/// * `operand` - the constant value
+pub fn const_sub_owned(a: &Output, operand: i16) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    const_sub(&mut output, operand);
+    output
+}
+
+/// Subtracts a constant value from each element of `a` for multiple blocks, returning the result, as [`const_sub_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT
+/// * `operand` - the constant value, per block
+pub fn const_sub_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    const_sub_multiple(&mut output, operand);
+    output
+}
+
+/// Multiplies each element of `a` by a constant value, returning the result, as [`const_mul`] does in place.
+/// 
+/// # Arguments
+/// * `a` - the hash value of SWIFFT
+/// * `operand` - the constant value
+pub fn const_mul_owned(a: &Output, operand: i16) -> Output {
+    let mut output = AlignedBuffer(a.0);
+    const_mul(&mut output, operand);
+    output
+}
+
+/// Multiplies each element of `a` by a constant value for multiple blocks, returning the result, as [`const_mul_multiple`] does in place.
+/// 
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `a` - the hash values of SWIFFT
+/// * `operand` - the constant value, per block
+pub fn const_mul_multiple_owned<const NUM_BLOCKS: usize>(a: &Outputs<NUM_BLOCKS>, operand: &[i16; NUM_BLOCKS]) -> Outputs<NUM_BLOCKS> {
+    let mut output = AlignedBuffer(a.0);
+    const_mul_multiple(&mut output, operand);
+    output
+}
+
+impl Mul<Z257> for Output {
+    type Output = Output;
+    fn mul(mut self, rhs: Z257) -> Self::Output {
+        scale(&mut self, rhs);
+        self
+    }
+}
+
+impl MulAssign<Z257> for Output {
+    fn mul_assign(&mut self, rhs: Z257) {
+        scale(self, rhs)
+    }
+}
+
+/// Maps a value to its centered representative modulo 257, in `[-128, 128]`
+#[inline]
+fn center(value: i16) -> i16 {
+    let value = value.rem_euclid(257);
+    if value > 128 { value - 257 } else { value }
+}
+
+/// Maps every element of `buffer` to its centered representative modulo 257.
+///
+/// The native library multiplies elements as plain `16`-bit integers before reducing,
+/// which only avoids overflow when both factors are centered.
+#[inline]
+fn center_elements<const NUM_BLOCKS: usize>(buffer: &mut Outputs<NUM_BLOCKS>) {
+    buffer.0.iter_mut().for_each(center_block)
+}
+
+/// Maps every element of a single block to its centered representative modulo 257, as [`center_elements`] does
+#[inline]
+fn center_block(block: &mut [u8; OUTPUT_BLOCK_SIZE]) {
+    for element in block.chunks_exact_mut(2) {
+        let centered = center(i16::from_le_bytes([element[0], element[1]])).to_le_bytes();
+        element.copy_from_slice(&centered);
+    }
+}
+
+/// Multiplies the hash value in `output` by that in `operand`, element-wise, centering both first.
+///
+/// The operand is centered in `scratch` rather than in place, so that batches are multiplied
+/// one block at a time without copying their operands whole
+///
+/// # Arguments
+/// * `output` - a block of a hash value to modify, aligned as the native library requires
+/// * `operand` - the block of the hash value to multiply by
+/// * `scratch` - the aligned block to center a copy of `operand` in, overwritten
+#[inline]
+fn mul_block(output: &mut [u8; OUTPUT_BLOCK_SIZE], operand: &[u8; OUTPUT_BLOCK_SIZE], scratch: &mut Output) {
+    scratch.0[0] = *operand;
+    center_block(&mut scratch.0[0]);
+    center_block(output);
     unsafe {
-        SWIFFT_ConstMulMultiple(NUM_BLOCKS.try_into().unwrap(), 
-            output.0[0].as_mut_ptr(), operand.map(|i| { i.rem_euclid(257) }).as_ptr())
+        SWIFFT_Mul(output.as_mut_ptr(), scratch.0[0].as_ptr())
     }
 }
\ No newline at end of file
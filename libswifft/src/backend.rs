@@ -0,0 +1,160 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! The backend that higher-level components hash blocks with, so that they can be tested against
+//! a misbehaving backend with [`MockBackend`] under the `test-doubles` feature
+
+use std::convert::Infallible;
+
+use crate::buffer::{Input, Output};
+use crate::hash;
+
+/// A provider of the SWIFFT compression function, which higher-level components such as
+/// [`SlidingWindowHasher`](crate::window::SlidingWindowHasher) hash blocks with
+pub trait SwifftBackend {
+    /// The error reported when the backend cannot hash, which is [`Infallible`] for [`NativeBackend`]
+    type Error;
+
+    /// Computes the result of a SWIFFT operation
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit)
+    /// * `output` - the resulting hash value of SWIFFT, of size 128 bytes (1024 bit)
+    ///
+    /// # Errors
+    /// Returns [`Self::Error`] if the backend could not hash `input`
+    fn compute(&self, input: &Input, output: &mut Output) -> Result<(), Self::Error>;
+
+    /// Computes the results of SWIFFT operations over consecutive blocks,
+    /// one block at a time with [`compute`](Self::compute) unless the backend batches them
+    ///
+    /// # Arguments
+    /// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+    /// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+    ///
+    /// # Errors
+    /// Returns [`Self::Error`] if the backend could not hash some block of `input`
+    ///
+    /// # Panics
+    /// If `output` does not hold exactly one block per block of `input`
+    fn compute_slice(&self, input: &[Input], output: &mut [Output]) -> Result<(), Self::Error> {
+        assert_eq!(input.len(), output.len(), "one output block per input block");
+        input.iter().zip(output).try_for_each(|(input, output)| self.compute(input, output))
+    }
+}
+
+/// The native library, through [`hash::compute`], which never fails.
+///
+/// Constructing it directly does not check that the running CPU supports the instruction sets of the native library;
+/// select it with [`IsetSupport::backend`](crate::capabilities::IsetSupport::backend) to check once instead
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NativeBackend;
+
+impl SwifftBackend for NativeBackend {
+    type Error = Infallible;
+
+    #[inline]
+    fn compute(&self, input: &Input, output: &mut Output) -> Result<(), Self::Error> {
+        hash::compute(input, output);
+        Ok(())
+    }
+
+    /// Hashes the blocks with [`hash::compute_slice`], many at a time
+    #[inline]
+    fn compute_slice(&self, input: &[Input], output: &mut [Output]) -> Result<(), Self::Error> {
+        assert_eq!(input.len(), output.len(), "one output block per input block");
+        hash::compute_slice(input, output).unwrap();
+        Ok(())
+    }
+}
+
+impl<B: SwifftBackend + ?Sized> SwifftBackend for &B {
+    type Error = B::Error;
+
+    #[inline]
+    fn compute(&self, input: &Input, output: &mut Output) -> Result<(), Self::Error> {
+        (**self).compute(input, output)
+    }
+
+    #[inline]
+    fn compute_slice(&self, input: &[Input], output: &mut [Output]) -> Result<(), Self::Error> {
+        (**self).compute_slice(input, output)
+    }
+}
+
+/// Unwraps the result of hashing with a backend that never fails, e.g. the [`NativeBackend`]
+#[inline]
+pub(crate) fn infallible<T>(result: Result<T, Infallible>) -> T {
+    match result {
+        Ok(value) => value,
+        Err(never) => match never {},
+    }
+}
+
+#[cfg(feature = "test-doubles")]
+pub use self::mock::{MockBackend, MockBehavior};
+
+#[cfg(feature = "test-doubles")]
+mod mock {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::{NativeBackend, SwifftBackend};
+    use crate::arithmetic::set;
+    use crate::buffer::{Input, Output};
+    use crate::error::BackendUnavailable;
+
+    /// How a [`MockBackend`] responds to each block it is asked to hash
+    #[derive(Debug, Clone)]
+    pub enum MockBehavior {
+        /// Writes the given hash value, whatever the input
+        Fixed(Output),
+        /// Fails with [`BackendUnavailable`]
+        Unavailable,
+        /// Hashes with the [`NativeBackend`] for the given number of blocks, then fails with [`BackendUnavailable`]
+        FailAfter(usize),
+        /// Sleeps for the given duration, then hashes with the [`NativeBackend`]
+        Delay(Duration),
+    }
+
+    /// A test double for the native backend, responding to every block according to its [`MockBehavior`]
+    /// and counting the blocks it was asked to hash
+    #[derive(Debug)]
+    pub struct MockBackend {
+        behavior: MockBehavior,
+        calls: AtomicUsize,
+    }
+
+    impl MockBackend {
+        /// Creates a mock that responds to every block with `behavior`
+        pub fn new(behavior: MockBehavior) -> Self {
+            Self { behavior, calls: AtomicUsize::new(0) }
+        }
+
+        /// The number of blocks the mock was asked to hash so far, including those it failed
+        pub fn calls(&self) -> usize {
+            self.calls.load(Ordering::Relaxed)
+        }
+    }
+
+    impl SwifftBackend for MockBackend {
+        type Error = BackendUnavailable;
+
+        fn compute(&self, input: &Input, output: &mut Output) -> Result<(), Self::Error> {
+            let call = self.calls.fetch_add(1, Ordering::Relaxed);
+            let native = |output: &mut Output| NativeBackend.compute(input, output).map_err(|never| match never {});
+            match &self.behavior {
+                MockBehavior::Fixed(fixed) => {
+                    set(output, fixed);
+                    Ok(())
+                }
+                MockBehavior::Unavailable => Err(BackendUnavailable),
+                MockBehavior::FailAfter(blocks) if call >= *blocks => Err(BackendUnavailable),
+                MockBehavior::FailAfter(_) => native(output),
+                MockBehavior::Delay(duration) => {
+                    std::thread::sleep(*duration);
+                    native(output)
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Splitting of large batches into chunks that the native library can safely process
+
+use std::cell::Cell;
+use std::ffi::c_int;
+
+use crate::constant::MAX_FFI_BLOCKS;
+
+thread_local! {
+    static CHUNK_BLOCKS: Cell<usize> = const { Cell::new(MAX_FFI_BLOCKS) };
+}
+
+/// Returns the maximum number of blocks currently passed to a single FFI call on this thread.
+/// This is [`MAX_FFI_BLOCKS`] unless overridden with [`with_max_ffi_blocks`],
+/// or while the thread-local storage of this thread is being destroyed.
+#[inline]
+pub fn max_ffi_blocks() -> usize {
+    CHUNK_BLOCKS.try_with(Cell::get).unwrap_or(MAX_FFI_BLOCKS)
+}
+
+/// Runs `f` with the maximum number of blocks per FFI call lowered to `max_blocks` on this thread,
+/// restoring the previous value afterwards. Intended for testing chunk boundaries
+/// without allocating millions of blocks.
+///
+/// # Arguments
+/// * `max_blocks` - the chunk size to use, clamped to `1..=MAX_FFI_BLOCKS`
+/// * `f` - the function to run with the overridden chunk size
+pub fn with_max_ffi_blocks<R>(max_blocks: usize, f: impl FnOnce() -> R) -> R {
+    struct Restore(usize);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            CHUNK_BLOCKS.with(|chunk_blocks| chunk_blocks.set(self.0))
+        }
+    }
+
+    let _restore = Restore(CHUNK_BLOCKS.with(|chunk_blocks| {
+        chunk_blocks.replace(max_blocks.clamp(1, MAX_FFI_BLOCKS))
+    }));
+    f()
+}
+
+/// Calls `f(first_block, num_blocks)` for consecutive chunks covering `0..total_blocks`,
+/// each chunk being at most [`max_ffi_blocks`] blocks long
+pub(crate) fn for_each_chunk(total_blocks: usize, mut f: impl FnMut(usize, c_int)) {
+    let chunk_blocks = max_ffi_blocks();
+    let mut first_block = 0;
+    while first_block < total_blocks {
+        let num_blocks = chunk_blocks.min(total_blocks - first_block);
+        f(first_block, num_blocks as c_int);
+        first_block += num_blocks;
+    }
+}
@@ -0,0 +1,233 @@
+//! Hashes files with [`hash_reader_with_backend`], printing and checking digests in the format of `sha256sum`
+//!
+//! Usage:
+//! * `swifft hash [<file>...]` prints the hex digest of every file, or of standard input for `-` or no files
+//! * `swifft check <manifest>` recomputes the digests recorded by `swifft hash` in the manifest,
+//!   `-` for standard input, and fails if any differs or cannot be computed
+//! * `swifft bench [<MiB>]` measures the throughput of hashing, over 64 MiB by default
+//! * `swifft --capabilities` prints the [`capabilities`](libswifft::capabilities()) of this build on a single line
+//!
+//! Files are streamed rather than read whole. While a file of at least 64 MiB is hashed, and standard error is a terminal,
+//! the share of it hashed so far is shown there. A file that cannot be read is reported on standard error,
+//! and the remaining files are still processed, but the exit code is then nonzero.
+//!
+//! Blocks are hashed with the [`NativeBackend`](libswifft::backend::NativeBackend), once the running CPU is known to support its instruction sets;
+//! otherwise every command but `--capabilities` fails. With the `test-doubles` feature, the `SWIFFT_MOCK_BACKEND`
+//! environment variable selects a [`MockBackend`] instead: `unavailable`, or `fail-after=<blocks>`
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, IsTerminal, Read};
+use std::process::ExitCode;
+use std::time::Instant;
+
+#[cfg(feature = "test-doubles")]
+use libswifft::backend::{MockBackend, MockBehavior};
+#[cfg(not(feature = "test-doubles"))]
+use libswifft::backend::NativeBackend;
+use libswifft::backend::SwifftBackend;
+use libswifft::buffer::Digest512;
+use libswifft::capabilities::swifft_runtime_support;
+use libswifft::hash::{hash_bytes_with_backend, hash_reader_with_backend, hash_reader_with_progress};
+
+const USAGE: &str = "\
+usage: swifft <command>
+       swifft --capabilities
+
+commands:
+    hash [<file>...]    print the digest of every file, or of standard input for `-` or no files
+    check <manifest>    verify the digests recorded by `swifft hash`, reading standard input for `-`
+    bench [<MiB>]       measure the throughput of hashing, over 64 MiB by default";
+
+/// The amount of data hashed by `bench` unless given, in MiB
+const DEFAULT_BENCH_MIB: usize = 64;
+
+/// The size from which the progress of hashing a file is shown, in bytes
+const PROGRESS_MIN_LEN: u64 = 64 << 20;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // the capabilities are diagnostics, so they are printed even where the native library cannot run
+    let result = if args.len() == 1 && args[0] == "--capabilities" {
+        println!("{}", libswifft::capabilities());
+        Ok(())
+    } else {
+        match mock_backend() {
+            Ok(Some(backend)) => run(&args, &backend),
+            Ok(None) => swifft_runtime_support().backend()
+                .map_err(|err| format!("swifft: {}", err))
+                .and_then(|backend| run(&args, &backend)),
+            Err(message) => Err(message),
+        }
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Runs the command of `args`, hashing with `backend`
+fn run<B>(args: &[String], backend: &B) -> Result<(), String>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    match args.first().map(String::as_str) {
+        Some("hash") => hash(&args[1..], backend),
+        Some("check") if args.len() == 2 => check(&args[1], backend),
+        Some("bench") if args.len() <= 2 => match args.get(1).map(|mib| mib.parse()) {
+            None => bench(DEFAULT_BENCH_MIB, backend),
+            Some(Ok(mib)) if mib > 0 => bench(mib, backend),
+            Some(_) => Err(format!("invalid size: {}", args[1])),
+        },
+        _ => Err(USAGE.to_string()),
+    }
+}
+
+/// The mock backend selected by `SWIFFT_MOCK_BACKEND`, if any
+#[cfg(feature = "test-doubles")]
+fn mock_backend() -> Result<Option<MockBackend>, String> {
+    let Ok(behavior) = std::env::var("SWIFFT_MOCK_BACKEND") else {
+        return Ok(None)
+    };
+    let behavior = match behavior.split_once('=') {
+        None if behavior == "unavailable" => MockBehavior::Unavailable,
+        Some(("fail-after", blocks)) => MockBehavior::FailAfter(blocks.parse().map_err(|_| format!("invalid block count: {}", blocks))?),
+        _ => return Err(format!("invalid SWIFFT_MOCK_BACKEND: {}", behavior)),
+    };
+    Ok(Some(MockBackend::new(behavior)))
+}
+
+/// No mock backend can be selected without the `test-doubles` feature
+#[cfg(not(feature = "test-doubles"))]
+fn mock_backend() -> Result<Option<NativeBackend>, String> {
+    Ok(None)
+}
+
+/// Prints the digest of every file in turn, carrying on past files that cannot be read
+fn hash<B>(paths: &[String], backend: &B) -> Result<(), String>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    let stdin = ["-".to_string()];
+    let paths = if paths.is_empty() { &stdin[..] } else { paths };
+    let mut unreadable = 0;
+    for path in paths {
+        match hash_path(path, backend) {
+            Ok(digest) => println!("{:x}  {}", digest, path),
+            Err(err) => {
+                eprintln!("swifft: {}: {}", path, err);
+                unreadable += 1
+            }
+        }
+    }
+    match unreadable {
+        0 => Ok(()),
+        _ => Err(format!("swifft: WARNING: {} listed file(s) could not be read", unreadable)),
+    }
+}
+
+/// Verifies every `<hex digest>  <path>` line of the manifest, printing `<path>: OK` or `<path>: FAILED`
+fn check<B>(manifest: &str, backend: &B) -> Result<(), String>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    let manifest = open(manifest).map_err(|err| format!("swifft: {}: {}", manifest, err))?;
+    let (mut mismatched, mut unreadable, mut malformed) = (0, 0, 0);
+    for line in BufReader::new(manifest).lines() {
+        let line = line.map_err(|err| format!("swifft: {}", err))?;
+        let Some((expected, path)) = line.split_once("  ") else {
+            malformed += 1;
+            continue
+        };
+        match hash_path(path, backend) {
+            Ok(digest) if digest.to_hex() == expected.to_ascii_lowercase() => println!("{}: OK", path),
+            Ok(_) => {
+                println!("{}: FAILED", path);
+                mismatched += 1
+            }
+            Err(err) => {
+                eprintln!("swifft: {}: {}", path, err);
+                println!("{}: FAILED open or read", path);
+                unreadable += 1
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if malformed > 0 {
+        warnings.push(format!("swifft: WARNING: {} line(s) are improperly formatted", malformed))
+    }
+    if unreadable > 0 {
+        warnings.push(format!("swifft: WARNING: {} listed file(s) could not be read", unreadable))
+    }
+    if mismatched > 0 {
+        warnings.push(format!("swifft: WARNING: {} computed digest(s) did NOT match", mismatched))
+    }
+    if warnings.is_empty() { Ok(()) } else { Err(warnings.join("\n")) }
+}
+
+/// Hashes `mib` MiB of data in memory, printing the throughput
+fn bench<B>(mib: usize, backend: &B) -> Result<(), String>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    // a buffer can hold at most `isize::MAX` bytes, and a smaller one may still fail to allocate
+    let len = mib.checked_mul(1 << 20)
+        .filter(|&len| isize::try_from(len).is_ok())
+        .ok_or_else(|| format!("invalid size: {} MiB is too large", mib))?;
+    let mut data = Vec::new();
+    data.try_reserve_exact(len).map_err(|_| format!("swifft: cannot allocate {} MiB", mib))?;
+    data.extend((0..len).map(|i| (i * 131 % 251) as u8));
+    let start = Instant::now();
+    let digest = hash_bytes_with_backend(std::hint::black_box(&data), backend)
+        .map_err(|err| format!("swifft: {}", err.into()))?;
+    let seconds = start.elapsed().as_secs_f64();
+    println!("hashed {} MiB in {:.3} s: {:.1} MiB/s", mib, seconds, mib as f64 / seconds);
+    std::hint::black_box(digest);
+    Ok(())
+}
+
+/// Streams the file at `path`, or standard input for `-`, through [`hash_reader_with_backend`],
+/// showing the progress on standard error for a large file
+fn hash_path<B>(path: &str, backend: &B) -> io::Result<Digest512>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn Error + Send + Sync>>,
+{
+    let reader = open(path)?;
+    let len = match path {
+        "-" => None,
+        _ => std::fs::metadata(path).ok().map(|metadata| metadata.len()),
+    };
+    match len {
+        Some(len) if len >= PROGRESS_MIN_LEN && io::stderr().is_terminal() => {
+            let mut shown = None;
+            let digest = hash_reader_with_progress(reader, backend, |hasher, _| {
+                let percent = hasher.position().min(len) * 100 / len;
+                if shown != Some(percent) {
+                    eprint!("\rswifft: {}: {}%", path, percent);
+                    shown = Some(percent)
+                }
+            });
+            // clears the progress line, so that it is not left next to the digest or the error
+            eprint!("\r\x1b[K");
+            digest
+        }
+        _ => hash_reader_with_backend(reader, backend),
+    }
+}
+
+fn open(path: &str) -> io::Result<Box<dyn Read>> {
+    if path == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(File::open(path)?))
+    }
+}
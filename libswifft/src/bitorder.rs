@@ -0,0 +1,14 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Conversion between the least significant bit first order of inputs and most significant bit first data
+
+/// Reverses the order of the bits within every byte,
+/// converting between most significant bit first and least significant bit first order
+///
+/// # Arguments
+/// * `bytes` - the bytes to convert in place
+pub fn reverse_bits_in_bytes(bytes: &mut [u8]) {
+    for byte in bytes {
+        *byte = byte.reverse_bits()
+    }
+}
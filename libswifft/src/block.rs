@@ -0,0 +1,91 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Traits relating the sizes of the buffer types, so that code can be generic over them
+
+use crate::buffer::AlignedBuffer;
+use crate::constant::{COMPACT_OUTPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A buffer of contiguous, equally-sized blocks that the SWIFFT functions operate on.
+///
+/// Implemented by the [`Inputs`](crate::buffer::Inputs), [`Outputs`](crate::buffer::Outputs)
+/// and [`CompactOutputs`](crate::buffer::CompactOutputs) families, and sealed so that
+/// the library can rely on their layout.
+///
+/// ```
+/// use libswifft::block::{Block, Compactable, Hashable};
+/// use libswifft::buffer::Inputs;
+/// use libswifft::hash::{compacted, computed};
+///
+/// fn digest<const N: usize>(input: &Inputs<N>) -> <<Inputs<N> as Hashable>::Hash as Compactable>::Compacted {
+///     compacted(&computed(input))
+/// }
+///
+/// let compact_outputs = digest(&Inputs::<3>::default());
+/// assert_eq!(<Inputs<3> as Block>::BYTE_SIZE, 3 * 256);
+/// assert_eq!(compact_outputs.0.len(), 3);
+/// ```
+///
+/// Types outside the library cannot implement it:
+///
+/// ```compile_fail,E0277
+/// use libswifft::block::Block;
+///
+/// #[derive(Default)]
+/// struct Custom([u8; 32]);
+///
+/// impl Block for Custom {
+///     const BLOCK_SIZE: usize = 32;
+///     const NUM_BLOCKS: usize = 1;
+/// }
+/// ```
+pub trait Block: sealed::Sealed + Default {
+    /// The size of each block in bytes
+    const BLOCK_SIZE: usize;
+    /// The number of blocks in the buffer
+    const NUM_BLOCKS: usize;
+    /// The size of the whole buffer in bytes
+    const BYTE_SIZE: usize = Self::BLOCK_SIZE * Self::NUM_BLOCKS;
+}
+
+/// A block buffer that can be hashed, block by block, into [`Hashable::Hash`]
+pub trait Hashable: Block {
+    /// The buffer of hash values with one block per input block
+    type Hash: Compactable;
+}
+
+/// A block buffer of hash values that can be compacted, block by block, into [`Compactable::Compacted`]
+pub trait Compactable: Block {
+    /// The buffer of compact hash values with one block per hash value
+    type Compacted: Block;
+}
+
+impl<const NUM_BLOCKS: usize> sealed::Sealed for AlignedBuffer<INPUT_BLOCK_SIZE, NUM_BLOCKS> {}
+impl<const NUM_BLOCKS: usize> sealed::Sealed for AlignedBuffer<OUTPUT_BLOCK_SIZE, NUM_BLOCKS> {}
+impl<const NUM_BLOCKS: usize> sealed::Sealed for AlignedBuffer<COMPACT_OUTPUT_BLOCK_SIZE, NUM_BLOCKS> {}
+
+impl<const NUM_BLOCKS: usize> Block for AlignedBuffer<INPUT_BLOCK_SIZE, NUM_BLOCKS> {
+    const BLOCK_SIZE: usize = INPUT_BLOCK_SIZE;
+    const NUM_BLOCKS: usize = NUM_BLOCKS;
+}
+
+impl<const NUM_BLOCKS: usize> Block for AlignedBuffer<OUTPUT_BLOCK_SIZE, NUM_BLOCKS> {
+    const BLOCK_SIZE: usize = OUTPUT_BLOCK_SIZE;
+    const NUM_BLOCKS: usize = NUM_BLOCKS;
+}
+
+impl<const NUM_BLOCKS: usize> Block for AlignedBuffer<COMPACT_OUTPUT_BLOCK_SIZE, NUM_BLOCKS> {
+    const BLOCK_SIZE: usize = COMPACT_OUTPUT_BLOCK_SIZE;
+    const NUM_BLOCKS: usize = NUM_BLOCKS;
+}
+
+impl<const NUM_BLOCKS: usize> Hashable for AlignedBuffer<INPUT_BLOCK_SIZE, NUM_BLOCKS> {
+    type Hash = AlignedBuffer<OUTPUT_BLOCK_SIZE, NUM_BLOCKS>;
+}
+
+impl<const NUM_BLOCKS: usize> Compactable for AlignedBuffer<OUTPUT_BLOCK_SIZE, NUM_BLOCKS> {
+    type Compacted = AlignedBuffer<COMPACT_OUTPUT_BLOCK_SIZE, NUM_BLOCKS>;
+}
@@ -1,13 +1,52 @@
 //! Parameters: n=64, m=32, q=257
 
-use crate::constant::{INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE, COMPACT_OUTPUT_BLOCK_SIZE};
+pub mod heap;
+pub mod layout;
+#[cfg(feature = "rand")]
+mod random;
+pub mod raw;
+#[cfg(feature = "serde")]
+mod serde;
+#[cfg(feature = "zeroize")]
+pub mod secret;
 
+use std::cmp::Ordering;
+use std::fmt::{Debug, Formatter, LowerHex, UpperHex};
+use std::hash::{Hash, Hasher};
+use std::mem::MaybeUninit;
+
+#[cfg(feature = "bitvec")]
+use bitvec::{order::Lsb0, slice::BitSlice, vec::BitVec, view::BitView};
+
+use crate::constant::{BUFFER_ALIGNMENT, INPUT_BLOCK_SIZE, INPUT_SIZE, OUTPUT_BLOCK_SIZE, COMPACT_OUTPUT_BLOCK_SIZE, M, N, Q};
+use crate::bitorder::reverse_bits_in_bytes;
+use crate::error::{BlockIndexOutOfRange, ConversionError, LenMismatch, OutOfRange, StraySignBit, TooLong, TritError};
+use crate::hash::{compact, pack_trits, unpack_trits};
+use self::layout::{convert_layout, Layout};
+
+/// A buffer of `NUM_CHUNKS` chunks of `CHUNK_SIZE` bytes, aligned as the native library requires.
+///
+/// It is [`Clone`] but deliberately not [`Copy`], so that copying a potentially large batch is always explicit.
+///
+/// With the `bytemuck` feature, it is [`Zeroable`](bytemuck::Zeroable), so arenas of blocks can be allocated zeroed
+/// in place, e.g. with `bytemuck::allocation::zeroed_slice_box`. It is not `Pod`, which requires [`Copy`]: byte slices are
+/// cast to blocks with the alignment-checked [`try_cast_input_slice`](crate::view::try_cast_input_slice) instead.
+///
+/// With the `serde` feature, it serializes as contiguous lowercase hex in human-readable formats such as JSON,
+/// and as raw bytes otherwise; deserializing checks the length, and that the elements of hash values are canonical,
+/// and copies into a fresh, aligned buffer
+#[derive(Clone)]
 #[repr(C, align(64))]
 pub struct AlignedBuffer<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize>(pub [[u8; CHUNK_SIZE]; NUM_CHUNKS]);
 
 /// 32 input vectors, each in `Z_2^{64}`,
 /// corresponding to `2048`-bit input size,
 /// where each element in a vector takes `1` bit
+///
+/// Bits are read least significant first: bit `b` of byte `i` (*where bit `0` is the least significant*)
+/// is coefficient `(8i + b) % 64` of input vector `(8i + b) / 64`. This bit order is a stable contract;
+/// data in most significant bit first order can be converted with [`Input::from_msb_first_bytes`]
+/// or [`bitorder::reverse_bits_in_bytes`](crate::bitorder::reverse_bits_in_bytes)
 pub type Input = Inputs<1>;
 
 /// An array of inputs
@@ -44,6 +83,811 @@ impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> AlignedBuffer<CHUNK_SIZE,
     pub fn new(value: u8) -> Self {
         Self([[value; CHUNK_SIZE]; NUM_CHUNKS])
     }
+
+    /// Creates a buffer from the start of its contents, zero padding the rest, e.g. for the last block of a message
+    ///
+    /// # Arguments
+    /// * `data` - the first bytes of the buffer, block after block
+    ///
+    /// # Errors
+    /// Returns [`TooLong`] if `data` is longer than `CHUNK_SIZE * NUM_CHUNKS` bytes
+    pub fn from_slice_padded(data: &[u8]) -> Result<Self, TooLong> {
+        let mut buffer = Self::default();
+        buffer.as_bytes_mut().get_mut(..data.len())
+            .ok_or(TooLong { max: CHUNK_SIZE * NUM_CHUNKS, actual: data.len() })?
+            .copy_from_slice(data);
+        Ok(buffer)
+    }
+
+    /// Overwrites the full contents with `data`, block after block
+    ///
+    /// # Arguments
+    /// * `data` - the new contents
+    ///
+    /// # Errors
+    /// Returns [`LenMismatch`], counting bytes, if `data` is not exactly `CHUNK_SIZE * NUM_CHUNKS` bytes long,
+    /// leaving the buffer as it was
+    pub fn copy_from_slice(&mut self, data: &[u8]) -> Result<(), LenMismatch> {
+        LenMismatch::check(self.len(), data.len())?;
+        self.as_bytes_mut().copy_from_slice(data);
+        Ok(())
+    }
+
+    /// Overwrites chunk `index` with `block`
+    ///
+    /// # Arguments
+    /// * `index` - the index of the chunk to overwrite
+    /// * `block` - its new contents
+    ///
+    /// # Errors
+    /// Returns [`BlockIndexOutOfRange`] if `index` is not less than `NUM_CHUNKS`, leaving the buffer as it was
+    pub fn write_block(&mut self, index: usize, block: &[u8; CHUNK_SIZE]) -> Result<(), BlockIndexOutOfRange> {
+        let chunk = self.0.get_mut(index).ok_or(BlockIndexOutOfRange { index, num_blocks: NUM_CHUNKS })?;
+        *chunk = *block;
+        Ok(())
+    }
+
+    /// Overwrites every byte with `value`
+    pub fn fill(&mut self, value: u8) {
+        self.as_bytes_mut().fill(value)
+    }
+
+    /// Creates a pseudorandom buffer from `seed`, the same on every target and in every version, e.g. for test vectors.
+    ///
+    /// The bytes are those of the xorshift64* generator behind the pseudorandom blocks of
+    /// [`VECTORS_CSV`](crate::testvectors::VECTORS_CSV), one byte per step. It is not cryptographically secure:
+    /// keys and the randomness of commitments must be drawn from a secure generator instead
+    ///
+    /// # Arguments
+    /// * `seed` - the seed. Different seeds give different bytes, but for the one exception of [`fill_from_seed`]
+    pub fn from_seed(seed: u64) -> Self {
+        let mut buffer = Self::default();
+        fill_from_seed(seed, buffer.as_bytes_mut());
+        buffer
+    }
+
+    /// Creates a buffer of uniformly random bytes drawn from `rng`, with the `rand` feature.
+    ///
+    /// The elements of a hash value made this way are not canonical: sample an [`Output`] from
+    /// [`Standard`](rand::distributions::Standard) for a uniformly random canonical one
+    ///
+    /// # Arguments
+    /// * `rng` - the generator to draw the bytes from
+    #[cfg(feature = "rand")]
+    pub fn random(rng: &mut (impl rand::RngCore + ?Sized)) -> Self {
+        let mut buffer = Self::default();
+        rng.fill_bytes(buffer.as_bytes_mut());
+        buffer
+    }
+
+    /// Views the full contents as one contiguous slice of `CHUNK_SIZE * NUM_CHUNKS` bytes
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_flattened()
+    }
+
+    /// Views the full contents as one contiguous mutable slice of `CHUNK_SIZE * NUM_CHUNKS` bytes,
+    /// which keeps the buffer aligned however it is written to
+    pub fn as_bytes_mut(&mut self) -> &mut [u8] {
+        self.0.as_flattened_mut()
+    }
+
+    /// The number of bytes in the buffer, `CHUNK_SIZE * NUM_CHUNKS`
+    pub fn len(&self) -> usize {
+        CHUNK_SIZE * NUM_CHUNKS
+    }
+
+    /// Whether the buffer holds no bytes, i.e. no chunks or empty chunks
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Allocates an uninitialized `AlignedBuffer` on the heap,
+    /// to be filled in by e.g. [`compute_multiple_uninit`](crate::hash::compute_multiple_uninit)
+    pub fn new_boxed_uninit() -> Box<MaybeUninit<Self>> {
+        let layout = std::alloc::Layout::new::<MaybeUninit<Self>>();
+        if layout.size() == 0 {
+            return Box::new(MaybeUninit::uninit())
+        }
+        // SAFETY: `layout` has a non-zero size
+        let buffer = unsafe { std::alloc::alloc(layout) };
+        if buffer.is_null() {
+            std::alloc::handle_alloc_error(layout)
+        }
+        // SAFETY: `buffer` was allocated by the global allocator with the layout of `MaybeUninit<Self>`,
+        // which needs no initialization
+        unsafe { Box::from_raw(buffer.cast()) }
+    }
+
+    /// Allocates a zeroed `AlignedBuffer` on the heap with the allocator `alloc`,
+    /// e.g. an arena for I/O buffers (*nightly only, with the `allocator_api` feature*)
+    ///
+    /// # Arguments
+    /// * `alloc` - the allocator to allocate the buffer with
+    #[cfg(feature = "allocator_api")]
+    pub fn new_boxed_in<A: std::alloc::Allocator>(alloc: A) -> Box<Self, A> {
+        let (buffer, alloc) = Box::into_raw_with_allocator(Box::<Self, A>::new_zeroed_in(alloc));
+        // SAFETY: the buffer consists of bytes only, for which all zeroes are valid
+        unsafe { Box::from_raw_in(buffer.cast(), alloc) }
+    }
+
+    /// Allocates an uninitialized `AlignedBuffer` on the heap with the allocator `alloc`,
+    /// to be filled in like [`new_boxed_uninit`](Self::new_boxed_uninit) (*nightly only, with the `allocator_api` feature*)
+    ///
+    /// # Arguments
+    /// * `alloc` - the allocator to allocate the buffer with
+    #[cfg(feature = "allocator_api")]
+    pub fn new_boxed_uninit_in<A: std::alloc::Allocator>(alloc: A) -> Box<MaybeUninit<Self>, A> {
+        Box::new_uninit_in(alloc)
+    }
+
+    /// Views chunk `index` as a single-chunk buffer, e.g. block `index` of [`Inputs`] as an [`Input`],
+    /// without copying
+    ///
+    /// # Panics
+    /// If `index` is not less than `NUM_CHUNKS`
+    pub fn block(&self, index: usize) -> &AlignedBuffer<CHUNK_SIZE, 1> {
+        &self.as_blocks()[index]
+    }
+
+    /// Views chunk `index` as a mutable single-chunk buffer, without copying
+    ///
+    /// # Panics
+    /// If `index` is not less than `NUM_CHUNKS`
+    pub fn block_mut(&mut self, index: usize) -> &mut AlignedBuffer<CHUNK_SIZE, 1> {
+        &mut self.as_blocks_mut()[index]
+    }
+
+    /// Iterates over the chunks as single-chunk buffers, without copying
+    pub fn iter_blocks(&self) -> std::slice::Iter<'_, AlignedBuffer<CHUNK_SIZE, 1>> {
+        self.as_blocks().iter()
+    }
+
+    /// Iterates over the chunks as mutable single-chunk buffers, without copying
+    pub fn iter_blocks_mut(&mut self) -> std::slice::IterMut<'_, AlignedBuffer<CHUNK_SIZE, 1>> {
+        self.as_blocks_mut().iter_mut()
+    }
+
+    /// Views the chunks as single-chunk buffers
+    fn as_blocks(&self) -> &[AlignedBuffer<CHUNK_SIZE, 1>; NUM_CHUNKS] {
+        const { assert!(size_of::<AlignedBuffer<CHUNK_SIZE, 1>>() == CHUNK_SIZE, "chunks are not whole multiples of the alignment") };
+        // SAFETY: a single-chunk buffer is exactly its `CHUNK_SIZE` bytes, so `CHUNK_SIZE` is a multiple of its alignment,
+        // and every chunk of this aligned buffer starts at an aligned address
+        unsafe { &*(self as *const Self).cast() }
+    }
+
+    /// Views the chunks as mutable single-chunk buffers
+    fn as_blocks_mut(&mut self) -> &mut [AlignedBuffer<CHUNK_SIZE, 1>; NUM_CHUNKS] {
+        const { assert!(size_of::<AlignedBuffer<CHUNK_SIZE, 1>>() == CHUNK_SIZE, "chunks are not whole multiples of the alignment") };
+        // SAFETY: as for `as_blocks`
+        unsafe { &mut *(self as *mut Self).cast() }
+    }
+
+    /// Whether the buffer at `buffer` is aligned to [`BUFFER_ALIGNMENT`] bytes, as the native library requires.
+    ///
+    /// Always `true` for buffers created safely. Takes a raw pointer rather than a reference, so that a pointer
+    /// obtained through an unsafe cast can be checked before it is turned into a reference, which must be aligned
+    ///
+    /// # Arguments
+    /// * `buffer` - the address of the buffer to check
+    pub fn verify_alignment(buffer: *const Self) -> bool {
+        buffer as usize % BUFFER_ALIGNMENT == 0
+    }
+
+    /// Writes the full contents as contiguous lowercase hex into `w`, without allocating
+    ///
+    /// # Arguments
+    /// * `w` - the writer to write into, e.g. a [`Formatter`] or a fixed-size buffer
+    pub fn write_hex_fmt(&self, w: &mut impl core::fmt::Write) -> core::fmt::Result {
+        write_hex_digits(self.0.as_flattened(), w, LOWER_HEX_DIGITS)
+    }
+
+    /// Formats the full contents as contiguous hex with `digits`, or with the chunks separated by spaces
+    /// if the alternate flag is set
+    fn fmt_hex(&self, f: &mut Formatter<'_>, digits: &[u8; 16]) -> std::fmt::Result {
+        if !f.alternate() {
+            return write_hex_digits(self.0.as_flattened(), f, digits)
+        }
+        for (i, chunk) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?
+            }
+            write_hex_digits(chunk, f, digits)?
+        }
+        Ok(())
+    }
+}
+
+impl Input {
+    /// Creates an input from bytes whose bits are in most significant bit first order,
+    /// by reversing the bits of every byte into the least significant bit first order of [`Input`]
+    ///
+    /// # Arguments
+    /// * `bytes` - the input of 256 bytes (2048 bit), most significant bit first
+    pub fn from_msb_first_bytes(bytes: &[u8; INPUT_BLOCK_SIZE]) -> Self {
+        let mut input = Self([*bytes]);
+        reverse_bits_in_bytes(&mut input.0[0]);
+        input
+    }
+
+    /// Creates an input from its 2048 bits, where bit `k` is bit `k % 8` (*least significant first*)
+    /// of byte `k / 8`, i.e. coefficient `k % 64` of input vector `k / 64`, as the native library reads them
+    ///
+    /// # Arguments
+    /// * `bits` - the bits of the input
+    ///
+    /// ```
+    /// use libswifft::buffer::Input;
+    ///
+    /// let mut bits = [false; 2048];
+    /// bits[9] = true;
+    /// let input = Input::from_bits(&bits);
+    /// assert_eq!(input.0[0][1], 0b10);
+    /// assert!(input.get_bit(9));
+    /// ```
+    pub fn from_bits(bits: &[bool; INPUT_SIZE]) -> Self {
+        let mut input = Self::default();
+        for (byte, bits) in input.0[0].iter_mut().zip(bits.chunks_exact(u8::BITS as usize)) {
+            *byte = bits.iter().rev().fold(0, |byte, &bit| byte << 1 | bit as u8);
+        }
+        input
+    }
+
+    /// Creates sign bits from the signs of the 2048 coefficients, numbered as in [`from_bits`](Self::from_bits):
+    /// `true` for a negative coefficient, whose sign bit is `1`, and `false` for a positive one.
+    ///
+    /// A sign bit only counts where the input bit is set; to build both from coefficients in `{-1, 0, 1}`,
+    /// use [`pack_trits`] or [`SignedInput::from_trits`]
+    ///
+    /// # Arguments
+    /// * `signs` - whether each coefficient is negative
+    pub fn from_signs(signs: &[bool; INPUT_SIZE]) -> SignInput {
+        Self::from_bits(signs)
+    }
+
+    /// The 2048 bits of this input, the inverse of [`from_bits`](Self::from_bits)
+    pub fn to_bits(&self) -> [bool; INPUT_SIZE] {
+        let mut bits = [false; INPUT_SIZE];
+        for (bit, value) in bits.iter_mut().zip(self.bits()) {
+            *bit = value;
+        }
+        bits
+    }
+
+    /// Iterates over the 2048 bits of this input, in the order of [`from_bits`](Self::from_bits)
+    pub fn bits(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0[0].iter().flat_map(|&byte| (0..u8::BITS).map(move |bit| byte >> bit & 1 == 1))
+    }
+
+    /// Bit `index` of this input, numbered as in [`from_bits`](Self::from_bits)
+    ///
+    /// # Panics
+    /// If `index` is not less than `2048`
+    pub fn get_bit(&self, index: usize) -> bool {
+        assert!(index < INPUT_SIZE, "bit index {} out of range 0..{}", index, INPUT_SIZE);
+        self.0[0][index / u8::BITS as usize] >> (index % u8::BITS as usize) & 1 == 1
+    }
+
+    /// Sets bit `index` of this input to `value`, numbered as in [`from_bits`](Self::from_bits)
+    ///
+    /// # Panics
+    /// If `index` is not less than `2048`
+    pub fn set_bit(&mut self, index: usize, value: bool) {
+        assert!(index < INPUT_SIZE, "bit index {} out of range 0..{}", index, INPUT_SIZE);
+        let (byte, mask) = (&mut self.0[0][index / u8::BITS as usize], 1u8 << (index % u8::BITS as usize));
+        if value { *byte |= mask } else { *byte &= !mask }
+    }
+
+    /// Creates an input from bytes whose bits are stored in `layout`,
+    /// by converting them into the [`Layout::Planar`] layout of [`Input`]
+    ///
+    /// # Arguments
+    /// * `bytes` - the input of 256 bytes (2048 bit), in `layout`
+    /// * `layout` - the layout of `bytes`
+    pub fn from_layout(bytes: &[u8; INPUT_BLOCK_SIZE], layout: Layout) -> Self {
+        Self([convert_layout(bytes, layout, Layout::Planar)])
+    }
+
+    /// Creates an input from a slice of 2048 bits, where bit `k` of the slice is bit `k % 8` of byte `k / 8`,
+    /// i.e. coefficient `k % 64` of input vector `k / 64`. With the [`Lsb0`] order,
+    /// this is the same bit order as that of the bytes of [`Input`]
+    ///
+    /// # Arguments
+    /// * `bits` - the input of 2048 bits
+    ///
+    /// # Errors
+    /// Returns [`LenMismatch`] if `bits` is not 2048 bits long
+    ///
+    /// ```
+    /// use bitvec::prelude::*;
+    /// use libswifft::buffer::Input;
+    ///
+    /// let mut bits = bitvec![u8, Lsb0; 0; 2048];
+    /// bits.set(9, true);
+    /// let input = Input::from_bitslice(&bits).unwrap();
+    /// assert_eq!(input.0[0][1], 0b10);
+    /// assert_eq!(input.to_bitvec(), bits);
+    /// ```
+    #[cfg(feature = "bitvec")]
+    pub fn from_bitslice(bits: &BitSlice<u8, Lsb0>) -> Result<Self, LenMismatch> {
+        LenMismatch::check(INPUT_BLOCK_SIZE * u8::BITS as usize, bits.len())?;
+        let mut input = Self::default();
+        input.0[0].view_bits_mut::<Lsb0>().copy_from_bitslice(bits);
+        Ok(input)
+    }
+
+    /// The 2048 bits of this input, the inverse of [`from_bitslice`](Self::from_bitslice)
+    #[cfg(feature = "bitvec")]
+    pub fn to_bitvec(&self) -> BitVec<u8, Lsb0> {
+        self.0[0].view_bits::<Lsb0>().to_bitvec()
+    }
+}
+
+impl<const NUM_BLOCKS: usize> Outputs<NUM_BLOCKS> {
+    /// Copies the hash values out into a vector of single hash values
+    pub fn to_vec(&self) -> Vec<Output> {
+        self.iter_blocks().cloned().collect()
+    }
+
+    /// Whether every element of every hash value is canonical,
+    /// i.e. a little endian `16`-bit value in `[0, 256]`
+    pub fn is_canonical(&self) -> bool {
+        self.0.iter()
+            .flat_map(|block| block.chunks_exact(2))
+            .all(|element| (0..Q as i16).contains(&i16::from_le_bytes([element[0], element[1]])))
+    }
+
+    /// Views the hash values as rows of their `64` elements, without copying.
+    ///
+    /// Only available on little endian targets, where the elements in memory are the little endian
+    /// `16`-bit values that the native library writes; elsewhere, use [`Outputs::to_element_matrix`]
+    #[cfg(target_endian = "little")]
+    pub fn as_element_matrix(&self) -> &[[u16; N]; NUM_BLOCKS] {
+        const { assert!(size_of::<[[u8; OUTPUT_BLOCK_SIZE]; NUM_BLOCKS]>() == size_of::<[[u16; N]; NUM_BLOCKS]>()) };
+        // the buffer is aligned to `BUFFER_ALIGNMENT` bytes, which exceeds the alignment of `u16`, so the cast cannot fail
+        bytemuck::cast_ref(&self.0)
+    }
+
+    /// Views the hash values as mutable rows of their `64` elements, without copying.
+    ///
+    /// Only available on little endian targets, as for [`Outputs::as_element_matrix`];
+    /// elsewhere, use [`Outputs::copy_from_element_matrix`]
+    #[cfg(target_endian = "little")]
+    pub fn as_element_matrix_mut(&mut self) -> &mut [[u16; N]; NUM_BLOCKS] {
+        const { assert!(size_of::<[[u8; OUTPUT_BLOCK_SIZE]; NUM_BLOCKS]>() == size_of::<[[u16; N]; NUM_BLOCKS]>()) };
+        bytemuck::cast_mut(&mut self.0)
+    }
+
+    /// Copies the hash values into rows of their `64` elements,
+    /// decoding the little endian elements explicitly, so that it works on every target
+    pub fn to_element_matrix(&self) -> Vec<[u16; N]> {
+        self.0.iter().map(|block| {
+            let mut elements = [0u16; N];
+            for (value, element) in elements.iter_mut().zip(block.chunks_exact(2)) {
+                *value = u16::from_le_bytes([element[0], element[1]]);
+            }
+            elements
+        }).collect()
+    }
+
+    /// Overwrites the hash values with rows of their `64` elements,
+    /// encoding them little endian explicitly, so that it works on every target
+    ///
+    /// # Arguments
+    /// * `elements` - the elements of each hash value
+    pub fn copy_from_element_matrix(&mut self, elements: &[[u16; N]; NUM_BLOCKS]) {
+        for (block, elements) in self.0.iter_mut().zip(elements) {
+            for (element, value) in block.chunks_exact_mut(2).zip(elements) {
+                element.copy_from_slice(&value.to_le_bytes());
+            }
+        }
+    }
+}
+
+impl Output {
+    /// Parses a hash value from its little endian encoding,
+    /// accepting only canonical elements in `[0, 256]`
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded hash value, of size 128 bytes (1024 bit)
+    ///
+    /// # Errors
+    /// Returns [`OutOfRange`] for the first element, read as a `16`-bit signed value, that lies outside `[0, 256]`
+    pub fn from_bytes_canonical(bytes: &[u8; OUTPUT_BLOCK_SIZE]) -> Result<Self, OutOfRange> {
+        check_canonical(bytes)?;
+        Ok(Self([*bytes]))
+    }
+
+    /// Parses a hash value from its little endian encoding,
+    /// reducing every element, read as a `16`-bit signed value, to its canonical representative in `[0, 256]`
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded hash value, of size 128 bytes (1024 bit)
+    pub fn from_bytes_reducing(bytes: &[u8; OUTPUT_BLOCK_SIZE]) -> Self {
+        let mut output = Self::default();
+        for (reduced, element) in output.0[0].chunks_exact_mut(2).zip(bytes.chunks_exact(2)) {
+            let value = i16::from_le_bytes([element[0], element[1]]).rem_euclid(Q as i16);
+            reduced.copy_from_slice(&value.to_le_bytes());
+        }
+        output
+    }
+
+    /// Whether every element is zero modulo [`Q`], e.g. for a difference from
+    /// [`compute_difference`](crate::hash::compute_difference), which is zero exactly when the hash values are equal
+    pub fn is_difference_zero(&self) -> bool {
+        self.0[0].chunks_exact(2)
+            .all(|element| i16::from_le_bytes([element[0], element[1]]).rem_euclid(Q as i16) == 0)
+    }
+
+    /// Converts the hash value into its centered representation,
+    /// where every element is mapped to its representative modulo [`Q`] in `[-128, 128]`:
+    /// `0..=128` are kept as they are, and `129..=256` map to `-128..=-1`, so e.g. `256` becomes `-1`
+    pub fn to_centered(&self) -> [i16; N] {
+        let mut centered = [0i16; N];
+        for (value, element) in centered.iter_mut().zip(self.0[0].chunks_exact(2)) {
+            let residue = i16::from_le_bytes([element[0], element[1]]).rem_euclid(Q as i16);
+            *value = if residue > 128 { residue - Q as i16 } else { residue };
+        }
+        centered
+    }
+
+    /// Reads the `64` coefficients of the hash value, i.e. its elements, as the little endian `16`-bit values
+    /// they are stored as, decoding them explicitly, so that it works on every target and without allocating
+    pub fn to_coefficients(&self) -> [u16; N] {
+        decode_elements(&self.0[0])
+    }
+
+    /// Creates a hash value from its `64` coefficients, encoding them little endian explicitly,
+    /// so that it works on every target.
+    ///
+    /// The coefficients are not reduced, so the hash value is canonical only if they are all in `[0, 256]`,
+    /// see [`Outputs::is_canonical`]
+    ///
+    /// # Arguments
+    /// * `coefficients` - the elements of the hash value
+    pub fn from_coefficients(coefficients: [u16; N]) -> Self {
+        let mut output = Self::default();
+        output.copy_from_element_matrix(&[coefficients]);
+        output
+    }
+
+    /// Views the `64` coefficients of the hash value in place, without copying.
+    ///
+    /// Only available on little endian targets, where the coefficients in memory are the little endian
+    /// `16`-bit values that the native library writes; elsewhere, use [`Output::to_coefficients`]
+    #[cfg(target_endian = "little")]
+    pub fn as_coefficients(&self) -> &[u16; N] {
+        // the buffer is aligned to `BUFFER_ALIGNMENT` bytes, which exceeds the alignment of `u16`, so the cast cannot fail
+        bytemuck::cast_ref(&self.0[0])
+    }
+
+    /// Views the `64` coefficients of the hash value in place as mutable, without copying.
+    ///
+    /// Only available on little endian targets, as for [`Output::as_coefficients`];
+    /// elsewhere, use [`Output::from_coefficients`]
+    #[cfg(target_endian = "little")]
+    pub fn as_coefficients_mut(&mut self) -> &mut [u16; N] {
+        bytemuck::cast_mut(&mut self.0[0])
+    }
+
+    /// Counts the coefficients in which this hash value differs from `other` modulo [`Q`], so at most `64`.
+    /// This is the Hamming distance over the coefficients, unlike [`CompactOutput::hamming_distance`] which counts bits
+    ///
+    /// # Arguments
+    /// * `other` - the hash value to compare against
+    pub fn hamming_distance(&self, other: &Self) -> u32 {
+        self.centered_differences(other).filter(|&difference| difference != 0).count() as u32
+    }
+
+    /// The squared Euclidean distance from `other`, over the differences of the elements modulo [`Q`]
+    /// taken as their centered representatives in `[-128, 128]`, so at most `64 * 128^2`
+    ///
+    /// # Arguments
+    /// * `other` - the hash value to compare against
+    pub fn l2_distance_squared(&self, other: &Self) -> u32 {
+        self.centered_differences(other).map(|difference| (difference as i32 * difference as i32) as u32).sum()
+    }
+
+    /// The largest absolute difference from `other` of any element, modulo [`Q`] and centered in `[-128, 128]`
+    ///
+    /// # Arguments
+    /// * `other` - the hash value to compare against
+    pub fn linf_distance(&self, other: &Self) -> u32 {
+        self.centered_differences(other).map(|difference| difference.unsigned_abs() as u32).max().unwrap_or(0)
+    }
+
+    /// The differences of the elements from those of `other`, centered in `[-128, 128]`
+    fn centered_differences(&self, other: &Self) -> impl Iterator<Item = i16> {
+        let (a, b) = (self.to_centered(), other.to_centered());
+        (0..N).map(move |i| {
+            let difference = (a[i] - b[i]).rem_euclid(Q as i16);
+            if difference > 128 { difference - Q as i16 } else { difference }
+        })
+    }
+
+    /// Creates a hash value from its centered representation, as produced by [`Output::to_centered`],
+    /// storing every element as its canonical representative in `[0, 256]`, so e.g. `-1` becomes `256`
+    ///
+    /// # Arguments
+    /// * `centered` - the elements of the hash value, each in `[-128, 128]`
+    ///
+    /// # Errors
+    /// Returns [`OutOfRange`] for the first element that lies outside `[-128, 128]`
+    pub fn from_centered(centered: &[i16; N]) -> Result<Self, OutOfRange> {
+        let mut output = Self::default();
+        for (index, (&value, element)) in centered.iter().zip(output.0[0].chunks_exact_mut(2)).enumerate() {
+            if !(-128..=128).contains(&value) {
+                return Err(OutOfRange { index, value })
+            }
+            element.copy_from_slice(&value.rem_euclid(Q as i16).to_le_bytes());
+        }
+        Ok(output)
+    }
+}
+
+impl<const NUM_BLOCKS: usize> CompactOutputs<NUM_BLOCKS> {
+    /// Views the compact hash values as one contiguous slice of `NUM_BLOCKS * 64` bytes
+    pub fn as_flat_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl Output {
+    /// Encodes this hash value as lowercase hex into `out`, without allocating
+    ///
+    /// # Arguments
+    /// * `out` - the buffer to encode into, `2` characters per byte
+    ///
+    /// Returns the encoded hex, borrowed from `out`
+    pub fn write_hex<'a>(&self, out: &'a mut [u8; 2 * OUTPUT_BLOCK_SIZE]) -> &'a str {
+        encode_hex(&self.0[0], out, LOWER_HEX_DIGITS)
+    }
+
+    /// Compacts this hash value into the final [`Digest512`] of a message, as by [`compact`]
+    pub fn compact(&self) -> Digest512 {
+        let mut compact_output = CompactOutput::default();
+        compact(self, &mut compact_output);
+        Digest512(compact_output)
+    }
+}
+
+impl CompactOutput {
+    /// Counts the bits in which this compact hash value differs from `other`, so at most `512`.
+    /// Unlike [`Output::hamming_distance`], which counts whole coefficients, this is the Hamming distance over bits
+    ///
+    /// # Arguments
+    /// * `other` - the compact hash value to compare against
+    pub fn hamming_distance(&self, other: &Self) -> u32 {
+        self.0[0].iter().zip(other.0[0].iter())
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum()
+    }
+
+    /// Counts the bytes in which this compact hash value differs from `other`
+    ///
+    /// # Arguments
+    /// * `other` - the compact hash value to compare against
+    pub fn byte_hamming_distance(&self, other: &Self) -> u32 {
+        self.byte_differences(other).filter(|&difference| difference != 0).count() as u32
+    }
+
+    /// The squared Euclidean distance from `other`, over the differences of the bytes modulo `256`
+    /// taken as their centered representatives in `[-128, 127]`, so at most `64 * 128^2`
+    ///
+    /// # Arguments
+    /// * `other` - the compact hash value to compare against
+    pub fn l2_distance_squared(&self, other: &Self) -> u32 {
+        self.byte_differences(other).map(|difference| (difference as i32 * difference as i32) as u32).sum()
+    }
+
+    /// The largest absolute difference from `other` of any byte, modulo `256` and centered in `[-128, 127]`
+    ///
+    /// # Arguments
+    /// * `other` - the compact hash value to compare against
+    pub fn linf_distance(&self, other: &Self) -> u32 {
+        self.byte_differences(other).map(|difference| difference.unsigned_abs() as u32).max().unwrap_or(0)
+    }
+
+    /// The differences of the bytes from those of `other`, centered in `[-128, 127]`
+    fn byte_differences<'a>(&'a self, other: &'a Self) -> impl Iterator<Item = i8> + 'a {
+        self.0[0].iter().zip(other.0[0].iter()).map(|(a, b)| a.wrapping_sub(*b) as i8)
+    }
+
+    /// Reads this compact hash value as a `512`-bit unsigned integer in `8` limbs of `64` bits.
+    ///
+    /// The integer is little endian throughout: byte `0` is the least significant byte, so limb `i` is bytes
+    /// `8i..8i + 8` read as a little endian `u64`, and limb `7` is the most significant, on every target
+    pub fn as_limbs(&self) -> [u64; 8] {
+        let mut limbs = [0u64; 8];
+        for (limb, bytes) in limbs.iter_mut().zip(self.0[0].chunks_exact(8)) {
+            *limb = u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        limbs
+    }
+
+    /// The number of leading zero bits of this compact hash value as a `512`-bit integer, as read by
+    /// [`as_limbs`](Self::as_limbs), i.e. counting down from the most significant bit of byte `63`;
+    /// `512` if it is zero
+    pub fn leading_zero_bits(&self) -> u32 {
+        let limbs = self.as_limbs();
+        match limbs.iter().rposition(|&limb| limb != 0) {
+            Some(i) => (7 - i as u32) * u64::BITS + limbs[i].leading_zeros(),
+            None => 8 * u64::BITS,
+        }
+    }
+
+    /// Compares this compact hash value with `other` as `512`-bit integers, as read by [`as_limbs`](Self::as_limbs).
+    ///
+    /// This is not the [`Ord`] of buffers, which compares the bytes lexicographically from byte `0`
+    ///
+    /// # Arguments
+    /// * `other` - the compact hash value to compare against
+    pub fn cmp_as_uint(&self, other: &Self) -> Ordering {
+        self.as_limbs().iter().rev().cmp(other.as_limbs().iter().rev())
+    }
+
+    /// Whether this compact hash value is at most `target` as `512`-bit integers, e.g. to check a difficulty target
+    ///
+    /// # Arguments
+    /// * `target` - the largest accepted value, encoded little endian as by [`as_limbs`](Self::as_limbs)
+    pub fn meets_target(&self, target: &[u8; COMPACT_OUTPUT_BLOCK_SIZE]) -> bool {
+        self.cmp_as_uint(&AlignedBuffer([*target])) != Ordering::Greater
+    }
+
+    /// Encodes this compact hash value as lowercase hex into `out`, without allocating
+    ///
+    /// # Arguments
+    /// * `out` - the buffer to encode into, `2` characters per byte
+    ///
+    /// Returns the encoded hex, borrowed from `out`
+    pub fn write_hex<'a>(&self, out: &'a mut [u8; 2 * COMPACT_OUTPUT_BLOCK_SIZE]) -> &'a str {
+        encode_hex(&self.0[0], out, LOWER_HEX_DIGITS)
+    }
+
+    /// A short fingerprint of this compact hash value, e.g. for Bloom filters and sharding.
+    ///
+    /// Reading the `64` bytes as the little endian `u64` words `w_0, ..., w_7`, the fingerprint is
+    /// `(w_1 ^ w_3 ^ w_5 ^ w_7) << 64 | (w_0 ^ w_2 ^ w_4 ^ w_6)` on every target.
+    /// This definition is frozen: fingerprints may be persisted and compared across versions.
+    ///
+    /// For compact hash values of unrelated inputs, two distinct values share a fingerprint with probability
+    /// about `2^-128`, so among `k` values a collision occurs with probability at most `k^2 / 2^129`.
+    /// The folding is linear, so fingerprints are not collision resistant against an adversary choosing the inputs;
+    /// compare the full compact hash values wherever that matters
+    pub fn fingerprint128(&self) -> u128 {
+        let mut words = self.0[0].chunks_exact(8).map(|word| u64::from_le_bytes(word.try_into().unwrap()));
+        let (mut low, mut high) = (0u64, 0u64);
+        while let (Some(even), Some(odd)) = (words.next(), words.next()) {
+            low ^= even;
+            high ^= odd;
+        }
+        (high as u128) << 64 | low as u128
+    }
+
+    /// The [`fingerprint128`](Self::fingerprint128) folded once more into a `u64`, as the exclusive or of its halves,
+    /// which is equally frozen.
+    ///
+    /// Among `k` compact hash values of unrelated inputs, a collision occurs with probability at most `k^2 / 2^65`,
+    /// so for more than about `2^24` values it should only be used where false positives are tolerable
+    pub fn fingerprint64(&self) -> u64 {
+        let fingerprint = self.fingerprint128();
+        (fingerprint >> 64) as u64 ^ fingerprint as u64
+    }
+
+    /// Assigns this compact hash value to one of `num_shards` shards, uniformly over compact hash values of unrelated inputs
+    ///
+    /// Reduces the low half of the [`fingerprint128`](Self::fingerprint128) by Lemire's multiply-and-shift method,
+    /// rejecting the at most `num_shards / 2^64` of values that would bias the result in favour of the high half,
+    /// which leaves a bias below `2^-64`. Like the fingerprint, the assignment is frozen
+    ///
+    /// # Arguments
+    /// * `num_shards` - the number of shards, which need not be a power of two
+    ///
+    /// # Panics
+    /// If `num_shards` is `0`
+    pub fn shard(&self, num_shards: u32) -> u32 {
+        assert_ne!(num_shards, 0, "cannot assign to zero shards");
+        let fingerprint = self.fingerprint128();
+        let num_shards = num_shards as u64;
+        let reduce = |word: u64| word as u128 * num_shards as u128;
+        let mut product = reduce(fingerprint as u64);
+        // the low words below `2^64 mod num_shards` are the biased ones
+        if (product as u64) < num_shards.wrapping_neg() % num_shards {
+            product = reduce((fingerprint >> 64) as u64);
+        }
+        (product >> 64) as u32
+    }
+}
+
+/// The final digest of a message: a compact hash value that is only ever compared, printed or stored.
+///
+/// Compact hash values lose the homomorphism of hash values, so adding them or feeding them back into
+/// the arithmetic gives meaningless results. The high-level hashers return this type rather than
+/// a [`CompactOutput`], which exposes neither mutation nor arithmetic; the lower-level
+/// [`compress_compact`](crate::hash::compress_compact) still works on compact hash values,
+/// through [`Digest512::as_compact_output`].
+///
+/// Digests are ordered and hashed by their bytes, so they can key ordered and hashed collections
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Digest512(CompactOutput);
+
+impl Digest512 {
+    /// Creates a digest from its bytes, e.g. as stored
+    ///
+    /// # Arguments
+    /// * `bytes` - the bytes of the digest
+    pub fn from_bytes(bytes: [u8; COMPACT_OUTPUT_BLOCK_SIZE]) -> Self {
+        Self(AlignedBuffer([bytes]))
+    }
+
+    /// The bytes of the digest
+    pub fn as_bytes(&self) -> &[u8; COMPACT_OUTPUT_BLOCK_SIZE] {
+        &self.0.0[0]
+    }
+
+    /// The digest as `128` lowercase hex digits
+    pub fn to_hex(&self) -> String {
+        format!("{:x}", self.0)
+    }
+
+    /// Encodes the digest as lowercase hex into `out`, without allocating
+    ///
+    /// # Arguments
+    /// * `out` - the buffer to encode into, `2` characters per byte
+    ///
+    /// Returns the encoded hex, borrowed from `out`
+    pub fn write_hex<'a>(&self, out: &'a mut [u8; 2 * COMPACT_OUTPUT_BLOCK_SIZE]) -> &'a str {
+        self.0.write_hex(out)
+    }
+
+    /// The digest as a compact hash value, e.g. to [`compress_compact`](crate::hash::compress_compact) it
+    pub fn as_compact_output(&self) -> &CompactOutput {
+        &self.0
+    }
+}
+
+impl From<CompactOutput> for Digest512 {
+    fn from(compact_output: CompactOutput) -> Self {
+        Self(compact_output)
+    }
+}
+
+impl From<Digest512> for CompactOutput {
+    fn from(digest: Digest512) -> Self {
+        digest.0
+    }
+}
+
+impl From<[u8; COMPACT_OUTPUT_BLOCK_SIZE]> for Digest512 {
+    fn from(bytes: [u8; COMPACT_OUTPUT_BLOCK_SIZE]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<Digest512> for [u8; COMPACT_OUTPUT_BLOCK_SIZE] {
+    fn from(digest: Digest512) -> Self {
+        digest.0.0[0]
+    }
+}
+
+impl LowerHex for Digest512 {
+    /// Formats the digest as `128` lowercase hex digits
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl UpperHex for Digest512 {
+    /// Formats the digest as `128` uppercase hex digits
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl Debug for Digest512 {
+    /// Formats the digest in full, as hex
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Digest512({:x})", self.0)
+    }
 }
 
 impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Default for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
@@ -51,4 +895,508 @@ impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Default for AlignedBuffer
     fn default() -> Self {
         Self([[0u8; CHUNK_SIZE]; NUM_CHUNKS])
     }
-}
\ No newline at end of file
+}
+
+// SAFETY: a buffer is nothing but bytes, so all zeros is a valid buffer
+#[cfg(feature = "bytemuck")]
+unsafe impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> bytemuck::Zeroable for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {}
+
+impl<const CHUNK_SIZE: usize> AlignedBuffer<CHUNK_SIZE, 1> {
+    /// Copies out the bytes of this single-block buffer
+    pub fn to_array(&self) -> [u8; CHUNK_SIZE] {
+        self.0[0]
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> AsRef<[u8]> for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    fn as_ref(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> AsMut<[u8]> for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_bytes_mut()
+    }
+}
+
+impl<const CHUNK_SIZE: usize> From<[u8; CHUNK_SIZE]> for AlignedBuffer<CHUNK_SIZE, 1> {
+    /// Copies the bytes of a single block into an aligned buffer.
+    ///
+    /// The bytes are not validated, so a hash value made this way may have elements outside `[0, 256]`:
+    /// use [`TryFrom<&[u8]>`](TryFrom) or [`Output::from_bytes_canonical`] to reject them
+    fn from(bytes: [u8; CHUNK_SIZE]) -> Self {
+        Self([bytes])
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> TryFrom<&[u8]> for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    type Error = ConversionError;
+
+    /// Copies `bytes` into a fresh aligned buffer, block after block,
+    /// accepting hash values only if their elements are canonical
+    ///
+    /// # Errors
+    /// Returns [`ConversionError::LenMismatch`], counting bytes, if `bytes` is not exactly `CHUNK_SIZE * NUM_CHUNKS` bytes long,
+    /// or [`ConversionError::OutOfRange`] for the first element of a hash value that lies outside `[0, 256]`
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        let mut buffer = Self::default();
+        buffer.copy_from_slice(bytes)?;
+        buffer.check_canonical_outputs()?;
+        Ok(buffer)
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> TryFrom<&[[u8; CHUNK_SIZE]]> for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    type Error = ConversionError;
+
+    /// Copies `blocks` into a fresh aligned buffer,
+    /// accepting hash values only if their elements are canonical
+    ///
+    /// # Errors
+    /// Returns [`ConversionError::LenMismatch`], counting blocks, if `blocks` does not hold exactly `NUM_CHUNKS` blocks,
+    /// or [`ConversionError::OutOfRange`] for the first element of a hash value that lies outside `[0, 256]`
+    fn try_from(blocks: &[[u8; CHUNK_SIZE]]) -> Result<Self, Self::Error> {
+        let blocks: &[[u8; CHUNK_SIZE]; NUM_CHUNKS] = blocks.try_into()
+            .map_err(|_| LenMismatch { expected: NUM_CHUNKS, actual: blocks.len() })?;
+        let buffer = Self(*blocks);
+        buffer.check_canonical_outputs()?;
+        Ok(buffer)
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Checks every [`OUTPUT_BLOCK_SIZE`]-byte chunk of a hash value with [`check_canonical`],
+    /// numbering the elements across the whole buffer; other buffers have no elements to check
+    fn check_canonical_outputs(&self) -> Result<(), OutOfRange> {
+        if CHUNK_SIZE != OUTPUT_BLOCK_SIZE {
+            return Ok(())
+        }
+        for (block, chunk) in self.0.iter().enumerate() {
+            check_canonical(chunk).map_err(|err| OutOfRange { index: block * OUTPUT_BLOCK_SIZE / 2 + err.index, ..err })?;
+        }
+        Ok(())
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> PartialEq for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Compares the bytes, so e.g. hash values whose elements are equal modulo `257`
+    /// but stored as different representatives are not equal
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Eq for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> PartialOrd for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Ord for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Orders buffers lexicographically by their bytes, as one contiguous slice
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.as_flattened().cmp(other.0.as_flattened())
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Hash for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Hashes the bytes, as one contiguous slice
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.as_flattened().hash(state)
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> LowerHex for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Formats the full contents as contiguous lowercase hex, or with the chunks separated by spaces for `{:#x}`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_hex(f, LOWER_HEX_DIGITS)
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> UpperHex for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Formats the full contents as contiguous uppercase hex, or with the chunks separated by spaces for `{:#X}`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.fmt_hex(f, UPPER_HEX_DIGITS)
+    }
+}
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Debug for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Formats the chunk shape and a hex preview of the first bytes,
+    /// followed by the elements in decimal if this is a single hash value, i.e. an [`Output`]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let bytes = self.0.as_flattened();
+        let mut debug = f.debug_struct("AlignedBuffer");
+        debug.field("chunk_size", &CHUNK_SIZE)
+            .field("num_chunks", &NUM_CHUNKS)
+            .field("bytes", &HexPreview(bytes));
+        if CHUNK_SIZE == OUTPUT_BLOCK_SIZE && NUM_CHUNKS == 1 {
+            // kept on one line even by `{:#?}`, rather than one line per element
+            debug.field("elements", &format_args!("{:?}", decode_elements(bytes)));
+        }
+        debug.finish()
+    }
+}
+
+/// The first bytes of a buffer as lowercase hex, followed by `...` if there are more
+struct HexPreview<'a>(&'a [u8]);
+
+impl Debug for HexPreview<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const PREVIEW_BYTES: usize = 16;
+        for byte in self.0.iter().take(PREVIEW_BYTES) {
+            write!(f, "{:02x}", byte)?
+        }
+        if self.0.len() > PREVIEW_BYTES {
+            f.write_str("...")?
+        }
+        Ok(())
+    }
+}
+
+/// Overwrites `bytes` with the pseudorandom bytes of [`AlignedBuffer::from_seed`], for any number of bytes,
+/// e.g. for a corpus larger than a buffer.
+///
+/// The bytes are one stream: those of a shorter slice are a prefix of those of a longer one from the same seed.
+///
+/// The generator starts from the seed mixed by the splitmix64 finalizer, which is a bijection, so different seeds
+/// start from different states. The one exception is the seed `7046029254386353131`, which the finalizer mixes to `0`,
+/// where xorshift would be stuck: it starts from the state of the seed `14787246121499254518` instead
+///
+/// # Arguments
+/// * `seed` - the seed
+/// * `bytes` - the bytes to overwrite
+pub fn fill_from_seed(seed: u64, bytes: &mut [u8]) {
+    const GOLDEN_GAMMA: u64 = 0x9E3779B97F4A7C15;
+    let mut state = seed.wrapping_add(GOLDEN_GAMMA);
+    state = (state ^ (state >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    state = (state ^ (state >> 27)).wrapping_mul(0x94D049BB133111EB);
+    state ^= state >> 31;
+    if state == 0 {
+        state = GOLDEN_GAMMA
+    }
+    for byte in bytes {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        *byte = (state.wrapping_mul(0x2545F4914F6CDD1D) >> 56) as u8;
+    }
+}
+
+const LOWER_HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const UPPER_HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+/// Encodes `bytes` as hex with `hex_digits` into the start of `out`, returning the encoded part
+///
+/// # Panics
+/// If `out` is shorter than `2 * bytes.len()`
+fn encode_hex<'a>(bytes: &[u8], out: &'a mut [u8], hex_digits: &[u8; 16]) -> &'a str {
+    let out = &mut out[..2 * bytes.len()];
+    for (digits, byte) in out.chunks_exact_mut(2).zip(bytes) {
+        digits[0] = hex_digits[(byte >> 4) as usize];
+        digits[1] = hex_digits[(byte & 0xf) as usize];
+    }
+    // SAFETY: every byte of `out` is an ASCII hex digit
+    unsafe { std::str::from_utf8_unchecked(out) }
+}
+
+/// Checks that every little endian element of the hash values encoded in `bytes` is canonical
+///
+/// # Errors
+/// Returns [`OutOfRange`] for the first element, read as a `16`-bit signed value, that lies outside `[0, 256]`
+pub(crate) fn check_canonical(bytes: &[u8]) -> Result<(), OutOfRange> {
+    for (index, element) in bytes.chunks_exact(2).enumerate() {
+        let value = i16::from_le_bytes([element[0], element[1]]);
+        if !(0..Q as i16).contains(&value) {
+            return Err(OutOfRange { index, value })
+        }
+    }
+    Ok(())
+}
+
+/// Decodes exactly `2 * out.len()` lowercase hex digits into `out`
+pub(crate) fn decode_hex(hex: &str, out: &mut [u8]) -> Option<()> {
+    if hex.len() != 2 * out.len() {
+        return None
+    }
+    let digit = |c: u8| match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        _ => None,
+    };
+    for (byte, pair) in out.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = digit(pair[0])? << 4 | digit(pair[1])?;
+    }
+    Some(())
+}
+
+/// Writes `bytes` as hex with `hex_digits` into `w`, through a small staging buffer
+fn write_hex_digits(bytes: &[u8], w: &mut impl core::fmt::Write, hex_digits: &[u8; 16]) -> core::fmt::Result {
+    const STAGING_BYTES: usize = 32;
+    let mut staging = [0u8; 2 * STAGING_BYTES];
+    for bytes in bytes.chunks(STAGING_BYTES) {
+        w.write_str(encode_hex(bytes, &mut staging, hex_digits))?
+    }
+    Ok(())
+}
+
+/// Decodes the elements of a hash value from its block of little endian `16`-bit values
+fn decode_elements(block: &[u8]) -> [u16; N] {
+    let mut elements = [0u16; N];
+    for (value, element) in elements.iter_mut().zip(block.chunks_exact(2)) {
+        *value = u16::from_le_bytes([element[0], element[1]]);
+    }
+    elements
+}
+
+/// A byte pattern that an [`InputBuilder`] can fill its input with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pattern {
+    /// Every bit is `0`
+    Zeros,
+    /// Every bit is `1`
+    Ones,
+    /// Every byte is `0b01010101`, setting every even-indexed bit
+    Alternating,
+    /// Every byte is the given value
+    Repeat(u8),
+    /// The `i`th byte is `i`
+    Counter,
+}
+
+/// A builder for constructing specific inputs in terms of their
+/// `32` input vectors of `64` bits, rather than by raw byte manipulation.
+///
+/// Bit `b` of byte `i` is bit `8i + b` of the input, and input vector `j`
+/// consists of bits `64j` to `64j + 63`, i.e. bytes `8j` to `8j + 7`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputBuilder([u8; INPUT_BLOCK_SIZE]);
+
+impl InputBuilder {
+    /// Creates a builder for the all-zero input
+    pub const fn new() -> Self {
+        Self([0u8; INPUT_BLOCK_SIZE])
+    }
+
+    /// Sets input vector `j` to `bits`, where `bits[k]` is the `k`th coefficient
+    ///
+    /// # Panics
+    /// If `j` is not less than `32`
+    pub fn set_vector(mut self, j: usize, bits: &[bool; N]) -> Self {
+        assert!(j < M, "input vector index {} out of range 0..{}", j, M);
+        for (k, &bit) in bits.iter().enumerate() {
+            let byte = &mut self.0[(j * N + k) / u8::BITS as usize];
+            let mask = 1u8 << (k % u8::BITS as usize);
+            if bit { *byte |= mask } else { *byte &= !mask }
+        }
+        self
+    }
+
+    /// Sets input vector `j` to `bits`, where bit `k` of `bits` is the `k`th coefficient
+    ///
+    /// # Panics
+    /// If `j` is not less than `32`
+    pub fn set_vector_from_u64(mut self, j: usize, bits: u64) -> Self {
+        assert!(j < M, "input vector index {} out of range 0..{}", j, M);
+        let offset = j * N / u8::BITS as usize;
+        self.0[offset..offset + N / u8::BITS as usize].copy_from_slice(&bits.to_le_bytes());
+        self
+    }
+
+    /// Sets byte `i` of the input to `value`
+    ///
+    /// # Panics
+    /// If `i` is not less than `256`
+    pub fn set_byte(mut self, i: usize, value: u8) -> Self {
+        assert!(i < INPUT_BLOCK_SIZE, "input byte index {} out of range 0..{}", i, INPUT_BLOCK_SIZE);
+        self.0[i] = value;
+        self
+    }
+
+    /// Sets byte `i` of the input to `value`, whose bits are in most significant bit first order
+    ///
+    /// # Panics
+    /// If `i` is not less than `256`
+    pub fn set_byte_msb_first(self, i: usize, value: u8) -> Self {
+        self.set_byte(i, value.reverse_bits())
+    }
+
+    /// Overwrites the whole input with `pattern`
+    pub fn fill_pattern(mut self, pattern: Pattern) -> Self {
+        for (i, byte) in self.0.iter_mut().enumerate() {
+            *byte = match pattern {
+                Pattern::Zeros => 0x00,
+                Pattern::Ones => 0xFF,
+                Pattern::Alternating => 0x55,
+                Pattern::Repeat(value) => value,
+                Pattern::Counter => i as u8,
+            }
+        }
+        self
+    }
+
+    /// Builds the input
+    pub fn build(self) -> Input {
+        AlignedBuffer([self.0])
+    }
+
+    /// Builds the input together with its paired sign buffer, built by `signs`
+    pub fn into_signed(self, signs: InputBuilder) -> (Input, SignInput) {
+        (self.build(), signs.build())
+    }
+
+    /// Packs the input, together with the high bits built by `high_bits`, into coefficients in `{0, 1, 2, 3}`
+    /// of `2` bits each: coefficient `k` is bit `k` of the input plus twice bit `k` of `high_bits`.
+    ///
+    /// Bytes `0..256` and `256..512` are the base-4 input blocks of the pure Rust SWIFFT inputs
+    /// that the input corresponds to, as read by [`swifft::hash::parse_input_block_base4`]
+    pub fn into_base4(self, high_bits: InputBuilder) -> [u8; 2 * INPUT_BLOCK_SIZE] {
+        let mut block = [0u8; 2 * INPUT_BLOCK_SIZE];
+        for (i, byte) in block.iter_mut().enumerate() {
+            // every byte of the input spans two bytes of the base-4 block
+            let shift = i % 2 * 4;
+            let (low, high) = (self.0[i / 2] >> shift, high_bits.0[i / 2] >> shift);
+            for b in 0..4 {
+                *byte |= (((low >> b) & 1) | ((high >> b) & 1) << 1) << (2 * b);
+            }
+        }
+        block
+    }
+}
+
+impl Default for InputBuilder {
+    /// Creates a builder for the all-zero input
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An input together with its sign bits, where no sign bit is set on a coefficient of `0`,
+/// so that every value stands for exactly one vector of coefficients in `{-1, 0, 1}`.
+///
+/// [`compute_signed`](crate::hash::compute_signed) accepts any pair of an input and sign bits, and reads a sign bit
+/// on a clear input bit as `0`; this type rules such stray sign bits out, by rejecting them in [`SignedInput::from_parts`]
+/// or clearing them in [`SignedInput::from_parts_canonicalizing`]. It is hashed by
+/// [`compute_signed_checked`](crate::hash::compute_signed_checked)
+///
+/// ```
+/// use libswifft::buffer::SignedInput;
+///
+/// let signed = SignedInput::new().set_trit(0, 1).unwrap().set_trit(1, -1).unwrap();
+/// assert_eq!(signed.trit(1), -1);
+/// assert!(SignedInput::new().set_trit(2, 2).is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SignedInput {
+    input: Input,
+    sign_input: SignInput,
+}
+
+impl SignedInput {
+    /// Creates the all-zero signed input
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Packs coefficients in `{-1, 0, 1}` as [`pack_trits`] does
+    ///
+    /// # Arguments
+    /// * `trits` - the coefficients of the `32` input vectors of `64` coefficients each
+    ///
+    /// # Errors
+    /// Returns [`TritError`] for the first element that is not a trit
+    pub fn from_trits(trits: &[i8; INPUT_SIZE]) -> Result<Self, TritError> {
+        pack_trits(trits).map(|(input, sign_input)| Self { input, sign_input })
+    }
+
+    /// Pairs an input with its sign bits, rejecting sign bits on coefficients of `0`
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit), whose bits are the magnitudes of the coefficients
+    /// * `sign_input` - the sign bits, set only where the input bit is set too
+    ///
+    /// # Errors
+    /// Returns [`StraySignBit`] for the first sign bit that is set where the input bit is clear
+    pub fn from_parts(input: Input, sign_input: SignInput) -> Result<Self, StraySignBit> {
+        let stray = sign_input.0[0].iter().zip(input.0[0].iter()).enumerate()
+            .find_map(|(byte, (sign, magnitude))| {
+                let stray = sign & !magnitude;
+                (stray != 0).then(|| byte * u8::BITS as usize + stray.trailing_zeros() as usize)
+            });
+        match stray {
+            None => Ok(Self { input, sign_input }),
+            Some(index) => Err(StraySignBit { index }),
+        }
+    }
+
+    /// Pairs an input with its sign bits, clearing sign bits on coefficients of `0`.
+    /// This keeps the coefficients that [`compute_signed`](crate::hash::compute_signed) reads, and so its hash value
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit), whose bits are the magnitudes of the coefficients
+    /// * `sign_input` - the sign bits
+    pub fn from_parts_canonicalizing(input: Input, mut sign_input: SignInput) -> Self {
+        for (sign, magnitude) in sign_input.0[0].iter_mut().zip(input.0[0].iter()) {
+            *sign &= magnitude;
+        }
+        Self { input, sign_input }
+    }
+
+    /// Sets coefficient `index` to `value`, numbered as in [`pack_trits`]
+    ///
+    /// # Errors
+    /// Returns [`TritError`] if `value` is not a trit
+    ///
+    /// # Panics
+    /// If `index` is not less than `2048`
+    pub fn set_trit(mut self, index: usize, value: i8) -> Result<Self, TritError> {
+        assert!(index < INPUT_SIZE, "trit index {} out of range 0..{}", index, INPUT_SIZE);
+        let (byte, mask) = (index / u8::BITS as usize, 1u8 << (index % u8::BITS as usize));
+        let (magnitude, sign) = match value {
+            0 => (false, false),
+            1 => (true, false),
+            -1 => (true, true),
+            _ => return Err(TritError { index, value }),
+        };
+        for (buffer, bit) in [(&mut self.input, magnitude), (&mut self.sign_input, sign)] {
+            if bit { buffer.0[0][byte] |= mask } else { buffer.0[0][byte] &= !mask }
+        }
+        Ok(self)
+    }
+
+    /// Coefficient `index`, numbered as in [`pack_trits`]
+    ///
+    /// # Panics
+    /// If `index` is not less than `2048`
+    pub fn trit(&self, index: usize) -> i8 {
+        assert!(index < INPUT_SIZE, "trit index {} out of range 0..{}", index, INPUT_SIZE);
+        let bit = |buffer: &Input| (buffer.0[0][index / u8::BITS as usize] >> (index % u8::BITS as usize)) & 1;
+        match (bit(&self.input), bit(&self.sign_input)) {
+            (0, _) => 0,
+            (_, 0) => 1,
+            _ => -1,
+        }
+    }
+
+    /// The coefficients, as [`unpack_trits`] unpacks them
+    pub fn to_trits(&self) -> [i8; INPUT_SIZE] {
+        unpack_trits(&self.input, &self.sign_input)
+    }
+
+    /// The input, whose bits are the magnitudes of the coefficients
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// The sign bits, set only where the input bit is set too
+    pub fn sign_input(&self) -> &SignInput {
+        &self.sign_input
+    }
+
+    /// Splits the signed input into the input and its sign bits, as [`compute_signed`](crate::hash::compute_signed) takes them
+    pub fn into_parts(self) -> (Input, SignInput) {
+        (self.input, self.sign_input)
+    }
+}
@@ -0,0 +1,193 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Growable batches of blocks on the heap, for numbers of blocks known only at runtime or too large for the stack.
+//!
+//! A [`BlockVec`] holds its blocks contiguously in a single allocation, each aligned as the native library requires,
+//! since every block type is aligned to [`BUFFER_ALIGNMENT`](crate::constant::BUFFER_ALIGNMENT) bytes and is
+//! a whole multiple of them in size. Growing reallocates with the alignment of the block type, so it is preserved.
+//! It dereferences to a slice of blocks, which the slice-based APIs such as
+//! [`compute_slice`](crate::hash::compute_slice) and [`add_slice`](crate::arithmetic::add_slice) take
+//!
+//! ```
+//! use libswifft::buffer::heap::{InputVec, OutputVec};
+//! use libswifft::hash::compute_slice;
+//!
+//! let mut inputs = InputVec::with_capacity(1000);
+//! for i in 0..1000 {
+//!     inputs.push_bytes([i as u8; 256]);
+//! }
+//! let mut outputs = OutputVec::zeroed(inputs.len());
+//! compute_slice(&inputs, &mut outputs).unwrap();
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use crate::buffer::AlignedBuffer;
+use crate::constant::{COMPACT_OUTPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE};
+use crate::error::BlockCountOverflow;
+
+/// A growable, contiguous batch of blocks of `BLOCK_SIZE` bytes on the heap, each aligned as the native library requires
+#[derive(Debug, Clone, Default)]
+pub struct BlockVec<const BLOCK_SIZE: usize>(Vec<AlignedBuffer<BLOCK_SIZE, 1>>);
+
+/// A growable batch of inputs, each of 256 bytes (2048 bit)
+pub type InputVec = BlockVec<INPUT_BLOCK_SIZE>;
+
+/// A growable batch of hash values, each of size 128 bytes (1024 bit)
+pub type OutputVec = BlockVec<OUTPUT_BLOCK_SIZE>;
+
+/// A growable batch of compact hash values, each of size 64 bytes (512 bit)
+pub type CompactOutputVec = BlockVec<COMPACT_OUTPUT_BLOCK_SIZE>;
+
+impl<const BLOCK_SIZE: usize> BlockVec<BLOCK_SIZE> {
+    /// Creates an empty batch, without allocating
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates an empty batch with room for at least `capacity` blocks
+    ///
+    /// # Arguments
+    /// * `capacity` - the number of blocks to allocate room for
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Creates a batch of `len` all-zero blocks, e.g. to hash into
+    ///
+    /// # Arguments
+    /// * `len` - the number of blocks
+    pub fn zeroed(len: usize) -> Self {
+        Self(vec![AlignedBuffer::default(); len])
+    }
+
+    /// Creates a batch of `len` all-zero blocks as [`zeroed`](Self::zeroed) does,
+    /// reporting a `len` too large to allocate rather than panicking
+    ///
+    /// # Arguments
+    /// * `len` - the number of blocks, e.g. read from an untrusted header
+    ///
+    /// # Errors
+    /// [`BlockCountOverflow`] if `len` blocks would exceed [`isize::MAX`] bytes
+    pub fn try_zeroed(len: usize) -> Result<Self, BlockCountOverflow> {
+        BlockCountOverflow::check(len, BLOCK_SIZE)?;
+        Ok(Self::zeroed(len))
+    }
+
+    /// The number of blocks the batch can hold without reallocating
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Appends a block
+    ///
+    /// # Arguments
+    /// * `block` - the block to append
+    pub fn push(&mut self, block: AlignedBuffer<BLOCK_SIZE, 1>) {
+        self.0.push(block)
+    }
+
+    /// Appends a block given by its bytes
+    ///
+    /// # Arguments
+    /// * `bytes` - the bytes of the block to append
+    pub fn push_bytes(&mut self, bytes: [u8; BLOCK_SIZE]) {
+        self.0.push(AlignedBuffer([bytes]))
+    }
+
+    /// Shortens the batch to its first `len` blocks, if it is longer
+    ///
+    /// # Arguments
+    /// * `len` - the number of blocks to keep
+    pub fn truncate(&mut self, len: usize) {
+        self.0.truncate(len)
+    }
+
+    /// Removes every block, keeping the allocation
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// The blocks, as a slice
+    pub fn as_slice(&self) -> &[AlignedBuffer<BLOCK_SIZE, 1>] {
+        &self.0
+    }
+
+    /// The blocks, as a mutable slice
+    pub fn as_mut_slice(&mut self) -> &mut [AlignedBuffer<BLOCK_SIZE, 1>] {
+        &mut self.0
+    }
+}
+
+impl<const BLOCK_SIZE: usize> Deref for BlockVec<BLOCK_SIZE> {
+    type Target = [AlignedBuffer<BLOCK_SIZE, 1>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<const BLOCK_SIZE: usize> DerefMut for BlockVec<BLOCK_SIZE> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<const BLOCK_SIZE: usize> From<Vec<[u8; BLOCK_SIZE]>> for BlockVec<BLOCK_SIZE> {
+    /// Copies the blocks into aligned storage
+    fn from(blocks: Vec<[u8; BLOCK_SIZE]>) -> Self {
+        blocks.into_iter().map(|block| AlignedBuffer([block])).collect()
+    }
+}
+
+impl<const BLOCK_SIZE: usize> From<BlockVec<BLOCK_SIZE>> for Vec<[u8; BLOCK_SIZE]> {
+    fn from(blocks: BlockVec<BLOCK_SIZE>) -> Self {
+        blocks.0.into_iter().map(|block| block.0[0]).collect()
+    }
+}
+
+impl<const BLOCK_SIZE: usize, const NUM_BLOCKS: usize> From<&AlignedBuffer<BLOCK_SIZE, NUM_BLOCKS>> for BlockVec<BLOCK_SIZE> {
+    /// Copies the blocks of a fixed-size batch, e.g. of [`Inputs`](crate::buffer::Inputs)
+    fn from(blocks: &AlignedBuffer<BLOCK_SIZE, NUM_BLOCKS>) -> Self {
+        blocks.0.iter().map(|&block| AlignedBuffer([block])).collect()
+    }
+}
+
+impl<const BLOCK_SIZE: usize> FromIterator<AlignedBuffer<BLOCK_SIZE, 1>> for BlockVec<BLOCK_SIZE> {
+    fn from_iter<I: IntoIterator<Item = AlignedBuffer<BLOCK_SIZE, 1>>>(blocks: I) -> Self {
+        Self(blocks.into_iter().collect())
+    }
+}
+
+impl<const BLOCK_SIZE: usize> Extend<AlignedBuffer<BLOCK_SIZE, 1>> for BlockVec<BLOCK_SIZE> {
+    fn extend<I: IntoIterator<Item = AlignedBuffer<BLOCK_SIZE, 1>>>(&mut self, blocks: I) {
+        self.0.extend(blocks)
+    }
+}
+
+impl<const BLOCK_SIZE: usize> IntoIterator for BlockVec<BLOCK_SIZE> {
+    type Item = AlignedBuffer<BLOCK_SIZE, 1>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, const BLOCK_SIZE: usize> IntoIterator for &'a BlockVec<BLOCK_SIZE> {
+    type Item = &'a AlignedBuffer<BLOCK_SIZE, 1>;
+    type IntoIter = std::slice::Iter<'a, AlignedBuffer<BLOCK_SIZE, 1>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, const BLOCK_SIZE: usize> IntoIterator for &'a mut BlockVec<BLOCK_SIZE> {
+    type Item = &'a mut AlignedBuffer<BLOCK_SIZE, 1>;
+    type IntoIter = std::slice::IterMut<'a, AlignedBuffer<BLOCK_SIZE, 1>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
@@ -0,0 +1,97 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Conversion between the layouts in which the bits of the `32` input vectors can be stored,
+//! both least significant bit first (*see [`Input`](crate::buffer::Input)*)
+
+use crate::constant::{INPUT_BLOCK_SIZE, INPUT_SIZE, M, N};
+
+/// The order in which the `2048` bits of an input are stored
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Layout {
+    /// Vector-major: coefficient `k` of input vector `j` is bit `64j + k`,
+    /// which is the layout that the SWIFFT functions expect
+    Planar,
+    /// Bit-major: coefficient `k` of input vector `j` is bit `32k + j`,
+    /// so the coefficients of equal power of all input vectors are adjacent
+    Interleaved,
+}
+
+/// Marker type for [`Layout::Planar`]
+pub struct Planar;
+
+/// Marker type for [`Layout::Interleaved`]
+pub struct Interleaved;
+
+/// A marker type naming a [`Layout`], so that conversions can be fixed at compile time with [`convert`]
+pub trait LayoutMarker {
+    /// The layout this marker stands for
+    const LAYOUT: Layout;
+}
+
+impl LayoutMarker for Planar {
+    const LAYOUT: Layout = Layout::Planar;
+}
+
+impl LayoutMarker for Interleaved {
+    const LAYOUT: Layout = Layout::Interleaved;
+}
+
+/// Converts the bits of an input from layout `from` to layout `to`
+///
+/// # Arguments
+/// * `src` - the input of 256 bytes (2048 bit), in layout `from`
+/// * `from` - the layout of `src`
+/// * `to` - the layout to convert into
+pub fn convert_layout(src: &[u8; INPUT_BLOCK_SIZE], from: Layout, to: Layout) -> [u8; INPUT_BLOCK_SIZE] {
+    match (from, to) {
+        (Layout::Planar, Layout::Interleaved) => gather(src, &PLANAR_POSITIONS),
+        (Layout::Interleaved, Layout::Planar) => gather(src, &INTERLEAVED_POSITIONS),
+        _ => *src,
+    }
+}
+
+/// Converts the bits of an input between the layouts named by the markers `From` and `To`
+///
+/// # Arguments
+/// * `src` - the input of 256 bytes (2048 bit), in layout `From`
+pub fn convert<From: LayoutMarker, To: LayoutMarker>(src: &[u8; INPUT_BLOCK_SIZE]) -> [u8; INPUT_BLOCK_SIZE] {
+    convert_layout(src, From::LAYOUT, To::LAYOUT)
+}
+
+/// Sets bit `i` of the result to bit `positions[i]` of `src`
+fn gather(src: &[u8; INPUT_BLOCK_SIZE], positions: &[u16; INPUT_SIZE]) -> [u8; INPUT_BLOCK_SIZE] {
+    let mut dst = [0u8; INPUT_BLOCK_SIZE];
+    for (byte, positions) in dst.iter_mut().zip(positions.chunks_exact(8)) {
+        for (bit, &position) in positions.iter().enumerate() {
+            let position = position as usize;
+            *byte |= ((src[position / 8] >> (position % 8)) & 1) << bit;
+        }
+    }
+    dst
+}
+
+/// The planar bit position of every interleaved bit position
+const PLANAR_POSITIONS: [u16; INPUT_SIZE] = compute_planar_positions(); const fn compute_planar_positions() -> [u16; INPUT_SIZE] {
+    let mut positions = [0u16; INPUT_SIZE];
+    let mut k = 0; while k < N {
+        let mut j = 0; while j < M {
+            positions[k * M + j] = (j * N + k) as u16;
+            j += 1
+        }
+        k += 1
+    }
+    positions
+}
+
+/// The interleaved bit position of every planar bit position
+const INTERLEAVED_POSITIONS: [u16; INPUT_SIZE] = compute_interleaved_positions(); const fn compute_interleaved_positions() -> [u16; INPUT_SIZE] {
+    let mut positions = [0u16; INPUT_SIZE];
+    let mut j = 0; while j < M {
+        let mut k = 0; while k < N {
+            positions[j * N + k] = (k * M + j) as u16;
+            k += 1
+        }
+        j += 1
+    }
+    positions
+}
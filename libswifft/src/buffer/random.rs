@@ -0,0 +1,23 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Sampling buffers from a `rand` generator, behind the `rand` feature: inputs of uniformly random bits,
+//! and canonical hash values of uniformly random elements
+
+use rand::distributions::{Distribution, Standard};
+use rand::Rng;
+
+use crate::buffer::{Input, Output};
+
+impl Distribution<Input> for Standard {
+    /// Samples an input of uniformly random bits, which is also a uniformly random [`SignInput`](crate::buffer::SignInput)
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Input {
+        Input::random(rng)
+    }
+}
+
+impl Distribution<Output> for Standard {
+    /// Samples a canonical hash value, with elements uniformly random in `[0, 256]`
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Output {
+        Output::from_coefficients(std::array::from_fn(|_| rng.gen_range(0..257)))
+    }
+}
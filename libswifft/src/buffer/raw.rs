@@ -0,0 +1,99 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Unchecked constructors for users managing their own buffers, e.g. next to other FFI code.
+//!
+//! Every unsafe escape hatch of the buffer types lives in this module, so that audits have a single place to look.
+//! Each one has a checked counterpart that should be preferred wherever the cost of its checks is acceptable:
+//! the views of [`view`](crate::view) for the pointer casts, and [`Output::from_bytes_canonical`] for canonicality.
+//!
+//! The layout that the contracts rely on is asserted at compile time: every buffer type is exactly as large as its bytes,
+//! and aligned to [`BUFFER_ALIGNMENT`] bytes, so a slice of `n` blocks is `n` times as large as one block.
+
+use crate::buffer::{CompactOutput, Input, Output};
+use crate::constant::{BUFFER_ALIGNMENT, COMPACT_OUTPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE};
+
+const _: () = {
+    assert!(size_of::<Input>() == INPUT_BLOCK_SIZE);
+    assert!(size_of::<Output>() == OUTPUT_BLOCK_SIZE);
+    assert!(size_of::<CompactOutput>() == COMPACT_OUTPUT_BLOCK_SIZE);
+    assert!(align_of::<Input>() == BUFFER_ALIGNMENT);
+    assert!(align_of::<Output>() == BUFFER_ALIGNMENT);
+    assert!(align_of::<CompactOutput>() == BUFFER_ALIGNMENT);
+};
+
+/// Whether `num_blocks` blocks of `block_size` bytes each fit in a single allocation, i.e. in [`isize::MAX`] bytes
+fn fits_in_allocation(num_blocks: usize, block_size: usize) -> bool {
+    num_blocks.checked_mul(block_size).is_some_and(|len| len <= isize::MAX as usize)
+}
+
+impl Input {
+    /// Views the 256 bytes (2048 bit) at `ptr` as an input, without checking them;
+    /// the checked counterpart is [`InputRef::new`](crate::view::InputRef::new)
+    ///
+    /// # Safety
+    /// * `ptr` must be non-null and aligned to [`BUFFER_ALIGNMENT`] bytes
+    /// * `ptr` must be valid for reads of 256 bytes, all initialized, for the lifetime `'a`
+    /// * the bytes must not be written through any other pointer for the lifetime `'a`
+    pub unsafe fn from_raw_parts<'a>(ptr: *const u8) -> &'a Self {
+        debug_assert!(ptr as usize % BUFFER_ALIGNMENT == 0, "misaligned input at {:p}", ptr);
+        // SAFETY: `Input` consists of 256 bytes aligned to `BUFFER_ALIGNMENT`, which the caller guarantees of `ptr`
+        unsafe { &*ptr.cast::<Self>() }
+    }
+
+    /// Views the `num_blocks` consecutive inputs at `ptr`, without checking them;
+    /// the checked counterpart is [`InputsRef::new`](crate::view::InputsRef::new)
+    ///
+    /// # Safety
+    /// As for [`Input::from_raw_parts`], for `num_blocks * 256` bytes rather than `256`,
+    /// which must not exceed [`isize::MAX`]. When `num_blocks` is `0`, `ptr` must still be non-null and aligned
+    pub unsafe fn slice_from_raw_parts<'a>(ptr: *const u8, num_blocks: usize) -> &'a [Self] {
+        debug_assert!(ptr as usize % BUFFER_ALIGNMENT == 0, "misaligned inputs at {:p}", ptr);
+        debug_assert!(fits_in_allocation(num_blocks, INPUT_BLOCK_SIZE), "{} inputs exceed `isize::MAX` bytes", num_blocks);
+        // SAFETY: consecutive `Input`s have no padding between them, and the caller upholds the rest
+        unsafe { std::slice::from_raw_parts(ptr.cast::<Self>(), num_blocks) }
+    }
+}
+
+impl Output {
+    /// Views the 128 bytes (1024 bit) at `ptr` as a hash value to be written, without checking them;
+    /// the checked counterpart is [`OutputMut::new`](crate::view::OutputMut::new)
+    ///
+    /// # Safety
+    /// * `ptr` must be non-null and aligned to [`BUFFER_ALIGNMENT`] bytes
+    /// * `ptr` must be valid for reads and writes of 128 bytes, all initialized, for the lifetime `'a`
+    /// * the bytes must not be accessed through any other pointer for the lifetime `'a`
+    pub unsafe fn from_raw_parts_mut<'a>(ptr: *mut u8) -> &'a mut Self {
+        debug_assert!(ptr as usize % BUFFER_ALIGNMENT == 0, "misaligned output at {:p}", ptr);
+        // SAFETY: `Output` consists of 128 bytes aligned to `BUFFER_ALIGNMENT`, which the caller guarantees of `ptr`
+        unsafe { &mut *ptr.cast::<Self>() }
+    }
+
+    /// Views the `num_blocks` consecutive hash values at `ptr` to be written, without checking them;
+    /// the checked counterpart is [`OutputsMut::new`](crate::view::OutputsMut::new)
+    ///
+    /// # Safety
+    /// As for [`Output::from_raw_parts_mut`], for `num_blocks * 128` bytes rather than `128`,
+    /// which must not exceed [`isize::MAX`]. When `num_blocks` is `0`, `ptr` must still be non-null and aligned
+    pub unsafe fn slice_from_raw_parts_mut<'a>(ptr: *mut u8, num_blocks: usize) -> &'a mut [Self] {
+        debug_assert!(ptr as usize % BUFFER_ALIGNMENT == 0, "misaligned outputs at {:p}", ptr);
+        debug_assert!(fits_in_allocation(num_blocks, OUTPUT_BLOCK_SIZE), "{} outputs exceed `isize::MAX` bytes", num_blocks);
+        // SAFETY: consecutive `Output`s have no padding between them, and the caller upholds the rest
+        unsafe { std::slice::from_raw_parts_mut(ptr.cast::<Self>(), num_blocks) }
+    }
+
+    /// Takes a hash value from its little endian encoding as is, without checking that it is canonical;
+    /// the checked counterpart is [`Output::from_bytes_canonical`]
+    ///
+    /// # Safety
+    /// Every element, read as a `16`-bit signed value, must lie in `[0, 256]`.
+    /// Nothing in this crate relies on that for memory safety yet, but it may in the future,
+    /// e.g. to index tables by elements; until then, violating it gives wrong hash values rather than undefined behaviour
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded hash value, of size 128 bytes (1024 bit)
+    pub unsafe fn assume_canonical(bytes: &[u8; OUTPUT_BLOCK_SIZE]) -> Self {
+        let output = Self([*bytes]);
+        debug_assert!(output.is_canonical(), "non-canonical hash value");
+        output
+    }
+}
@@ -0,0 +1,81 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Wiping buffers that hold secrets such as keys, behind the `zeroize` feature.
+//!
+//! Every [`AlignedBuffer`] implements [`Zeroize`], overwriting all of its chunks with volatile writes
+//! that the compiler cannot elide, and a [`SecretInput`] wipes its input when it is dropped.
+//! Only the buffer itself is wiped: copies of the secret made before it was wrapped, e.g. the input it was
+//! created from, must be wiped by whoever made them
+//!
+//! ```
+//! use libswifft::buffer::secret::SecretInput;
+//! use libswifft::buffer::Output;
+//! use libswifft::hash::compute;
+//!
+//! let mut input = SecretInput::new();
+//! input.0[0][..8].copy_from_slice(b"password");
+//! let mut output = Output::default();
+//! compute(&input, &mut output);
+//! // the input is wiped here, as it goes out of scope
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::buffer::{AlignedBuffer, Input};
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Zeroize for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    /// Overwrites every chunk with zeros, in writes that are not optimized away
+    fn zeroize(&mut self) {
+        self.as_bytes_mut().zeroize()
+    }
+}
+
+/// An input holding a secret, which is wiped when it is dropped.
+///
+/// It dereferences to the [`Input`], so it can be passed to the hashing functions as is.
+/// The secret is deliberately not printed by `Debug`, which is not implemented
+#[derive(Clone, Default)]
+pub struct SecretInput {
+    input: Input,
+}
+
+/// A sign input holding a secret, which is wiped when it is dropped
+pub type SecretSignInput = SecretInput;
+
+impl SecretInput {
+    /// Creates a zero-initialized secret input, to write the secret into
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Input> for SecretInput {
+    /// Wraps `input`, which is moved rather than copied, though the compiler may still leave a copy behind
+    fn from(input: Input) -> Self {
+        Self { input }
+    }
+}
+
+impl Deref for SecretInput {
+    type Target = Input;
+
+    fn deref(&self) -> &Self::Target {
+        &self.input
+    }
+}
+
+impl DerefMut for SecretInput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.input
+    }
+}
+
+impl Drop for SecretInput {
+    fn drop(&mut self) {
+        self.input.zeroize()
+    }
+}
+
+impl ZeroizeOnDrop for SecretInput {}
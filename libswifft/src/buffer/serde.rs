@@ -0,0 +1,84 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Serializing buffers, behind the `serde` feature: as contiguous lowercase hex in human-readable formats,
+//! and as raw bytes otherwise. Deserializing checks the length, and copies into a fresh buffer so that it is aligned.
+//! Hash values are only deserialized with canonical elements, as [`Output::from_bytes_canonical`](crate::buffer::Output::from_bytes_canonical)
+//! parses them
+
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+
+use serde::de::{Error, SeqAccess, Unexpected, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::buffer::{check_canonical, decode_hex, AlignedBuffer};
+use crate::constant::OUTPUT_BLOCK_SIZE;
+
+impl<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Serialize for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{:x}", self))
+        } else {
+            serializer.serialize_bytes(self.as_bytes())
+        }
+    }
+}
+
+impl<'de, const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Deserialize<'de> for AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(BufferVisitor(PhantomData))
+        } else {
+            deserializer.deserialize_bytes(BufferVisitor(PhantomData))
+        }
+    }
+}
+
+/// Visits the hex or the bytes of an [`AlignedBuffer`]
+struct BufferVisitor<const CHUNK_SIZE: usize, const NUM_CHUNKS: usize>(PhantomData<AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS>>);
+
+impl<'de, const CHUNK_SIZE: usize, const NUM_CHUNKS: usize> Visitor<'de> for BufferVisitor<CHUNK_SIZE, NUM_CHUNKS> {
+    type Value = AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS>;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let len = CHUNK_SIZE * NUM_CHUNKS;
+        write!(f, "{} bytes, or {} lowercase hex digits", len, 2 * len)
+    }
+
+    fn visit_str<E: Error>(self, hex: &str) -> Result<Self::Value, E> {
+        let mut buffer = AlignedBuffer::default();
+        if hex.len() != 2 * buffer.len() {
+            return Err(E::invalid_length(hex.len(), &self))
+        }
+        decode_hex(hex, buffer.as_bytes_mut()).ok_or_else(|| E::invalid_value(Unexpected::Str(hex), &self))?;
+        canonical(buffer)
+    }
+
+    fn visit_bytes<E: Error>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+        let mut buffer = AlignedBuffer::default();
+        buffer.copy_from_slice(bytes).map_err(|_| E::invalid_length(bytes.len(), &self))?;
+        canonical(buffer)
+    }
+
+    /// Accepts the bytes as a sequence too, as formats without a native byte string give them
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut buffer = AlignedBuffer::default();
+        for (i, byte) in buffer.as_bytes_mut().iter_mut().enumerate() {
+            *byte = seq.next_element()?.ok_or_else(|| A::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(A::Error::invalid_length(CHUNK_SIZE * NUM_CHUNKS + 1, &self))
+        }
+        canonical(buffer)
+    }
+}
+
+/// Rejects hash values, the buffers of [`OUTPUT_BLOCK_SIZE`]-byte chunks, with any element outside `[0, 256]`
+fn canonical<E: Error, const CHUNK_SIZE: usize, const NUM_CHUNKS: usize>(
+    buffer: AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS>,
+) -> Result<AlignedBuffer<CHUNK_SIZE, NUM_CHUNKS>, E> {
+    if CHUNK_SIZE == OUTPUT_BLOCK_SIZE {
+        check_canonical(buffer.as_bytes()).map_err(E::custom)?;
+    }
+    Ok(buffer)
+}
@@ -0,0 +1,240 @@
+//! Runtime introspection of the optional capabilities compiled into this crate,
+//! and of the instruction sets of the running CPU, for deployment decisions and diagnostics
+
+use std::fmt::{Display, Formatter};
+
+use crate::backend::NativeBackend;
+use crate::error::UnsupportedIsa;
+
+/// The widest x86 vector instruction set that the running CPU supports, as far as the native library uses them
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Isa {
+    /// AVX512F
+    Avx512,
+    /// AVX2
+    Avx2,
+    /// AVX
+    Avx,
+    /// None of the above, or not an x86 CPU
+    Scalar,
+}
+
+impl Isa {
+    /// Detects the widest instruction set of the running CPU
+    pub fn detect() -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            if std::is_x86_feature_detected!("avx512f") {
+                return Self::Avx512
+            }
+            if std::is_x86_feature_detected!("avx2") {
+                return Self::Avx2
+            }
+            if std::is_x86_feature_detected!("avx") {
+                return Self::Avx
+            }
+        }
+        Self::Scalar
+    }
+
+    /// The widest instruction set that the native library is compiled for, which the running CPU must support.
+    ///
+    /// The native library is compiled with `-march=native`, so this is the widest instruction set of the build
+    /// machine, or AVX, the least that the native library compiles with, if the crate was cross compiled
+    pub fn native() -> Self {
+        match env!("LIBSWIFFT_NATIVE_ISA") {
+            "avx512" => Self::Avx512,
+            "avx2" => Self::Avx2,
+            _ => Self::Avx,
+        }
+    }
+
+    /// Whether a CPU with this instruction set can run code compiled for `other`
+    ///
+    /// # Arguments
+    /// * `other` - the instruction set that the code is compiled for
+    pub fn includes(self, other: Self) -> bool {
+        self.width() >= other.width()
+    }
+
+    fn width(self) -> u8 {
+        match self {
+            Self::Avx512 => 3,
+            Self::Avx2 => 2,
+            Self::Avx => 1,
+            Self::Scalar => 0,
+        }
+    }
+}
+
+impl Display for Isa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Avx512 => "avx512",
+            Self::Avx2 => "avx2",
+            Self::Avx => "avx",
+            Self::Scalar => "scalar",
+        })
+    }
+}
+
+/// Whether the running CPU can run the native library, see [`swifft_runtime_support`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IsetSupport {
+    /// The widest instruction set of the running CPU
+    pub detected: Isa,
+    /// The widest instruction set that the native library is compiled for
+    pub required: Isa,
+}
+
+impl IsetSupport {
+    /// Whether the running CPU supports every instruction set that the native library is compiled for
+    pub fn is_supported(&self) -> bool {
+        self.detected.includes(self.required)
+    }
+
+    /// Selects the backend to hash with on the running CPU.
+    ///
+    /// There is no portable fallback: the pure Rust implementation is parameterised differently,
+    /// so its hash values are not those of the native library
+    ///
+    /// # Errors
+    /// Returns [`UnsupportedIsa`] if the running CPU cannot run the native library,
+    /// which would otherwise fail with an illegal instruction
+    pub fn backend(&self) -> Result<NativeBackend, UnsupportedIsa> {
+        if self.is_supported() {
+            Ok(NativeBackend)
+        } else {
+            Err(UnsupportedIsa { required: self.required, detected: self.detected })
+        }
+    }
+}
+
+/// Detects whether the running CPU supports the instruction sets that the native library is compiled for
+///
+/// ```
+/// let support = libswifft::capabilities::swifft_runtime_support();
+/// if let Err(err) = support.backend() {
+///     eprintln!("{}", err);
+/// }
+/// ```
+pub fn swifft_runtime_support() -> IsetSupport {
+    IsetSupport { detected: Isa::detect(), required: Isa::native() }
+}
+
+/// The optional capabilities of this build of the crate, see [`capabilities`].
+///
+/// Fields may be added as features are, so it cannot be constructed or destructured exhaustively outside this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Whether hashing goes through the native library, which is always linked in
+    pub native_backend: bool,
+    /// The widest instruction set of the running CPU. The native library is compiled for the instruction sets
+    /// of the build machine, so this is what it can use at most, not necessarily what it does use
+    pub isa: Isa,
+    /// The `parallel` feature: batches are hashed on the `rayon` thread pool
+    pub parallel: bool,
+    /// The `paranoid` feature: the values emitted by the native library are checked
+    pub paranoid: bool,
+    /// The `analysis` feature
+    pub analysis: bool,
+    /// The `no-panic` feature
+    pub no_panic: bool,
+    /// The `bitvec` feature
+    pub bitvec: bool,
+    /// The `digest` feature
+    pub digest: bool,
+    /// The `registry` feature
+    pub registry: bool,
+    /// The `test-doubles` feature, which should never be enabled in deployments
+    pub test_doubles: bool,
+    /// The `allocator_api` feature
+    pub allocator_api: bool,
+    /// The `bytemuck` feature: zeroed arenas of blocks through `bytemuck`
+    pub bytemuck: bool,
+    /// The `rand` feature: random buffers drawn from a `rand` generator
+    pub rand: bool,
+    /// The `serde` feature: serialization of buffers
+    pub serde: bool,
+    /// The `zeroize` feature: wiping of buffers that hold secrets
+    pub zeroize: bool,
+    /// The `ct` feature of the `swifft` crate: constant-time field operations in the pure Rust implementation
+    pub swifft_ct: bool,
+    /// The `serde` feature of the `swifft` crate: serialization of its digests
+    pub swifft_serde: bool,
+}
+
+/// Reports the optional capabilities compiled into this crate, and detects the instruction sets of the running CPU
+///
+/// ```
+/// let capabilities = libswifft::capabilities();
+/// assert!(capabilities.native_backend);
+/// println!("{}", capabilities);
+/// ```
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        native_backend: true,
+        isa: Isa::detect(),
+        parallel: cfg!(feature = "parallel"),
+        paranoid: cfg!(feature = "paranoid"),
+        analysis: cfg!(feature = "analysis"),
+        no_panic: cfg!(feature = "no-panic"),
+        bitvec: cfg!(feature = "bitvec"),
+        digest: cfg!(feature = "digest"),
+        registry: cfg!(feature = "registry"),
+        test_doubles: cfg!(feature = "test-doubles"),
+        allocator_api: cfg!(feature = "allocator_api"),
+        bytemuck: cfg!(feature = "bytemuck"),
+        rand: cfg!(feature = "rand"),
+        serde: cfg!(feature = "serde"),
+        zeroize: cfg!(feature = "zeroize"),
+        swifft_ct: swifft::CT_ENABLED,
+        swifft_serde: swifft::SERDE_ENABLED,
+    }
+}
+
+impl Capabilities {
+    /// The names of the enabled features, in the order of the fields.
+    /// Features of the `swifft` crate are named as on the command line of `cargo`, e.g. `swifft/ct`
+    pub fn features(&self) -> impl Iterator<Item = &'static str> {
+        [
+            (self.parallel, "parallel"),
+            (self.paranoid, "paranoid"),
+            (self.analysis, "analysis"),
+            (self.no_panic, "no-panic"),
+            (self.bitvec, "bitvec"),
+            (self.digest, "digest"),
+            (self.registry, "registry"),
+            (self.test_doubles, "test-doubles"),
+            (self.allocator_api, "allocator_api"),
+            (self.bytemuck, "bytemuck"),
+            (self.rand, "rand"),
+            (self.serde, "serde"),
+            (self.zeroize, "zeroize"),
+            (self.swifft_ct, "swifft/ct"),
+            (self.swifft_serde, "swifft/serde"),
+        ].into_iter().filter_map(|(enabled, name)| enabled.then_some(name))
+    }
+}
+
+impl Display for Capabilities {
+    /// Formats the capabilities as a single diagnostic line,
+    /// e.g. `libswifft 0.2.0 backend=native isa=avx2 features=parallel,bitvec`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "libswifft {} backend={} isa={} features=", env!("CARGO_PKG_VERSION"),
+            if self.native_backend { "native" } else { "none" }, self.isa)?;
+        let mut features = self.features().peekable();
+        if features.peek().is_none() {
+            return f.write_str("none")
+        }
+        for (i, feature) in features.enumerate() {
+            if i > 0 {
+                f.write_str(",")?
+            }
+            f.write_str(feature)?
+        }
+        Ok(())
+    }
+}
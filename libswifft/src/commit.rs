@@ -0,0 +1,155 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Additively homomorphic commitments to short messages, from the linearity of SWIFFT.
+//!
+//! The commitment to a message `m` of at most [`MESSAGE_SIZE`] bytes with the randomness `r` of [`RANDOMNESS_SIZE`]
+//! bytes is the hash value of a single input block holding `m`, zero padded, in bytes `0..223`, `r` in bytes `223..255`,
+//! and the domain byte `4` in byte `255`. SWIFFT is linear in the bits of its input, and the three parts occupy
+//! disjoint bits, so this is `H(m) + H(r) + H(d)`: the randomness is kept apart from the message by its position,
+//! and the domain byte tells commitments apart from the blocks of [`hash_bytes`](crate::hash::hash_bytes).
+//! Commitments stay full hash values in `Z_{257}^{64}`, since compacting them would lose the linearity.
+//!
+//! Adding commitments adds the bits of the blocks they commit to, so the sum of `commit(m_1, r_1)` and
+//! `commit(m_2, r_2)` opens to both openings together, as checked by [`open_sum`].
+//!
+//! # Security
+//! * Binding: opening one commitment to two different padded messages is finding a collision of SWIFFT.
+//!   A sum of commitments, however, only binds the element-wise integer sum of the blocks of its openings,
+//!   so e.g. swapping messages between the openings, or moving a set bit from one opening to another, goes unnoticed
+//! * Hiding: nothing is proven. The randomness contributes only `256` bits to a `512`-bit hash value,
+//!   so the commitment is not statistically hiding, and SWIFFT is not known to be a pseudorandom function.
+//!   The randomness must be uniform, secret and never reused. With the `zeroize` feature, the input block
+//!   that holds it while committing is wiped afterwards
+//! * Messages are zero padded, so a message and the same message followed by zero bytes have the same commitment;
+//!   messages of varying length should be encoded with their length first
+//!
+//! ```
+//! use libswifft::commit::{commit, open, open_sum};
+//!
+//! let (r1, r2) = ([1; 32], [2; 32]);
+//! let c1 = commit(b"vote: yes", &r1);
+//! let c2 = commit(b"vote: no", &r2);
+//! assert!(open(&c1, b"vote: yes", &r1));
+//! assert!(!open(&c1, b"vote: no", &r1));
+//! assert!(open_sum(&(c1 + c2), &[(&b"vote: yes"[..], &r1), (&b"vote: no"[..], &r2)]));
+//! ```
+
+use std::ops::{Add, AddAssign, Sub, SubAssign};
+
+use crate::arithmetic::{add, sub};
+use crate::buffer::Output;
+use crate::constant::INPUT_BLOCK_SIZE;
+use crate::hash::compute;
+
+/// The maximum size of a message, in bytes
+pub const MESSAGE_SIZE: usize = INPUT_BLOCK_SIZE - RANDOMNESS_SIZE - 1;
+
+/// The size of the randomness of a commitment, in bytes
+pub const RANDOMNESS_SIZE: usize = 32;
+
+/// The last byte of the input block of a commitment
+const COMMIT_DOMAIN: u8 = 4;
+
+/// The input block of a commitment, which holds the randomness, so is wiped when dropped with the `zeroize` feature
+#[cfg(feature = "zeroize")]
+type CommitBlock = crate::buffer::secret::SecretInput;
+#[cfg(not(feature = "zeroize"))]
+type CommitBlock = crate::buffer::Input;
+
+/// A commitment to a message, or a sum or difference of commitments
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Commitment(Output);
+
+impl Commitment {
+    /// The commitment as a hash value, with elements in `[0, 256]`
+    pub fn as_output(&self) -> &Output {
+        &self.0
+    }
+}
+
+impl From<Output> for Commitment {
+    /// Treats a hash value as a commitment, e.g. as received from whoever committed
+    fn from(output: Output) -> Self {
+        Self(output)
+    }
+}
+
+impl Add for Commitment {
+    type Output = Commitment;
+
+    /// Commits to the openings of both commitments together
+    fn add(mut self, rhs: Self) -> Self::Output {
+        self += &rhs;
+        self
+    }
+}
+
+impl AddAssign<&Commitment> for Commitment {
+    fn add_assign(&mut self, rhs: &Commitment) {
+        add(&mut self.0, &rhs.0)
+    }
+}
+
+impl Sub for Commitment {
+    type Output = Commitment;
+
+    /// Removes the openings of `rhs` from those of this commitment
+    fn sub(mut self, rhs: Self) -> Self::Output {
+        self -= &rhs;
+        self
+    }
+}
+
+impl SubAssign<&Commitment> for Commitment {
+    fn sub_assign(&mut self, rhs: &Commitment) {
+        sub(&mut self.0, &rhs.0)
+    }
+}
+
+/// Commits to `message` with `randomness`
+///
+/// # Arguments
+/// * `message` - the message, of at most [`MESSAGE_SIZE`] bytes
+/// * `randomness` - uniformly random bytes, kept secret until the commitment is opened and never reused
+///
+/// # Panics
+/// If `message` is longer than [`MESSAGE_SIZE`] bytes
+pub fn commit(message: &[u8], randomness: &[u8; RANDOMNESS_SIZE]) -> Commitment {
+    assert!(message.len() <= MESSAGE_SIZE, "cannot commit to a message of {} bytes", message.len());
+    let mut input = CommitBlock::default();
+    input.0[0][..message.len()].copy_from_slice(message);
+    input.0[0][MESSAGE_SIZE..][..RANDOMNESS_SIZE].copy_from_slice(randomness);
+    input.0[0][INPUT_BLOCK_SIZE - 1] = COMMIT_DOMAIN;
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    Commitment(output)
+}
+
+/// Whether `commitment` is the commitment to `message` with `randomness`
+///
+/// # Arguments
+/// * `commitment` - the commitment to open
+/// * `message` - the message it should commit to, which never opens it if longer than [`MESSAGE_SIZE`] bytes
+/// * `randomness` - the randomness it should have been made with
+pub fn open(commitment: &Commitment, message: &[u8], randomness: &[u8; RANDOMNESS_SIZE]) -> bool {
+    open_sum(commitment, &[(message, randomness)])
+}
+
+/// Whether `commitment` is the sum of the commitments to every message of `openings` with its randomness,
+/// which only binds the element-wise sum of their blocks, see [Security](self#security).
+///
+/// The sums are compared through their [centered representations](Output::to_centered), so a commitment
+/// whose elements are not reduced, e.g. one made [`From`] an [`Output`], opens if it is congruent modulo `257`
+///
+/// # Arguments
+/// * `commitment` - the commitment to open, e.g. a sum of commitments
+/// * `openings` - the messages and their randomness, which never open it if any message is longer than [`MESSAGE_SIZE`] bytes
+pub fn open_sum(commitment: &Commitment, openings: &[(&[u8], &[u8; RANDOMNESS_SIZE])]) -> bool {
+    if openings.iter().any(|(message, _)| message.len() > MESSAGE_SIZE) {
+        return false
+    }
+    let sum = openings.iter()
+        .map(|(message, randomness)| commit(message, randomness))
+        .fold(Commitment(Output::default()), |sum, commitment| sum + commitment);
+    sum.0.to_centered() == commitment.0.to_centered()
+}
@@ -5,3 +5,14 @@ pub const INPUT_SIZE: usize = N * M;
 pub const INPUT_BLOCK_SIZE: usize = INPUT_SIZE / u8::BITS as usize;
 pub const OUTPUT_BLOCK_SIZE: usize = 2*N;
 pub const COMPACT_OUTPUT_BLOCK_SIZE: usize = 512 / u8::BITS as usize;
+
+/// The alignment in bytes that the native library requires of every buffer, `SWIFFT_ALIGNMENT`,
+/// as its vectorized loads and stores fault on misaligned addresses
+pub const BUFFER_ALIGNMENT: usize = 64;
+
+/// The maximum number of blocks passed to a single `*Multiple` FFI call.
+///
+/// The native library takes the block count as an `int` and computes byte offsets as
+/// `block * INPUT_BLOCK_SIZE` in `int` arithmetic, so larger batches are split into
+/// chunks of at most this many blocks.
+pub const MAX_FFI_BLOCKS: usize = std::ffi::c_int::MAX as usize / INPUT_BLOCK_SIZE;
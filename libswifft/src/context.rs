@@ -0,0 +1,212 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Hashing in hot loops with scratch buffers that are allocated once and reused by every call.
+//!
+//! The stateless functions of [`hash`](crate::hash) build their aligned input and output blocks afresh for every
+//! message, and [`hash_bytes`](crate::hash::hash_bytes) allocates the blocks of its leaves on the heap.
+//! A [`SwifftContext`] owns those blocks instead, so after it is constructed hashing never allocates,
+//! whatever the length of the message. The results are identical to those of the stateless functions.
+//!
+//! [`verify`](crate::hash::verify) and [`verify_reader`](crate::hash::verify_reader) hash through a context of their own,
+//! so that checking a message of any length takes a fixed number of allocations.
+//!
+//! A context is [`Send`] but hashes through `&mut self`, so the usual pattern is one context per worker thread
+//!
+//! ```
+//! use libswifft::context::SwifftContext;
+//! use libswifft::hash::hash_bytes;
+//!
+//! let mut ctx = SwifftContext::new();
+//! for message in [&b"alice"[..], b"bob", b"carol"] {
+//!     assert_eq!(ctx.hash_bytes(message), hash_bytes(message));
+//! }
+//! ```
+
+use std::io::{ErrorKind, Read};
+
+use crate::backend::{infallible, NativeBackend};
+use crate::buffer::{CompactOutput, Digest512, Input, Output};
+use crate::hash::{
+    compact, compact_slice, compute, compute_slice, write_node_block, MessageTree, LEAF_DOMAIN,
+    MESSAGE_BATCH_BLOCKS, MESSAGE_BYTES_PER_BLOCK
+};
+
+/// Reusable scratch buffers for hashing, for one thread at a time.
+///
+/// Every call starts from a clean state, so the result of a call never depends on the calls before it
+pub struct SwifftContext {
+    /// The leaf blocks of the batch being hashed
+    inputs: Vec<Input>,
+    /// The hash values of the leaf blocks of the batch being hashed
+    outputs: Vec<Output>,
+    /// The compact hash values of the leaf blocks of the batch being hashed
+    leaves: Vec<CompactOutput>,
+    /// The roots of the complete subtrees of the message being hashed
+    tree: MessageTree,
+    /// The block of the node being compressed
+    input: Input,
+    /// The hash value of the node being compressed
+    output: Output,
+}
+
+impl SwifftContext {
+    /// Creates a context that hashes the leaf blocks of a message `64` at a time, as [`hash_bytes`](crate::hash::hash_bytes) does
+    pub fn new() -> Self {
+        Self::with_batch_blocks(MESSAGE_BATCH_BLOCKS)
+    }
+
+    /// Creates a context that hashes the leaf blocks of a message `batch_blocks` at a time,
+    /// so that messages of up to `batch_blocks * 255` bytes take a single call into the native library.
+    /// Longer messages take several, but still never allocate
+    ///
+    /// # Arguments
+    /// * `batch_blocks` - the number of leaf blocks to hash at a time, at least `1`
+    ///
+    /// # Panics
+    /// If `batch_blocks` is `0`
+    pub fn with_batch_blocks(batch_blocks: usize) -> Self {
+        assert_ne!(batch_blocks, 0, "cannot hash batches of zero blocks");
+        Self {
+            inputs: vec![Input::default(); batch_blocks],
+            outputs: vec![Output::default(); batch_blocks],
+            leaves: vec![CompactOutput::default(); batch_blocks],
+            tree: MessageTree::with_max_roots(),
+            input: Input::default(),
+            output: Output::default(),
+        }
+    }
+
+    /// The number of leaf blocks hashed at a time
+    pub fn batch_blocks(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// Hashes a message of any length, as [`hash_bytes`](crate::hash::hash_bytes) does
+    ///
+    /// # Arguments
+    /// * `data` - the message, of any length
+    pub fn hash_bytes(&mut self, data: &[u8]) -> Digest512 {
+        self.tree.clear();
+        if data.is_empty() {
+            self.push_leaves(data);
+        }
+        for batch in data.chunks(self.batch_blocks() * MESSAGE_BYTES_PER_BLOCK) {
+            self.push_leaves(batch);
+        }
+        infallible(self.tree.finalize(data.len() as u64, &NativeBackend)).compact()
+    }
+
+    /// Hashes the message read from `reader` until its end, as [`hash_reader`](crate::hash::hash_reader) does,
+    /// reading it straight into the leaf blocks of the context
+    ///
+    /// # Arguments
+    /// * `reader` - the source of the message
+    ///
+    /// # Errors
+    /// The first error of `reader`, other than [`ErrorKind::Interrupted`] which is retried
+    pub fn hash_reader(&mut self, mut reader: impl Read) -> std::io::Result<Digest512> {
+        self.tree.clear();
+        let mut len = 0u64;
+        let mut end = false;
+        while !end {
+            let mut num_leaves = 0;
+            while num_leaves < self.batch_blocks() && !end {
+                let input = &mut self.inputs[num_leaves];
+                input.0[0].fill(0);
+                input.0[0][MESSAGE_BYTES_PER_BLOCK] = LEAF_DOMAIN;
+                let chunk_len = read_chunk(&mut reader, &mut input.0[0][..MESSAGE_BYTES_PER_BLOCK])?;
+                len += chunk_len as u64;
+                end = chunk_len < MESSAGE_BYTES_PER_BLOCK;
+                // a partial chunk is the last leaf, and an empty one only if the whole message is empty
+                if chunk_len > 0 || len == 0 {
+                    num_leaves += 1;
+                }
+            }
+            self.hash_leaves(num_leaves);
+        }
+        Ok(infallible(self.tree.finalize(len, &NativeBackend)).compact())
+    }
+
+    /// Compresses two compact hash values into one hash value, as [`compress`](crate::hash::compress) does
+    ///
+    /// # Arguments
+    /// * `left` - the compact hash value on the left
+    /// * `right` - the compact hash value on the right
+    pub fn compress(&mut self, left: &CompactOutput, right: &CompactOutput) -> Output {
+        write_node_block(left, right, &mut self.input);
+        compute(&self.input, &mut self.output);
+        self.output.clone()
+    }
+
+    /// Compresses two compact hash values into one compact hash value,
+    /// as [`compress_compact`](crate::hash::compress_compact) does
+    ///
+    /// # Arguments
+    /// * `left` - the compact hash value on the left
+    /// * `right` - the compact hash value on the right
+    pub fn compress_compact(&mut self, left: &CompactOutput, right: &CompactOutput) -> CompactOutput {
+        write_node_block(left, right, &mut self.input);
+        compute(&self.input, &mut self.output);
+        let mut node = CompactOutput::default();
+        compact(&self.output, &mut node);
+        node
+    }
+
+    /// Hashes the chunks of `batch` as the next leaves of the message, or a single empty leaf for an empty batch
+    fn push_leaves(&mut self, batch: &[u8]) {
+        let num_leaves = batch.len().div_ceil(MESSAGE_BYTES_PER_BLOCK).max(1);
+        let inputs = &mut self.inputs[..num_leaves];
+        for input in inputs.iter_mut() {
+            input.0[0].fill(0);
+            input.0[0][MESSAGE_BYTES_PER_BLOCK] = LEAF_DOMAIN;
+        }
+        for (input, chunk) in inputs.iter_mut().zip(batch.chunks(MESSAGE_BYTES_PER_BLOCK)) {
+            input.0[0][..chunk.len()].copy_from_slice(chunk);
+        }
+        self.hash_leaves(num_leaves);
+    }
+
+    /// Hashes the first `num_leaves` leaf blocks as the next leaves of the message
+    fn hash_leaves(&mut self, num_leaves: usize) {
+        let inputs = &self.inputs[..num_leaves];
+        compute_slice(inputs, &mut self.outputs[..num_leaves]).unwrap();
+        compact_slice(&self.outputs[..num_leaves], &mut self.leaves[..num_leaves]).unwrap();
+        for leaf in &self.leaves[..num_leaves] {
+            infallible(self.tree.push_leaf(leaf.clone(), &NativeBackend));
+        }
+    }
+}
+
+impl Clone for SwifftContext {
+    /// Copies the context, with the same room for roots as a new one, so that the copy never allocates either
+    fn clone(&self) -> Self {
+        Self {
+            inputs: self.inputs.clone(),
+            outputs: self.outputs.clone(),
+            leaves: self.leaves.clone(),
+            tree: self.tree.clone_with_max_roots(),
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+}
+
+impl Default for SwifftContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reads from `reader` until `chunk` is full or the message ends, returning the number of bytes read
+fn read_chunk(reader: &mut impl Read, chunk: &mut [u8]) -> std::io::Result<usize> {
+    let mut filled = 0;
+    while filled < chunk.len() {
+        match reader.read(&mut chunk[filled..]) {
+            Ok(0) => break,
+            Ok(len) => filled += len,
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
@@ -0,0 +1,236 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Sorted, deduplicated sets of compact hash values, for building indices over large batches of digests
+
+use std::cmp::Ordering;
+use std::iter::FusedIterator;
+
+use crate::buffer::CompactOutput;
+use crate::constant::COMPACT_OUTPUT_BLOCK_SIZE;
+
+/// The number of `u64` words that a compact hash value is compared by
+const WORDS: usize = COMPACT_OUTPUT_BLOCK_SIZE / size_of::<u64>();
+
+/// Orders compact hash values lexicographically by their bytes,
+/// comparing them a big endian `u64` word at a time rather than a byte at a time
+///
+/// # Arguments
+/// * `a` - the compact hash value on the left
+/// * `b` - the compact hash value on the right
+pub fn compare(a: &CompactOutput, b: &CompactOutput) -> Ordering {
+    let (a, b) = (&a.0[0], &b.0[0]);
+    for i in 0..WORDS {
+        let word = |bytes: &[u8; COMPACT_OUTPUT_BLOCK_SIZE]| {
+            u64::from_be_bytes(bytes[8 * i..8 * i + 8].try_into().unwrap())
+        };
+        match word(a).cmp(&word(b)) {
+            Ordering::Equal => continue,
+            ordering => return ordering,
+        }
+    }
+    Ordering::Equal
+}
+
+/// A set of compact hash values, kept as a sorted vector without duplicates in the order of [`compare`].
+///
+/// Unlike a `BTreeSet<[u8; 64]>`, the digests are stored contiguously, so building the set from a batch
+/// with [`from_unsorted`](Self::from_unsorted) is a single sort, and lookups are binary searches over the vector.
+/// Inserting a single digest is linear in the size of the set, so large sets should be built in batches
+/// and combined with [`merge`](Self::merge).
+///
+/// ```
+/// use libswifft::buffer::{AlignedBuffer, CompactOutput};
+/// use libswifft::digest_set::DigestSet;
+///
+/// let digest = |byte| -> CompactOutput { AlignedBuffer::new(byte) };
+/// let set = DigestSet::from_unsorted(vec![digest(3), digest(1), digest(3)]);
+/// assert_eq!(set.len(), 2);
+/// assert!(set.contains(&digest(1)));
+/// assert!(!set.contains(&digest(2)));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DigestSet(Vec<CompactOutput>);
+
+impl DigestSet {
+    /// Creates an empty set
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates an empty set with room for `capacity` digests
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Creates a set of the given digests, in any order and with any duplicates,
+    /// using an unstable sort that reuses their allocation
+    ///
+    /// # Arguments
+    /// * `digests` - the compact hash values to collect
+    pub fn from_unsorted(mut digests: Vec<CompactOutput>) -> Self {
+        digests.sort_unstable_by(compare);
+        digests.dedup_by(|a, b| compare(a, b).is_eq());
+        Self(digests)
+    }
+
+    /// Inserts a digest, keeping the set sorted
+    ///
+    /// # Arguments
+    /// * `digest` - the compact hash value to insert
+    ///
+    /// Returns whether the digest was not already in the set
+    pub fn insert(&mut self, digest: CompactOutput) -> bool {
+        match self.search(&digest) {
+            Ok(_) => false,
+            Err(index) => {
+                self.0.insert(index, digest);
+                true
+            }
+        }
+    }
+
+    /// Whether the set holds `digest`, found by binary search
+    pub fn contains(&self, digest: &CompactOutput) -> bool {
+        self.search(digest).is_ok()
+    }
+
+    /// Adds every digest of `other` to this set, in time linear in the sizes of both sets
+    ///
+    /// # Arguments
+    /// * `other` - the set to merge into this one
+    pub fn merge(&mut self, other: &Self) {
+        let mut merged = Vec::with_capacity(self.0.len() + other.0.len());
+        let mut left = std::mem::take(&mut self.0).into_iter().peekable();
+        let mut right = other.0.iter().peekable();
+        loop {
+            let ordering = match (left.peek(), right.peek()) {
+                (Some(a), Some(b)) => compare(a, b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Less => merged.extend(left.next()),
+                Ordering::Greater => merged.extend(right.next().cloned()),
+                Ordering::Equal => {
+                    merged.extend(left.next());
+                    right.next();
+                }
+            }
+        }
+        self.0 = merged;
+    }
+
+    /// Iterates, in order, over the digests held by both this set and `other`
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a> {
+        Intersection { left: &self.0, right: &other.0 }
+    }
+
+    /// Iterates, in order, over the digests held by this set but not by `other`
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a> {
+        Difference { left: &self.0, right: &other.0 }
+    }
+
+    /// Iterates over the digests in order
+    pub fn iter(&self) -> std::slice::Iter<'_, CompactOutput> {
+        self.0.iter()
+    }
+
+    /// The digests in order, as a slice
+    pub fn as_slice(&self) -> &[CompactOutput] {
+        &self.0
+    }
+
+    /// The number of digests in the set
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the set holds no digests
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn search(&self, digest: &CompactOutput) -> Result<usize, usize> {
+        self.0.binary_search_by(|probe| compare(probe, digest))
+    }
+}
+
+impl<'a> IntoIterator for &'a DigestSet {
+    type Item = &'a CompactOutput;
+    type IntoIter = std::slice::Iter<'a, CompactOutput>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<CompactOutput> for DigestSet {
+    fn from_iter<I: IntoIterator<Item = CompactOutput>>(iter: I) -> Self {
+        Self::from_unsorted(iter.into_iter().collect())
+    }
+}
+
+impl From<DigestSet> for Vec<CompactOutput> {
+    fn from(set: DigestSet) -> Self {
+        set.0
+    }
+}
+
+/// The iterator of [`DigestSet::intersection`]
+pub struct Intersection<'a> {
+    left: &'a [CompactOutput],
+    right: &'a [CompactOutput],
+}
+
+impl<'a> Iterator for Intersection<'a> {
+    type Item = &'a CompactOutput;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let ([a, left @ ..], [b, right @ ..]) = (self.left, self.right) {
+            match compare(a, b) {
+                Ordering::Less => self.left = left,
+                Ordering::Greater => self.right = right,
+                Ordering::Equal => {
+                    (self.left, self.right) = (left, right);
+                    return Some(a)
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Intersection<'_> {}
+
+/// The iterator of [`DigestSet::difference`]
+pub struct Difference<'a> {
+    left: &'a [CompactOutput],
+    right: &'a [CompactOutput],
+}
+
+impl<'a> Iterator for Difference<'a> {
+    type Item = &'a CompactOutput;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let [a, left @ ..] = self.left {
+            match self.right {
+                [b, right @ ..] => match compare(a, b) {
+                    Ordering::Less => {
+                        self.left = left;
+                        return Some(a)
+                    }
+                    Ordering::Greater => self.right = right,
+                    Ordering::Equal => (self.left, self.right) = (left, right),
+                },
+                [] => {
+                    self.left = left;
+                    return Some(a)
+                }
+            }
+        }
+        None
+    }
+}
+
+impl FusedIterator for Difference<'_> {}
@@ -0,0 +1,543 @@
+//! Error types reported by the fallible APIs
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use crate::capabilities::Isa;
+use crate::tagged::Mode;
+
+/// The number of elements supplied, e.g. blocks or bytes, did not match the number required
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LenMismatch {
+    /// The number of elements that were required
+    pub expected: usize,
+    /// The number of elements that were supplied
+    pub actual: usize,
+}
+
+impl Display for LenMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "length mismatch: expected {} elements, got {}", self.expected, self.actual)
+    }
+}
+
+impl Error for LenMismatch {}
+
+impl LenMismatch {
+    /// Checks that `actual` elements were supplied where `expected` are required
+    pub(crate) fn check(expected: usize, actual: usize) -> Result<(), Self> {
+        if expected == actual { Ok(()) } else { Err(Self { expected, actual }) }
+    }
+}
+
+/// A value lay outside the range accepted for it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    /// The position of the offending value
+    pub index: usize,
+    /// The offending value
+    pub value: i16,
+}
+
+impl Display for OutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value {} at index {} is out of range", self.value, self.index)
+    }
+}
+
+impl Error for OutOfRange {}
+
+/// A trit was neither `-1`, `0` nor `1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TritError {
+    /// The position of the offending trit
+    pub index: usize,
+    /// The offending trit
+    pub value: i8,
+}
+
+impl Display for TritError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "value {} at index {} is not a trit", self.value, self.index)
+    }
+}
+
+impl Error for TritError {}
+
+/// A sign bit was set on a coefficient whose input bit is clear, i.e. a coefficient of `0`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StraySignBit {
+    /// The position of the offending coefficient
+    pub index: usize,
+}
+
+impl Display for StraySignBit {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sign bit {} is set on a zero coefficient", self.index)
+    }
+}
+
+impl Error for StraySignBit {}
+
+/// A block was addressed past the end of a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockIndexOutOfRange {
+    /// The index of the block addressed
+    pub index: usize,
+    /// The number of blocks of the buffer
+    pub num_blocks: usize,
+}
+
+impl Display for BlockIndexOutOfRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block index {} is out of range for {} blocks", self.index, self.num_blocks)
+    }
+}
+
+impl Error for BlockIndexOutOfRange {}
+
+/// More bytes were supplied than fit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooLong {
+    /// The largest number of bytes that fit
+    pub max: usize,
+    /// The number of bytes that were supplied
+    pub actual: usize,
+}
+
+impl Display for TooLong {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "too long: at most {} bytes fit, got {}", self.max, self.actual)
+    }
+}
+
+impl Error for TooLong {}
+
+/// More blocks were requested than a single allocation can hold, i.e. their bytes would exceed [`isize::MAX`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockCountOverflow {
+    /// The largest number of blocks that fit
+    pub max: usize,
+    /// The number of blocks that were requested
+    pub actual: usize,
+}
+
+impl Display for BlockCountOverflow {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "block count overflow: at most {} blocks fit, got {}", self.max, self.actual)
+    }
+}
+
+impl Error for BlockCountOverflow {}
+
+impl BlockCountOverflow {
+    /// Checks that `num_blocks` blocks of `block_size` bytes each fit in a single allocation
+    pub(crate) fn check(num_blocks: usize, block_size: usize) -> Result<(), Self> {
+        let max = isize::MAX as usize / block_size;
+        if num_blocks <= max { Ok(()) } else { Err(Self { max, actual: num_blocks }) }
+    }
+}
+
+/// A pure Rust SWIFFT input could not be converted into an input block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InteropError {
+    /// The number of pure Rust inputs did not match the number of input blocks
+    LenMismatch(LenMismatch),
+    /// A coefficient was neither `0` nor `1`
+    NonBinaryCoefficient {
+        /// The position of the offending input
+        input: usize,
+        /// The position of the offending polynomial within the input
+        polynomial: usize,
+        /// The position of the offending coefficient within the polynomial
+        coefficient: usize,
+    },
+}
+
+impl Display for InteropError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenMismatch(err) => Display::fmt(err, f),
+            Self::NonBinaryCoefficient { input, polynomial, coefficient } => write!(f,
+                "coefficient {} of polynomial {} of input {} is not binary", coefficient, polynomial, input),
+        }
+    }
+}
+
+impl Error for InteropError {}
+
+impl From<LenMismatch> for InteropError {
+    fn from(err: LenMismatch) -> Self {
+        Self::LenMismatch(err)
+    }
+}
+
+/// A foreign byte buffer could not be viewed as blocks that the native library can operate on in place
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewError {
+    /// The number of bytes did not match the size of the view
+    LenMismatch(LenMismatch),
+    /// The number of bytes was not a whole number of blocks
+    PartialBlock {
+        /// The size of each block in bytes
+        block_size: usize,
+        /// The number of bytes supplied
+        len: usize,
+    },
+    /// The buffer was not aligned as the native library requires
+    Misaligned {
+        /// The address of the buffer
+        address: usize,
+        /// The alignment required, in bytes
+        alignment: usize,
+    },
+}
+
+impl Display for ViewError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenMismatch(err) => Display::fmt(err, f),
+            Self::PartialBlock { block_size, len } => write!(f,
+                "{} bytes is not a whole number of {}-byte blocks", len, block_size),
+            Self::Misaligned { address, alignment } => write!(f,
+                "buffer at {:#x} is not {}-byte aligned", address, alignment),
+        }
+    }
+}
+
+impl Error for ViewError {}
+
+impl From<LenMismatch> for ViewError {
+    fn from(err: LenMismatch) -> Self {
+        Self::LenMismatch(err)
+    }
+}
+
+/// A byte slice could not be converted into a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionError {
+    /// The number of bytes or blocks did not match the size of the buffer
+    LenMismatch(LenMismatch),
+    /// An element of a hash value was outside `[0, 256]`
+    OutOfRange(OutOfRange),
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenMismatch(err) => Display::fmt(err, f),
+            Self::OutOfRange(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for ConversionError {}
+
+impl From<LenMismatch> for ConversionError {
+    fn from(err: LenMismatch) -> Self {
+        Self::LenMismatch(err)
+    }
+}
+
+impl From<OutOfRange> for ConversionError {
+    fn from(err: OutOfRange) -> Self {
+        Self::OutOfRange(err)
+    }
+}
+
+/// The SWIFFT backend could not hash, e.g. because the native library is unavailable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackendUnavailable;
+
+impl Display for BackendUnavailable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the SWIFFT backend is unavailable")
+    }
+}
+
+impl Error for BackendUnavailable {}
+
+/// The running CPU does not support the instruction sets that the native library is compiled for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedIsa {
+    /// The widest instruction set that the native library is compiled for
+    pub required: Isa,
+    /// The widest instruction set of the running CPU
+    pub detected: Isa,
+}
+
+impl Display for UnsupportedIsa {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "the native SWIFFT library requires {}, but the CPU only supports {}", self.required, self.detected)
+    }
+}
+
+impl Error for UnsupportedIsa {}
+
+/// The native library failed its known-answer tests, see [`self_test`](crate::testvectors::self_test)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelfTestError {
+    /// The running CPU cannot run the native library, so nothing was computed
+    UnsupportedIsa(UnsupportedIsa),
+    /// The native library computed an unexpected result for a test vector
+    Mismatch {
+        /// The position of the vector among the [`known_answers`](crate::testvectors::known_answers)
+        index: usize,
+        /// The mode of the vector, e.g. `compute`
+        mode: &'static str,
+    },
+}
+
+impl Display for SelfTestError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnsupportedIsa(err) => write!(f, "{}", err),
+            Self::Mismatch { index, mode } =>
+                write!(f, "the native SWIFFT library failed {} test vector {}", mode, index),
+        }
+    }
+}
+
+impl Error for SelfTestError {}
+
+impl From<UnsupportedIsa> for SelfTestError {
+    fn from(err: UnsupportedIsa) -> Self {
+        Self::UnsupportedIsa(err)
+    }
+}
+
+/// A tagged digest could not be decoded, or was compared against a digest of another mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagError {
+    /// The binary encoding did not have the size of an encoded tagged digest
+    LenMismatch(LenMismatch),
+    /// The mode byte is not known to this version of the crate, e.g. because a newer version encoded it
+    UnknownMode(u8),
+    /// The mode name is not known to this version of the crate, e.g. because a newer version encoded it
+    UnknownModeName,
+    /// The parameter hash is not that of the parameters of this crate
+    UnknownParameters(u64),
+    /// The string encoding was not of the form `swifft:v1:<mode>:<hex>`
+    Malformed,
+    /// The digests compared were produced by different modes
+    ModeMismatch {
+        /// The mode of the digest compared against
+        expected: Mode,
+        /// The mode of the digest compared
+        actual: Mode,
+    },
+}
+
+impl Display for TagError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenMismatch(err) => Display::fmt(err, f),
+            Self::UnknownMode(byte) => write!(f, "unknown mode byte {:#04x}", byte),
+            Self::UnknownModeName => write!(f, "unknown mode name"),
+            Self::UnknownParameters(hash) => write!(f, "unknown parameter hash {:#018x}", hash),
+            Self::Malformed => write!(f, "malformed tagged digest, expected `swifft:v1:<mode>:<hex>`"),
+            Self::ModeMismatch { expected, actual } => write!(f,
+                "cannot compare a {} digest against a {} digest", actual, expected),
+        }
+    }
+}
+
+impl Error for TagError {}
+
+impl From<LenMismatch> for TagError {
+    fn from(err: LenMismatch) -> Self {
+        Self::LenMismatch(err)
+    }
+}
+
+/// An update proof of a registry did not hold, or could not be decoded
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofError {
+    /// A root was not tagged as the root of a registry
+    Tag(TagError),
+    /// The proof does not start from the root before the update
+    OldRootMismatch,
+    /// The proof does not end at the root claimed after the update
+    NewRootMismatch,
+    /// The encoded proof was truncated, had trailing bytes, or an invalid flag
+    Malformed,
+}
+
+#[cfg(feature = "registry")]
+impl Display for ProofError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tag(err) => Display::fmt(err, f),
+            Self::OldRootMismatch => write!(f, "the proof does not start from the previous root"),
+            Self::NewRootMismatch => write!(f, "the proof does not end at the claimed root"),
+            Self::Malformed => write!(f, "malformed update proof"),
+        }
+    }
+}
+
+#[cfg(feature = "registry")]
+impl Error for ProofError {}
+
+#[cfg(feature = "registry")]
+impl From<TagError> for ProofError {
+    fn from(err: TagError) -> Self {
+        Self::Tag(err)
+    }
+}
+
+/// Any error reported by the fallible APIs of this crate, for callers that handle them alike.
+///
+/// Every fallible API reports its own, precise error type, which converts into this one,
+/// so that `?` can gather the errors of several APIs into a single `Result<_, SwifftError>`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SwifftError {
+    /// The number of elements supplied did not match the number of blocks operated on
+    LenMismatch(LenMismatch),
+    /// An element of a hash value was outside `[0, 256]`
+    OutOfRange(OutOfRange),
+    /// A coefficient of a signed input was not a trit
+    Trit(TritError),
+    /// A sign bit of a signed input was set on a zero coefficient
+    StraySignBit(StraySignBit),
+    /// A block was addressed past the end of a buffer
+    BlockIndexOutOfRange(BlockIndexOutOfRange),
+    /// More elements were supplied than fit
+    TooLong(TooLong),
+    /// More blocks were requested than fit in a single allocation
+    BlockCountOverflow(BlockCountOverflow),
+    /// A pure Rust SWIFFT input could not be converted into an input block
+    Interop(InteropError),
+    /// A foreign byte buffer could not be viewed as blocks, e.g. because it was misaligned
+    View(ViewError),
+    /// A byte slice could not be converted into a buffer
+    Conversion(ConversionError),
+    /// The SWIFFT backend could not hash
+    BackendUnavailable(BackendUnavailable),
+    /// The running CPU cannot run the native library
+    UnsupportedIsa(UnsupportedIsa),
+    /// The native library failed its known-answer tests
+    SelfTest(SelfTestError),
+    /// A tagged digest could not be decoded or compared
+    Tag(TagError),
+    /// An update proof of a registry did not hold, or could not be decoded
+    #[cfg(feature = "registry")]
+    Proof(ProofError),
+}
+
+impl Display for SwifftError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::LenMismatch(err) => Display::fmt(err, f),
+            Self::OutOfRange(err) => Display::fmt(err, f),
+            Self::Trit(err) => Display::fmt(err, f),
+            Self::StraySignBit(err) => Display::fmt(err, f),
+            Self::BlockIndexOutOfRange(err) => Display::fmt(err, f),
+            Self::TooLong(err) => Display::fmt(err, f),
+            Self::BlockCountOverflow(err) => Display::fmt(err, f),
+            Self::Interop(err) => Display::fmt(err, f),
+            Self::View(err) => Display::fmt(err, f),
+            Self::Conversion(err) => Display::fmt(err, f),
+            Self::BackendUnavailable(err) => Display::fmt(err, f),
+            Self::UnsupportedIsa(err) => Display::fmt(err, f),
+            Self::SelfTest(err) => Display::fmt(err, f),
+            Self::Tag(err) => Display::fmt(err, f),
+            #[cfg(feature = "registry")]
+            Self::Proof(err) => Display::fmt(err, f),
+        }
+    }
+}
+
+impl Error for SwifftError {}
+
+impl From<LenMismatch> for SwifftError {
+    fn from(err: LenMismatch) -> Self {
+        Self::LenMismatch(err)
+    }
+}
+
+impl From<OutOfRange> for SwifftError {
+    fn from(err: OutOfRange) -> Self {
+        Self::OutOfRange(err)
+    }
+}
+
+impl From<TritError> for SwifftError {
+    fn from(err: TritError) -> Self {
+        Self::Trit(err)
+    }
+}
+
+impl From<StraySignBit> for SwifftError {
+    fn from(err: StraySignBit) -> Self {
+        Self::StraySignBit(err)
+    }
+}
+
+impl From<BlockIndexOutOfRange> for SwifftError {
+    fn from(err: BlockIndexOutOfRange) -> Self {
+        Self::BlockIndexOutOfRange(err)
+    }
+}
+
+impl From<TooLong> for SwifftError {
+    fn from(err: TooLong) -> Self {
+        Self::TooLong(err)
+    }
+}
+
+impl From<BlockCountOverflow> for SwifftError {
+    fn from(err: BlockCountOverflow) -> Self {
+        Self::BlockCountOverflow(err)
+    }
+}
+
+impl From<InteropError> for SwifftError {
+    fn from(err: InteropError) -> Self {
+        Self::Interop(err)
+    }
+}
+
+impl From<ViewError> for SwifftError {
+    fn from(err: ViewError) -> Self {
+        Self::View(err)
+    }
+}
+
+impl From<ConversionError> for SwifftError {
+    fn from(err: ConversionError) -> Self {
+        Self::Conversion(err)
+    }
+}
+
+impl From<BackendUnavailable> for SwifftError {
+    fn from(err: BackendUnavailable) -> Self {
+        Self::BackendUnavailable(err)
+    }
+}
+
+impl From<UnsupportedIsa> for SwifftError {
+    fn from(err: UnsupportedIsa) -> Self {
+        Self::UnsupportedIsa(err)
+    }
+}
+
+impl From<SelfTestError> for SwifftError {
+    fn from(err: SelfTestError) -> Self {
+        Self::SelfTest(err)
+    }
+}
+
+impl From<TagError> for SwifftError {
+    fn from(err: TagError) -> Self {
+        Self::Tag(err)
+    }
+}
+
+#[cfg(feature = "registry")]
+impl From<ProofError> for SwifftError {
+    fn from(err: ProofError) -> Self {
+        Self::Proof(err)
+    }
+}
@@ -0,0 +1,68 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! The SWIFFT functions in expression position, returning their results by value
+
+use crate::buffer::{CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs, SignInput};
+use crate::hash;
+
+/// The SWIFFT functions, returning their results by value instead of writing them to an out-parameter,
+/// e.g. `Swifft::compact(&Swifft::compute(&input))`.
+///
+/// The buffer types are aligned to 64 bytes by their type, so the returned values stay aligned wherever they
+/// are moved to. Returning by value may copy the result once, and places it on the stack, so large batches
+/// are better hashed into a boxed buffer with the out-parameter functions of [`hash`]
+///
+/// ```
+/// use libswifft::buffer::Input;
+/// use libswifft::Swifft;
+///
+/// let digest = Swifft::compact(&Swifft::compute(&Input::new(0x5a)));
+/// assert_eq!(digest.0[0].len(), 64);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Swifft;
+
+impl Swifft {
+    /// Computes the result of a SWIFFT operation, as [`hash::compute`]
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit)
+    pub fn compute(input: &Input) -> Output {
+        hash::compute_owned(input)
+    }
+
+    /// Computes the result of a SWIFFT operation with sign bits, as [`hash::compute_signed`]
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit)
+    /// * `sign_input` - the sign bits corresponding to the input of 256 bytes (2048 bit)
+    pub fn compute_signed(input: &Input, sign_input: &SignInput) -> Output {
+        hash::compute_signed_owned(input, sign_input)
+    }
+
+    /// Computes the result of multiple SWIFFT operations, as [`hash::compute_multiple`]
+    ///
+    /// # Arguments
+    /// * `NUM_BLOCKS` - the number of blocks to operate on
+    /// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+    pub fn compute_multiple<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+        hash::compute_multiple_owned(input)
+    }
+
+    /// Compacts a hash value of SWIFFT, as [`hash::compact`]
+    ///
+    /// # Arguments
+    /// * `output` - the hash value of SWIFFT, of size 128 bytes (1024 bit)
+    pub fn compact(output: &Output) -> CompactOutput {
+        hash::compact_owned(output)
+    }
+
+    /// Compacts multiple hash values of SWIFFT, as [`hash::compact_multiple`]
+    ///
+    /// # Arguments
+    /// * `NUM_BLOCKS` - the number of blocks to operate on
+    /// * `output` - the blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+    pub fn compact_multiple<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>) -> CompactOutputs<NUM_BLOCKS> {
+        hash::compact_multiple_owned(output)
+    }
+}
@@ -3,14 +3,62 @@
 //! INPUTS AND OUTPUTS SHOULD BE LITTLE ENDIAN
 //! 0th pos = 0th power of polynomial
 //! 0th pos = 0th power of 257
+//!
+//! # Panics
+//! The hashing hot path never panics, so it is usable from signal handlers and allocator-free contexts.
+//! This covers [`compute`], [`compute_multiple`], [`compute_signed`] and [`compute_multiple_signed`]
+//! for any `NUM_BLOCKS` whose buffers fit in memory, every `*` and `*_multiple` wrapper in
+//! [`arithmetic`](crate::arithmetic), and [`swifft::hash::swifft_hash`] without the `parallel` feature.
+//!
+//! With the `no-panic` feature these functions are annotated with `no_panic` in builds without debug assertions,
+//! which fails to link whenever the optimizer cannot rule out a panic; `cargo xtask check-no-panic` builds such a target.
+//! The only exception is the `paranoid` feature, whose checks panic in debug builds by design.
+//!
+//! # Instruction sets
+//! The native library is compiled with `-march=native`, and only [`try_compute`] and [`try_compute_multiple`] check
+//! that the running CPU supports its instruction sets. Every other function here hashes unchecked, and fails with an
+//! illegal instruction on a CPU without them: the `compute*` and `compact*` functions and their owned variants,
+//! [`hash_bytes`], [`hash_reader`], [`Hasher`], and through them [`merkle`](crate::merkle),
+//! [`SwifftContext`] and the other high-level modules.
+//! To hash checked, select the backend with [`swifft_runtime_support`] once, and hash with it,
+//! e.g. with [`hash_bytes_with_backend`]:
+//!
+//! ```
+//! use libswifft::capabilities::swifft_runtime_support;
+//! use libswifft::hash::hash_bytes_with_backend;
+//!
+//! let backend = swifft_runtime_support().backend()?;
+//! let digest = hash_bytes_with_backend(b"message", &backend).unwrap_or_else(|never| match never {});
+//! # let _ = digest;
+//! # Ok::<(), libswifft::error::UnsupportedIsa>(())
+//! ```
+
+use std::ffi::c_int;
+use std::io::{ErrorKind, Read, Write};
+use std::mem::MaybeUninit;
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
 use crate::sys::{
     SWIFFT_Compact, SWIFFT_CompactMultiple, SWIFFT_Compute, SWIFFT_ComputeMultiple,
     SWIFFT_ComputeMultipleSigned, SWIFFT_ComputeSigned
 };
+use crate::arithmetic;
+use crate::backend::{infallible, NativeBackend, SwifftBackend};
+use crate::batch::{for_each_chunk, max_ffi_blocks};
+use crate::block::{Block, Compactable, Hashable};
 use crate::buffer::{
-    CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs, SignInput, SignInputs
+    AlignedBuffer, CompactOutput, CompactOutputs, Digest512, Input, Inputs, Output, Outputs, SignInput, SignInputs,
+    SignedInput
 };
+use crate::capabilities::swifft_runtime_support;
+use crate::context::SwifftContext;
+use crate::constant::{COMPACT_OUTPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE, INPUT_SIZE, OUTPUT_BLOCK_SIZE};
+use crate::error::{LenMismatch, TritError, UnsupportedIsa};
+use crate::paranoid::{debug_assert_canonical, debug_assert_ffi_buffer};
+use crate::tagged::{Mode, TaggedDigest};
+use crate::view::{InputRef, InputsRef, OutputMut, OutputsMut};
 
 /// Computes the result of a SWIFFT operation.
 /// The result is composable with other hash values.
@@ -18,10 +66,29 @@ use crate::buffer::{
 /// # Arguments
 /// * `input` - the input of 256 bytes (2048 bit)
 /// * `output` - the resulting hash value of SWIFFT, of size 128 bytes (1024 bit)
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn compute(input: &Input, output: &mut Output) {
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(output, "output");
     unsafe {
         SWIFFT_Compute(input.0[0].as_ptr(), output.0[0].as_mut_ptr())
     }
+    debug_assert_canonical(output);
+}
+
+/// Computes the result of a SWIFFT operation as [`compute`] does,
+/// if the running CPU supports the instruction sets that the native library is compiled for
+///
+/// # Arguments
+/// * `input` - the input of 256 bytes (2048 bit)
+/// * `output` - the resulting hash value of SWIFFT, of size 128 bytes (1024 bit)
+///
+/// # Errors
+/// Returns [`UnsupportedIsa`] without hashing if the running CPU cannot run the native library
+pub fn try_compute(input: &Input, output: &mut Output) -> Result<(), UnsupportedIsa> {
+    swifft_runtime_support().backend()?;
+    compute(input, output);
+    Ok(())
 }
 
 /// Computes the result of multiple SWIFFT operations.
@@ -31,11 +98,154 @@ pub fn compute(input: &Input, output: &mut Output) {
 /// * `NUM_BLOCKS` - the number of blocks to operate on
 /// * `input` - the blocks of input, each of 256 bytes (2048 bit)
 /// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn compute_multiple<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
                                                  output: &mut Outputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_ComputeMultiple(NUM_BLOCKS.try_into().unwrap(), input.0[0].as_ptr(), output.0[0].as_mut_ptr())
-    }
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(output, "output");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultiple(num_blocks, input.0[first..].as_ptr().cast(), output.0[first..].as_mut_ptr().cast())
+    });
+    debug_assert_canonical(output);
+}
+
+/// Computes the result of multiple SWIFFT operations as [`compute_multiple`] does,
+/// if the running CPU supports the instruction sets that the native library is compiled for
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+///
+/// # Errors
+/// Returns [`UnsupportedIsa`] without hashing if the running CPU cannot run the native library
+pub fn try_compute_multiple<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
+                                                     output: &mut Outputs<NUM_BLOCKS>) -> Result<(), UnsupportedIsa> {
+    swifft_runtime_support().backend()?;
+    compute_multiple(input, output);
+    Ok(())
+}
+
+/// Computes the result of multiple SWIFFT operations into an uninitialized output buffer,
+/// avoiding zero-initializing large batches only to overwrite them.
+/// The result is composable with other hash values.
+///
+/// This is sound because `SWIFFT_ComputeMultiple` writes every byte of each of the `NUM_BLOCKS` output blocks,
+/// so `output` is fully initialized once all chunks have been hashed.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the uninitialized buffer for the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+///
+/// Returns a reference to `output`, now initialized
+pub fn compute_multiple_uninit<'a, const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
+                                                            output: &'a mut MaybeUninit<Outputs<NUM_BLOCKS>>) -> &'a mut Outputs<NUM_BLOCKS> {
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    let blocks: *mut [u8; OUTPUT_BLOCK_SIZE] = output.as_mut_ptr().cast();
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultiple(num_blocks, input.0[first..].as_ptr().cast(), blocks.add(first).cast())
+    });
+    // SAFETY: every block of `output` has been written above
+    let output = unsafe { output.assume_init_mut() };
+    debug_assert_canonical(output);
+    output
+}
+
+/// Like [`compute_multiple`], but hashes contiguous chunks of blocks on the `rayon` thread pool,
+/// sized by [`parallel_chunk_blocks`] so that every thread hashes one chunk.
+/// The result is identical to that of [`compute_multiple`], whatever the number of threads.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+#[cfg(feature = "parallel")]
+pub fn compute_multiple_parallel<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
+                                                          output: &mut Outputs<NUM_BLOCKS>) {
+    compute_multiple_parallel_chunked(input, output, parallel_chunk_blocks(NUM_BLOCKS))
+}
+
+/// Like [`compute_multiple_parallel`], but with chunks of `chunk_blocks` blocks,
+/// e.g. to trade scheduling overhead against load balancing
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+/// * `chunk_blocks` - the number of blocks hashed by each task, raised to `1` if `0`; the last chunk may be shorter
+#[cfg(feature = "parallel")]
+pub fn compute_multiple_parallel_chunked<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
+                                                                  output: &mut Outputs<NUM_BLOCKS>,
+                                                                  chunk_blocks: usize) {
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(output, "output");
+    // every chunk starts at a multiple of the block sizes, so it stays aligned for the native library
+    let chunk_blocks = chunk_blocks.max(1);
+    input.0.par_chunks(chunk_blocks).zip(output.0.par_chunks_mut(chunk_blocks)).for_each(|(input, output)| {
+        for_each_chunk(input.len(), |first, num_blocks| unsafe {
+            SWIFFT_ComputeMultiple(num_blocks, input[first..].as_ptr().cast(), output[first..].as_mut_ptr().cast())
+        })
+    });
+    debug_assert_canonical(output);
+}
+
+/// The number of blocks per chunk that [`compute_multiple_parallel`] splits `num_blocks` blocks into,
+/// so that each thread of the current `rayon` thread pool hashes at most one chunk
+///
+/// # Arguments
+/// * `num_blocks` - the number of blocks to split
+#[cfg(feature = "parallel")]
+pub fn parallel_chunk_blocks(num_blocks: usize) -> usize {
+    num_blocks.div_ceil(rayon::current_num_threads()).max(1)
+}
+
+/// Like [`compute_multiple`], but with the number of blocks given at runtime by the lengths of the slices,
+/// for callers that cannot name `NUM_BLOCKS` at compile time.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `output` does not hold exactly one block per block of `input`,
+/// leaving `output` unmodified
+pub fn compute_slice(input: &[Input], output: &mut [Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(input.len(), output.len())?;
+    debug_assert_ffi_buffer(input.as_ptr(), "input");
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    for_each_chunk(input.len(), |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultiple(num_blocks, input[first..].as_ptr().cast(), output[first..].as_mut_ptr().cast())
+    });
+    output.iter().for_each(debug_assert_canonical);
+    Ok(())
+}
+
+/// Like [`compute`], but hashes a foreign byte buffer in place into another, as validated by [`view`](crate::view).
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the view of the input of 256 bytes (2048 bit)
+/// * `output` - the view of the resulting hash value of SWIFFT, of size 128 bytes (1024 bit)
+pub fn compute_ref(input: InputRef<'_>, mut output: OutputMut<'_>) {
+    compute(input.as_input(), output.as_output_mut())
+}
+
+/// Like [`compute_slice`], but hashes foreign byte buffers in place, as validated by [`view`](crate::view).
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the view of the blocks of input, each of 256 bytes (2048 bit)
+/// * `output` - the view of the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `output` does not view exactly one block per block of `input`,
+/// leaving `output` unmodified
+pub fn compute_multiple_ref(input: InputsRef<'_>, mut output: OutputsMut<'_>) -> Result<(), LenMismatch> {
+    compute_slice(input.as_inputs(), output.as_outputs_mut())
 }
 
 /// Computes the result of a SWIFFT operation.
@@ -45,10 +255,15 @@ pub fn compute_multiple<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
 /// * `input` - the input of 256 bytes (2048 bit)
 /// * `sign_input` - the sign bits corresponding to the input of 256 bytes (2048 bit)
 /// * `output` - the resulting hash value of SWIFFT, of size 128 bytes (1024 bit)
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn compute_signed(input: &Input, sign_input: &SignInput, output: &mut Output) {
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(sign_input, "sign_input");
+    debug_assert_ffi_buffer(output, "output");
     unsafe {
         SWIFFT_ComputeSigned(input.0[0].as_ptr(), sign_input.0[0].as_ptr(), output.0[0].as_mut_ptr())
     }
+    debug_assert_canonical(output);
 }
 
 /// Computes the result of multiple SWIFFT operations.
@@ -59,10 +274,928 @@ pub fn compute_signed(input: &Input, sign_input: &SignInput, output: &mut Output
 /// * `input` - the blocks of input, each of 256 bytes (2048 bit)
 /// * `sign_input` - the blocks of sign bits corresponding to blocks of input of 256 bytes (2048 bit)
 /// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
 pub fn compute_multiple_signed<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>, sign_input: &SignInputs<NUM_BLOCKS>, output: &mut Outputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_ComputeMultipleSigned(NUM_BLOCKS.try_into().unwrap(), input.0[0].as_ptr(), sign_input.0[0].as_ptr(), output.0[0].as_mut_ptr())
+    debug_assert_ffi_buffer(input, "input");
+    debug_assert_ffi_buffer(sign_input, "sign_input");
+    debug_assert_ffi_buffer(output, "output");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultipleSigned(num_blocks, input.0[first..].as_ptr().cast(),
+            sign_input.0[first..].as_ptr().cast(), output.0[first..].as_mut_ptr().cast())
+    });
+    debug_assert_canonical(output);
+}
+
+/// Like [`compute_multiple_signed`], but with the number of blocks given at runtime by the lengths of the slices,
+/// for callers that cannot name `NUM_BLOCKS` at compile time.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `sign_input` - the blocks of sign bits corresponding to blocks of input of 256 bytes (2048 bit)
+/// * `output` - the resulting blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `sign_input` or `output` does not hold exactly one block per block of `input`,
+/// leaving `output` unmodified
+pub fn compute_signed_slice(input: &[Input], sign_input: &[SignInput], output: &mut [Output]) -> Result<(), LenMismatch> {
+    LenMismatch::check(input.len(), sign_input.len())?;
+    LenMismatch::check(input.len(), output.len())?;
+    debug_assert_ffi_buffer(input.as_ptr(), "input");
+    debug_assert_ffi_buffer(sign_input.as_ptr(), "sign_input");
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    for_each_chunk(input.len(), |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultipleSigned(num_blocks, input[first..].as_ptr().cast(),
+            sign_input[first..].as_ptr().cast(), output[first..].as_mut_ptr().cast())
+    });
+    output.iter().for_each(debug_assert_canonical);
+    Ok(())
+}
+
+/// Packs coefficients in `{-1, 0, 1}` into an input and its sign bits, as [`compute_signed`] reads them:
+/// trit `i` is coefficient `i % 64` of input vector `i / 64`, stored least significant bit first as bit `i % 8`
+/// of byte `i / 8`. Its magnitude is the input bit, and the sign bit is set only for `-1`
+///
+/// # Arguments
+/// * `trits` - the coefficients of the `32` input vectors of `64` coefficients each
+///
+/// # Errors
+/// Returns [`TritError`] for the first element that is not a trit
+pub fn pack_trits(trits: &[i8; INPUT_SIZE]) -> Result<(Input, SignInput), TritError> {
+    let (mut input, mut sign_input) = (Input::default(), SignInput::default());
+    for (index, &value) in trits.iter().enumerate() {
+        let mask = 1 << (index % u8::BITS as usize);
+        let byte = index / u8::BITS as usize;
+        match value {
+            0 => {}
+            1 => input.0[0][byte] |= mask,
+            -1 => {
+                input.0[0][byte] |= mask;
+                sign_input.0[0][byte] |= mask;
+            }
+            _ => return Err(TritError { index, value }),
+        }
+    }
+    Ok((input, sign_input))
+}
+
+/// Unpacks an input and its sign bits into coefficients in `{-1, 0, 1}`, the inverse of [`pack_trits`].
+///
+/// A sign bit whose input bit is clear stands for `0`, as it does to [`compute_signed`],
+/// so it does not survive a round trip
+///
+/// # Arguments
+/// * `input` - the input of 256 bytes (2048 bit)
+/// * `sign_input` - the sign bits corresponding to the input of 256 bytes (2048 bit)
+pub fn unpack_trits(input: &Input, sign_input: &SignInput) -> [i8; INPUT_SIZE] {
+    std::array::from_fn(|index| {
+        let bit = |buffer: &Input| (buffer.0[0][index / u8::BITS as usize] >> (index % u8::BITS as usize)) & 1;
+        match (bit(input), bit(sign_input)) {
+            (0, _) => 0,
+            (_, 0) => 1,
+            _ => -1,
+        }
+    })
+}
+
+/// Computes the result of a SWIFFT operation on coefficients in `{-1, 0, 1}`, packed by [`pack_trits`]
+/// and hashed by [`compute_signed`].
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `trits` - the coefficients of the `32` input vectors of `64` coefficients each
+///
+/// # Errors
+/// Returns [`TritError`] for the first element that is not a trit
+pub fn compute_trits(trits: &[i8; INPUT_SIZE]) -> Result<Output, TritError> {
+    let (input, sign_input) = pack_trits(trits)?;
+    let mut output = Output::default();
+    compute_signed(&input, &sign_input, &mut output);
+    Ok(output)
+}
+
+/// Computes the result of a SWIFFT operation on a signed input, as [`compute_signed`] does,
+/// for inputs whose sign bits have been validated by [`SignedInput`].
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the input and its sign bits
+pub fn compute_signed_checked(input: &SignedInput) -> Output {
+    let mut output = Output::default();
+    compute_signed(input.input(), input.sign_input(), &mut output);
+    output
+}
+
+/// The number of blocks hashed at a time by [`compute_diff`], [`compute_diff_update`]
+/// and [`compute_and_compact_table`], before post-processing them
+pub const DIFF_BATCH_BLOCKS: usize = 64;
+
+/// Computes the result of multiple SWIFFT operations, returning only the hash values
+/// that differ, element-wise, from the corresponding blocks of `baseline`, together with their block index.
+///
+/// Blocks are hashed [`DIFF_BATCH_BLOCKS`] at a time into a reusable buffer,
+/// so only the changed hash values are ever copied out.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `baseline` - the previous blocks of hash values of SWIFFT to compare against
+pub fn compute_diff<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>, baseline: &Outputs<NUM_BLOCKS>) -> Vec<(usize, Output)> {
+    let mut changed = Vec::new();
+    for_each_batch(input, |first, batch| {
+        for (i, (block, previous)) in batch.iter().zip(&baseline.0[first..]).enumerate() {
+            if block != previous {
+                changed.push((first + i, AlignedBuffer([*block])))
+            }
+        }
+    });
+    changed
+}
+
+/// Computes the result of multiple SWIFFT operations, updating `baseline` in place
+/// and returning the indices of the blocks whose hash values changed, element-wise.
+///
+/// Blocks are hashed [`DIFF_BATCH_BLOCKS`] at a time into a reusable buffer,
+/// so only the changed hash values are ever copied into `baseline`.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `baseline` - the previous blocks of hash values of SWIFFT, overwritten with the new ones
+pub fn compute_diff_update<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>, baseline: &mut Outputs<NUM_BLOCKS>) -> Vec<usize> {
+    let mut changed = Vec::new();
+    for_each_batch(input, |first, batch| {
+        for (i, (block, previous)) in batch.iter().zip(&mut baseline.0[first..]).enumerate() {
+            if block != previous {
+                *previous = *block;
+                changed.push(first + i)
+            }
+        }
+    });
+    changed
+}
+
+/// Computes the result of multiple SWIFFT operations and compacts them in one pass,
+/// returning the compact hash values laid out contiguously on the heap, e.g. to write out as an index.
+///
+/// Blocks are hashed [`DIFF_BATCH_BLOCKS`] at a time into a reusable buffer,
+/// so the full hash values of all blocks are never held at once.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+pub fn compute_and_compact_table<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>) -> Box<CompactOutputs<NUM_BLOCKS>> {
+    let mut table = CompactOutputs::<NUM_BLOCKS>::new_boxed_uninit();
+    let blocks: *mut [u8; COMPACT_OUTPUT_BLOCK_SIZE] = table.as_mut_ptr().cast();
+    debug_assert_ffi_buffer(blocks, "table");
+    for_each_batch(input, |first, batch| unsafe {
+        SWIFFT_CompactMultiple(batch.len() as c_int, batch.as_ptr().cast(), blocks.add(first).cast())
+    });
+    // SAFETY: `SWIFFT_CompactMultiple` has written every block of `table` above
+    unsafe { Box::from_raw(Box::into_raw(table).cast()) }
+}
+
+/// Hashes `input` in batches of at most [`DIFF_BATCH_BLOCKS`] blocks,
+/// calling `f(first_block, hash_values)` for each batch
+fn for_each_batch<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>,
+                                                mut f: impl FnMut(usize, &[[u8; OUTPUT_BLOCK_SIZE]])) {
+    debug_assert_ffi_buffer(input, "input");
+    let batch_blocks = DIFF_BATCH_BLOCKS.min(max_ffi_blocks());
+    let mut batch = Outputs::<DIFF_BATCH_BLOCKS>::default();
+    let mut first = 0;
+    while first < NUM_BLOCKS {
+        let num_blocks = batch_blocks.min(NUM_BLOCKS - first);
+        unsafe {
+            SWIFFT_ComputeMultiple(num_blocks as c_int, input.0[first..].as_ptr().cast(), batch.0.as_mut_ptr().cast())
+        }
+        debug_assert_canonical(&batch);
+        f(first, &batch.0[..num_blocks]);
+        first += num_blocks;
+    }
+}
+
+/// The number of message bytes in each leaf block of [`hash_bytes`], the last byte of the block holding its domain
+pub const MESSAGE_BYTES_PER_BLOCK: usize = INPUT_BLOCK_SIZE - 1;
+
+/// The number of leaf blocks that [`hash_bytes`] and [`Hasher`] hash at a time
+pub(crate) const MESSAGE_BATCH_BLOCKS: usize = 64;
+
+/// The number of message bytes that a [`Hasher`] buffers before hashing their leaf blocks together
+const PENDING_SIZE: usize = MESSAGE_BATCH_BLOCKS * MESSAGE_BYTES_PER_BLOCK;
+
+/// Copies of message bytes, which may be secrets such as the key of a [`SwifftMac`](crate::mac::SwifftMac),
+/// wiped when dropped with the `zeroize` feature
+#[cfg(feature = "zeroize")]
+type MessageCopy<T> = zeroize::Zeroizing<T>;
+#[cfg(not(feature = "zeroize"))]
+type MessageCopy<T> = T;
+
+/// The last byte of the leaf blocks of [`hash_bytes`]
+pub(crate) const LEAF_DOMAIN: u8 = 0;
+
+/// The last byte of the node blocks of [`hash_bytes`]
+const NODE_DOMAIN: u8 = 1;
+
+/// The last byte of the final block of [`hash_bytes`]
+const FINAL_DOMAIN: u8 = 2;
+
+/// The last byte of the leaf blocks of the prefix that [`HashOptions`] add to the tree of [`hash_bytes`]
+const PREFIX_DOMAIN: u8 = 3;
+
+/// The size of the salt of [`HashOptions`], in bytes
+pub const SALT_SIZE: usize = 32;
+
+/// A salt and a domain that separate the hash values of [`hash_bytes_with`], [`hash_reader_with`] and
+/// [`Hasher::with_options`] from those of other salts and domains, e.g. of other steps of one protocol.
+///
+/// Both are encoded into a prefix of leaves before the leaves of the message: a byte of flags telling which of
+/// the salt and domain are present, then the salt, then the domain prefixed by its length in bytes
+/// as a little endian `u64`. The prefix is split into chunks of [`MESSAGE_BYTES_PER_BLOCK`] bytes like the message,
+/// but its leaf blocks end in a domain byte of their own, so they never hash like the leaves of a message.
+/// Without a salt or a domain there is no prefix, so the hash value is that of [`hash_bytes`]
+///
+/// ```
+/// use libswifft::hash::{hash_bytes, hash_bytes_with, HashOptions};
+///
+/// let signing = HashOptions::new().with_domain("example protocol: signing");
+/// let commitment = HashOptions::new().with_domain("example protocol: commitment");
+/// assert_ne!(hash_bytes_with(b"message", &signing), hash_bytes_with(b"message", &commitment));
+/// assert_eq!(hash_bytes_with(b"message", &HashOptions::new()), hash_bytes(b"message"));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashOptions<'a> {
+    /// The salt, if any
+    pub salt: Option<[u8; SALT_SIZE]>,
+    /// The domain, of any length, if any
+    pub domain: Option<&'a str>,
+}
+
+impl<'a> HashOptions<'a> {
+    /// Creates options without a salt or a domain
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the salt
+    ///
+    /// # Arguments
+    /// * `salt` - the salt
+    pub fn with_salt(self, salt: [u8; SALT_SIZE]) -> Self {
+        Self { salt: Some(salt), ..self }
+    }
+
+    /// Sets the domain
+    ///
+    /// # Arguments
+    /// * `domain` - the domain, of any length
+    pub fn with_domain(self, domain: &'a str) -> Self {
+        Self { domain: Some(domain), ..self }
+    }
+
+    /// The bytes hashed into the leaves of the prefix, none without a salt or a domain
+    fn prefix(&self) -> Vec<u8> {
+        if self.salt.is_none() && self.domain.is_none() {
+            return Vec::new()
+        }
+        let mut prefix = vec![self.salt.is_some() as u8 | (self.domain.is_some() as u8) << 1];
+        if let Some(salt) = &self.salt {
+            prefix.extend_from_slice(salt);
+        }
+        if let Some(domain) = self.domain {
+            prefix.extend_from_slice(&(domain.len() as u64).to_le_bytes());
+            prefix.extend_from_slice(domain.as_bytes());
+        }
+        prefix
+    }
+}
+
+/// Hashes a message of any length, e.g. `hash_bytes(b"Hello, world!")`, into its digest.
+///
+/// The message is split into chunks of [`MESSAGE_BYTES_PER_BLOCK`] bytes, the last one zero padded,
+/// and an empty message into a single empty chunk. Every chunk is hashed as a leaf block,
+/// `SWIFFT_ComputeMultiple` hashing many at a time, and the compact hash values of the leaves are combined
+/// by a binary tree: the root of `n > 1` leaves is the node of the roots of the first `k` and the other `n - k` leaves,
+/// where `k` is the largest power of two below `n`. Each node hashes a block holding the compact hash values
+/// of its two children. Finally, a block holding the compact hash value of the root and the length of the message
+/// in bytes, as a little endian `u64`, is hashed and compacted into the result.
+///
+/// The last byte of every block tells leaves, nodes and the final block apart, so a collision between two messages
+/// implies a collision of SWIFFT on some pair of blocks; the length tells apart messages that differ only in padding.
+/// Unlike a sum of the hash values of the blocks, which SWIFFT would make linear in their bits,
+/// this binds every chunk to its position. The construction is frozen
+///
+/// # Arguments
+/// * `data` - the message, of any length
+pub fn hash_bytes(data: &[u8]) -> Digest512 {
+    hash_bytes_with(data, &HashOptions::default())
+}
+
+/// Hashes a message of any length as [`hash_bytes`] does, hashing its leaves and the nodes of its tree
+/// on the `rayon` thread pool.
+///
+/// The result is the same as that of [`hash_bytes`] and of [`Hasher`], whatever the number of threads:
+/// the leaves are the chunks of [`MESSAGE_BYTES_PER_BLOCK`] bytes and the tree is the one that [`hash_bytes`] defines,
+/// so only the order of computation differs. The compact hash values of all leaves are held at once,
+/// a quarter of the size of the message
+///
+/// # Arguments
+/// * `data` - the message, of any length
+#[cfg(feature = "parallel")]
+pub fn hash_bytes_parallel(data: &[u8]) -> Digest512 {
+    if data.is_empty() {
+        return hash_bytes(data)
+    }
+    let leaves: Vec<CompactOutput> = data.par_chunks(PENDING_SIZE)
+        .flat_map_iter(|batch| infallible(leaf_hashes(&batch.chunks(MESSAGE_BYTES_PER_BLOCK).collect::<Vec<_>>(), LEAF_DOMAIN, &NativeBackend)))
+        .collect();
+    infallible(final_block(&parallel_root(&leaves), data.len() as u64, &NativeBackend)).compact()
+}
+
+/// The root of the tree of [`hash_bytes`] over `leaves`, computing the two subtrees of large trees in parallel
+#[cfg(feature = "parallel")]
+fn parallel_root(leaves: &[CompactOutput]) -> CompactOutput {
+    if leaves.len() == 1 {
+        return leaves[0].clone()
+    }
+    let (left, right) = leaves.split_at(1 << (leaves.len() - 1).ilog2());
+    let (left, right) = if leaves.len() > MESSAGE_BATCH_BLOCKS {
+        rayon::join(|| parallel_root(left), || parallel_root(right))
+    } else {
+        (parallel_root(left), parallel_root(right))
+    };
+    compress_compact(&left, &right)
+}
+
+/// Hashes a message of any length as [`hash_bytes`] does, separated by the salt and domain of `options`
+///
+/// # Arguments
+/// * `data` - the message, of any length
+/// * `options` - the salt and domain
+pub fn hash_bytes_with(data: &[u8], options: &HashOptions) -> Digest512 {
+    infallible(try_hash_bytes(data, options, &NativeBackend))
+}
+
+/// Hashes a message of any length as [`hash_bytes`] does, hashing every block with `backend`
+///
+/// # Arguments
+/// * `data` - the message, of any length
+/// * `backend` - the backend to hash blocks with
+///
+/// # Errors
+/// Returns the error of the backend if it could not hash some block
+pub fn hash_bytes_with_backend<B: SwifftBackend>(data: &[u8], backend: &B) -> Result<Digest512, B::Error> {
+    try_hash_bytes(data, &HashOptions::default(), backend)
+}
+
+/// Hashes a message of any length as [`hash_bytes_with`] does, hashing every block with `backend`
+fn try_hash_bytes<B: SwifftBackend>(data: &[u8], options: &HashOptions, backend: &B) -> Result<Digest512, B::Error> {
+    let mut tree = MessageTree::try_new(options, backend)?;
+    let mut chunks = data.chunks(MESSAGE_BYTES_PER_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        tree.push_leaves(&[&[]], LEAF_DOMAIN, backend)?;
+    }
+    let mut batch = Vec::with_capacity(MESSAGE_BATCH_BLOCKS);
+    for chunk in chunks {
+        batch.push(chunk);
+        if batch.len() == MESSAGE_BATCH_BLOCKS {
+            tree.push_leaves(&batch, LEAF_DOMAIN, backend)?;
+            batch.clear();
+        }
+    }
+    tree.push_leaves(&batch, LEAF_DOMAIN, backend)?;
+    Ok(tree.finalize(data.len() as u64, backend)?.compact())
+}
+
+/// Hashes the message read from `reader` until its end, as [`hash_bytes`] hashes it in memory.
+///
+/// The message is read a batch of leaf blocks at a time, so files of any size can be hashed in constant memory.
+/// Reads that return fewer bytes than asked for are continued, and only a read of `0` bytes ends the message
+///
+/// # Arguments
+/// * `reader` - the source of the message
+///
+/// # Errors
+/// The first error of `reader`, other than [`ErrorKind::Interrupted`] which is retried
+pub fn hash_reader(reader: impl Read) -> std::io::Result<Digest512> {
+    hash_reader_with(reader, &HashOptions::default())
+}
+
+/// Hashes the message read from `reader` as [`hash_reader`] does, separated by the salt and domain of `options`
+///
+/// # Arguments
+/// * `reader` - the source of the message
+/// * `options` - the salt and domain
+///
+/// # Errors
+/// The first error of `reader`, other than [`ErrorKind::Interrupted`] which is retried
+pub fn hash_reader_with(reader: impl Read, options: &HashOptions) -> std::io::Result<Digest512> {
+    try_hash_reader(reader, Hasher::with_options(options), &NativeBackend, |_, _| ())
+}
+
+/// Hashes the message read from `reader` as [`hash_reader`] does, hashing every block with `backend`
+///
+/// # Arguments
+/// * `reader` - the source of the message
+/// * `backend` - the backend to hash blocks with
+///
+/// # Errors
+/// The first error of `reader`, other than [`ErrorKind::Interrupted`] which is retried,
+/// or the error of the backend if it could not hash some block, as an error of kind [`ErrorKind::Other`]
+pub fn hash_reader_with_backend<B>(reader: impl Read, backend: &B) -> std::io::Result<Digest512>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    try_hash_reader(reader, Hasher::new(), backend, |_, _| ())
+}
+
+/// Hashes the message read from `reader` as [`hash_reader_with_backend`] does, calling `progress` after every read
+/// with the hasher, whose [`position`](Hasher::position) is the number of bytes read so far, and what the read added
+///
+/// # Arguments
+/// * `reader` - the source of the message
+/// * `backend` - the backend to hash blocks with
+/// * `progress` - called with the hasher and the [`BlocksConsumed`] of every read, e.g. to report progress on a large file
+///
+/// # Errors
+/// The first error of `reader`, other than [`ErrorKind::Interrupted`] which is retried,
+/// or the error of the backend if it could not hash some block, as an error of kind [`ErrorKind::Other`]
+pub fn hash_reader_with_progress<B>(reader: impl Read, backend: &B, progress: impl FnMut(&Hasher, BlocksConsumed)) -> std::io::Result<Digest512>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    try_hash_reader(reader, Hasher::new(), backend, progress)
+}
+
+/// Reads `reader` until its end into `hasher`, hashing every block with `backend` and calling `progress` after every read
+fn try_hash_reader<B>(mut reader: impl Read, mut hasher: Hasher, backend: &B, mut progress: impl FnMut(&Hasher, BlocksConsumed)) -> std::io::Result<Digest512>
+where
+    B: SwifftBackend,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let mut buffer = vec![0; PENDING_SIZE];
+    loop {
+        match reader.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(len) => {
+                let consumed = hasher.try_update_counted(&buffer[..len], backend).map_err(std::io::Error::other)?;
+                progress(&hasher, consumed)
+            }
+            Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(hasher.try_finalize(backend).map_err(std::io::Error::other)?.compact())
+}
+
+/// Whether `expected` is the digest of `data` under [`hash_bytes`], e.g. a digest stored next to a file.
+///
+/// The message is hashed through the fixed blocks of a [`SwifftContext`], so the allocations do not grow
+/// with its length. The digests are compared in time independent of their bytes, looking at all `64` bytes
+/// rather than stopping at the first that differs
+///
+/// # Arguments
+/// * `data` - the message, of any length
+/// * `expected` - the digest that the message should have
+pub fn verify(data: &[u8], expected: &Digest512) -> bool {
+    constant_time_eq(&SwifftContext::new().hash_bytes(data), expected)
+}
+
+/// Whether `expected` is the digest of everything read from `reader` under [`hash_reader`],
+/// read into the fixed blocks of a [`SwifftContext`] and compared in time independent of the bytes of the digests
+/// as [`verify`] does
+///
+/// # Arguments
+/// * `reader` - the source of the message, read until it reports the end of the stream
+/// * `expected` - the digest that the message should have
+///
+/// # Errors
+/// Returns the first error of `reader` other than [`ErrorKind::Interrupted`]
+pub fn verify_reader(reader: impl Read, expected: &Digest512) -> std::io::Result<bool> {
+    Ok(constant_time_eq(&SwifftContext::new().hash_reader(reader)?, expected))
+}
+
+/// The number of pairs that [`verify_batch`] hashes at a time
+const VERIFY_BATCH_BLOCKS: usize = 64;
+
+/// Checks that every input block of `pairs` hashes to its expected hash value, e.g. when replicating
+/// hash values computed elsewhere, reporting every mismatch rather than stopping at the first.
+///
+/// The pairs are hashed `64` at a time by [`compute_slice`], through a single scratch buffer
+/// of inputs and one of hash values, so nothing is allocated unless some pair mismatches.
+/// The hash values are compared by their bytes, so an expected hash value that is not canonical never matches
+///
+/// # Arguments
+/// * `pairs` - the input blocks, each with the hash value it should have
+///
+/// # Errors
+/// Returns the indices of the mismatching pairs into `pairs`, in increasing order
+pub fn verify_batch(pairs: &[(Input, Output)]) -> Result<(), Vec<usize>> {
+    let mut inputs: [Input; VERIFY_BATCH_BLOCKS] = std::array::from_fn(|_| Input::default());
+    let mut outputs: [Output; VERIFY_BATCH_BLOCKS] = std::array::from_fn(|_| Output::default());
+    let mut mismatched = Vec::new();
+    for (chunk_index, chunk) in pairs.chunks(VERIFY_BATCH_BLOCKS).enumerate() {
+        let (inputs, outputs) = (&mut inputs[..chunk.len()], &mut outputs[..chunk.len()]);
+        for (block, (input, _)) in inputs.iter_mut().zip(chunk) {
+            block.0[0] = input.0[0];
+        }
+        compute_slice(inputs, outputs).unwrap();
+        let first = chunk_index * VERIFY_BATCH_BLOCKS;
+        mismatched.extend(outputs.iter().zip(chunk).enumerate()
+            .filter(|(_, (output, (_, expected)))| **output != *expected)
+            .map(|(i, _)| first + i));
+    }
+    if mismatched.is_empty() { Ok(()) } else { Err(mismatched) }
+}
+
+/// Compares two digests in time independent of their bytes
+pub(crate) fn constant_time_eq(a: &Digest512, b: &Digest512) -> bool {
+    let difference = a.as_bytes().iter().zip(b.as_bytes()).fold(0, |acc, (a, b)| acc | (a ^ b));
+    std::hint::black_box(difference) == 0
+}
+
+/// Hashes a message that arrives in pieces of any size, as [`hash_bytes`] hashes it at once:
+/// compacting the result of [`Hasher::finalize`] gives [`hash_bytes`] of the whole message.
+///
+/// Message bytes are buffered until a batch of full leaf blocks is available, so at most a few kilobytes of
+/// the message and one compact hash value per level of the tree are held at any time.
+/// With the `zeroize` feature, the buffered bytes and the leaf blocks made of them are wiped once hashed
+///
+/// ```
+/// use libswifft::hash::{hash_bytes, Hasher};
+///
+/// let mut hasher = Hasher::new();
+/// hasher.update(b"Hello, ");
+/// hasher.update(b"world!");
+/// assert_eq!(hasher.finalize().compact(), hash_bytes(b"Hello, world!"));
+/// ```
+#[derive(Clone)]
+pub struct Hasher {
+    tree: MessageTree,
+    /// The bytes of the message that are not yet hashed, at most [`PENDING_SIZE`]
+    pending: MessageCopy<Vec<u8>>,
+    /// The length of the message so far, in bytes
+    len: u64,
+}
+
+impl Default for Hasher {
+    fn default() -> Self {
+        Self { tree: MessageTree::default(), pending: MessageCopy::from(Vec::new()), len: 0 }
+    }
+}
+
+impl Hasher {
+    /// Creates a hasher of an empty message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a hasher of an empty message, separated by the salt and domain of `options`
+    ///
+    /// # Arguments
+    /// * `options` - the salt and domain
+    pub fn with_options(options: &HashOptions) -> Self {
+        Self { tree: infallible(MessageTree::try_new(options, &NativeBackend)), ..Self::default() }
+    }
+
+    /// Appends `data` to the message
+    ///
+    /// # Arguments
+    /// * `data` - the next bytes of the message, of any length
+    pub fn update(&mut self, data: &[u8]) {
+        infallible(self.try_update(data, &NativeBackend))
+    }
+
+    /// Appends `data` to the message as [`update`](Self::update) does, reporting how far it got
+    ///
+    /// # Arguments
+    /// * `data` - the next bytes of the message, of any length
+    pub fn update_counted(&mut self, data: &[u8]) -> BlocksConsumed {
+        infallible(self.try_update_counted(data, &NativeBackend))
+    }
+
+    /// Appends `data` to the message as [`update_counted`](Self::update_counted) does, hashing every full batch with `backend`
+    fn try_update_counted<B: SwifftBackend>(&mut self, data: &[u8], backend: &B) -> Result<BlocksConsumed, B::Error> {
+        let before = self.len / MESSAGE_BYTES_PER_BLOCK as u64;
+        self.try_update(data, backend)?;
+        Ok(BlocksConsumed {
+            blocks: self.len / MESSAGE_BYTES_PER_BLOCK as u64 - before,
+            remainder: MESSAGE_BYTES_PER_BLOCK - self.remaining_in_block(),
+        })
+    }
+
+    /// Appends `data` to the message as [`update`](Self::update) does, hashing every full batch with `backend`
+    fn try_update<B: SwifftBackend>(&mut self, mut data: &[u8], backend: &B) -> Result<(), B::Error> {
+        #[cfg(feature = "zeroize")]
+        if self.pending.capacity() < PENDING_SIZE {
+            // allocated whole, so that no bytes are left behind in a smaller allocation that the buffer outgrows
+            let mut pending = MessageCopy::from(Vec::with_capacity(PENDING_SIZE));
+            pending.extend_from_slice(&self.pending);
+            self.pending = pending;
+        }
+        self.len += data.len() as u64;
+        while !data.is_empty() {
+            let (head, rest) = data.split_at(data.len().min(PENDING_SIZE - self.pending.len()));
+            self.pending.extend_from_slice(head);
+            data = rest;
+            if self.pending.len() == PENDING_SIZE {
+                self.tree.push_leaves(&self.pending.chunks(MESSAGE_BYTES_PER_BLOCK).collect::<Vec<_>>(), LEAF_DOMAIN, backend)?;
+                #[cfg(feature = "zeroize")]
+                zeroize::Zeroize::zeroize(&mut *self.pending);
+                #[cfg(not(feature = "zeroize"))]
+                self.pending.clear();
+            }
+        }
+        Ok(())
+    }
+
+    /// The length of the message so far, i.e. the number of bytes passed to [`Hasher::update`] or written
+    pub fn bytes_written(&self) -> u64 {
+        self.len
+    }
+
+    /// The total number of bytes absorbed so far, the same as [`Hasher::bytes_written`]
+    pub fn position(&self) -> u64 {
+        self.len
+    }
+
+    /// The number of bytes that complete the current leaf block of [`MESSAGE_BYTES_PER_BLOCK`] bytes,
+    /// all of them when the message so far ends on a block boundary
+    pub fn remaining_in_block(&self) -> usize {
+        MESSAGE_BYTES_PER_BLOCK - (self.len % MESSAGE_BYTES_PER_BLOCK as u64) as usize
+    }
+
+    /// Pads and hashes the trailing partial block, and hashes the root of the message with its length,
+    /// returning the hash value that [`hash_bytes`] compacts
+    pub fn finalize(self) -> Output {
+        infallible(self.try_finalize(&NativeBackend))
+    }
+
+    /// Finishes the message as [`finalize`](Self::finalize) does, hashing the remaining blocks with `backend`
+    fn try_finalize<B: SwifftBackend>(mut self, backend: &B) -> Result<Output, B::Error> {
+        if self.len == 0 {
+            self.tree.push_leaves(&[&[]], LEAF_DOMAIN, backend)?;
+        } else if !self.pending.is_empty() {
+            self.tree.push_leaves(&self.pending.chunks(MESSAGE_BYTES_PER_BLOCK).collect::<Vec<_>>(), LEAF_DOMAIN, backend)?;
+        }
+        self.tree.finalize(self.len, backend)
+    }
+
+    /// The compact hash value of the message, tagged as [`Mode::Chained`], as [`TaggedDigest::chained`] computes it
+    /// when the hasher has no salt or domain, which the tag does not record
+    pub fn finalize_tagged(self) -> TaggedDigest {
+        TaggedDigest::compacted(Mode::Chained, &self.finalize())
+    }
+}
+
+/// How far a call to [`Hasher::update_counted`] got through the leaf blocks of the message
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlocksConsumed {
+    /// The number of leaf blocks of [`MESSAGE_BYTES_PER_BLOCK`] bytes completed by the call,
+    /// which are hashed once a batch of them is buffered
+    pub blocks: u64,
+    /// The number of bytes buffered in the partial leaf block that follows them, less than [`MESSAGE_BYTES_PER_BLOCK`]
+    pub remainder: usize,
+}
+
+impl Write for Hasher {
+    /// Appends all of `buf` to the message, e.g. for `std::io::copy(&mut file, &mut hasher)`
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    /// Does nothing, since the hasher holds no resource to flush to
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The compact hash values of the leaves of a message hashed so far, merged into the roots of complete subtrees
+/// as soon as they are complete, so that only one root per bit set in the number of leaves is kept
+#[derive(Clone, Default)]
+pub(crate) struct MessageTree {
+    /// The roots of the complete subtrees, from the largest and leftmost
+    roots: Vec<CompactOutput>,
+    num_leaves: u64,
+}
+
+impl MessageTree {
+    /// Creates the tree of an empty message, with the leaves of the prefix of `options` hashed with `backend`
+    fn try_new<B: SwifftBackend>(options: &HashOptions, backend: &B) -> Result<Self, B::Error> {
+        let mut tree = Self::default();
+        let prefix = options.prefix();
+        for batch in prefix.chunks(PENDING_SIZE) {
+            tree.push_leaves(&batch.chunks(MESSAGE_BYTES_PER_BLOCK).collect::<Vec<_>>(), PREFIX_DOMAIN, backend)?;
+        }
+        Ok(tree)
+    }
+
+    /// Hashes each of `chunks` as the next leaf with `backend`, each at most [`MESSAGE_BYTES_PER_BLOCK`] bytes,
+    /// in a leaf block ending in `domain`
+    fn push_leaves<B: SwifftBackend>(&mut self, chunks: &[&[u8]], domain: u8, backend: &B) -> Result<(), B::Error> {
+        for leaf in leaf_hashes(chunks, domain, backend)? {
+            self.push_leaf(leaf, backend)?;
+        }
+        Ok(())
+    }
+
+    /// Creates the tree of an empty message without a prefix, with room for the roots of any number of leaves,
+    /// so that it never allocates
+    pub(crate) fn with_max_roots() -> Self {
+        Self { roots: Vec::with_capacity(u64::BITS as usize), num_leaves: 0 }
+    }
+
+    /// Copies the tree into one with room for the roots of any number of leaves, as [`MessageTree::with_max_roots`]
+    pub(crate) fn clone_with_max_roots(&self) -> Self {
+        let mut roots = Vec::with_capacity(u64::BITS as usize);
+        roots.extend_from_slice(&self.roots);
+        Self { roots, num_leaves: self.num_leaves }
+    }
+
+    /// Empties the tree, e.g. of the leaves of a message whose hashing was abandoned, keeping its capacity
+    pub(crate) fn clear(&mut self) {
+        self.roots.clear();
+        self.num_leaves = 0;
+    }
+
+    /// Adds the next leaf, merging every subtree that it completes with `backend`
+    pub(crate) fn push_leaf<B: SwifftBackend>(&mut self, leaf: CompactOutput, backend: &B) -> Result<(), B::Error> {
+        self.num_leaves += 1;
+        let mut node = leaf;
+        let mut num_leaves = self.num_leaves;
+        while num_leaves % 2 == 0 {
+            let left = self.roots.pop().unwrap();
+            node = node_with(&left, &node, backend)?;
+            num_leaves /= 2;
+        }
+        self.roots.push(node);
+        Ok(())
     }
+
+    /// Merges the roots of the complete subtrees from the right, and hashes the final block with `backend`,
+    /// leaving the tree empty
+    pub(crate) fn finalize<B: SwifftBackend>(&mut self, len: u64, backend: &B) -> Result<Output, B::Error> {
+        let mut root = self.roots.pop().unwrap();
+        while let Some(left) = self.roots.pop() {
+            root = node_with(&left, &root, backend)?;
+        }
+        self.num_leaves = 0;
+        final_block(&root, len, backend)
+    }
+}
+
+/// Hashes each of `chunks`, each at most [`MESSAGE_BYTES_PER_BLOCK`] bytes, in a leaf block ending in `domain`,
+/// into the compact hash values of the leaves
+fn leaf_hashes<B: SwifftBackend>(chunks: &[&[u8]], domain: u8, backend: &B) -> Result<Vec<CompactOutput>, B::Error> {
+    let mut inputs: MessageCopy<Vec<Input>> = MessageCopy::from(vec![Input::default(); chunks.len()]);
+    for (input, chunk) in inputs.iter_mut().zip(chunks) {
+        input.0[0][..chunk.len()].copy_from_slice(chunk);
+        input.0[0][MESSAGE_BYTES_PER_BLOCK] = domain;
+    }
+    let mut outputs = vec![Output::default(); chunks.len()];
+    let mut leaves = vec![CompactOutput::default(); chunks.len()];
+    backend.compute_slice(&inputs, &mut outputs)?;
+    compact_slice(&outputs, &mut leaves).unwrap();
+    Ok(leaves)
+}
+
+/// Hashes the final block of a message with `backend`, holding the compact hash value of its root and its length in bytes
+fn final_block<B: SwifftBackend>(root: &CompactOutput, len: u64, backend: &B) -> Result<Output, B::Error> {
+    let mut input = Input::default();
+    input.0[0][..COMPACT_OUTPUT_BLOCK_SIZE].copy_from_slice(&root.0[0]);
+    input.0[0][COMPACT_OUTPUT_BLOCK_SIZE..][..size_of::<u64>()].copy_from_slice(&len.to_le_bytes());
+    input.0[0][MESSAGE_BYTES_PER_BLOCK] = FINAL_DOMAIN;
+    let mut output = Output::default();
+    backend.compute(&input, &mut output)?;
+    Ok(output)
+}
+
+/// Compresses two compact hash values into one hash value, the 2-to-1 function of the nodes of [`hash_bytes`]
+/// and of [`MerkleTree`](crate::merkle::MerkleTree), for building other authenticated data structures that
+/// interoperate with them.
+///
+/// The input block holds `left` in bytes `0..64` and `right` in bytes `64..128`, then zeros, and the node domain byte
+/// `1` in its last byte. This layout is frozen, and injective, so finding two different pairs that compress to the same
+/// hash value is finding a collision of SWIFFT
+///
+/// # Arguments
+/// * `left` - the compact hash value on the left
+/// * `right` - the compact hash value on the right
+pub fn compress(left: &CompactOutput, right: &CompactOutput) -> Output {
+    let mut input = Input::default();
+    write_node_block(left, right, &mut input);
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    output
+}
+
+/// Overwrites `input` with the node block of [`compress`] of `left` and `right`
+pub(crate) fn write_node_block(left: &CompactOutput, right: &CompactOutput, input: &mut Input) {
+    input.0[0] = [0; INPUT_BLOCK_SIZE];
+    input.0[0][..COMPACT_OUTPUT_BLOCK_SIZE].copy_from_slice(&left.0[0]);
+    input.0[0][COMPACT_OUTPUT_BLOCK_SIZE..][..COMPACT_OUTPUT_BLOCK_SIZE].copy_from_slice(&right.0[0]);
+    input.0[0][MESSAGE_BYTES_PER_BLOCK] = NODE_DOMAIN;
+}
+
+/// Compresses two compact hash values into one compact hash value, as [`compress`] followed by [`compact`]
+///
+/// # Arguments
+/// * `left` - the compact hash value on the left
+/// * `right` - the compact hash value on the right
+pub fn compress_compact(left: &CompactOutput, right: &CompactOutput) -> CompactOutput {
+    infallible(node_with(left, right, &NativeBackend))
+}
+
+/// Compresses two compact hash values into one compact hash value as [`compress_compact`] does, hashing with `backend`
+pub(crate) fn node_with<B: SwifftBackend>(left: &CompactOutput, right: &CompactOutput, backend: &B) -> Result<CompactOutput, B::Error> {
+    let mut input = Input::default();
+    write_node_block(left, right, &mut input);
+    let mut output = Output::default();
+    backend.compute(&input, &mut output)?;
+    let mut node = CompactOutput::default();
+    compact(&output, &mut node);
+    Ok(node)
+}
+
+/// Computes the result of SWIFFT operations over every block of a buffer of any size,
+/// returning the corresponding buffer of hash values.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+pub fn computed<I: Hashable>(input: &I) -> I::Hash {
+    debug_assert_ffi_buffer(input, "input");
+    let mut output = I::Hash::default();
+    // SAFETY: `Block` is sealed, and only implemented by `AlignedBuffer`s of contiguous blocks,
+    // so both buffers hold `I::NUM_BLOCKS` blocks of their respective block sizes
+    let input_ptr: *const u8 = (input as *const I).cast();
+    let output_ptr: *mut u8 = (&mut output as *mut I::Hash).cast();
+    for_each_chunk(I::NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_ComputeMultiple(num_blocks, input_ptr.add(first * I::BLOCK_SIZE),
+            output_ptr.add(first * <I::Hash as Block>::BLOCK_SIZE).cast())
+    });
+    output
+}
+
+/// Compacts every hash value of a buffer of any size,
+/// returning the corresponding buffer of compact hash values.
+/// The result is not composable with other hash values.
+///
+/// # Arguments
+/// * `output` - the blocks of hash values of SWIFFT, each of size 128 bytes (1024 bit)
+pub fn compacted<O: Compactable>(output: &O) -> O::Compacted {
+    debug_assert_ffi_buffer(output, "output");
+    let mut compact_output = O::Compacted::default();
+    // SAFETY: `Block` is sealed, and only implemented by `AlignedBuffer`s of contiguous blocks,
+    // so both buffers hold `O::NUM_BLOCKS` blocks of their respective block sizes
+    let output_ptr: *const u8 = (output as *const O).cast();
+    let compact_output_ptr: *mut u8 = (&mut compact_output as *mut O::Compacted).cast();
+    for_each_chunk(O::NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_CompactMultiple(num_blocks, output_ptr.add(first * O::BLOCK_SIZE).cast(),
+            compact_output_ptr.add(first * <O::Compacted as Block>::BLOCK_SIZE))
+    });
+    compact_output
+}
+
+/// Composes two SWIFFT hash values, element-wise, by adding them.
+/// This is the blessed composition operation, delegating to [`arithmetic::add`].
+///
+/// Composition is what makes SWIFFT homomorphic: for inputs `x` and `y` with no bits in common,
+/// `compose(compute(x), compute(y)) == compute(x | y)`. More generally,
+/// subtracting `compute(y)` from `compute(x)` equals `compute_signed` of `x ^ y`
+/// with sign bits `y & !x`. Compacted hash values are not composable.
+///
+/// # Arguments
+/// * `a` - the first hash value of SWIFFT
+/// * `b` - the second hash value of SWIFFT
+pub fn compose(a: &Output, b: &Output) -> Output {
+    let mut output = Output::default();
+    arithmetic::set(&mut output, a);
+    arithmetic::add(&mut output, b);
+    output
+}
+
+/// Computes the difference `compute(a) - compute(b)` of the hash values of two inputs in a single pass,
+/// as one [`compute_signed`] over the bits in which they differ, with sign bits `b & !a`.
+/// This costs one SWIFFT operation rather than the two of hashing both inputs and subtracting.
+/// The result is composable with other hash values, and is zero exactly when the hash values are equal,
+/// see [`Output::is_difference_zero`].
+///
+/// # Arguments
+/// * `a` - the input of 256 bytes (2048 bit) whose hash value is subtracted from
+/// * `b` - the input of 256 bytes (2048 bit) whose hash value is subtracted
+pub fn compute_difference(a: &Input, b: &Input) -> Output {
+    let mut input = Input::default();
+    let mut sign_input = SignInput::default();
+    for (((input, sign), &a), &b) in input.0[0].iter_mut().zip(sign_input.0[0].iter_mut()).zip(&a.0[0]).zip(&b.0[0]) {
+        *input = a ^ b;
+        *sign = b & !a;
+    }
+    let mut output = Output::default();
+    compute_signed(&input, &sign_input, &mut output);
+    output
 }
 
 /// Compacts a hash value of SWIFFT.
@@ -72,6 +1205,8 @@ pub fn compute_multiple_signed<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCK
 /// * `output` - the hash value of SWIFFT, of size 128 bytes (1024 bit)
 /// * `compact_output` - the compacted hash value of SWIFFT, of size 64 bytes (512 bit)
 pub fn compact(output: &Output, compact_output: &mut CompactOutput) {
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(compact_output, "compact_output");
     unsafe {
         SWIFFT_Compact(output.0[0].as_ptr(), compact_output.0[0].as_mut_ptr())
     }
@@ -85,7 +1220,98 @@ pub fn compact(output: &Output, compact_output: &mut CompactOutput) {
 /// * `output` - the hash value of SWIFFT, of size 128 bytes (1024 bit)
 /// * `compact_output` - the compacted hash value of SWIFFT, of size 64 bytes (512 bit)
 pub fn compact_multiple<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>, compact_output: &mut CompactOutputs<NUM_BLOCKS>) {
-    unsafe {
-        SWIFFT_CompactMultiple(NUM_BLOCKS.try_into().unwrap(), output.0[0].as_ptr(), compact_output.0[0].as_mut_ptr())
-    }
-}
\ No newline at end of file
+    debug_assert_ffi_buffer(output, "output");
+    debug_assert_ffi_buffer(compact_output, "compact_output");
+    for_each_chunk(NUM_BLOCKS, |first, num_blocks| unsafe {
+        SWIFFT_CompactMultiple(num_blocks, output.0[first..].as_ptr().cast(), compact_output.0[first..].as_mut_ptr().cast())
+    })
+}
+
+/// Like [`compact_multiple`], but with the number of blocks given at runtime by the lengths of the slices.
+/// The result is not composable with other compacted hash values.
+///
+/// # Arguments
+/// * `output` - the hash values of SWIFFT, each of size 128 bytes (1024 bit)
+/// * `compact_output` - the compacted hash values of SWIFFT, each of size 64 bytes (512 bit)
+///
+/// # Errors
+/// Returns [`LenMismatch`] if `compact_output` does not hold exactly one block per block of `output`,
+/// leaving `compact_output` unmodified
+pub fn compact_slice(output: &[Output], compact_output: &mut [CompactOutput]) -> Result<(), LenMismatch> {
+    LenMismatch::check(output.len(), compact_output.len())?;
+    debug_assert_ffi_buffer(output.as_ptr(), "output");
+    debug_assert_ffi_buffer(compact_output.as_ptr(), "compact_output");
+    for_each_chunk(output.len(), |first, num_blocks| unsafe {
+        SWIFFT_CompactMultiple(num_blocks, output[first..].as_ptr().cast(), compact_output[first..].as_mut_ptr().cast())
+    });
+    Ok(())
+}
+
+/// Computes the result of a SWIFFT operation, returning it, as [`compute`] does into an out-parameter.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the input of 256 bytes (2048 bit)
+pub fn compute_owned(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+/// Computes the result of multiple SWIFFT operations, returning them, as [`compute_multiple`] does into an out-parameter.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+pub fn compute_multiple_owned<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    computed(input)
+}
+
+/// Computes the result of a SWIFFT operation with sign bits, returning it,
+/// as [`compute_signed`] does into an out-parameter.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `input` - the input of 256 bytes (2048 bit)
+/// * `sign_input` - the sign bits corresponding to the input of 256 bytes (2048 bit)
+pub fn compute_signed_owned(input: &Input, sign_input: &SignInput) -> Output {
+    let mut output = Output::default();
+    compute_signed(input, sign_input, &mut output);
+    output
+}
+
+/// Computes the result of multiple SWIFFT operations with sign bits, returning them,
+/// as [`compute_multiple_signed`] does into an out-parameter.
+/// The result is composable with other hash values.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+/// * `sign_input` - the blocks of sign bits corresponding to blocks of input of 256 bytes (2048 bit)
+pub fn compute_multiple_signed_owned<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>, sign_input: &SignInputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut output = Outputs::default();
+    compute_multiple_signed(input, sign_input, &mut output);
+    output
+}
+
+/// Compacts a hash value of SWIFFT, returning it, as [`compact`] does into an out-parameter.
+/// The result is not composable with other compacted hash values.
+///
+/// # Arguments
+/// * `output` - the hash value of SWIFFT, of size 128 bytes (1024 bit)
+pub fn compact_owned(output: &Output) -> CompactOutput {
+    let mut compact_output = CompactOutput::default();
+    compact(output, &mut compact_output);
+    compact_output
+}
+
+/// Compacts multiple hash values of SWIFFT, returning them, as [`compact_multiple`] does into an out-parameter.
+/// The result is not composable with other compacted hash values.
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to operate on
+/// * `output` - the hash values of SWIFFT, each of size 128 bytes (1024 bit)
+pub fn compact_multiple_owned<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>) -> CompactOutputs<NUM_BLOCKS> {
+    compacted(output)
+}
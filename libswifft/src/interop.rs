@@ -0,0 +1,115 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Bulk conversion between input blocks of the C library and inputs of the pure Rust implementation.
+//!
+//! The pure Rust implementation takes `16` input vectors rather than `32`,
+//! so every input block corresponds to [`SWIFFT_INPUTS_PER_BLOCK`] consecutive [`SwifftInput`]s:
+//! the first takes bytes `0..128` of the block, and the second takes bytes `128..256`.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use swifft::hash::{SwifftInput, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE, M as SWIFFT_M};
+use swifft::polynomial::{Coefficients, Polynomial};
+use swifft::z257::Z257;
+
+use crate::buffer::Inputs;
+use crate::constant::INPUT_BLOCK_SIZE;
+use crate::error::{InteropError, LenMismatch};
+
+/// The number of pure Rust SWIFFT inputs that make up one input block
+pub const SWIFFT_INPUTS_PER_BLOCK: usize = INPUT_BLOCK_SIZE / SWIFFT_INPUT_BLOCK_SIZE;
+
+/// Converts every input block into [`SWIFFT_INPUTS_PER_BLOCK`] pure Rust SWIFFT inputs,
+/// in the order of the blocks (*in parallel with the `parallel` feature*),
+/// which does not depend on the number of threads
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to convert
+/// * `inputs` - the blocks of input, each of 256 bytes (2048 bit)
+pub fn inputs_to_swifft<const NUM_BLOCKS: usize>(inputs: &Inputs<NUM_BLOCKS>) -> Vec<SwifftInput> {
+    #[cfg(feature = "parallel")]
+    let swifft_inputs = inputs.0.par_iter().flat_map_iter(parse_block).collect();
+    #[cfg(not(feature = "parallel"))]
+    let swifft_inputs = inputs.0.iter().flat_map(parse_block).collect();
+    swifft_inputs
+}
+
+/// Converts pure Rust SWIFFT inputs back into input blocks,
+/// taking [`SWIFFT_INPUTS_PER_BLOCK`] consecutive inputs per block (*in parallel with the `parallel` feature*)
+///
+/// Writes into `output` rather than returning the blocks, so that large batches can live on the heap
+///
+/// # Arguments
+/// * `NUM_BLOCKS` - the number of blocks to convert into
+/// * `inputs` - the pure Rust SWIFFT inputs, whose coefficients must all be `0` or `1`
+/// * `output` - the blocks to overwrite with the converted inputs
+///
+/// # Errors
+/// Returns [`InteropError::LenMismatch`] if there are not exactly [`SWIFFT_INPUTS_PER_BLOCK`] inputs per block,
+/// without writing to `output`, and [`InteropError::NonBinaryCoefficient`] for the first coefficient that is
+/// neither `0` nor `1`, first in block order whichever thread finds it, after which the contents of `output` are unspecified
+pub fn swifft_to_inputs<const NUM_BLOCKS: usize>(inputs: &[SwifftInput], output: &mut Inputs<NUM_BLOCKS>) -> Result<(), InteropError> {
+    let expected = NUM_BLOCKS * SWIFFT_INPUTS_PER_BLOCK;
+    if inputs.len() != expected {
+        return Err(LenMismatch { expected, actual: inputs.len() }.into())
+    }
+
+    #[cfg(feature = "parallel")]
+    let error = output.0.par_iter_mut().zip(inputs.par_chunks_exact(SWIFFT_INPUTS_PER_BLOCK)).enumerate()
+        .map(write_block)
+        .find_first(Result::is_err);
+    #[cfg(not(feature = "parallel"))]
+    let error = output.0.iter_mut().zip(inputs.chunks_exact(SWIFFT_INPUTS_PER_BLOCK)).enumerate()
+        .map(write_block)
+        .find(Result::is_err);
+    error.unwrap_or(Ok(()))
+}
+
+/// The coefficients of the `8` input vector elements packed into each possible byte
+const BYTE_COEFFICIENTS: [[Z257; 8]; 256] = compute_byte_coefficients(); const fn compute_byte_coefficients() -> [[Z257; 8]; 256] {
+    let mut byte_coefficients = [[Z257::ZERO; 8]; 256];
+    let mut byte = 0; while byte < 256 {
+        let mut bit = 0; while bit < 8 {
+            byte_coefficients[byte][bit] = Z257::from_bool((byte >> bit) & 1 != 0);
+            bit += 1
+        }
+        byte += 1
+    }
+    byte_coefficients
+}
+
+/// Parses an input block into [`SWIFFT_INPUTS_PER_BLOCK`] pure Rust SWIFFT inputs
+fn parse_block(block: &[u8; INPUT_BLOCK_SIZE]) -> [SwifftInput; SWIFFT_INPUTS_PER_BLOCK] {
+    let mut swifft_inputs = [[Polynomial::ZERO; SWIFFT_M]; SWIFFT_INPUTS_PER_BLOCK];
+    for (swifft_input, bytes) in swifft_inputs.iter_mut().zip(block.chunks_exact(SWIFFT_INPUT_BLOCK_SIZE)) {
+        let mut coefficients: [Coefficients; SWIFFT_M] = [[Z257::ZERO; Polynomial::N]; SWIFFT_M];
+        for (i, &byte) in bytes.iter().enumerate() {
+            let position = i * 8;
+            coefficients[position / Polynomial::N][position % Polynomial::N..][..8]
+                .copy_from_slice(&BYTE_COEFFICIENTS[byte as usize]);
+        }
+        *swifft_input = coefficients.map(Polynomial::new);
+    }
+    swifft_inputs
+}
+
+/// Writes [`SWIFFT_INPUTS_PER_BLOCK`] pure Rust SWIFFT inputs into the `block_index`th input block
+fn write_block((block_index, (block, swifft_inputs)): (usize, (&mut [u8; INPUT_BLOCK_SIZE], &[SwifftInput]))) -> Result<(), InteropError> {
+    block.fill(0);
+    for (half, (bytes, swifft_input)) in block.chunks_exact_mut(SWIFFT_INPUT_BLOCK_SIZE).zip(swifft_inputs).enumerate() {
+        for (polynomial, coefficients) in swifft_input.iter().enumerate() {
+            for (coefficient, value) in coefficients.coefficients().iter().enumerate() {
+                let bit = match value.value() {
+                    0 => 0,
+                    1 => 1,
+                    _ => return Err(InteropError::NonBinaryCoefficient {
+                        input: block_index * SWIFFT_INPUTS_PER_BLOCK + half, polynomial, coefficient
+                    }),
+                };
+                let position = polynomial * Polynomial::N + coefficient;
+                bytes[position / 8] |= bit << (position % 8);
+            }
+        }
+    }
+    Ok(())
+}
@@ -1,5 +1,39 @@
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
 pub use libswifft_sys as sys;
+pub use buffer::Digest512;
+pub use capabilities::capabilities;
+pub use error::SwifftError;
+pub use facade::Swifft;
+pub mod bitorder;
 pub mod buffer;
+pub mod block;
 pub mod hash;
 pub mod arithmetic;
-pub mod constant;
\ No newline at end of file
+pub mod backend;
+pub mod batch;
+pub mod capabilities;
+pub mod commit;
+pub mod constant;
+pub mod context;
+pub mod digest_set;
+pub mod error;
+pub mod facade;
+pub mod interop;
+pub mod log;
+pub mod mac;
+pub mod merkle;
+mod paranoid;
+pub mod pool;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "digest")]
+pub mod rustcrypto;
+pub mod sethash;
+pub mod table;
+pub mod tagged;
+pub mod testvectors;
+pub mod view;
+pub mod window;
+#[cfg(feature = "analysis")]
+pub mod analysis;
\ No newline at end of file
@@ -0,0 +1,114 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Incremental digests of append-only logs that bind every entry to its position
+
+use crate::arithmetic::{add, const_mul};
+use crate::buffer::{AlignedBuffer, Input, Output};
+use crate::constant::{INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE, Q};
+use crate::hash::compute;
+use crate::tagged::{Mode, TaggedDigest};
+
+/// The number of entry bytes absorbed per SWIFFT operation by [`LogDigest::entry_hash`],
+/// the rest of each input block holding the previous hash value
+pub const ENTRY_CHUNK_SIZE: usize = INPUT_BLOCK_SIZE - OUTPUT_BLOCK_SIZE;
+
+/// A primitive root modulo `257`, whose powers weight the entries of a log by their position
+const POSITION_BASE: i16 = 3;
+
+/// The digest of an append-only log, where entry `i` contributes `3^i * H(i, entry_i)`.
+///
+/// Summing the entry hashes keeps appends incremental, while the position-dependent weights
+/// and the position hashed into every entry make reordered logs digest differently.
+/// The digest of every prefix is kept, so that [`LogDigest::verify_prefix`] takes constant time.
+pub struct LogDigest {
+    prefixes: Vec<Output>,
+    weight: i16,
+}
+
+impl LogDigest {
+    /// Creates the digest of an empty log
+    pub fn new() -> Self {
+        Self { prefixes: Vec::new(), weight: 1 }
+    }
+
+    /// Appends the next entry of the log
+    ///
+    /// # Arguments
+    /// * `entry` - the entry, of any length
+    pub fn append(&mut self, entry: &[u8]) {
+        let mut digest = Self::entry_hash(self.len() as u64, entry);
+        const_mul(&mut digest, self.weight);
+        if let Some(previous) = self.prefixes.last() {
+            add(&mut digest, previous);
+        }
+        self.prefixes.push(digest);
+        self.weight = (self.weight * POSITION_BASE) % Q as i16;
+    }
+
+    /// The digest of the whole log, which is all zeros for an empty log
+    pub fn digest(&self) -> Output {
+        self.prefixes.last().map_or_else(Output::default, |digest| AlignedBuffer(digest.0))
+    }
+
+    /// The compact digest of the whole log, tagged as [`Mode::Log`]
+    pub fn tagged_digest(&self) -> TaggedDigest {
+        TaggedDigest::compacted(Mode::Log, &self.digest())
+    }
+
+    /// The number of entries in the log
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    /// Whether the log has no entries
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// Whether the log digested by `other` is a prefix of this log, i.e. whether this log
+    /// has at least as many entries, and its first `other.len()` entries digest the same
+    ///
+    /// # Arguments
+    /// * `other` - the digest of the supposed prefix
+    pub fn verify_prefix(&self, other: &LogDigest) -> bool {
+        match other.prefixes.last() {
+            Some(digest) => self.prefixes.get(other.len() - 1).is_some_and(|prefix| prefix.0 == digest.0),
+            None => true,
+        }
+    }
+
+    /// Hashes an entry at `position` of a log, chaining SWIFFT operations over its chunks of [`ENTRY_CHUNK_SIZE`] bytes.
+    ///
+    /// The chain starts from the position and the length of the entry, so that the zero padding
+    /// of its last chunk is unambiguous, and each operation hashes the previous hash value with the next chunk.
+    /// A marker bit keeps the first input nonzero, as SWIFFT hashes the zero input to zero.
+    ///
+    /// # Arguments
+    /// * `position` - the index of the entry in the log
+    /// * `entry` - the entry, of any length
+    pub fn entry_hash(position: u64, entry: &[u8]) -> Output {
+        let mut input = Input::default();
+        input.0[0][..8].copy_from_slice(&position.to_le_bytes());
+        input.0[0][8..16].copy_from_slice(&(entry.len() as u64).to_le_bytes());
+        input.0[0][16] = 1;
+
+        let mut output = Output::default();
+        let mut chunks = entry.chunks(ENTRY_CHUNK_SIZE);
+        let first = chunks.next().unwrap_or_default();
+        for chunk in std::iter::once(first).chain(chunks) {
+            let next = &mut input.0[0][OUTPUT_BLOCK_SIZE..];
+            next.fill(0);
+            next[..chunk.len()].copy_from_slice(chunk);
+            compute(&input, &mut output);
+            input.0[0][..OUTPUT_BLOCK_SIZE].copy_from_slice(&output.0[0]);
+        }
+        output
+    }
+}
+
+impl Default for LogDigest {
+    /// Creates the digest of an empty log
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,112 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Message authentication codes keyed by a secret of `32` bytes, over messages streamed as [`Hasher`] streams them.
+//!
+//! The tag is the sandwich construction over [`hash_bytes`](crate::hash::hash_bytes): the tag of a message `m`
+//! under a key `k` is `hash_bytes(k' || m || k)`, where `k'` is `k` zero padded to a whole leaf block of
+//! [`MESSAGE_BYTES_PER_BLOCK`] bytes, so that the message starts at a leaf of its own.
+//! Hashing the key after the message as well as before it keeps the tag bound to the key
+//! even if the hash values of the inner nodes were to leak.
+//!
+//! SWIFFT is collision resistant, but it is not known to be a pseudorandom function, so unlike HMAC over
+//! a standard hash function this construction has no proof of security as a MAC.
+//!
+//! With the `zeroize` feature, the copies of the key that the MAC makes itself are wiped when dropped,
+//! as are those in the buffers and leaf blocks of the [`Hasher`] that hashes them
+//!
+//! ```
+//! use libswifft::mac::SwifftMac;
+//!
+//! let key = [7; 32];
+//! let mut mac = SwifftMac::new(&key);
+//! mac.update(b"attack at dawn");
+//! let tag = mac.finalize();
+//!
+//! let mut mac = SwifftMac::new(&key);
+//! mac.update(b"attack at dawn");
+//! assert!(mac.verify(&tag));
+//! ```
+
+use std::io::Write;
+
+use crate::buffer::Digest512;
+use crate::hash::{constant_time_eq, Hasher, MESSAGE_BYTES_PER_BLOCK};
+use crate::tagged::{Mode, TaggedDigest};
+
+/// The size of a key, in bytes
+pub const KEY_SIZE: usize = 32;
+
+/// A copy of the key, which is wiped when dropped with the `zeroize` feature
+#[cfg(feature = "zeroize")]
+type KeyCopy<T> = zeroize::Zeroizing<T>;
+#[cfg(not(feature = "zeroize"))]
+type KeyCopy<T> = T;
+
+/// A MAC of a message that arrives in pieces of any size, keyed by a secret of [`KEY_SIZE`] bytes.
+///
+/// The key is deliberately not printed by `Debug`, which is not implemented
+#[derive(Clone)]
+pub struct SwifftMac {
+    hasher: Hasher,
+    key: KeyCopy<[u8; KEY_SIZE]>,
+}
+
+impl SwifftMac {
+    /// Creates a MAC of an empty message under `key`
+    ///
+    /// # Arguments
+    /// * `key` - the secret key
+    pub fn new(key: &[u8; KEY_SIZE]) -> Self {
+        let mut key_block: KeyCopy<[u8; MESSAGE_BYTES_PER_BLOCK]> = KeyCopy::from([0; MESSAGE_BYTES_PER_BLOCK]);
+        key_block[..KEY_SIZE].copy_from_slice(key);
+        let mut hasher = Hasher::new();
+        hasher.update(&key_block[..]);
+        Self { hasher, key: KeyCopy::from(*key) }
+    }
+
+    /// Appends `data` to the message
+    ///
+    /// # Arguments
+    /// * `data` - the next bytes of the message, of any length
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data)
+    }
+
+    /// The length of the message so far, i.e. the number of bytes passed to [`SwifftMac::update`] or written
+    pub fn bytes_written(&self) -> u64 {
+        self.hasher.bytes_written() - MESSAGE_BYTES_PER_BLOCK as u64
+    }
+
+    /// The tag of the message
+    pub fn finalize(mut self) -> Digest512 {
+        self.hasher.update(&self.key[..]);
+        self.hasher.finalize().compact()
+    }
+
+    /// The tag of the message, tagged as [`Mode::Keyed`], so that it is never mistaken for the unkeyed digest
+    /// of another mode, e.g. when tags and digests are stored side by side
+    pub fn finalize_tagged(self) -> TaggedDigest {
+        TaggedDigest::new(Mode::Keyed, self.finalize())
+    }
+
+    /// Whether `tag` is the tag of the message, compared in time independent of the bytes of either tag
+    ///
+    /// # Arguments
+    /// * `tag` - the tag to check
+    pub fn verify(self, tag: &Digest512) -> bool {
+        constant_time_eq(&self.finalize(), tag)
+    }
+}
+
+impl Write for SwifftMac {
+    /// Appends all of `buf` to the message, e.g. for `std::io::copy(&mut file, &mut mac)`
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    /// Does nothing, since the MAC holds no resource to flush to
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
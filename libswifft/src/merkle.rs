@@ -0,0 +1,315 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Merkle trees over leaves of any length, with proofs that a leaf is included under a root.
+//!
+//! Each leaf is hashed by [`hash_bytes`](crate::hash::hash_bytes),
+//! and each inner node is [`compress_compact`](crate::hash::compress_compact) of its two children, the same node as in the tree of [`hash_bytes`](crate::hash::hash_bytes).
+//! A level with an odd number of nodes promotes its last node to the next level unchanged, rather than duplicating it,
+//! so that e.g. the leaves `[a, b, c]` and `[a, b, c, c]` have different roots. The tree is therefore the left-complete tree of [`hash_bytes`](crate::hash::hash_bytes):
+//! the root of `n > 1` leaves is the node of the roots of the first `k` and the other `n - k` leaves,
+//! where `k` is the largest power of two below `n`.
+//!
+//! The root of a single leaf is the hash value of that leaf, and the root of an empty tree is the all-zero
+//! compact value, which is the hash value of the all-zero input block rather than of any leaf or node,
+//! whose blocks have nonzero domain bytes.
+//!
+//! For more leaves than fit in memory, a [`StreamingBuilder`] computes the same root from leaves pushed one at a time,
+//! holding only a logarithmic number of subtree roots.
+//!
+//! Blocks are hashed with the [`NativeBackend`] unless another backend is given, e.g. to
+//! [`MerkleTree::build_with_backend`] or [`StreamingBuilder::with_backend`]
+//!
+//! ```
+//! use libswifft::merkle::MerkleTree;
+//!
+//! let tree = MerkleTree::build(&["alice", "bob", "carol"]);
+//! let proof = tree.prove(1);
+//! assert!(proof.verify(&tree.root(), b"bob"));
+//! assert!(!proof.verify(&tree.root(), b"mallory"));
+//! ```
+
+use crate::backend::{infallible, NativeBackend, SwifftBackend};
+use crate::buffer::{CompactOutput, Digest512};
+use crate::hash::{hash_bytes_with_backend, node_with};
+use crate::tagged::{Mode, TaggedDigest};
+
+/// A Merkle tree, holding the digests of every level from the leaves to the root.
+/// The default tree is the empty one
+#[derive(Debug, Clone, Default)]
+pub struct MerkleTree {
+    /// The levels of the tree, from the hash values of the leaves to the single root, or none for an empty tree
+    levels: Vec<Vec<Digest512>>,
+}
+
+impl MerkleTree {
+    /// Builds the tree of `leaves`, hashing every leaf and node
+    ///
+    /// # Arguments
+    /// * `leaves` - the leaves, each of any length
+    pub fn build(leaves: &[impl AsRef<[u8]>]) -> Self {
+        infallible(Self::build_with_backend(leaves, &NativeBackend))
+    }
+
+    /// Builds the tree of `leaves` as [`build`](Self::build) does, hashing every leaf and node with `backend`
+    ///
+    /// # Arguments
+    /// * `leaves` - the leaves, each of any length
+    /// * `backend` - the backend to hash blocks with
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash some block
+    pub fn build_with_backend<B: SwifftBackend>(leaves: &[impl AsRef<[u8]>], backend: &B) -> Result<Self, B::Error> {
+        if leaves.is_empty() {
+            return Ok(Self { levels: Vec::new() })
+        }
+        let leaves = leaves.iter().map(|leaf| hash_bytes_with_backend(leaf.as_ref(), backend)).collect::<Result<_, _>>()?;
+        let mut levels: Vec<Vec<Digest512>> = vec![leaves];
+        while let Some(level) = levels.last().filter(|level| level.len() > 1) {
+            let next = level.chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right, backend),
+                    [last] => Ok(last.clone()),
+                    _ => unreachable!(),
+                })
+                .collect::<Result<_, _>>()?;
+            levels.push(next);
+        }
+        Ok(Self { levels })
+    }
+
+    /// The root of the tree, which is all zeros for an empty tree
+    pub fn root(&self) -> Digest512 {
+        self.levels.last().map_or_else(|| CompactOutput::default().into(), |level| level[0].clone())
+    }
+
+    /// The root of the tree, tagged as [`Mode::MerkleRoot`], so that it is never mistaken for the digest of
+    /// another mode, e.g. the [`hash_bytes`](crate::hash::hash_bytes) of the single leaf that is also the root
+    pub fn tagged_root(&self) -> TaggedDigest {
+        TaggedDigest::new(Mode::MerkleRoot, self.root())
+    }
+
+    /// The number of leaves of the tree
+    pub fn len(&self) -> usize {
+        self.levels.first().map_or(0, Vec::len)
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Proves that the leaf at `index` is included under [`root`](Self::root)
+    ///
+    /// # Arguments
+    /// * `index` - the index of the leaf
+    ///
+    /// # Panics
+    /// If `index` is not the index of a leaf
+    pub fn prove(&self, index: usize) -> MerkleProof {
+        assert!(index < self.len(), "leaf {} of a tree of {} leaves", index, self.len());
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(sibling.clone());
+            }
+            position /= 2;
+        }
+        MerkleProof { index, num_leaves: self.len(), siblings }
+    }
+}
+
+/// A proof that a leaf is included at some index of a Merkle tree, made by [`MerkleTree::prove`]
+#[derive(Debug, Clone)]
+pub struct MerkleProof {
+    index: usize,
+    num_leaves: usize,
+    /// The siblings of the nodes on the path from the leaf to the root, skipping the levels where the node is promoted
+    siblings: Vec<Digest512>,
+}
+
+impl MerkleProof {
+    /// Assembles a proof from its parts, e.g. as received from whoever holds the tree
+    ///
+    /// # Arguments
+    /// * `index` - the index of the leaf
+    /// * `num_leaves` - the number of leaves of the tree
+    /// * `siblings` - the siblings of the nodes on the path from the leaf to the root, from the leaf up
+    pub fn new(index: usize, num_leaves: usize, siblings: Vec<Digest512>) -> Self {
+        Self { index, num_leaves, siblings }
+    }
+
+    /// The index of the leaf
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The number of leaves of the tree
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// The siblings of the nodes on the path from the leaf to the root, from the leaf up
+    pub fn siblings(&self) -> &[Digest512] {
+        &self.siblings
+    }
+
+    /// Whether this proves that `leaf` is included under `root`, at the index of this proof.
+    /// The number of leaves is not hashed into the root, so it is only checked as far as it shapes the path of the leaf
+    ///
+    /// # Arguments
+    /// * `root` - the root of the tree
+    /// * `leaf` - the leaf, of any length
+    pub fn verify(&self, root: &Digest512, leaf: &[u8]) -> bool {
+        infallible(self.verify_with_backend(root, leaf, &NativeBackend))
+    }
+
+    /// Whether this proves that `leaf` is included under `root` as [`verify`](Self::verify) checks it,
+    /// hashing the leaf and the nodes on its path with `backend`
+    ///
+    /// # Arguments
+    /// * `root` - the root of the tree
+    /// * `leaf` - the leaf, of any length
+    /// * `backend` - the backend to hash blocks with
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash some block
+    pub fn verify_with_backend<B: SwifftBackend>(&self, root: &Digest512, leaf: &[u8], backend: &B) -> Result<bool, B::Error> {
+        Ok(self.root_with_backend(leaf, backend)?.is_some_and(|node| node == *root))
+    }
+
+    /// The root that this proves `leaf` to be included under, e.g. to check it against a commitment to the root
+    /// rather than the root itself, or `None` if the index is out of range or the siblings do not fit the path of the leaf
+    ///
+    /// # Arguments
+    /// * `leaf` - the leaf, of any length
+    pub fn root(&self, leaf: &[u8]) -> Option<Digest512> {
+        infallible(self.root_with_backend(leaf, &NativeBackend))
+    }
+
+    /// The root that this proves `leaf` to be included under as [`root`](Self::root) computes it,
+    /// hashing the leaf and the nodes on its path with `backend`
+    ///
+    /// # Arguments
+    /// * `leaf` - the leaf, of any length
+    /// * `backend` - the backend to hash blocks with
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash some block
+    pub fn root_with_backend<B: SwifftBackend>(&self, leaf: &[u8], backend: &B) -> Result<Option<Digest512>, B::Error> {
+        if self.index >= self.num_leaves {
+            return Ok(None)
+        }
+        let mut siblings = self.siblings.iter();
+        let mut node = hash_bytes_with_backend(leaf, backend)?;
+        let (mut position, mut width) = (self.index, self.num_leaves);
+        while width > 1 {
+            if position ^ 1 < width {
+                let Some(sibling) = siblings.next() else { return Ok(None) };
+                node = if position % 2 == 0 { self::node(&node, sibling, backend)? } else { self::node(sibling, &node, backend)? };
+            }
+            position /= 2;
+            width = width.div_ceil(2);
+        }
+        Ok(siblings.next().is_none().then_some(node))
+    }
+}
+
+/// Computes the root of [`MerkleTree::build`] over leaves pushed one at a time, e.g. as streamed from disk,
+/// without holding them: only the roots of the complete subtrees so far are kept, one per bit set in the number of leaves
+///
+/// ```
+/// use libswifft::merkle::{MerkleTree, StreamingBuilder};
+///
+/// let mut builder = StreamingBuilder::new();
+/// for leaf in ["alice", "bob", "carol"] {
+///     builder.push_leaf(leaf.as_bytes());
+/// }
+/// assert_eq!(builder.num_leaves(), 3);
+/// assert_eq!(builder.finalize(), MerkleTree::build(&["alice", "bob", "carol"]).root());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StreamingBuilder<B: SwifftBackend = NativeBackend> {
+    backend: B,
+    /// The roots of the complete subtrees, from the largest and leftmost
+    frontier: Vec<Digest512>,
+    num_leaves: u64,
+}
+
+impl StreamingBuilder {
+    /// Creates a builder of an empty tree
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hashes `leaf` as the next leaf, merging every subtree that it completes
+    ///
+    /// # Arguments
+    /// * `leaf` - the leaf, of any length
+    pub fn push_leaf(&mut self, leaf: &[u8]) {
+        infallible(self.try_push_leaf(leaf))
+    }
+
+    /// The root of the tree of the leaves pushed, which is all zeros for an empty tree as for [`MerkleTree::root`]
+    pub fn finalize(self) -> Digest512 {
+        infallible(self.try_finalize())
+    }
+}
+
+impl<B: SwifftBackend> StreamingBuilder<B> {
+    /// Creates a builder of an empty tree that hashes with `backend`
+    ///
+    /// # Arguments
+    /// * `backend` - the backend to hash blocks with
+    pub fn with_backend(backend: B) -> Self {
+        Self { backend, frontier: Vec::new(), num_leaves: 0 }
+    }
+
+    /// Hashes `leaf` as the next leaf with the backend, merging every subtree that it completes
+    ///
+    /// # Arguments
+    /// * `leaf` - the leaf, of any length
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash some block, leaving the builder unchanged
+    pub fn try_push_leaf(&mut self, leaf: &[u8]) -> Result<(), B::Error> {
+        let mut subtree = hash_bytes_with_backend(leaf, &self.backend)?;
+        let mut merged = 0;
+        let mut num_leaves = self.num_leaves + 1;
+        while num_leaves % 2 == 0 {
+            merged += 1;
+            let left = &self.frontier[self.frontier.len() - merged];
+            subtree = node(left, &subtree, &self.backend)?;
+            num_leaves /= 2;
+        }
+        self.frontier.truncate(self.frontier.len() - merged);
+        self.frontier.push(subtree);
+        self.num_leaves += 1;
+        Ok(())
+    }
+
+    /// The number of leaves pushed so far
+    pub fn num_leaves(&self) -> u64 {
+        self.num_leaves
+    }
+
+    /// The root of the tree of the leaves pushed as [`finalize`](StreamingBuilder::finalize) computes it,
+    /// merging the subtrees with the backend
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash some block
+    pub fn try_finalize(mut self) -> Result<Digest512, B::Error> {
+        let Some(mut root) = self.frontier.pop() else {
+            return Ok(CompactOutput::default().into())
+        };
+        while let Some(left) = self.frontier.pop() {
+            root = node(&left, &root, &self.backend)?;
+        }
+        Ok(root)
+    }
+}
+
+/// The inner node of `left` and `right`, hashed with `backend`
+fn node<B: SwifftBackend>(left: &Digest512, right: &Digest512, backend: &B) -> Result<Digest512, B::Error> {
+    Ok(node_with(left.as_compact_output(), right.as_compact_output(), backend)?.into())
+}
@@ -0,0 +1,51 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Checks on the buffers passed to the native library, enabled in debug builds,
+//! and on the values it emits, enabled by the `paranoid` feature
+
+use crate::buffer::Outputs;
+use crate::constant::BUFFER_ALIGNMENT;
+
+/// Asserts, in debug builds, that a buffer about to be passed to the native library
+/// is non-null and aligned to [`BUFFER_ALIGNMENT`] bytes
+///
+/// # Arguments
+/// * `buffer` - the buffer to check
+/// * `argument` - the name of the argument holding the buffer, for the panic message
+#[inline]
+#[track_caller]
+pub(crate) fn debug_assert_ffi_buffer<T>(buffer: *const T, argument: &str) {
+    debug_assert!(!buffer.is_null(), "`{}` passed to the native library is null", argument);
+    debug_assert!(buffer as usize % BUFFER_ALIGNMENT == 0,
+        "`{}` passed to the native library at {:p} is not {}-byte aligned", argument, buffer, BUFFER_ALIGNMENT);
+}
+
+/// Asserts, in debug builds with the `paranoid` feature, that every element of an operand that the native library
+/// copies without reducing, as `SWIFFT_Set` does, is canonical, i.e. in `[0, 256]`, so that a non-canonical result
+/// is blamed on the caller rather than the library
+///
+/// # Arguments
+/// * `operand` - the blocks about to be passed to the native library
+/// * `argument` - the name of the argument holding the blocks, for the panic message
+#[inline]
+#[track_caller]
+pub(crate) fn debug_assert_canonical_operand<const NUM_BLOCKS: usize>(operand: &Outputs<NUM_BLOCKS>, argument: &str) {
+    #[cfg(feature = "paranoid")]
+    debug_assert!(operand.is_canonical(),
+        "`{}` passed to the native library holds a non-canonical element, which it copies unreduced", argument);
+    #[cfg(not(feature = "paranoid"))]
+    let _ = (operand, argument);
+}
+
+/// Asserts, in debug builds with the `paranoid` feature, that every element written by the native library
+/// is canonical, i.e. in `[0, 256]`.
+///
+/// Every operation reduces its operands except copies, whose operands are checked beforehand
+/// by [`debug_assert_canonical_operand`], so a non-canonical element here is the library's
+#[inline]
+pub(crate) fn debug_assert_canonical<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>) {
+    #[cfg(feature = "paranoid")]
+    debug_assert!(output.is_canonical(), "native library emitted a non-canonical element");
+    #[cfg(not(feature = "paranoid"))]
+    let _ = output;
+}
@@ -0,0 +1,128 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Recycling of large heap-allocated buffers between batches
+
+use std::alloc::{alloc_zeroed, dealloc, handle_alloc_error, Layout};
+use std::collections::HashMap;
+use std::ptr::NonNull;
+use std::sync::Mutex;
+
+use crate::block::Block;
+use crate::constant::BUFFER_ALIGNMENT;
+
+/// An allocation of a size class, owned by the pool while it is not handed out
+struct Allocation(NonNull<u8>);
+
+// SAFETY: an `Allocation` uniquely owns plain bytes, which may be freed or reused from any thread
+unsafe impl Send for Allocation {}
+
+/// A thread-safe pool of boxed buffers, such as large [`Inputs`](crate::buffer::Inputs)
+/// and [`Outputs`](crate::buffer::Outputs), that avoids repeated large allocations in hot batch loops.
+///
+/// Buffers are recycled by size class, i.e. by their size in bytes,
+/// so a buffer returned with [`put`](Self::put) can be handed out again by [`get`](Self::get)
+/// as any buffer type of the same size.
+///
+/// ```
+/// use libswifft::buffer::{Inputs, Outputs};
+/// use libswifft::hash::compute_multiple;
+/// use libswifft::pool::AlignedBufferPool;
+///
+/// let pool = AlignedBufferPool::new(4);
+/// for _ in 0..3 {
+///     let input = pool.get::<Inputs<1024>>();
+///     let mut output = pool.get::<Outputs<1024>>();
+///     compute_multiple(&input, &mut output);
+///     pool.put(input);
+///     pool.put(output);
+/// }
+/// ```
+pub struct AlignedBufferPool {
+    classes: Mutex<HashMap<usize, Vec<Allocation>>>,
+    max_per_class: usize,
+}
+
+impl AlignedBufferPool {
+    /// Creates an empty pool
+    ///
+    /// # Arguments
+    /// * `max_per_class` - the number of buffers of each size class to keep for reuse,
+    ///   beyond which returned buffers are freed
+    pub fn new(max_per_class: usize) -> Self {
+        Self { classes: Mutex::new(HashMap::new()), max_per_class }
+    }
+
+    /// Hands out a buffer, reusing one of the same size class if available and allocating a zeroed one otherwise.
+    ///
+    /// The contents of a reused buffer are those it was [`put`](Self::put) back with
+    ///
+    /// # Arguments
+    /// * `B` - the type of buffer to hand out
+    pub fn get<B: Block>(&self) -> Box<B> {
+        let layout = Layout::new::<B>();
+        if layout.size() == 0 {
+            return Box::default()
+        }
+        let reused = self.lock().get_mut(&layout.size()).and_then(Vec::pop);
+        let buffer = match reused {
+            Some(Allocation(buffer)) => buffer.as_ptr(),
+            None => {
+                // SAFETY: `layout` has a non-zero size
+                let buffer = unsafe { alloc_zeroed(layout) };
+                if buffer.is_null() {
+                    handle_alloc_error(layout)
+                }
+                buffer
+            }
+        };
+        // SAFETY: `Block` is sealed, and only implemented by `AlignedBuffer`s, which are valid for any initialized bytes.
+        // All buffers of a size class share its layout, since every `AlignedBuffer` has the same alignment,
+        // and their bytes are initialized, either zeroed or by the buffer previously put back
+        unsafe { Box::from_raw(buffer.cast()) }
+    }
+
+    /// Returns a buffer to the pool for reuse, or frees it if its size class is already full
+    ///
+    /// # Arguments
+    /// * `buffer` - the buffer to return, which need not have come from this pool
+    pub fn put<B: Block>(&self, buffer: Box<B>) {
+        let size = Layout::new::<B>().size();
+        if size == 0 {
+            return
+        }
+        let mut classes = self.lock();
+        let class = classes.entry(size).or_default();
+        if class.len() < self.max_per_class {
+            // SAFETY: `Box::into_raw` never returns null
+            class.push(Allocation(unsafe { NonNull::new_unchecked(Box::into_raw(buffer).cast()) }));
+        }
+    }
+
+    /// The number of buffers currently kept for reuse, across all size classes
+    pub fn len(&self) -> usize {
+        self.lock().values().map(Vec::len).sum()
+    }
+
+    /// Whether no buffers are currently kept for reuse
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<usize, Vec<Allocation>>> {
+        // the pool is never left inconsistent by a panic, so recover from poisoning
+        self.classes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for AlignedBufferPool {
+    fn drop(&mut self) {
+        let classes = self.classes.get_mut().unwrap_or_else(|poisoned| poisoned.into_inner());
+        for (&size, class) in classes.iter() {
+            let layout = Layout::from_size_align(size, BUFFER_ALIGNMENT).unwrap();
+            for Allocation(buffer) in class {
+                // SAFETY: every allocation of this size class was allocated with `layout`
+                unsafe { dealloc(buffer.as_ptr(), layout) }
+            }
+        }
+    }
+}
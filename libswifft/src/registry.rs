@@ -0,0 +1,313 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! A small verifiable key-value registry, built only from the primitives of this crate.
+//!
+//! Keys map to the digests of their values. Every key is stored at a leaf of a [`MerkleTree`], in the order that keys
+//! were first inserted, and the leaf holds the digests of the key and of its value. The root is a [`TaggedDigest`] of
+//! [`Mode::Registry`] over [`hash_bytes`] of the root of the tree followed by the number of keys, so that it commits to
+//! the shape of the tree as well as to its leaves.
+//!
+//! Every update produces an [`UpdateProof`], from which an [`Auditor`] who only knows the previous root
+//! can check the transition to the new root, without the contents of the registry:
+//! setting the value of a key proves the old leaf and the new one with the same [`MerkleProof`],
+//! and inserting a key proves that its leaf was appended after every existing leaf.
+//!
+//! This is an example of composing the crate rather than a production design: keys and values are hashed
+//! with [`LogDigest::entry_hash`], every update rebuilds the tree, and an insertion does not prove that the key
+//! was absent before, which only the registry itself checks
+//!
+//! ```
+//! use libswifft::registry::{Auditor, Registry};
+//!
+//! let mut registry = Registry::new();
+//! let mut auditor = Auditor::new(registry.root());
+//! let proof = registry.insert(b"alice", b"public key of alice");
+//! auditor.replay(&proof, &registry.root()).unwrap();
+//! assert_eq!(auditor.root().verify(&registry.root()), Ok(true));
+//! ```
+
+use std::collections::HashMap;
+
+use crate::buffer::Digest512;
+use crate::constant::COMPACT_OUTPUT_BLOCK_SIZE;
+use crate::error::ProofError;
+use crate::hash::{compress_compact, hash_bytes};
+use crate::log::LogDigest;
+use crate::merkle::{MerkleProof, MerkleTree};
+use crate::tagged::{Mode, TaggedDigest};
+
+/// The position that keys are hashed at with [`LogDigest::entry_hash`], separating them from values
+const KEY_POSITION: u64 = u64::MAX;
+
+/// The position that values are hashed at with [`LogDigest::entry_hash`], separating them from keys
+const VALUE_POSITION: u64 = u64::MAX - 1;
+
+/// A key-value registry whose contents are committed to by a single root
+#[derive(Debug, Default)]
+pub struct Registry {
+    /// The key and value digests of every leaf, in the order that the keys were first inserted
+    leaves: Vec<(Digest512, Digest512)>,
+    /// The index of the leaf of every key digest
+    positions: HashMap<Digest512, usize>,
+    tree: MerkleTree,
+}
+
+impl Registry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The root of the registry, which commits to every key and the hash of its value
+    pub fn root(&self) -> TaggedDigest {
+        root(&self.tree.root(), self.leaves.len())
+    }
+
+    /// The digest of the value of `key`, if it is in the registry
+    ///
+    /// # Arguments
+    /// * `key` - the key to look up, of any length
+    pub fn get(&self, key: &[u8]) -> Option<&Digest512> {
+        let index = *self.positions.get(&hash_key(key))?;
+        Some(&self.leaves[index].1)
+    }
+
+    /// The number of keys in the registry
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    /// Whether the registry has no keys
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    /// Sets the value of `key`, inserting the key if it is not in the registry yet
+    ///
+    /// # Arguments
+    /// * `key` - the key to set, of any length
+    /// * `value` - the value to set it to, of any length
+    ///
+    /// Returns the proof of the transition from the previous root to the new one
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) -> UpdateProof {
+        let key = hash_key(key);
+        let value = hash_value(value);
+        let num_leaves = self.leaves.len();
+        let (index, old_value) = match self.positions.get(&key) {
+            Some(&index) => (index, Some(std::mem::replace(&mut self.leaves[index].1, value.clone()))),
+            None => {
+                self.positions.insert(key.clone(), num_leaves);
+                self.leaves.push((key.clone(), value.clone()));
+                (num_leaves, None)
+            }
+        };
+        self.tree = MerkleTree::build(&self.leaves.iter().map(|(key, value)| leaf(key, value)).collect::<Vec<_>>());
+        let siblings = self.tree.prove(index).siblings().to_vec();
+        UpdateProof { key, old_value, new_value: value, index, num_leaves, siblings }
+    }
+}
+
+/// The proof that a single update to a [`Registry`] turned one root into another
+#[derive(Debug, Clone)]
+pub struct UpdateProof {
+    key: Digest512,
+    old_value: Option<Digest512>,
+    new_value: Digest512,
+    /// The index of the leaf of the key
+    index: usize,
+    /// The number of keys before the update
+    num_leaves: usize,
+    /// The siblings of the path from the leaf to the root after the update, as in a [`MerkleProof`]
+    siblings: Vec<Digest512>,
+}
+
+impl UpdateProof {
+    /// The digest of the key that was updated
+    pub fn key(&self) -> &Digest512 {
+        &self.key
+    }
+
+    /// The digest of the value of the key before the update, if it was in the registry
+    pub fn old_value(&self) -> Option<&Digest512> {
+        self.old_value.as_ref()
+    }
+
+    /// The digest of the value of the key after the update
+    pub fn new_value(&self) -> &Digest512 {
+        &self.new_value
+    }
+
+    /// Checks that this proof turns `old_root` into `new_root`
+    ///
+    /// # Arguments
+    /// * `old_root` - the root before the update
+    /// * `new_root` - the root claimed after the update
+    ///
+    /// # Errors
+    /// * [`ProofError::Tag`] if either root is not of [`Mode::Registry`]
+    /// * [`ProofError::OldRootMismatch`] if the proof does not start from `old_root`
+    /// * [`ProofError::NewRootMismatch`] if the proof does not end at `new_root`
+    pub fn verify(&self, old_root: &TaggedDigest, new_root: &TaggedDigest) -> Result<(), ProofError> {
+        let (old_tree_root, num_leaves) = match &self.old_value {
+            // the leaf is overwritten in place, so the tree keeps its shape and the siblings of the leaf
+            Some(old_value) => (self.proof(self.num_leaves).root(&leaf(&self.key, old_value)), self.num_leaves),
+            // the leaf is appended, so its siblings are the roots of the complete subtrees of the old tree
+            None => (self.appended_root(), self.num_leaves.saturating_add(1)),
+        };
+        let old_tree_root = old_tree_root.ok_or(ProofError::OldRootMismatch)?;
+        if !root(&old_tree_root, self.num_leaves).verify(old_root)? {
+            return Err(ProofError::OldRootMismatch)
+        }
+        let new_tree_root = self.proof(num_leaves).root(&leaf(&self.key, &self.new_value)).ok_or(ProofError::NewRootMismatch)?;
+        if !root(&new_tree_root, num_leaves).verify(new_root)? {
+            return Err(ProofError::NewRootMismatch)
+        }
+        Ok(())
+    }
+
+    /// The proof of the leaf of the key in a tree of `num_leaves` leaves
+    fn proof(&self, num_leaves: usize) -> MerkleProof {
+        MerkleProof::new(self.index, num_leaves, self.siblings.clone())
+    }
+
+    /// The root of the tree before the leaf of the key was appended to it, from the siblings of that leaf:
+    /// the roots of the complete subtrees of the tree, from the smallest up, which the left-complete tree nests
+    /// right to left. `None` if the leaf is not the last one, whose siblings those are
+    fn appended_root(&self) -> Option<Digest512> {
+        if self.index != self.num_leaves {
+            return None
+        }
+        let mut subtrees = self.siblings.iter();
+        let Some(smallest) = subtrees.next() else { return Some(MerkleTree::default().root()) };
+        Some(subtrees.fold(smallest.clone(), |node, subtree| compress_compact(subtree.as_compact_output(), node.as_compact_output()).into()))
+    }
+
+    /// Encodes this proof as bytes: the key, a flag byte and the old value if the flag is `1`, the new value,
+    /// the index of the leaf and the number of keys before the update as little endian `u64`s, and the siblings
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(3 * COMPACT_OUTPUT_BLOCK_SIZE + 17 + self.siblings.len() * COMPACT_OUTPUT_BLOCK_SIZE);
+        bytes.extend_from_slice(self.key.as_bytes());
+        match &self.old_value {
+            Some(value) => {
+                bytes.push(1);
+                bytes.extend_from_slice(value.as_bytes());
+            }
+            None => bytes.push(0),
+        }
+        bytes.extend_from_slice(self.new_value.as_bytes());
+        bytes.extend_from_slice(&(self.index as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.num_leaves as u64).to_le_bytes());
+        for sibling in &self.siblings {
+            bytes.extend_from_slice(sibling.as_bytes());
+        }
+        bytes
+    }
+
+    /// Decodes a proof from the bytes encoded by [`UpdateProof::encode`]
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded proof
+    ///
+    /// # Errors
+    /// [`ProofError::Malformed`] if `bytes` is not exactly an encoded proof, e.g. because it is truncated
+    pub fn decode(bytes: &[u8]) -> Result<Self, ProofError> {
+        let mut reader = Reader(bytes);
+        let key = reader.digest()?;
+        let old_value = match reader.take(1)? {
+            [0] => None,
+            [1] => Some(reader.digest()?),
+            _ => return Err(ProofError::Malformed),
+        };
+        let new_value = reader.digest()?;
+        let index = reader.length()?;
+        let num_leaves = reader.length()?;
+        if reader.0.len() % COMPACT_OUTPUT_BLOCK_SIZE != 0 {
+            return Err(ProofError::Malformed)
+        }
+        let siblings = (0..reader.0.len() / COMPACT_OUTPUT_BLOCK_SIZE).map(|_| reader.digest()).collect::<Result<_, _>>()?;
+        Ok(Self { key, old_value, new_value, index, num_leaves, siblings })
+    }
+}
+
+/// Follows the root of a [`Registry`] through its updates, checking each with its [`UpdateProof`]
+#[derive(Debug, Clone)]
+pub struct Auditor {
+    root: TaggedDigest,
+}
+
+impl Auditor {
+    /// Creates an auditor that trusts `root`, e.g. the root of a new registry
+    ///
+    /// # Arguments
+    /// * `root` - the root to start from
+    pub fn new(root: TaggedDigest) -> Self {
+        Self { root }
+    }
+
+    /// The last root that the auditor checked
+    pub fn root(&self) -> &TaggedDigest {
+        &self.root
+    }
+
+    /// Checks the update from the current root to `new_root`, and moves on to `new_root` if it holds
+    ///
+    /// # Arguments
+    /// * `proof` - the proof of the update
+    /// * `new_root` - the root claimed after the update
+    ///
+    /// # Errors
+    /// As for [`UpdateProof::verify`], leaving the current root unchanged
+    pub fn replay(&mut self, proof: &UpdateProof, new_root: &TaggedDigest) -> Result<(), ProofError> {
+        proof.verify(&self.root, new_root)?;
+        self.root = new_root.clone();
+        Ok(())
+    }
+}
+
+/// Reads consecutive fields of an encoded proof
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    /// The next `len` bytes
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ProofError> {
+        if self.0.len() < len {
+            return Err(ProofError::Malformed)
+        }
+        let (field, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(field)
+    }
+
+    /// The next digest
+    fn digest(&mut self) -> Result<Digest512, ProofError> {
+        Ok(Digest512::from_bytes(self.take(COMPACT_OUTPUT_BLOCK_SIZE)?.try_into().unwrap()))
+    }
+
+    /// The next index or length, as a little endian `u64`
+    fn length(&mut self) -> Result<usize, ProofError> {
+        let length = u64::from_le_bytes(self.take(8)?.try_into().unwrap());
+        length.try_into().map_err(|_| ProofError::Malformed)
+    }
+}
+
+/// The root of a registry whose tree has the root `tree_root` and `num_leaves` leaves
+fn root(tree_root: &Digest512, num_leaves: usize) -> TaggedDigest {
+    let mut bytes = tree_root.as_bytes().to_vec();
+    bytes.extend_from_slice(&(num_leaves as u64).to_le_bytes());
+    TaggedDigest::new(Mode::Registry, hash_bytes(&bytes))
+}
+
+/// The leaf of the key digest `key` with the value digest `value`
+fn leaf(key: &Digest512, value: &Digest512) -> Vec<u8> {
+    [key.as_bytes().as_slice(), value.as_bytes()].concat()
+}
+
+/// The digest of `key`
+fn hash_key(key: &[u8]) -> Digest512 {
+    LogDigest::entry_hash(KEY_POSITION, key).compact()
+}
+
+/// The digest of `value`
+fn hash_value(value: &[u8]) -> Digest512 {
+    LogDigest::entry_hash(VALUE_POSITION, value).compact()
+}
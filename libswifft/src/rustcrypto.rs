@@ -0,0 +1,59 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! The [RustCrypto](https://github.com/RustCrypto/traits) `digest` traits, behind the `digest` feature,
+//! so that SWIFFT can be used by code that is generic over [`digest::Digest`].
+//!
+//! [`Swifft512`] hashes messages of any length exactly as [`hash_bytes`](crate::hash::hash_bytes) does, into its `64` byte compact hash value
+//!
+//! ```
+//! use digest::Digest;
+//! use libswifft::hash::hash_bytes;
+//! use libswifft::rustcrypto::Swifft512;
+//!
+//! let mut hasher = Swifft512::new();
+//! hasher.update(b"Hello, ");
+//! hasher.update(b"world!");
+//! assert_eq!(hasher.finalize()[..], hash_bytes(b"Hello, world!").as_bytes()[..]);
+//! ```
+
+use digest::consts::U64;
+use digest::{FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update};
+
+use crate::hash::Hasher;
+
+/// A streaming SWIFFT hasher with a `64` byte output, the digest of [`hash_bytes`](crate::hash::hash_bytes) of the message.
+///
+/// This is a [`Hasher`] whose hash value is compacted, so feeding the message in pieces of any size
+/// gives the same output as hashing it at once
+#[derive(Clone, Default)]
+pub struct Swifft512(Hasher);
+
+impl HashMarker for Swifft512 {}
+
+impl OutputSizeUser for Swifft512 {
+    type OutputSize = U64;
+}
+
+impl Update for Swifft512 {
+    fn update(&mut self, data: &[u8]) {
+        self.0.update(data)
+    }
+}
+
+impl FixedOutput for Swifft512 {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(self.0.finalize().compact().as_bytes());
+    }
+}
+
+impl FixedOutputReset for Swifft512 {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        std::mem::take(self).finalize_into(out)
+    }
+}
+
+impl Reset for Swifft512 {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
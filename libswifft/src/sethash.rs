@@ -0,0 +1,105 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Incremental hashes of multisets, which do not depend on the order that their items are inserted in.
+//!
+//! The digest of a multiset is the element-wise sum modulo `257` of the hash values of its items, each hashed by
+//! [`Hasher`] as [`hash_bytes`](crate::hash::hash_bytes) hashes it but without compacting. Inserting and removing an
+//! item are a single addition or subtraction, and the digests of two multisets add up to the digest of their union.
+//! Unlike the item hashes, the sum is linear, so it only resists collisions as far as finding items whose hash values
+//! sum to a given value is hard; it should not be used where an adversary chooses very many items
+//!
+//! ```
+//! use libswifft::sethash::SetDigest;
+//!
+//! let mut a = SetDigest::new();
+//! a.insert(b"apple");
+//! a.insert(b"pear");
+//! let mut b = SetDigest::new();
+//! b.insert(b"pear");
+//! b.insert(b"plum");
+//! b.insert(b"apple");
+//! b.remove(b"plum");
+//! assert_eq!(a, b);
+//! ```
+
+use crate::arithmetic::{add, sub};
+use crate::buffer::Output;
+use crate::constant::OUTPUT_BLOCK_SIZE;
+use crate::error::OutOfRange;
+use crate::hash::Hasher;
+
+/// The digest of a multiset of byte strings.
+///
+/// Removing an item that was never inserted is not detected: the digest then stands for a multiset
+/// where that item occurs a negative number of times, until it is inserted again
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SetDigest {
+    sum: Output,
+}
+
+impl SetDigest {
+    /// Creates the digest of the empty multiset, which is all zeros
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts one occurrence of `item`
+    ///
+    /// # Arguments
+    /// * `item` - the item, of any length
+    pub fn insert(&mut self, item: &[u8]) {
+        add(&mut self.sum, &Self::item_hash(item));
+    }
+
+    /// Removes one occurrence of `item`
+    ///
+    /// # Arguments
+    /// * `item` - the item, of any length
+    pub fn remove(&mut self, item: &[u8]) {
+        sub(&mut self.sum, &Self::item_hash(item));
+    }
+
+    /// Adds every item of `other` to this multiset
+    ///
+    /// # Arguments
+    /// * `other` - the digest of the multiset to add
+    pub fn union(&mut self, other: &Self) {
+        add(&mut self.sum, &other.sum);
+    }
+
+    /// Whether this is the digest of the empty multiset, i.e. every insertion has been matched by a removal
+    pub fn is_empty_digest(&self) -> bool {
+        self.sum.0[0].iter().all(|&byte| byte == 0)
+    }
+
+    /// The digest, with elements in `[0, 256]`
+    pub fn digest(&self) -> &Output {
+        &self.sum
+    }
+
+    /// Encodes the state of the digest, its elements as little endian `16`-bit values
+    pub fn encode(&self) -> [u8; OUTPUT_BLOCK_SIZE] {
+        self.sum.0[0]
+    }
+
+    /// Decodes the state of a digest, as encoded by [`SetDigest::encode`]
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded state, of size 128 bytes (1024 bit)
+    ///
+    /// # Errors
+    /// Returns [`OutOfRange`] for the first element that lies outside `[0, 256]`, which no digest holds
+    pub fn decode(bytes: &[u8; OUTPUT_BLOCK_SIZE]) -> Result<Self, OutOfRange> {
+        Output::from_bytes_canonical(bytes).map(|sum| Self { sum })
+    }
+
+    /// The hash value that an item contributes to the digest
+    ///
+    /// # Arguments
+    /// * `item` - the item, of any length
+    pub fn item_hash(item: &[u8]) -> Output {
+        let mut hasher = Hasher::new();
+        hasher.update(item);
+        hasher.finalize()
+    }
+}
@@ -0,0 +1,107 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Contiguous tables of compact hash values, e.g. for writing out as segments of an on-disk index
+
+use std::io::{self, Read, Write};
+
+use crate::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Inputs};
+use crate::constant::COMPACT_OUTPUT_BLOCK_SIZE;
+use crate::hash::compute_and_compact_table;
+
+/// A table of compact hash values, stored contiguously as `64` bytes each.
+///
+/// Lookups with [`DigestTable::binary_search`] require the table to have been sorted
+/// with [`DigestTable::sort`], which orders the compact hash values by their bytes.
+#[derive(Debug, Default)]
+pub struct DigestTable(Vec<CompactOutput>);
+
+impl DigestTable {
+    /// Hashes and compacts every block of `input` into a table, in the order of the blocks
+    ///
+    /// # Arguments
+    /// * `NUM_BLOCKS` - the number of blocks to operate on
+    /// * `input` - the blocks of input, each of 256 bytes (2048 bit)
+    pub fn compute<const NUM_BLOCKS: usize>(input: &Inputs<NUM_BLOCKS>) -> Self {
+        Self::from_compact_outputs(&compute_and_compact_table(input))
+    }
+
+    /// Creates a table of the compact hash values, in order
+    ///
+    /// # Arguments
+    /// * `NUM_BLOCKS` - the number of compact hash values
+    /// * `compact_outputs` - the compact hash values to copy into the table
+    pub fn from_compact_outputs<const NUM_BLOCKS: usize>(compact_outputs: &CompactOutputs<NUM_BLOCKS>) -> Self {
+        Self(compact_outputs.0.iter().map(|block| AlignedBuffer([*block])).collect())
+    }
+
+    /// Reads a table of contiguous compact hash values, as written by [`DigestTable::write_to`]
+    ///
+    /// # Arguments
+    /// * `reader` - the reader to read until its end
+    ///
+    /// # Errors
+    /// Returns any error of `reader`, and an [`io::ErrorKind::InvalidData`] error
+    /// if the number of bytes read is not a multiple of `64`
+    pub fn read_from(reader: &mut impl Read) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        if bytes.len() % COMPACT_OUTPUT_BLOCK_SIZE != 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData,
+                format!("{} bytes is not a whole number of compact hash values", bytes.len())))
+        }
+        Ok(Self(bytes.chunks_exact(COMPACT_OUTPUT_BLOCK_SIZE)
+            .map(|block| AlignedBuffer([block.try_into().unwrap()]))
+            .collect()))
+    }
+
+    /// Writes the compact hash values contiguously, in the order of the table
+    ///
+    /// # Arguments
+    /// * `writer` - the writer to write into
+    ///
+    /// # Errors
+    /// Returns any error of `writer`
+    pub fn write_to(&self, writer: &mut impl Write) -> io::Result<()> {
+        for compact_output in &self.0 {
+            writer.write_all(&compact_output.0[0])?
+        }
+        Ok(())
+    }
+
+    /// The `i`th compact hash value of the table
+    ///
+    /// # Arguments
+    /// * `i` - the index of the compact hash value
+    ///
+    /// # Panics
+    /// If `i` is not less than [`DigestTable::len`]
+    pub fn get(&self, i: usize) -> &CompactOutput {
+        &self.0[i]
+    }
+
+    /// The number of compact hash values in the table
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table holds no compact hash values
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sorts the table by the bytes of the compact hash values, for use with [`DigestTable::binary_search`]
+    pub fn sort(&mut self) {
+        self.0.sort_unstable_by_key(|compact_output| compact_output.0)
+    }
+
+    /// Searches a sorted table for `digest`
+    ///
+    /// # Arguments
+    /// * `digest` - the compact hash value to search for
+    ///
+    /// Returns the index of `digest` if it is present, or else the index at which it could be inserted,
+    /// as with [`slice::binary_search`]. The result is meaningless if the table is not sorted
+    pub fn binary_search(&self, digest: &CompactOutput) -> Result<usize, usize> {
+        self.0.binary_search_by(|compact_output| compact_output.0.cmp(&digest.0))
+    }
+}
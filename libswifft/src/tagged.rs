@@ -0,0 +1,288 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Compact hash values tagged with the mode that produced them and the parameters of the hash function,
+//! so that values of different modes that end up side by side, e.g. in one database, are never mistaken for one another.
+//!
+//! The binary encoding is [`ENCODED_SIZE`] bytes: the mode byte, the little endian [`PARAMETER_HASH`],
+//! and the `64` bytes of the compact hash value. The string encoding is `swifft:v1:<mode>:<hex>`,
+//! where `v1` stands for the parameters and `<hex>` is the compact hash value in lowercase hex.
+//! Both encodings are frozen, and decoding either rejects anything that encoding would not produce
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use crate::arithmetic::{add, add_assign_self};
+use crate::buffer::{decode_hex, Digest512, Input, Output, SignInput};
+use crate::constant::COMPACT_OUTPUT_BLOCK_SIZE;
+use crate::error::{LenMismatch, TagError};
+use crate::hash::{compute, compute_signed, constant_time_eq, hash_bytes};
+
+/// A hash of the parameters `n=64, m=32, q=257`, the 64-bit FNV-1a of `n=64,m=32,q=257`,
+/// which encoded values carry so that values of other parameter sets are rejected
+pub const PARAMETER_HASH: u64 = fnv1a(b"n=64,m=32,q=257");
+
+/// The size of the binary encoding of a [`TaggedDigest`], in bytes
+pub const ENCODED_SIZE: usize = 1 + size_of::<u64>() + COMPACT_OUTPUT_BLOCK_SIZE;
+
+/// The prefix of the string encoding of a [`TaggedDigest`], naming the hash function and its parameters
+const STRING_PREFIX: &str = "swifft:v1:";
+
+/// The hashing mode that produced a compact hash value.
+///
+/// The byte of every mode is frozen; modes may be added, so bytes that are unknown to this version are rejected
+/// with [`TagError::UnknownMode`] rather than misread
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[repr(u8)]
+pub enum Mode {
+    /// A single input block, hashed by [`compute`]
+    Plain = 1,
+    /// A single input block with its sign bits, hashed by [`compute_signed`]
+    Signed = 2,
+    /// An append-only log, digested by [`LogDigest`](crate::log::LogDigest)
+    Log = 3,
+    /// The last blocks of a stream, digested by [`SlidingWindowHasher`](crate::window::SlidingWindowHasher)
+    Window = 4,
+    /// The root of a registry, of the `registry` module behind the `registry` feature
+    Registry = 5,
+    /// The root of a [`MerkleTree`](crate::merkle::MerkleTree)
+    MerkleRoot = 6,
+    /// The tag of a message, computed by [`SwifftMac`](crate::mac::SwifftMac) under a secret key
+    Keyed = 7,
+    /// A message of any length, whose blocks are chained by [`hash_bytes`] or a [`Hasher`](crate::hash::Hasher)
+    Chained = 8,
+    /// A single input block with coefficients in `{0, 1, 2, 3}`, hashed by [`TaggedDigest::base4`]
+    Base4 = 9,
+}
+
+impl Mode {
+    /// Every mode, in the order of their bytes
+    const ALL: [Self; 9] = [
+        Self::Plain, Self::Signed, Self::Log, Self::Window, Self::Registry, Self::MerkleRoot, Self::Keyed, Self::Chained,
+        Self::Base4,
+    ];
+
+    /// The byte that identifies this mode in the binary encoding
+    pub const fn byte(self) -> u8 {
+        self as u8
+    }
+
+    /// The mode identified by `byte` in the binary encoding
+    ///
+    /// # Errors
+    /// [`TagError::UnknownMode`] if no mode of this version is identified by `byte`
+    pub fn from_byte(byte: u8) -> Result<Self, TagError> {
+        Self::ALL.into_iter().find(|mode| mode.byte() == byte).ok_or(TagError::UnknownMode(byte))
+    }
+
+    /// The name that identifies this mode in the string encoding
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Plain => "plain",
+            Self::Signed => "signed",
+            Self::Log => "log",
+            Self::Window => "window",
+            Self::Registry => "registry",
+            Self::MerkleRoot => "merkle-root",
+            Self::Keyed => "keyed",
+            Self::Chained => "chained",
+            Self::Base4 => "base4",
+        }
+    }
+
+    /// The mode identified by `name` in the string encoding
+    ///
+    /// # Errors
+    /// [`TagError::UnknownModeName`] if no mode of this version is identified by `name`
+    pub fn from_name(name: &str) -> Result<Self, TagError> {
+        Self::ALL.into_iter().find(|mode| mode.name() == name).ok_or(TagError::UnknownModeName)
+    }
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// A [`Digest512`], tagged with the [`Mode`] that produced it.
+///
+/// Tagged digests deliberately do not implement `PartialEq`: they are compared with [`TaggedDigest::verify`],
+/// which refuses to compare digests of different modes instead of reporting them as merely different
+///
+/// ```
+/// use libswifft::buffer::Input;
+/// use libswifft::tagged::TaggedDigest;
+///
+/// let digest = TaggedDigest::plain(&Input::new(0x5a));
+/// let decoded: TaggedDigest = digest.to_string().parse().unwrap();
+/// assert_eq!(decoded.verify(&digest), Ok(true));
+/// assert_eq!(TaggedDigest::decode(&digest.encode()).unwrap().verify(&digest), Ok(true));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaggedDigest {
+    mode: Mode,
+    digest: Digest512,
+}
+
+impl TaggedDigest {
+    /// Tags a digest that was computed elsewhere, e.g. by the batch APIs of [`hash`](crate::hash).
+    /// Prefer the constructors of each mode, which cannot mismatch the tag and the value
+    ///
+    /// # Arguments
+    /// * `mode` - the mode that produced `digest`
+    /// * `digest` - the digest
+    pub fn new(mode: Mode, digest: Digest512) -> Self {
+        Self { mode, digest }
+    }
+
+    /// Hashes and compacts `input`, tagged as [`Mode::Plain`]
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit)
+    pub fn plain(input: &Input) -> Self {
+        let mut output = Output::default();
+        compute(input, &mut output);
+        Self::compacted(Mode::Plain, &output)
+    }
+
+    /// Hashes and compacts `input` with its sign bits, tagged as [`Mode::Signed`]
+    ///
+    /// # Arguments
+    /// * `input` - the input of 256 bytes (2048 bit)
+    /// * `sign_input` - the sign bits corresponding to the input of 256 bytes (2048 bit)
+    pub fn signed(input: &Input, sign_input: &SignInput) -> Self {
+        let mut output = Output::default();
+        compute_signed(input, sign_input, &mut output);
+        Self::compacted(Mode::Signed, &output)
+    }
+
+    /// Hashes a message of any length with [`hash_bytes`], tagged as [`Mode::Chained`]
+    ///
+    /// # Arguments
+    /// * `data` - the message, of any length
+    pub fn chained(data: &[u8]) -> Self {
+        Self::new(Mode::Chained, hash_bytes(data))
+    }
+
+    /// Hashes and compacts a base-4 input, tagged as [`Mode::Base4`]: coefficient `k` is bit `k` of `input`
+    /// plus twice bit `k` of `high_bits`, as packed by [`InputBuilder::into_base4`](crate::buffer::InputBuilder::into_base4).
+    ///
+    /// SWIFFT is linear modulo `257`, so the hash value is that of `input` plus twice that of `high_bits`,
+    /// and without high bits it is the hash value of [`plain`](Self::plain). As for
+    /// [`swifft_hash_base4`](swifft::hash::swifft_hash_base4), collision resistance for base-4 inputs rests on
+    /// a stronger hardness assumption than for binary inputs
+    ///
+    /// # Arguments
+    /// * `input` - the low bits of the coefficients, of 256 bytes (2048 bit)
+    /// * `high_bits` - the high bits of the coefficients, of 256 bytes (2048 bit)
+    pub fn base4(input: &Input, high_bits: &Input) -> Self {
+        let (mut output, mut high_output) = (Output::default(), Output::default());
+        compute(input, &mut output);
+        compute(high_bits, &mut high_output);
+        add_assign_self(&mut high_output);
+        add(&mut output, &high_output);
+        Self::compacted(Mode::Base4, &output)
+    }
+
+    /// Compacts `output`, tagged as `mode`
+    pub(crate) fn compacted(mode: Mode, output: &Output) -> Self {
+        Self { mode, digest: output.compact() }
+    }
+
+    /// The mode that produced the digest
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// The digest, without its tag
+    pub fn digest(&self) -> &Digest512 {
+        &self.digest
+    }
+
+    /// Whether `other` is the same digest as this one, compared in time independent of their bytes
+    ///
+    /// # Arguments
+    /// * `other` - the tagged digest to compare against
+    ///
+    /// # Errors
+    /// [`TagError::ModeMismatch`] if `other` was produced by a different mode,
+    /// since its digest says nothing about this one
+    pub fn verify(&self, other: &Self) -> Result<bool, TagError> {
+        if self.mode != other.mode {
+            return Err(TagError::ModeMismatch { expected: self.mode, actual: other.mode })
+        }
+        Ok(constant_time_eq(&self.digest, &other.digest))
+    }
+
+    /// Encodes this tagged digest in its binary form of [`ENCODED_SIZE`] bytes
+    pub fn encode(&self) -> [u8; ENCODED_SIZE] {
+        let mut bytes = [0; ENCODED_SIZE];
+        let (mode, rest) = bytes.split_at_mut(1);
+        let (parameters, digest) = rest.split_at_mut(size_of::<u64>());
+        mode[0] = self.mode.byte();
+        parameters.copy_from_slice(&PARAMETER_HASH.to_le_bytes());
+        digest.copy_from_slice(self.digest.as_bytes());
+        bytes
+    }
+
+    /// Decodes a tagged digest from its binary form, as encoded by [`TaggedDigest::encode`]
+    ///
+    /// # Arguments
+    /// * `bytes` - the encoded tagged digest, of [`ENCODED_SIZE`] bytes
+    ///
+    /// # Errors
+    /// * [`TagError::LenMismatch`] if `bytes` is not [`ENCODED_SIZE`] bytes long
+    /// * [`TagError::UnknownMode`] if the mode byte is not known to this version
+    /// * [`TagError::UnknownParameters`] if the parameter hash is not [`PARAMETER_HASH`]
+    pub fn decode(bytes: &[u8]) -> Result<Self, TagError> {
+        LenMismatch::check(ENCODED_SIZE, bytes.len())?;
+        let (mode, rest) = bytes.split_at(1);
+        let (parameters, digest) = rest.split_at(size_of::<u64>());
+        let mode = Mode::from_byte(mode[0])?;
+        let parameters = u64::from_le_bytes(parameters.try_into().unwrap());
+        if parameters != PARAMETER_HASH {
+            return Err(TagError::UnknownParameters(parameters))
+        }
+        Ok(Self { mode, digest: Digest512::from_bytes(digest.try_into().unwrap()) })
+    }
+}
+
+impl Display for TaggedDigest {
+    /// Formats this tagged digest in its string form, e.g. `swifft:v1:plain:<hex>`
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut hex = [0; 2 * COMPACT_OUTPUT_BLOCK_SIZE];
+        write!(f, "{}{}:{}", STRING_PREFIX, self.mode, self.digest.write_hex(&mut hex))
+    }
+}
+
+impl FromStr for TaggedDigest {
+    type Err = TagError;
+
+    /// Parses a tagged digest from its string form, as formatted by its `Display` implementation
+    ///
+    /// # Errors
+    /// * [`TagError::Malformed`] if `s` does not start with `swifft:v1:`, or its hex is not
+    ///   `128` lowercase hex digits
+    /// * [`TagError::UnknownModeName`] if the mode is not known to this version
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mode, hex) = s.strip_prefix(STRING_PREFIX)
+            .and_then(|rest| rest.split_once(':'))
+            .ok_or(TagError::Malformed)?;
+        let mode = Mode::from_name(mode)?;
+        let mut digest = [0; COMPACT_OUTPUT_BLOCK_SIZE];
+        decode_hex(hex, &mut digest).ok_or(TagError::Malformed)?;
+        Ok(Self { mode, digest: Digest512::from_bytes(digest) })
+    }
+}
+
+/// The 64-bit FNV-1a hash of `bytes`
+const fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash = (hash ^ bytes[i] as u64).wrapping_mul(0x100000001b3);
+        i += 1;
+    }
+    hash
+}
@@ -0,0 +1,112 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Known-answer test vectors of the native library, embedded in the crate, and a [`self_test`] against them.
+//!
+//! The vectors are those of `libswifft/vectors/libswifft.csv`, written by `cargo xtask gen-vectors`:
+//! the all-zero, all-one, alternating and counter blocks, blocks with a single bit set,
+//! and pseudorandom blocks, each hashed, compacted, and hashed with the sign bits of the next block, then compacted.
+//! They are exported as [`VECTORS_CSV`], with the header `mode,input,sign,output` and lowercase hex columns,
+//! so that other implementations can check against them too
+//!
+//! ```
+//! libswifft::testvectors::self_test().expect("the native library computes SWIFFT incorrectly");
+//! ```
+
+use crate::buffer::{self, AlignedBuffer, CompactOutput, Input, Output, SignInput};
+use crate::capabilities::swifft_runtime_support;
+use crate::error::SelfTestError;
+use crate::hash::{compact, compute, compute_signed};
+
+/// The known-answer test vectors, one per line after the header `mode,input,sign,output`,
+/// where the mode is `compute`, `compact`, `compute_signed` or `compact_signed`, and `sign` is empty unless signed
+pub const VECTORS_CSV: &str = include_str!("../vectors/libswifft.csv");
+
+/// A known-answer test vector: an input, and the result that the native library must compute for it
+#[derive(Debug, Clone)]
+pub struct KnownAnswer {
+    /// The input block
+    pub input: Input,
+    /// The sign bits of the input block, if it is hashed by [`compute_signed`] rather than [`compute`]
+    pub sign_input: Option<SignInput>,
+    /// The expected result
+    pub expected: Expected,
+}
+
+/// The expected result of a [`KnownAnswer`]
+#[derive(Debug, Clone)]
+pub enum Expected {
+    /// The hash value
+    Output(Output),
+    /// The hash value, compacted by [`compact`]
+    CompactOutput(CompactOutput),
+}
+
+impl KnownAnswer {
+    /// The name of the mode of this vector, as in the first column of [`VECTORS_CSV`]
+    pub fn mode(&self) -> &'static str {
+        match (&self.sign_input, &self.expected) {
+            (None, Expected::Output(_)) => "compute",
+            (None, Expected::CompactOutput(_)) => "compact",
+            (Some(_), Expected::Output(_)) => "compute_signed",
+            (Some(_), Expected::CompactOutput(_)) => "compact_signed",
+        }
+    }
+
+    /// Whether the native library computes the expected result for this vector
+    pub fn check(&self) -> bool {
+        let mut output = Output::default();
+        match &self.sign_input {
+            None => compute(&self.input, &mut output),
+            Some(sign_input) => compute_signed(&self.input, sign_input, &mut output),
+        }
+        match &self.expected {
+            Expected::Output(expected) => output.0 == expected.0,
+            Expected::CompactOutput(expected) => {
+                let mut compact_output = CompactOutput::default();
+                compact(&output, &mut compact_output);
+                compact_output.0 == expected.0
+            }
+        }
+    }
+}
+
+/// Parses the known-answer test vectors of [`VECTORS_CSV`], in the order of their lines
+pub fn known_answers() -> Vec<KnownAnswer> {
+    VECTORS_CSV.lines().skip(1).map(|line| parse_line(line).expect("the embedded test vectors are well formed")).collect()
+}
+
+/// Checks that the native library computes the expected result for every known-answer test vector,
+/// e.g. once at startup, to catch a miscompiled or mislinked native library
+///
+/// # Errors
+/// * [`SelfTestError::UnsupportedIsa`] without computing anything, if the running CPU cannot run the native library
+/// * [`SelfTestError::Mismatch`] for the first vector whose result differs
+pub fn self_test() -> Result<(), SelfTestError> {
+    swifft_runtime_support().backend()?;
+    match known_answers().iter().enumerate().find(|(_, vector)| !vector.check()) {
+        None => Ok(()),
+        Some((index, vector)) => Err(SelfTestError::Mismatch { index, mode: vector.mode() }),
+    }
+}
+
+fn parse_line(line: &str) -> Option<KnownAnswer> {
+    let [mode, input, sign, output] = line.split(',').collect::<Vec<_>>()[..] else {
+        return None
+    };
+    let input = AlignedBuffer([decode_hex(input)?]);
+    let (sign_input, expected) = match mode {
+        "compute" => (None, Expected::Output(AlignedBuffer([decode_hex(output)?]))),
+        "compact" => (None, Expected::CompactOutput(AlignedBuffer([decode_hex(output)?]))),
+        "compute_signed" => (Some(AlignedBuffer([decode_hex(sign)?])), Expected::Output(AlignedBuffer([decode_hex(output)?]))),
+        "compact_signed" => (Some(AlignedBuffer([decode_hex(sign)?])), Expected::CompactOutput(AlignedBuffer([decode_hex(output)?]))),
+        _ => return None,
+    };
+    Some(KnownAnswer { input, sign_input, expected })
+}
+
+/// Decodes exactly `2 * SIZE` lowercase hex digits
+fn decode_hex<const SIZE: usize>(hex: &str) -> Option<[u8; SIZE]> {
+    let mut bytes = [0; SIZE];
+    buffer::decode_hex(hex, &mut bytes)?;
+    Some(bytes)
+}
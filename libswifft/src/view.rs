@@ -0,0 +1,188 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Borrowed views of foreign byte buffers, e.g. those of a zero-copy network stack,
+//! that the hash functions operate on in place rather than copying them into owned buffers.
+//!
+//! The constructors check the length and the alignment to [`BUFFER_ALIGNMENT`] bytes that the native library requires,
+//! so that the hash functions taking the views, such as [`compute_ref`](crate::hash::compute_ref), stay safe.
+
+use crate::buffer::{Input, Output};
+use crate::constant::{BUFFER_ALIGNMENT, INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE};
+use crate::error::{LenMismatch, ViewError};
+
+/// A borrowed input of 256 bytes (2048 bit), viewed in place
+#[derive(Debug, Clone, Copy)]
+pub struct InputRef<'a>(&'a Input);
+
+/// Borrowed blocks of input, each of 256 bytes (2048 bit), viewed in place
+#[derive(Debug, Clone, Copy)]
+pub struct InputsRef<'a>(&'a [Input]);
+
+/// A mutably borrowed hash value of 128 bytes (1024 bit), viewed in place
+#[derive(Debug)]
+pub struct OutputMut<'a>(&'a mut Output);
+
+/// Mutably borrowed blocks of hash values, each of 128 bytes (1024 bit), viewed in place
+#[derive(Debug)]
+pub struct OutputsMut<'a>(&'a mut [Output]);
+
+impl<'a> InputRef<'a> {
+    /// Views `bytes` as an input
+    ///
+    /// # Arguments
+    /// * `bytes` - the input of 256 bytes (2048 bit), aligned to [`BUFFER_ALIGNMENT`] bytes
+    ///
+    /// # Errors
+    /// Returns [`ViewError::LenMismatch`] if `bytes` is not 256 bytes long,
+    /// and [`ViewError::Misaligned`] if it is not aligned to [`BUFFER_ALIGNMENT`] bytes
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ViewError> {
+        LenMismatch::check(INPUT_BLOCK_SIZE, bytes.len())?;
+        check_alignment(bytes.as_ptr())?;
+        // SAFETY: `bytes` is 256 initialized bytes aligned to `BUFFER_ALIGNMENT`, borrowed immutably for `'a`
+        Ok(Self(unsafe { Input::from_raw_parts(bytes.as_ptr()) }))
+    }
+
+    /// The viewed input
+    pub fn as_input(&self) -> &'a Input {
+        self.0
+    }
+}
+
+impl<'a> InputsRef<'a> {
+    /// Views `bytes` as consecutive blocks of input
+    ///
+    /// # Arguments
+    /// * `bytes` - the blocks of input, each of 256 bytes (2048 bit), aligned to [`BUFFER_ALIGNMENT`] bytes unless empty
+    ///
+    /// # Errors
+    /// Returns [`ViewError::PartialBlock`] if `bytes` is not a whole number of blocks,
+    /// and [`ViewError::Misaligned`] if it is not aligned to [`BUFFER_ALIGNMENT`] bytes
+    pub fn new(bytes: &'a [u8]) -> Result<Self, ViewError> {
+        let num_blocks = num_blocks(bytes.as_ptr(), bytes.len(), INPUT_BLOCK_SIZE)?;
+        // SAFETY: `bytes` is `num_blocks * 256` initialized bytes borrowed immutably for `'a`,
+        // and aligned to `BUFFER_ALIGNMENT` unless empty, in which case no pointer is used at all
+        Ok(Self(match num_blocks {
+            0 => &[],
+            _ => unsafe { Input::slice_from_raw_parts(bytes.as_ptr(), num_blocks) },
+        }))
+    }
+
+    /// The viewed blocks of input
+    pub fn as_inputs(&self) -> &'a [Input] {
+        self.0
+    }
+
+    /// The number of blocks viewed
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no blocks are viewed
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<'a> OutputMut<'a> {
+    /// Views `bytes` as a hash value to be written
+    ///
+    /// # Arguments
+    /// * `bytes` - the hash value of 128 bytes (1024 bit), aligned to [`BUFFER_ALIGNMENT`] bytes
+    ///
+    /// # Errors
+    /// Returns [`ViewError::LenMismatch`] if `bytes` is not 128 bytes long,
+    /// and [`ViewError::Misaligned`] if it is not aligned to [`BUFFER_ALIGNMENT`] bytes
+    pub fn new(bytes: &'a mut [u8]) -> Result<Self, ViewError> {
+        LenMismatch::check(OUTPUT_BLOCK_SIZE, bytes.len())?;
+        check_alignment(bytes.as_ptr())?;
+        // SAFETY: `bytes` is 128 initialized bytes aligned to `BUFFER_ALIGNMENT`, borrowed mutably for `'a`
+        Ok(Self(unsafe { Output::from_raw_parts_mut(bytes.as_mut_ptr()) }))
+    }
+
+    /// The viewed hash value
+    pub fn as_output_mut(&mut self) -> &mut Output {
+        self.0
+    }
+
+    /// Ends the view, returning the viewed hash value for the rest of its lifetime
+    pub fn into_output(self) -> &'a mut Output {
+        self.0
+    }
+}
+
+impl<'a> OutputsMut<'a> {
+    /// Views `bytes` as consecutive blocks of hash values to be written
+    ///
+    /// # Arguments
+    /// * `bytes` - the blocks of hash values, each of 128 bytes (1024 bit), aligned to [`BUFFER_ALIGNMENT`] bytes unless empty
+    ///
+    /// # Errors
+    /// Returns [`ViewError::PartialBlock`] if `bytes` is not a whole number of blocks,
+    /// and [`ViewError::Misaligned`] if it is not aligned to [`BUFFER_ALIGNMENT`] bytes
+    pub fn new(bytes: &'a mut [u8]) -> Result<Self, ViewError> {
+        let num_blocks = num_blocks(bytes.as_ptr(), bytes.len(), OUTPUT_BLOCK_SIZE)?;
+        // SAFETY: as for `InputsRef::new`, and `bytes` is borrowed mutably for `'a`
+        Ok(Self(match num_blocks {
+            0 => &mut [],
+            _ => unsafe { Output::slice_from_raw_parts_mut(bytes.as_mut_ptr(), num_blocks) },
+        }))
+    }
+
+    /// The viewed blocks of hash values
+    pub fn as_outputs_mut(&mut self) -> &mut [Output] {
+        self.0
+    }
+
+    /// Ends the view, returning the viewed blocks of hash values for the rest of their lifetime
+    pub fn into_outputs(self) -> &'a mut [Output] {
+        self.0
+    }
+
+    /// The number of blocks viewed
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether no blocks are viewed
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Casts `bytes` to consecutive blocks of input without copying, e.g. a region of a memory-mapped arena of blocks;
+/// the slice counterpart of [`InputsRef::new`].
+///
+/// Sources that are not aligned to [`BUFFER_ALIGNMENT`] bytes cannot be cast, and must take the copying path instead,
+/// e.g. [`Input::try_from`] on each block
+///
+/// # Arguments
+/// * `bytes` - the blocks of input, each of 256 bytes (2048 bit), aligned to [`BUFFER_ALIGNMENT`] bytes unless empty
+///
+/// # Errors
+/// Returns [`ViewError::PartialBlock`] if `bytes` is not a whole number of blocks,
+/// and [`ViewError::Misaligned`] if it is not aligned to [`BUFFER_ALIGNMENT`] bytes
+pub fn try_cast_input_slice(bytes: &[u8]) -> Result<&[Input], ViewError> {
+    InputsRef::new(bytes).map(|inputs| inputs.as_inputs())
+}
+
+/// Checks that a buffer is aligned to [`BUFFER_ALIGNMENT`] bytes
+fn check_alignment(buffer: *const u8) -> Result<(), ViewError> {
+    let address = buffer as usize;
+    if address % BUFFER_ALIGNMENT == 0 {
+        Ok(())
+    } else {
+        Err(ViewError::Misaligned { address, alignment: BUFFER_ALIGNMENT })
+    }
+}
+
+/// Checks that a buffer of `len` bytes is a whole number of blocks, aligned unless empty,
+/// and returns the number of blocks
+fn num_blocks(buffer: *const u8, len: usize, block_size: usize) -> Result<usize, ViewError> {
+    if len % block_size != 0 {
+        return Err(ViewError::PartialBlock { block_size, len })
+    }
+    if len != 0 {
+        check_alignment(buffer)?;
+    }
+    Ok(len / block_size)
+}
@@ -0,0 +1,119 @@
+//! Parameters: n=64, m=32, q=257
+//!
+//! Composable digests over a sliding window of a stream of input blocks
+
+use crate::arithmetic::{add, set, sub};
+use crate::backend::{infallible, NativeBackend, SwifftBackend};
+use crate::buffer::{AlignedBuffer, Output};
+use crate::constant::INPUT_BLOCK_SIZE;
+use crate::tagged::{Mode, TaggedDigest};
+
+/// Maintains the sum of the SWIFFT hash values of the last `window_blocks` blocks of a stream.
+///
+/// The hash value of every block in the window is kept in a ring buffer,
+/// so that the contribution of the oldest block can be subtracted from the running sum when it is evicted.
+/// Memory use is bounded by the window size.
+///
+/// Blocks are hashed with the [`NativeBackend`] unless another backend is given with [`with_backend`](Self::with_backend).
+pub struct SlidingWindowHasher<B: SwifftBackend = NativeBackend> {
+    backend: B,
+    hashes: Vec<Output>,
+    window_blocks: usize,
+    oldest: usize,
+    sum: Output,
+}
+
+impl SlidingWindowHasher {
+    /// Creates a hasher over a window of `window_blocks` blocks, allocating its ring buffer up front
+    ///
+    /// # Arguments
+    /// * `window_blocks` - the number of most recent blocks that the digest covers
+    ///
+    /// # Panics
+    /// Panics if `window_blocks` is `0`
+    pub fn new(window_blocks: usize) -> Self {
+        Self::with_backend(window_blocks, NativeBackend)
+    }
+
+    /// Hashes the next block of the stream into the window,
+    /// evicting the oldest block once the window is full
+    ///
+    /// # Arguments
+    /// * `block` - the next input block of 256 bytes (2048 bit)
+    pub fn push_block(&mut self, block: &[u8; INPUT_BLOCK_SIZE]) {
+        infallible(self.try_push_block(block))
+    }
+}
+
+impl<B: SwifftBackend> SlidingWindowHasher<B> {
+    /// Creates a hasher over a window of `window_blocks` blocks that hashes with `backend`,
+    /// allocating its ring buffer up front
+    ///
+    /// # Arguments
+    /// * `window_blocks` - the number of most recent blocks that the digest covers
+    /// * `backend` - the backend to hash blocks with
+    ///
+    /// # Panics
+    /// Panics if `window_blocks` is `0`
+    pub fn with_backend(window_blocks: usize, backend: B) -> Self {
+        assert!(window_blocks > 0, "window must hold at least one block");
+        Self {
+            backend,
+            hashes: Vec::with_capacity(window_blocks),
+            window_blocks,
+            oldest: 0,
+            sum: Output::default(),
+        }
+    }
+
+    /// Hashes the next block of the stream into the window with the backend,
+    /// evicting the oldest block once the window is full
+    ///
+    /// # Arguments
+    /// * `block` - the next input block of 256 bytes (2048 bit)
+    ///
+    /// # Errors
+    /// Returns the error of the backend if it could not hash `block`, leaving the window unchanged
+    pub fn try_push_block(&mut self, block: &[u8; INPUT_BLOCK_SIZE]) -> Result<(), B::Error> {
+        let mut hash = Output::default();
+        self.backend.compute(&AlignedBuffer([*block]), &mut hash)?;
+        add(&mut self.sum, &hash);
+
+        if self.hashes.len() < self.window_blocks {
+            self.hashes.push(hash);
+        } else {
+            let oldest = &mut self.hashes[self.oldest];
+            sub(&mut self.sum, oldest);
+            set(oldest, &hash);
+            self.oldest = (self.oldest + 1) % self.window_blocks;
+        }
+        Ok(())
+    }
+
+    /// The sum of the SWIFFT hash values of the blocks currently in the window
+    pub fn digest(&self) -> Output {
+        let mut digest = Output::default();
+        set(&mut digest, &self.sum);
+        digest
+    }
+
+    /// The compact digest of the blocks currently in the window, tagged as [`Mode::Window`]
+    pub fn tagged_digest(&self) -> TaggedDigest {
+        TaggedDigest::compacted(Mode::Window, &self.sum)
+    }
+
+    /// The number of blocks currently in the window, at most `window_blocks`
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Whether no blocks have been pushed yet
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    /// The maximum number of blocks in the window
+    pub fn window_blocks(&self) -> usize {
+        self.window_blocks
+    }
+}
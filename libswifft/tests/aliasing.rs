@@ -0,0 +1,129 @@
+//! Operations of hash values with themselves, and what the native library does with aliasing pointers
+
+use libswifft::arithmetic::{
+    add, add_assign_self, add_assign_self_multiple, add_multiple, mul, mul_multiple, square_elements,
+    square_elements_multiple,
+};
+use libswifft::buffer::{AlignedBuffer, Input, Inputs, Output, Outputs};
+use libswifft::hash::{compute, compute_multiple};
+use libswifft::sys::{SWIFFT_Add, SWIFFT_AddMultiple, SWIFFT_Mul, SWIFFT_Sub};
+
+fn output(seed: u8) -> Output {
+    let mut output = Output::default();
+    compute(&Input::new(seed), &mut output);
+    output
+}
+
+fn outputs() -> Box<Outputs<5>> {
+    let inputs = Box::new(Inputs::<5>::from_seed(5));
+    let mut outputs = Box::<Outputs<5>>::default();
+    compute_multiple(&inputs, &mut outputs);
+    outputs
+}
+
+fn elements(output: &Output) -> Vec<i16> {
+    output.0[0].chunks_exact(2).map(|element| i16::from_le_bytes([element[0], element[1]])).collect()
+}
+
+/// The centered representative of `value` modulo 257, as the native multiplication requires of its factors
+fn centered(output: &Output) -> Output {
+    let mut centered = Output::default();
+    for (bytes, element) in centered.0[0].chunks_exact_mut(2).zip(elements(output)) {
+        let value = element.rem_euclid(257);
+        bytes.copy_from_slice(&(if value > 128 { value - 257 } else { value }).to_le_bytes());
+    }
+    centered
+}
+
+#[test]
+fn add_assign_self_matches_add() {
+    for seed in [0, 1, 0x5a, 0xff] {
+        let mut expected = output(seed);
+        add(&mut expected, &output(seed));
+        let mut doubled = output(seed);
+        add_assign_self(&mut doubled);
+        assert_eq!(doubled.0, expected.0);
+        assert_eq!(elements(&doubled), elements(&output(seed)).iter().map(|e| e * 2 % 257).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn square_elements_matches_mul() {
+    for seed in [0, 1, 0x5a, 0xff] {
+        let mut expected = output(seed);
+        mul(&mut expected, &output(seed));
+        let mut squared = output(seed);
+        square_elements(&mut squared);
+        assert_eq!(squared.0, expected.0);
+        let squares = elements(&output(seed)).iter().map(|&e| (e as i32 * e as i32 % 257) as i16).collect::<Vec<_>>();
+        assert_eq!(elements(&squared), squares);
+    }
+}
+
+#[test]
+fn multiple_self_ops_match_their_two_buffer_equivalents() {
+    let mut expected = outputs();
+    add_multiple(&mut expected, &AlignedBuffer(outputs().0));
+    let mut doubled = outputs();
+    add_assign_self_multiple(&mut doubled);
+    assert_eq!(doubled.0, expected.0);
+
+    let mut expected = outputs();
+    mul_multiple(&mut expected, &AlignedBuffer(outputs().0));
+    let mut squared = outputs();
+    square_elements_multiple(&mut squared);
+    assert_eq!(squared.0, expected.0);
+}
+
+#[test]
+fn native_add_and_sub_accept_identical_pointers() {
+    for seed in [0, 1, 0x5a, 0xff] {
+        let mut expected = output(seed);
+        add(&mut expected, &output(seed));
+        let mut aliased = output(seed);
+        let ptr = aliased.0[0].as_mut_ptr();
+        // SAFETY: `ptr` is an aligned output of 128 bytes, accessed only through `ptr` until the view is dropped
+        let doubled = unsafe {
+            SWIFFT_Add(ptr, ptr);
+            Output::from_raw_parts_mut(ptr)
+        };
+        assert_eq!(doubled.0, expected.0);
+
+        // SAFETY: as above
+        let zero = unsafe {
+            SWIFFT_Sub(ptr, ptr);
+            Output::from_raw_parts_mut(ptr)
+        };
+        assert_eq!(elements(zero), [0; 64]);
+    }
+}
+
+#[test]
+fn native_mul_accepts_identical_pointers() {
+    for seed in [0, 1, 0x5a, 0xff] {
+        let mut expected = output(seed);
+        mul(&mut expected, &output(seed));
+        let mut aliased = centered(&output(seed));
+        let ptr = aliased.0[0].as_mut_ptr();
+        // SAFETY: as above, with the elements centered as the native multiplication requires
+        let squared = unsafe {
+            SWIFFT_Mul(ptr, ptr);
+            Output::from_raw_parts_mut(ptr)
+        };
+        assert_eq!(squared.0, expected.0);
+    }
+}
+
+#[test]
+fn native_add_multiple_accepts_identical_pointers() {
+    let mut expected = outputs();
+    add_multiple(&mut expected, &AlignedBuffer(outputs().0));
+    let mut aliased = outputs();
+    let ptr = aliased.0.as_flattened_mut().as_mut_ptr();
+    // SAFETY: `ptr` is 5 consecutive aligned outputs, accessed only through `ptr` until the view is dropped
+    let doubled = unsafe {
+        SWIFFT_AddMultiple(5, ptr, ptr);
+        Output::slice_from_raw_parts_mut(ptr, 5)
+    };
+    assert_eq!(doubled.iter().map(|block| block.0[0]).collect::<Vec<_>>(), expected.0);
+}
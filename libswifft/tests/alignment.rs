@@ -0,0 +1,96 @@
+//! Alignment checks on the buffers passed to the native library.
+//!
+//! A misaligned reference is undefined behaviour to create, so misaligned buffers are only ever
+//! handled here as raw pointers or byte slices, through the library's checked views, or through its unchecked
+//! constructors, whose debug assertions must fire before any reference is created
+
+use libswifft::buffer::{Input, Output};
+#[cfg(debug_assertions)]
+use libswifft::constant::{INPUT_BLOCK_SIZE, OUTPUT_BLOCK_SIZE};
+use libswifft::constant::BUFFER_ALIGNMENT;
+use libswifft::error::ViewError;
+use libswifft::view::{InputRef, InputsRef, OutputMut, OutputsMut};
+
+/// Backing storage for deliberately misaligned buffers, one byte past an aligned address
+#[repr(C, align(64))]
+struct Storage([u8; 2 * BUFFER_ALIGNMENT + 512]);
+
+impl Storage {
+    fn new() -> Self {
+        Self([0; 2 * BUFFER_ALIGNMENT + 512])
+    }
+
+    /// The address one byte past the start, as a faulty unsafe cast would produce
+    fn misaligned_address(&self) -> usize {
+        self.0.as_ptr() as usize + 1
+    }
+}
+
+#[test]
+fn safely_created_buffers_are_aligned() {
+    assert!(Input::verify_alignment(&Input::default()));
+    assert!(Output::verify_alignment(&*Box::<Output>::default()));
+}
+
+#[test]
+fn misaligned_pointers_fail_verification() {
+    let storage = Storage::new();
+    let misaligned = storage.0[1..].as_ptr().cast::<Output>();
+    assert!(!Output::verify_alignment(misaligned));
+    assert!(Output::verify_alignment(storage.0.as_ptr().cast()));
+}
+
+#[test]
+fn views_reject_misaligned_bytes() {
+    let mut storage = Storage::new();
+    let misaligned = ViewError::Misaligned { address: storage.misaligned_address(), alignment: BUFFER_ALIGNMENT };
+    assert_eq!(InputRef::new(&storage.0[1..257]).unwrap_err(), misaligned);
+    assert_eq!(InputsRef::new(&storage.0[1..513]).unwrap_err(), misaligned);
+    assert_eq!(OutputMut::new(&mut storage.0[1..129]).unwrap_err(), misaligned);
+    assert_eq!(OutputsMut::new(&mut storage.0[1..257]).unwrap_err(), misaligned);
+}
+
+#[test]
+fn views_accept_aligned_bytes() {
+    let mut storage = Storage::new();
+    assert!(InputRef::new(&storage.0[..256]).is_ok());
+    assert!(InputsRef::new(&storage.0[BUFFER_ALIGNMENT..BUFFER_ALIGNMENT + 512]).is_ok());
+    assert!(OutputMut::new(&mut storage.0[..128]).is_ok());
+    assert!(OutputsMut::new(&mut storage.0[..256]).is_ok());
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "misaligned input at")]
+fn misaligned_raw_input_is_caught_before_the_cast() {
+    let storage = Storage::new();
+    // SAFETY: none, deliberately: the debug assertion must panic before the misaligned reference is created
+    let _ = unsafe { Input::from_raw_parts(storage.0[1..].as_ptr()) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "misaligned outputs at")]
+fn misaligned_raw_outputs_are_caught_before_the_cast() {
+    let mut storage = Storage::new();
+    // SAFETY: none, deliberately: the debug assertion must panic before the misaligned slice is created
+    let _ = unsafe { Output::slice_from_raw_parts_mut(storage.0[1..].as_mut_ptr(), 2) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "inputs exceed `isize::MAX` bytes")]
+fn oversized_raw_inputs_are_caught_before_the_cast() {
+    let storage = Storage::new();
+    // SAFETY: none, deliberately: the debug assertion must panic before the oversized slice is created
+    let _ = unsafe { Input::slice_from_raw_parts(storage.0.as_ptr(), isize::MAX as usize / INPUT_BLOCK_SIZE + 1) };
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "outputs exceed `isize::MAX` bytes")]
+fn oversized_raw_outputs_are_caught_before_the_cast() {
+    let mut storage = Storage::new();
+    // SAFETY: none, deliberately: the debug assertion must panic before the oversized slice is created
+    let _ = unsafe { Output::slice_from_raw_parts_mut(storage.0.as_mut_ptr(), usize::MAX / OUTPUT_BLOCK_SIZE) };
+}
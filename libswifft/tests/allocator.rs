@@ -0,0 +1,41 @@
+//! Allocating buffers with a custom allocator, on nightly with the `allocator_api` feature
+
+#![cfg(feature = "allocator_api")]
+#![feature(allocator_api)]
+
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::ptr::NonNull;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::{compute_multiple, compute_multiple_uninit};
+
+/// Forwards to the global allocator, counting the bytes allocated through it
+#[derive(Default)]
+struct Counting(AtomicUsize);
+
+unsafe impl Allocator for &Counting {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.fetch_add(layout.size(), Ordering::Relaxed);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn buffers_are_allocated_from_the_allocator() {
+    let arena = Counting::default();
+    let input = Inputs::<64>::new_boxed_in(&arena);
+    assert_eq!(input.0, Inputs::<64>::default().0);
+    assert!(Inputs::<64>::verify_alignment(&*input));
+    assert_eq!(arena.0.load(Ordering::Relaxed), 64 * 256);
+
+    let mut expected = Outputs::<64>::default();
+    compute_multiple(&input, &mut expected);
+    let mut output = Outputs::<64>::new_boxed_uninit_in(&arena);
+    assert_eq!(compute_multiple_uninit(&input, &mut output).0, expected.0);
+    assert_eq!(arena.0.load(Ordering::Relaxed), 64 * 256 + 64 * 128);
+}
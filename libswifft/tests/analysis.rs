@@ -0,0 +1,103 @@
+//! Element histograms of hash values, and collision-check utilities for compact hash values:
+//! `cargo test -p libswifft --features analysis --test analysis`
+#![cfg(feature = "analysis")]
+
+use libswifft::analysis::{chi_squared_per_element, element_histogram, expected_collisions, find_duplicate};
+use libswifft::buffer::{CompactOutput, Input, InputBuilder, Output};
+use libswifft::hash::{compact, compute};
+
+fn compact_hash(counter: u64) -> CompactOutput {
+    let input = InputBuilder::new().set_vector_from_u64(0, counter).build();
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+    compact_output
+}
+
+#[test]
+fn histograms_count_reduced_elements() {
+    let mut non_canonical = Output::default();
+    non_canonical.0[0][..2].copy_from_slice(&257u16.to_le_bytes());
+    let outputs = [Output::default(), Output::from_coefficients(std::array::from_fn(|i| i as u16)), non_canonical];
+    let histogram = element_histogram(&outputs);
+    // 257 is counted as 0
+    assert_eq!((histogram[0][0], histogram[0][1]), (3, 0));
+    assert_eq!((histogram[5][0], histogram[5][5]), (2, 1));
+    assert_eq!((histogram[63][0], histogram[63][63]), (2, 1));
+    assert!(histogram.iter().all(|counts| counts.iter().sum::<u32>() == 3));
+    assert_eq!(element_histogram(&[]), [[0; 257]; 64]);
+}
+
+#[test]
+fn chi_squared_of_known_histograms() {
+    let mut histogram = [[1; 257]; 64];
+    // all 257 values of element 1 are 0, where 1 of each is expected
+    histogram[1] = [0; 257];
+    histogram[1][0] = 257;
+    histogram[2] = [0; 257];
+    let chi_squared = chi_squared_per_element(&histogram);
+    assert_eq!(chi_squared[0], 0.0);
+    assert_eq!(chi_squared[1], 256.0 * 256.0 + 256.0);
+    // an empty histogram has no statistic
+    assert_eq!(chi_squared[2], 0.0);
+}
+
+#[test]
+fn random_blocks_fill_every_bucket() {
+    // about 19.5 per bucket, so an empty one has probability about 2^-28 each
+    let outputs: Vec<Output> = (0..5000).map(|seed| {
+        let mut output = Output::default();
+        compute(&Input::from_seed(seed), &mut output);
+        output
+    }).collect();
+    let histogram = element_histogram(&outputs);
+    assert!(histogram.iter().flatten().all(|&count| count > 0));
+    // 256 degrees of freedom have a standard deviation of about 23, so this bound is very loose
+    assert!(chi_squared_per_element(&histogram).iter().all(|&statistic| statistic < 500.0));
+}
+
+#[test]
+fn finds_injected_duplicate() {
+    let corpus = (0..1000).map(compact_hash)
+        .chain(std::iter::once(compact_hash(417)))
+        .chain((1000..1100).map(compact_hash));
+    assert_eq!(find_duplicate(corpus), Some((417, 1000)));
+}
+
+#[test]
+fn distinct_corpus_has_no_duplicate() {
+    assert_eq!(find_duplicate((0..1000).map(compact_hash)), None);
+    assert_eq!(find_duplicate(std::iter::empty()), None);
+}
+
+/// An iterator over compact hash values whose size hint claims far more items than it yields
+struct LyingSizeHint<I>(I);
+
+impl<I: Iterator<Item = CompactOutput>> Iterator for LyingSizeHint<I> {
+    type Item = CompactOutput;
+
+    fn next(&mut self) -> Option<CompactOutput> {
+        self.0.next()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+#[test]
+fn huge_size_hints_are_not_allocated_for() {
+    let corpus = [1, 2, 1].map(compact_hash).into_iter();
+    assert_eq!(find_duplicate(LyingSizeHint(corpus)), Some((0, 2)));
+}
+
+#[test]
+fn expected_collisions_follows_birthday_bound() {
+    assert_eq!(expected_collisions(0), 0.0);
+    assert_eq!(expected_collisions(1), 0.0);
+    assert_eq!(expected_collisions(2), 2f64.powi(-512));
+    // 2^64 items form about 2^127 pairs, each colliding with probability 2^-512
+    let scaled = expected_collisions(u64::MAX) * 2f64.powi(385);
+    assert!((scaled - 1.0).abs() < 1e-9);
+}
@@ -0,0 +1,169 @@
+//! Higher-level components surface the failures of a misbehaving backend instead of panicking:
+//! `cargo test -p libswifft --features test-doubles --test backend`
+#![cfg(feature = "test-doubles")]
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use libswifft::backend::{MockBackend, MockBehavior, NativeBackend, SwifftBackend};
+use libswifft::buffer::{Input, Output};
+use libswifft::error::BackendUnavailable;
+use libswifft::hash::{hash_bytes, hash_bytes_with_backend, hash_reader_with_backend};
+use libswifft::merkle::{MerkleTree, StreamingBuilder};
+use libswifft::window::SlidingWindowHasher;
+
+const LEAVES: [&str; 5] = ["alice", "bob", "carol", "dave", "erin"];
+
+fn block(seed: u8) -> [u8; 256] {
+    std::array::from_fn(|i| (i as u8).wrapping_mul(31).wrapping_add(seed))
+}
+
+#[test]
+fn window_surfaces_unavailable_backend() {
+    let backend = MockBackend::new(MockBehavior::Unavailable);
+    let mut hasher = SlidingWindowHasher::with_backend(4, &backend);
+    assert_eq!(hasher.try_push_block(&block(0)), Err(BackendUnavailable));
+    assert!(hasher.is_empty());
+    assert_eq!(hasher.digest().0, Output::default().0);
+    assert_eq!(backend.calls(), 1);
+}
+
+#[test]
+fn window_is_unchanged_by_a_failure_mid_stream() {
+    let mut native = SlidingWindowHasher::new(2);
+    let mut hasher = SlidingWindowHasher::with_backend(2, MockBackend::new(MockBehavior::FailAfter(3)));
+    for seed in 0..3 {
+        native.push_block(&block(seed));
+        hasher.try_push_block(&block(seed)).unwrap();
+    }
+    assert_eq!(hasher.try_push_block(&block(3)), Err(BackendUnavailable));
+    assert_eq!(hasher.len(), 2);
+    assert_eq!(hasher.digest().0, native.digest().0);
+}
+
+#[test]
+fn fixed_digests_are_summed_over_the_window() {
+    let mut fixed = Output::default();
+    fixed.0[0][0] = 1;
+    let mut hasher = SlidingWindowHasher::with_backend(3, MockBackend::new(MockBehavior::Fixed(fixed)));
+    for seed in 0..5 {
+        hasher.try_push_block(&block(seed)).unwrap();
+    }
+    let mut expected = Output::default();
+    expected.0[0][0] = 3;
+    assert_eq!(hasher.digest().0, expected.0);
+}
+
+#[test]
+fn delayed_backend_still_hashes() {
+    let backend = MockBackend::new(MockBehavior::Delay(Duration::from_millis(20)));
+    let (mut expected, mut output) = (Output::default(), Output::default());
+    let start = Instant::now();
+    backend.compute(&Input::new(7), &mut output).unwrap();
+    assert!(start.elapsed() >= Duration::from_millis(20));
+    NativeBackend.compute(&Input::new(7), &mut expected).unwrap();
+    assert_eq!(output.0, expected.0);
+}
+
+#[test]
+fn hashing_with_a_working_backend_matches_native_hashing() {
+    let data: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+    let backend = MockBackend::new(MockBehavior::FailAfter(usize::MAX));
+    assert_eq!(hash_bytes_with_backend(&data, &backend), Ok(hash_bytes(&data)));
+    assert_eq!(hash_reader_with_backend(&data[..], &backend).unwrap(), hash_bytes(&data));
+    assert_eq!(MerkleTree::build_with_backend(&LEAVES, &backend).unwrap().root(), MerkleTree::build(&LEAVES).root());
+}
+
+#[test]
+fn hashing_surfaces_unavailable_backend() {
+    let backend = MockBackend::new(MockBehavior::Unavailable);
+    assert_eq!(hash_bytes_with_backend(b"message", &backend), Err(BackendUnavailable));
+    let err = hash_reader_with_backend(&b"message"[..], &backend).unwrap_err();
+    assert_eq!(err.to_string(), BackendUnavailable.to_string());
+}
+
+#[test]
+fn tree_surfaces_a_failure_mid_build() {
+    // the first two leaves hash, then the third fails
+    let backend = MockBackend::new(MockBehavior::FailAfter(4));
+    assert_eq!(MerkleTree::build_with_backend(&LEAVES, &backend).unwrap_err(), BackendUnavailable);
+    assert!(MerkleTree::build_with_backend(&[] as &[&str], &backend).unwrap().is_empty());
+}
+
+#[test]
+fn proof_verification_surfaces_unavailable_backend() {
+    let tree = MerkleTree::build(&LEAVES);
+    let proof = tree.prove(2);
+    let backend = MockBackend::new(MockBehavior::Unavailable);
+    assert_eq!(proof.verify_with_backend(&tree.root(), b"carol", &backend), Err(BackendUnavailable));
+    assert_eq!(proof.verify_with_backend(&tree.root(), b"carol", &NativeBackend), Ok(true));
+}
+
+#[test]
+fn streaming_builder_is_unchanged_by_a_failure() {
+    // 2 blocks per leaf and 1 per node: the fourth leaf hashes, then fails merging
+    let backend = MockBackend::new(MockBehavior::FailAfter(10));
+    let mut builder = StreamingBuilder::with_backend(&backend);
+    let mut native = StreamingBuilder::new();
+    for leaf in &LEAVES[..3] {
+        builder.try_push_leaf(leaf.as_bytes()).unwrap();
+        native.push_leaf(leaf.as_bytes());
+    }
+    assert_eq!(builder.try_push_leaf(LEAVES[3].as_bytes()), Err(BackendUnavailable));
+    assert_eq!(builder.num_leaves(), 3);
+    assert_eq!(builder.try_finalize(), Err(BackendUnavailable));
+    assert_eq!(native.finalize(), MerkleTree::build(&LEAVES[..3]).root());
+}
+
+/// Runs the `swifft` binary built with the `test-doubles` feature, with `SWIFFT_MOCK_BACKEND` set to `backend`
+fn swifft(args: &[&str], backend: &str) -> std::process::Output {
+    let binary = escargot::CargoBuild::new()
+        .manifest_path(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .bin("swifft")
+        // not `features`, which passes `-features` rather than `--features` in this version of escargot
+        .arg("--features=test-doubles")
+        .current_release()
+        .run()
+        .unwrap_or_else(|err| panic!("building the swifft binary failed: {}", err));
+    let mut child = Command::new(binary.path())
+        .args(args)
+        .env("SWIFFT_MOCK_BACKEND", backend)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"from stdin").unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn cli_surfaces_unavailable_backend() {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("backend");
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("file");
+    std::fs::write(&path, b"contents").unwrap();
+    let path = path.to_str().unwrap();
+
+    let output = swifft(&["hash", path, "-"], "unavailable");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert!(output.stdout.is_empty());
+    assert!(stderr.contains(&format!("swifft: {}: {}", path, BackendUnavailable)), "{}", stderr);
+    assert!(stderr.contains("2 listed file(s) could not be read"), "{}", stderr);
+    assert!(!stderr.contains("panicked"), "{}", stderr);
+
+    let output = swifft(&["bench", "1"], "fail-after=100");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(output.status.code(), Some(1), "{}", stderr);
+    assert_eq!(stderr, format!("swifft: {}\n", BackendUnavailable));
+}
+
+#[test]
+fn cli_rejects_an_invalid_mock_backend() {
+    let output = swifft(&["hash", "-"], "sometimes");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "invalid SWIFFT_MOCK_BACKEND: sometimes\n");
+}
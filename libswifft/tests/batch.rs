@@ -0,0 +1,130 @@
+//! Batches chunked into FFI calls of at most `max_ffi_blocks` blocks, just below, at and just above the chunk size,
+//! against the single-block functions, on boundary elements
+
+use libswifft::arithmetic::{add, add_multiple, const_add, const_add_multiple, const_mul, const_mul_multiple, mul, mul_multiple, sub, sub_multiple};
+use libswifft::batch::{max_ffi_blocks, with_max_ffi_blocks};
+use libswifft::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Inputs, Output, Outputs};
+use libswifft::hash::{compact, compact_multiple, compute, compute_multiple};
+
+const CHUNK_BLOCKS: usize = 4;
+
+/// Hash values whose elements are all `0`, all `256`, or canonical but varied
+fn boundary_outputs<const NUM_BLOCKS: usize>(seed: u16) -> Outputs<NUM_BLOCKS> {
+    AlignedBuffer(std::array::from_fn(|block| {
+        let element = |i: usize| match (block + seed as usize) % 3 {
+            0 => 0,
+            1 => 256,
+            _ => (i as u16 * 67 + seed) % 257,
+        };
+        Output::from_coefficients(std::array::from_fn(element)).0[0]
+    }))
+}
+
+/// Hash values with non-canonical elements, which the native library must treat alike in every chunk
+fn non_canonical_outputs<const NUM_BLOCKS: usize>() -> Outputs<NUM_BLOCKS> {
+    AlignedBuffer(std::array::from_fn(|block| {
+        Output::from_coefficients(std::array::from_fn(|i| [257, 300, 511, 256][(i + block) % 4])).0[0]
+    }))
+}
+
+/// Applies a single-block operation to each block of `output` with the matching block of `operand`
+fn per_block<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>, operand: &Outputs<NUM_BLOCKS>, op: fn(&mut Output, &Output)) -> Outputs<NUM_BLOCKS> {
+    AlignedBuffer(std::array::from_fn(|i| {
+        let mut block = AlignedBuffer([output.0[i]]);
+        op(&mut block, &AlignedBuffer([operand.0[i]]));
+        block.0[0]
+    }))
+}
+
+fn chunked<const NUM_BLOCKS: usize>(output: &Outputs<NUM_BLOCKS>, f: impl FnOnce(&mut Outputs<NUM_BLOCKS>)) -> Outputs<NUM_BLOCKS> {
+    let mut output = output.clone();
+    with_max_ffi_blocks(CHUNK_BLOCKS, || f(&mut output));
+    output
+}
+
+fn check_around_the_chunk_size<const NUM_BLOCKS: usize>() {
+    let inputs = Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64);
+    let mut expected = Outputs::<NUM_BLOCKS>::default();
+    for (input, output) in inputs.0.iter().zip(expected.0.iter_mut()) {
+        let mut block = Output::default();
+        compute(&AlignedBuffer([*input]), &mut block);
+        *output = block.0[0];
+    }
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    with_max_ffi_blocks(CHUNK_BLOCKS, || compute_multiple(&inputs, &mut outputs));
+    assert_eq!(outputs, expected, "compute_multiple of {} blocks", NUM_BLOCKS);
+
+    let mut expected = CompactOutputs::<NUM_BLOCKS>::default();
+    for (output, compact_output) in outputs.0.iter().zip(expected.0.iter_mut()) {
+        let mut block = CompactOutput::default();
+        compact(&AlignedBuffer([*output]), &mut block);
+        *compact_output = block.0[0];
+    }
+    let mut compact_outputs = CompactOutputs::<NUM_BLOCKS>::default();
+    with_max_ffi_blocks(CHUNK_BLOCKS, || compact_multiple(&outputs, &mut compact_outputs));
+    assert_eq!(compact_outputs, expected, "compact_multiple of {} blocks", NUM_BLOCKS);
+
+    for (a, b) in [(boundary_outputs::<NUM_BLOCKS>(0), boundary_outputs(1)), (boundary_outputs(2), non_canonical_outputs())] {
+        assert_eq!(chunked(&a, |output| add_multiple(output, &b)), per_block(&a, &b, add), "add_multiple of {} blocks", NUM_BLOCKS);
+        assert_eq!(chunked(&a, |output| sub_multiple(output, &b)), per_block(&a, &b, sub), "sub_multiple of {} blocks", NUM_BLOCKS);
+        assert_eq!(chunked(&a, |output| mul_multiple(output, &b)), per_block(&a, &b, mul), "mul_multiple of {} blocks", NUM_BLOCKS);
+    }
+
+    let a = boundary_outputs::<NUM_BLOCKS>(0);
+    let scalars: [i16; NUM_BLOCKS] = std::array::from_fn(|i| [0, 1, -1, 256, 128][i % 5]);
+    for (multiple, single) in [(const_add_multiple::<NUM_BLOCKS> as fn(&mut _, &_), const_add as fn(&mut _, _)), (const_mul_multiple, const_mul)] {
+        let expected = AlignedBuffer(std::array::from_fn(|i| {
+            let mut block = AlignedBuffer([a.0[i]]);
+            single(&mut block, scalars[i]);
+            block.0[0]
+        }));
+        assert_eq!(chunked(&a, |output| multiple(output, &scalars)), expected, "{} blocks", NUM_BLOCKS);
+    }
+}
+
+#[test]
+fn batches_around_the_chunk_size_match_the_single_block_functions() {
+    check_around_the_chunk_size::<{ CHUNK_BLOCKS - 1 }>();
+    check_around_the_chunk_size::<CHUNK_BLOCKS>();
+    check_around_the_chunk_size::<{ CHUNK_BLOCKS + 1 }>();
+    check_around_the_chunk_size::<{ 2 * CHUNK_BLOCKS + 1 }>();
+}
+
+#[test]
+fn canonical_boundary_elements_are_reduced() {
+    // block 0 is all 0, and block 1 all 256, i.e. -1: 256 + 256 = 255, 0 - 256 = 1 and 256 * 256 = 1
+    let a = boundary_outputs::<3>(0);
+    let sum = chunked(&a, |output| add_multiple(output, &a));
+    assert_eq!((sum.block(0).to_coefficients(), sum.block(1).to_coefficients()), ([0; 64], [255; 64]));
+    let difference = chunked(&a, |output| sub_multiple(output, &AlignedBuffer([a.0[1]; 3])));
+    assert_eq!(difference.block(0).to_coefficients(), [1; 64]);
+    let product = chunked(&a, |output| mul_multiple(output, &a));
+    assert_eq!(product.block(1).to_coefficients(), [1; 64]);
+    assert!(sum.is_canonical() && difference.is_canonical() && product.is_canonical());
+}
+
+#[test]
+fn the_chunk_size_is_restored() {
+    let default = max_ffi_blocks();
+    with_max_ffi_blocks(CHUNK_BLOCKS, || assert_eq!(max_ffi_blocks(), CHUNK_BLOCKS));
+    assert_eq!(max_ffi_blocks(), default);
+    with_max_ffi_blocks(0, || assert_eq!(max_ffi_blocks(), 1));
+}
+
+#[test]
+fn multiplying_large_batches_does_not_copy_them_onto_the_stack() {
+    const NUM_BLOCKS: usize = 2000;
+    // 256 KiB per batch, while the thread multiplying them has a stack of 64 KiB
+    let (mut output, operand) = (Box::new(boundary_outputs::<NUM_BLOCKS>(2)), Box::new(boundary_outputs::<NUM_BLOCKS>(1)));
+    let expected = Box::new(per_block(&output, &operand, mul));
+    let output = std::thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(move || {
+            mul_multiple(&mut output, &operand);
+            output
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    assert_eq!(output, expected);
+}
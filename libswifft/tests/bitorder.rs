@@ -0,0 +1,63 @@
+//! The least significant bit first input order, and conversion from most significant bit first data
+
+use libswifft::bitorder::reverse_bits_in_bytes;
+use libswifft::buffer::{AlignedBuffer, Input, InputBuilder, Output};
+use libswifft::hash::compute;
+use swifft::hash::{parse_input_block, parse_input_block_msb, swifft_hash};
+
+fn hash(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+/// Bytes whose bits are not palindromic, so that the two orders disagree
+fn msb_first_bytes() -> [u8; 256] {
+    let mut bytes = [0u8; 256];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = (i as u8).wrapping_mul(29) | 0x80;
+    }
+    bytes
+}
+
+#[test]
+fn bit_orders_give_different_digests() {
+    let bytes = msb_first_bytes();
+    assert_ne!(hash(&AlignedBuffer([bytes])).0, hash(&Input::from_msb_first_bytes(&bytes)).0);
+}
+
+#[test]
+fn bit_zero_is_the_lowest_degree_coefficient() {
+    let mut bits = [false; 64];
+    bits[0] = true;
+    let from_vector = InputBuilder::new().set_vector(0, &bits).build();
+    let from_byte = InputBuilder::new().set_byte(0, 0b0000_0001).build();
+    let from_msb_byte = InputBuilder::new().set_byte_msb_first(0, 0b1000_0000).build();
+    assert_eq!(from_vector.0, from_byte.0);
+    assert_eq!(from_vector.0, from_msb_byte.0);
+}
+
+#[test]
+fn converters_reconcile_bit_orders() {
+    let bytes = msb_first_bytes();
+    let mut reversed = bytes;
+    reverse_bits_in_bytes(&mut reversed);
+    let builder = bytes.iter().enumerate()
+        .fold(InputBuilder::new(), |builder, (i, &byte)| builder.set_byte_msb_first(i, byte));
+
+    let expected = hash(&Input::from_msb_first_bytes(&bytes));
+    assert_eq!(hash(&AlignedBuffer([reversed])).0, expected.0);
+    assert_eq!(hash(&builder.build()).0, expected.0);
+
+    reverse_bits_in_bytes(&mut reversed);
+    assert_eq!(reversed, bytes);
+}
+
+#[test]
+fn pure_parsers_reconcile_bit_orders() {
+    let bytes: [u8; 128] = msb_first_bytes()[..128].try_into().unwrap();
+    let mut reversed = bytes;
+    reverse_bits_in_bytes(&mut reversed);
+    assert_ne!(swifft_hash(&parse_input_block(&bytes)), swifft_hash(&parse_input_block_msb(&bytes)));
+    assert_eq!(parse_input_block_msb(&bytes), parse_input_block(&reversed));
+}
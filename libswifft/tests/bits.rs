@@ -0,0 +1,102 @@
+//! Bit-level access to inputs, in the order that the native library reads them
+
+use libswifft::buffer::{Input, InputBuilder, Output};
+use libswifft::hash::compute;
+use libswifft::testvectors::VECTORS_CSV;
+use proptest::prelude::*;
+
+const Q: u32 = 257;
+
+fn hash(input: &Input) -> [u32; 64] {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output.to_coefficients().map(u32::from)
+}
+
+fn single_bit(index: usize) -> Input {
+    let mut input = Input::default();
+    input.set_bit(index, true);
+    input
+}
+
+#[test]
+fn bits_are_least_significant_first() {
+    let mut input = Input::default();
+    input.set_bit(0, true);
+    input.set_bit(15, true);
+    input.set_bit(2047, true);
+    assert_eq!((input.0[0][0], input.0[0][1], input.0[0][255]), (0x01, 0x80, 0x80));
+    assert!(input.get_bit(15) && !input.get_bit(14));
+    input.set_bit(15, false);
+    assert_eq!(input.0[0][1], 0);
+
+    let bits: Vec<_> = Input::new(0b0000_0101).bits().take(8).collect();
+    assert_eq!(bits, [true, false, true, false, false, false, false, false]);
+}
+
+#[test]
+fn bit_k_is_coefficient_k_mod_64_of_vector_k_div_64() {
+    for k in [0, 1, 7, 8, 63, 64, 100, 1000, 2047] {
+        let mut vector = [false; 64];
+        vector[k % 64] = true;
+        let expected = InputBuilder::new().set_vector(k / 64, &vector).build();
+        assert_eq!(single_bit(k), expected, "bit {}", k);
+    }
+}
+
+/// The bits whose single-bit inputs are recorded in the known-answer vectors, in the order they are recorded,
+/// after the four fill patterns
+const KNOWN_ANSWER_BITS: [usize; 6] = [0, 1, 63, 64, 1000, 2047];
+
+/// Single bits hash to the hash values that the native library produced for them when the known-answer vectors
+/// were recorded, so bit `k` is the bit that the native library reads as bit `k`, whatever bytes it is stored in
+#[test]
+fn single_bits_hash_as_their_known_answers() {
+    let outputs: Vec<&str> = VECTORS_CSV.lines()
+        .filter(|line| line.starts_with("compute,"))
+        .map(|line| line.rsplit(',').next().unwrap())
+        .skip(4)
+        .take(KNOWN_ANSWER_BITS.len())
+        .collect();
+    assert_eq!(outputs.len(), KNOWN_ANSWER_BITS.len());
+    for (k, expected) in KNOWN_ANSWER_BITS.into_iter().zip(outputs) {
+        let mut output = Output::default();
+        compute(&single_bit(k), &mut output);
+        assert_eq!(format!("{:x}", output), expected, "bit {}", k);
+    }
+}
+
+#[test]
+#[should_panic]
+fn getting_a_bit_past_the_input_panics() {
+    Input::default().get_bit(2048);
+}
+
+#[test]
+#[should_panic]
+fn setting_a_bit_past_the_input_panics() {
+    Input::default().set_bit(2048, true);
+}
+
+proptest! {
+    #[test]
+    fn bits_round_trip(bytes in prop::collection::vec(any::<u8>(), 256)) {
+        let input = Input::from(<[u8; 256]>::try_from(bytes).unwrap());
+        let bits = input.to_bits();
+        prop_assert_eq!(Input::from_bits(&bits), input.clone());
+        prop_assert!(input.bits().eq(bits.iter().copied()));
+        prop_assert!((0..2048).all(|k| input.get_bit(k) == bits[k]));
+    }
+
+    /// Setting bit `k` of any input where it is clear moves its hash value by the hash value of bit `k` alone
+    #[test]
+    fn setting_a_bit_moves_the_digest_in_its_basis_direction(bytes in prop::collection::vec(any::<u8>(), 256), k in 0..2048usize) {
+        let mut input = Input::from(<[u8; 256]>::try_from(bytes).unwrap());
+        input.set_bit(k, false);
+        let before = hash(&input);
+        input.set_bit(k, true);
+        let direction = hash(&single_bit(k));
+        let expected: Vec<_> = before.iter().zip(direction).map(|(a, b)| (a + b) % Q).collect();
+        prop_assert_eq!(hash(&input).to_vec(), expected);
+    }
+}
@@ -0,0 +1,51 @@
+//! Inputs built from `bitvec` bit slices:
+//! `cargo test -p libswifft --features bitvec --test bitvec`
+#![cfg(feature = "bitvec")]
+
+use bitvec::prelude::*;
+use libswifft::buffer::{AlignedBuffer, Input, Output};
+use libswifft::error::LenMismatch;
+use libswifft::hash::compute;
+use proptest::prelude::*;
+
+fn bytes() -> impl Strategy<Value = [u8; 256]> {
+    prop::collection::vec(any::<u8>(), 256).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+proptest! {
+    #[test]
+    fn from_bitslice_round_trips(bytes in bytes()) {
+        let bits = BitVec::<u8, Lsb0>::from_slice(&bytes);
+        let input = Input::from_bitslice(&bits).unwrap();
+        prop_assert_eq!(input.0[0], bytes);
+        prop_assert_eq!(input.to_bitvec(), bits);
+    }
+
+    #[test]
+    fn digests_match_the_byte_based_input(bytes in bytes(), offset in 0usize..8) {
+        // a bit slice that does not start on a byte boundary
+        let mut backing = bitvec![u8, Lsb0; 0; offset];
+        backing.extend_from_bitslice(bytes.view_bits::<Lsb0>());
+        let (mut from_bits, mut from_bytes) = (Output::default(), Output::default());
+        compute(&Input::from_bitslice(&backing[offset..]).unwrap(), &mut from_bits);
+        compute(&AlignedBuffer([bytes]), &mut from_bytes);
+        prop_assert_eq!(from_bits.0, from_bytes.0);
+    }
+}
+
+#[test]
+fn bits_map_onto_input_vectors_in_order() {
+    let mut bits = bitvec![u8, Lsb0; 0; 2048];
+    // coefficient 3 of input vector 5
+    bits.set(5 * 64 + 3, true);
+    let input = Input::from_bitslice(&bits).unwrap();
+    assert_eq!(input.0[0][5 * 8], 0b1000);
+    assert_eq!(input.0[0].iter().map(|byte| byte.count_ones()).sum::<u32>(), 1);
+}
+
+#[test]
+fn wrongly_sized_bit_slices_are_rejected() {
+    let bits = bitvec![u8, Lsb0; 1; 2049];
+    assert_eq!(Input::from_bitslice(&bits[..2047]).unwrap_err(), LenMismatch { expected: 2048, actual: 2047 });
+    assert_eq!(Input::from_bitslice(&bits).unwrap_err(), LenMismatch { expected: 2048, actual: 2049 });
+}
@@ -0,0 +1,45 @@
+//! Single blocks of batches of blocks, viewed in place
+
+use libswifft::buffer::{CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs};
+use libswifft::hash::{compact, compute, compute_multiple};
+
+#[test]
+fn mutating_a_block_is_seen_by_batch_hashing() {
+    let mut inputs = Inputs::<4>::new(0x11);
+    *inputs.block_mut(2) = Input::new(0x22);
+    let mut outputs = Outputs::<4>::default();
+    compute_multiple(&inputs, &mut outputs);
+
+    let mut expected = Output::default();
+    compute(&Input::new(0x22), &mut expected);
+    assert_eq!(outputs.block(2), &expected);
+    compute(inputs.block(1), &mut expected);
+    assert_eq!(outputs.block(1), &expected);
+    assert_ne!(outputs.block(1), outputs.block(2));
+}
+
+#[test]
+fn blocks_are_the_chunks_in_order() {
+    let mut outputs = Outputs::<3>::default();
+    for (i, block) in outputs.iter_blocks_mut().enumerate() {
+        block.0[0][0] = i as u8;
+        assert!(Output::verify_alignment(block));
+    }
+    assert_eq!(outputs.iter_blocks().map(|block| block.0[0][0]).collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(outputs.to_vec().iter().map(|block| block.0[0][0]).collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(outputs.to_vec()[1], *outputs.block(1));
+
+    let mut compact_outputs = CompactOutputs::<2>::default();
+    compact(outputs.block(2), compact_outputs.block_mut(1));
+    let mut expected = CompactOutput::default();
+    compact(&outputs.to_vec()[2], &mut expected);
+    assert_eq!(compact_outputs.block(1), &expected);
+    assert_eq!(compact_outputs.iter_blocks().count(), 2);
+    assert_eq!(Outputs::<0>::default().iter_blocks().count(), 0);
+}
+
+#[test]
+#[should_panic]
+fn blocks_past_the_batch_panic() {
+    Inputs::<2>::default().block(2);
+}
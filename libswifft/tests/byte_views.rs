@@ -0,0 +1,44 @@
+//! Flat byte views of buffers
+
+use libswifft::buffer::{AlignedBuffer, CompactOutputs, Input, Output, Outputs};
+use libswifft::hash::{compute, hash_bytes};
+
+fn takes_bytes(bytes: impl AsRef<[u8]>) -> Vec<u8> {
+    bytes.as_ref().to_vec()
+}
+
+#[test]
+fn views_cover_every_block_in_order() {
+    let mut outputs = Outputs::<3>::default();
+    outputs.0[0][0] = 1;
+    outputs.0[2][127] = 2;
+    let bytes = outputs.as_bytes();
+    assert_eq!((bytes.len(), bytes[0], bytes[3 * 128 - 1]), (3 * 128, 1, 2));
+    assert_eq!(outputs.len(), 3 * 128);
+    assert!(!outputs.is_empty());
+    assert!(CompactOutputs::<0>::default().is_empty());
+    assert!(AlignedBuffer::<0, 4>::default().is_empty());
+
+    let digest = hash_bytes(b"digest");
+    assert_eq!(digest.as_compact_output().as_bytes(), digest.as_bytes());
+    assert_eq!(takes_bytes(digest.as_compact_output()), digest.as_bytes());
+    assert_eq!(takes_bytes(Input::new(7)), [7; 256]);
+}
+
+#[test]
+fn mutable_views_write_through() {
+    let mut input = Input::default();
+    input.as_bytes_mut()[..4].copy_from_slice(b"swft");
+    AsMut::<[u8]>::as_mut(&mut input)[255] = 0xff;
+    assert_eq!(&input.0[0][..4], b"swft");
+    assert_eq!(input.0[0][255], 0xff);
+    assert!(Input::verify_alignment(&input));
+
+    let mut expected = Output::default();
+    compute(&input, &mut expected);
+    let mut copied = Input::default();
+    copied.as_bytes_mut().copy_from_slice(input.as_ref());
+    let mut output = Output::default();
+    compute(&copied, &mut output);
+    assert_eq!(output, expected);
+}
@@ -0,0 +1,18 @@
+//! Buffers through bytemuck:
+//! `cargo test -p libswifft --features bytemuck --test bytemuck`
+#![cfg(feature = "bytemuck")]
+
+use libswifft::buffer::{CompactOutputs, Input, Output};
+use libswifft::constant::BUFFER_ALIGNMENT;
+
+#[test]
+fn arenas_are_allocated_zeroed_and_aligned() {
+    let arena = bytemuck::allocation::zeroed_slice_box::<Input>(1000);
+    assert_eq!(arena.len(), 1000);
+    assert_eq!(arena.as_ptr() as usize % BUFFER_ALIGNMENT, 0);
+    assert!(arena.iter().all(|input| *input == Input::default()));
+
+    let outputs: Box<CompactOutputs<16>> = bytemuck::allocation::zeroed_box();
+    assert_eq!(*outputs, CompactOutputs::default());
+    assert_eq!(<Output as bytemuck::Zeroable>::zeroed(), Output::default());
+}
@@ -0,0 +1,71 @@
+//! Strict and reducing parsing of encoded SWIFFT hash values
+
+use libswifft::buffer::{Output, Outputs};
+use libswifft::error::OutOfRange;
+use proptest::prelude::*;
+
+fn encode(elements: &[i16]) -> [u8; 128] {
+    let mut bytes = [0u8; 128];
+    for (encoded, element) in bytes.chunks_exact_mut(2).zip(elements) {
+        encoded.copy_from_slice(&element.to_le_bytes());
+    }
+    bytes
+}
+
+fn with_element(index: usize, value: i16) -> [u8; 128] {
+    let mut elements = [0i16; 64];
+    elements[index] = value;
+    encode(&elements)
+}
+
+#[test]
+fn canonical_accepts_exactly_0_to_256() {
+    for value in i16::MIN..=i16::MAX {
+        for index in [0, 63] {
+            let bytes = with_element(index, value);
+            match Output::from_bytes_canonical(&bytes) {
+                Ok(output) => {
+                    assert!((0..=256).contains(&value), "accepted {value}");
+                    assert_eq!(output.0[0], bytes);
+                }
+                Err(err) => {
+                    assert!(!(0..=256).contains(&value), "rejected {value}");
+                    assert_eq!(err, OutOfRange { index, value });
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn canonical_reports_first_offending_index() {
+    let mut elements = [0i16; 64];
+    elements[9] = 257;
+    elements[40] = -1;
+    assert_eq!(Output::from_bytes_canonical(&encode(&elements)).unwrap_err(), OutOfRange { index: 9, value: 257 });
+}
+
+#[test]
+fn reducing_maps_every_value_to_its_residue() {
+    for value in i16::MIN..=i16::MAX {
+        let output = Output::from_bytes_reducing(&with_element(5, value));
+        assert_eq!(output.0[0], with_element(5, value.rem_euclid(257)));
+        assert!(output.is_canonical());
+    }
+}
+
+#[test]
+fn is_canonical_checks_every_block() {
+    let mut outputs = Outputs::<3>::default();
+    assert!(outputs.is_canonical());
+    outputs.0[2][126..].copy_from_slice(&257i16.to_le_bytes());
+    assert!(!outputs.is_canonical());
+}
+
+proptest! {
+    #[test]
+    fn reducing_agrees_with_canonical_on_canonical_input(elements in prop::collection::vec(0i16..=256, 64)) {
+        let bytes = encode(&elements);
+        prop_assert_eq!(Output::from_bytes_reducing(&bytes).0, Output::from_bytes_canonical(&bytes).unwrap().0);
+    }
+}
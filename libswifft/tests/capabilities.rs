@@ -0,0 +1,85 @@
+//! The reported capabilities reflect the features this test binary was compiled with,
+//! so it should be run under several combinations of features, e.g. `cargo test -p libswifft --no-default-features --test capabilities`
+//! and `cargo test -p libswifft --features bitvec,paranoid --test capabilities`
+
+use libswifft::capabilities::{Capabilities, Isa};
+
+#[test]
+fn capabilities_reflect_compiled_features() {
+    let capabilities = libswifft::capabilities();
+    assert!(capabilities.native_backend);
+    assert_eq!(capabilities.parallel, cfg!(feature = "parallel"));
+    assert_eq!(capabilities.paranoid, cfg!(feature = "paranoid"));
+    assert_eq!(capabilities.analysis, cfg!(feature = "analysis"));
+    assert_eq!(capabilities.no_panic, cfg!(feature = "no-panic"));
+    assert_eq!(capabilities.bitvec, cfg!(feature = "bitvec"));
+    assert_eq!(capabilities.digest, cfg!(feature = "digest"));
+    assert_eq!(capabilities.registry, cfg!(feature = "registry"));
+    assert_eq!(capabilities.test_doubles, cfg!(feature = "test-doubles"));
+    assert_eq!(capabilities.allocator_api, cfg!(feature = "allocator_api"));
+    assert_eq!(capabilities.bytemuck, cfg!(feature = "bytemuck"));
+    assert_eq!(capabilities.rand, cfg!(feature = "rand"));
+    assert_eq!(capabilities.serde, cfg!(feature = "serde"));
+    assert_eq!(capabilities.zeroize, cfg!(feature = "zeroize"));
+}
+
+/// The features declared in the `[features]` table of `manifest`, other than `default`
+fn manifest_features(manifest: &str) -> Vec<String> {
+    manifest.lines()
+        .skip_while(|line| line.trim() != "[features]")
+        .skip(1)
+        .take_while(|line| !line.starts_with('['))
+        .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+        .map(|line| line.split_once('=').unwrap().0.trim().to_string())
+        .filter(|feature| feature != "default")
+        .collect()
+}
+
+#[test]
+fn every_feature_of_the_manifest_is_reported() {
+    let mut capabilities = libswifft::capabilities();
+    for enabled in [
+        &mut capabilities.parallel, &mut capabilities.paranoid, &mut capabilities.analysis, &mut capabilities.no_panic,
+        &mut capabilities.bitvec, &mut capabilities.digest, &mut capabilities.registry, &mut capabilities.test_doubles,
+        &mut capabilities.allocator_api, &mut capabilities.bytemuck, &mut capabilities.rand, &mut capabilities.serde,
+        &mut capabilities.zeroize, &mut capabilities.swifft_ct, &mut capabilities.swifft_serde,
+    ] {
+        *enabled = true;
+    }
+    let (reported, reported_swifft): (Vec<&str>, Vec<&str>) =
+        capabilities.features().partition(|feature| !feature.starts_with("swifft/"));
+    let (mut reported, mut declared) = (reported, manifest_features(include_str!("../Cargo.toml")));
+    reported.sort_unstable();
+    declared.sort_unstable();
+    assert_eq!(reported, declared);
+
+    // only some features of the `swifft` crate are reported, but each of those must exist
+    let swifft_features = manifest_features(include_str!("../../swifft-rs/Cargo.toml"));
+    assert_eq!(reported_swifft, ["swifft/ct", "swifft/serde"]);
+    for feature in reported_swifft {
+        assert!(swifft_features.iter().any(|declared| feature == format!("swifft/{}", declared)), "{}", feature);
+    }
+}
+
+#[test]
+fn isa_matches_runtime_detection() {
+    let isa = libswifft::capabilities().isa;
+    assert_eq!(isa, Isa::detect());
+    #[cfg(target_arch = "x86_64")]
+    assert_eq!(isa == Isa::Scalar, !std::is_x86_feature_detected!("avx"));
+    #[cfg(not(any(target_arch = "x86", target_arch = "x86_64")))]
+    assert_eq!(isa, Isa::Scalar);
+}
+
+#[test]
+fn display_is_a_single_diagnostic_line() {
+    let capabilities: Capabilities = libswifft::capabilities();
+    let line = capabilities.to_string();
+    assert!(!line.contains('\n'));
+    assert!(line.starts_with(&format!("libswifft {} backend=native isa={} features=",
+        env!("CARGO_PKG_VERSION"), capabilities.isa)));
+    let features: Vec<&str> = capabilities.features().collect();
+    let expected = if features.is_empty() { "none".to_string() } else { features.join(",") };
+    assert!(line.ends_with(&format!("features={}", expected)), "{line}");
+    assert_eq!(features.contains(&"parallel"), cfg!(feature = "parallel"));
+}
@@ -0,0 +1,95 @@
+//! The centered `[-128, 128]` representation of SWIFFT hash values
+
+use libswifft::arithmetic::{mul, set};
+use libswifft::buffer::{Input, InputBuilder, Output};
+use libswifft::constant::N;
+use libswifft::error::OutOfRange;
+use libswifft::hash::compute;
+use proptest::prelude::*;
+
+fn output_with_element(index: usize, value: i16) -> Output {
+    let mut output = Output::default();
+    output.0[0][2*index..2*index + 2].copy_from_slice(&value.to_le_bytes());
+    output
+}
+
+fn hash(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+fn input(bytes: &[u8]) -> Input {
+    bytes.iter().enumerate()
+        .fold(InputBuilder::new(), |builder, (i, &byte)| builder.set_byte(i, byte))
+        .build()
+}
+
+#[test]
+fn to_centered_maps_every_canonical_element() {
+    for value in 0..=256i16 {
+        let expected = if value <= 128 { value } else { value - 257 };
+        for index in 0..N {
+            let centered = output_with_element(index, value).to_centered();
+            assert_eq!(centered[index], expected, "element {index} with value {value}");
+            assert!(centered.iter().enumerate().all(|(i, &c)| i == index || c == 0));
+        }
+    }
+    assert_eq!(output_with_element(0, 256).to_centered()[0], -1);
+    assert_eq!(output_with_element(0, 129).to_centered()[0], -128);
+}
+
+#[test]
+fn to_centered_reduces_non_canonical_elements() {
+    for value in -256..=-1i16 {
+        assert_eq!(output_with_element(0, value).to_centered()[0], output_with_element(0, value + 257).to_centered()[0]);
+    }
+}
+
+#[test]
+fn from_centered_maps_every_centered_element() {
+    for value in -128..=128i16 {
+        let expected = if value < 0 { value + 257 } else { value };
+        for index in 0..N {
+            let mut centered = [0i16; N];
+            centered[index] = value;
+            let output = Output::from_centered(&centered).unwrap();
+            assert_eq!(output.0[0], output_with_element(index, expected).0[0], "element {index} with value {value}");
+        }
+    }
+}
+
+#[test]
+fn from_centered_rejects_out_of_range_elements() {
+    for value in [i16::MIN, -256, -129, 129, 256, i16::MAX] {
+        for index in [0, 17, N - 1] {
+            let mut centered = [0i16; N];
+            centered[index] = value;
+            assert_eq!(Output::from_centered(&centered).unwrap_err(), OutOfRange { index, value });
+        }
+    }
+}
+
+proptest! {
+    #[test]
+    fn centered_round_trips(centered in prop::collection::vec(-128..=128i16, N)) {
+        let centered: [i16; N] = centered.try_into().unwrap();
+        prop_assert_eq!(Output::from_centered(&centered).unwrap().to_centered(), centered);
+    }
+
+    #[test]
+    fn hash_values_round_trip(bytes in prop::collection::vec(any::<u8>(), 256)) {
+        let output = hash(&input(&bytes));
+        prop_assert_eq!(Output::from_centered(&output.to_centered()).unwrap().0, output.0);
+    }
+
+    #[test]
+    fn products_round_trip_to_canonical(a in prop::collection::vec(any::<u8>(), 256), b in prop::collection::vec(any::<u8>(), 256)) {
+        let (a, b) = (hash(&input(&a)), hash(&input(&b)));
+        let mut product = Output::default();
+        set(&mut product, &a);
+        mul(&mut product, &b);
+        let canonical = Output::from_centered(&product.to_centered()).unwrap();
+        prop_assert_eq!(canonical.to_centered(), product.to_centered());
+    }
+}
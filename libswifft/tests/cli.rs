@@ -0,0 +1,137 @@
+//! The `swifft` binary: hashing files and standard input, and checking manifests of digests
+
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Output, Stdio};
+
+use libswifft::hash::hash_bytes;
+
+/// Runs the `swifft` binary, built with exactly the features of this test crate,
+/// so that what it reports of its build is what the library reports here
+fn swifft(args: &[&str], stdin: &[u8]) -> Output {
+    let features: Vec<&str> = libswifft::capabilities().features().collect();
+    let mut build = escargot::CargoBuild::new()
+        .manifest_path(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .bin("swifft")
+        .current_release()
+        .no_default_features();
+    if !features.is_empty() {
+        // not `features`, which passes `-features` rather than `--features` in this version of escargot
+        build = build.arg(format!("--features={}", features.join(",")));
+    }
+    let binary = build
+        .run()
+        .unwrap_or_else(|err| panic!("building the swifft binary failed: {}", err));
+    let mut child = Command::new(binary.path())
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// Writes `contents` to a file named `name` in a directory of its own for `test`
+fn file(test: &str, name: &str, contents: &[u8]) -> String {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cli").join(test);
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    std::fs::write(&path, contents).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+fn stderr(output: &Output) -> String {
+    String::from_utf8(output.stderr.clone()).unwrap()
+}
+
+#[test]
+fn hash_prints_digests_like_sha256sum() {
+    let big: Vec<u8> = (0..100_000u32).map(|i| (i % 251) as u8).collect();
+    let (small_path, big_path) = (file("hash", "small", b"Hello, world!"), file("hash", "big", &big));
+    let output = swifft(&["hash", &small_path, &big_path], b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), format!("{:x}  {}\n{:x}  {}\n",
+        hash_bytes(b"Hello, world!"), small_path, hash_bytes(&big), big_path));
+}
+
+#[test]
+fn hash_reads_standard_input() {
+    for args in [&["hash", "-"][..], &["hash"]] {
+        let output = swifft(args, b"from stdin");
+        assert!(output.status.success());
+        assert_eq!(stdout(&output), format!("{:x}  -\n", hash_bytes(b"from stdin")));
+    }
+}
+
+#[test]
+fn unreadable_files_are_reported_and_skipped() {
+    let path = file("unreadable", "present", b"present");
+    let missing = format!("{}.missing", path);
+    let output = swifft(&["hash", &missing, &path], b"");
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), format!("{:x}  {}\n", hash_bytes(b"present"), path));
+    assert!(stderr(&output).contains(&missing), "{}", stderr(&output));
+}
+
+#[test]
+fn check_verifies_a_manifest() {
+    let (a, b) = (file("check", "a", b"a"), file("check", "b", b"b"));
+    let manifest = stdout(&swifft(&["hash", &a, &b], b""));
+    let manifest_path = file("check", "manifest", manifest.as_bytes());
+    let output = swifft(&["check", &manifest_path], b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), format!("{}: OK\n{}: OK\n", a, b));
+
+    let output = swifft(&["check", "-"], manifest.as_bytes());
+    assert!(output.status.success());
+
+    // changing a file, removing another and garbling a line are each reported, but checking carries on
+    file("check", "a", b"changed");
+    let missing = format!("{:x}  {}.missing\n", hash_bytes(b"gone"), b);
+    let output = swifft(&["check", "-"], format!("{}{}not a manifest line\n", manifest, missing).as_bytes());
+    assert!(!output.status.success());
+    assert_eq!(stdout(&output), format!("{}: FAILED\n{}: OK\n{}.missing: FAILED open or read\n", a, b, b));
+    let stderr = stderr(&output);
+    assert!(stderr.contains("1 line(s) are improperly formatted"), "{}", stderr);
+    assert!(stderr.contains("1 listed file(s) could not be read"), "{}", stderr);
+    assert!(stderr.contains("1 computed digest(s) did NOT match"), "{}", stderr);
+}
+
+#[test]
+fn bench_reports_throughput() {
+    let output = swifft(&["bench", "1"], b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert!(stdout(&output).starts_with("hashed 1 MiB in "), "{}", stdout(&output));
+}
+
+#[test]
+fn bench_rejects_sizes_too_large_to_address() {
+    for mib in [usize::MAX, (isize::MAX as usize >> 20) + 1] {
+        let output = swifft(&["bench", &mib.to_string()], b"");
+        assert!(!output.status.success());
+        assert_eq!(stderr(&output), format!("invalid size: {} MiB is too large\n", mib));
+    }
+}
+
+#[test]
+fn capabilities_prints_the_diagnostic_line() {
+    let output = swifft(&["--capabilities"], b"");
+    assert!(output.status.success(), "{}", stderr(&output));
+    assert_eq!(stdout(&output), format!("{}\n", libswifft::capabilities()));
+    assert!(!swifft(&["--capabilities", "extra"], b"").status.success());
+}
+
+#[test]
+fn misuse_prints_usage() {
+    for args in [&[][..], &["unknown"], &["check"], &["bench", "0"], &["bench", "many"]] {
+        let output = swifft(args, b"");
+        assert!(!output.status.success(), "{:?}", args);
+        assert!(!stderr(&output).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+//! Typed coefficient access to single hash values
+
+use libswifft::buffer::{Input, Output};
+use libswifft::hash::compute;
+use proptest::prelude::*;
+
+fn hash_value() -> Output {
+    let mut output = Output::default();
+    compute(&Input::new(0x3c), &mut output);
+    output
+}
+
+#[test]
+fn coefficients_are_little_endian_elements() {
+    let mut output = Output::default();
+    output.0[0][..4].copy_from_slice(&[0x01, 0x00, 0x00, 0x01]);
+    let coefficients = output.to_coefficients();
+    assert_eq!((coefficients[0], coefficients[1], coefficients[2]), (1, 256, 0));
+    assert_eq!(Output::from_coefficients(hash_value().to_coefficients()), hash_value());
+    // out of range coefficients are kept as they are
+    assert!(!Output::from_coefficients([257; 64]).is_canonical());
+}
+
+#[cfg(target_endian = "little")]
+#[test]
+fn in_place_views_agree_with_the_copies() {
+    let mut output = hash_value();
+    assert_eq!(*output.as_coefficients(), output.to_coefficients());
+    output.as_coefficients_mut()[63] = 0x0102;
+    assert_eq!(&output.0[0][126..], &[0x02, 0x01]);
+    assert_eq!(output.to_coefficients()[63], 0x0102);
+}
+
+proptest! {
+    #[test]
+    fn coefficients_round_trip(coefficients in prop::array::uniform32(any::<u16>()), high in prop::array::uniform32(any::<u16>())) {
+        let mut all = [0u16; 64];
+        all[..32].copy_from_slice(&coefficients);
+        all[32..].copy_from_slice(&high);
+        prop_assert_eq!(Output::from_coefficients(all).to_coefficients(), all);
+    }
+}
@@ -0,0 +1,91 @@
+//! Homomorphic commitments, their openings and their layout
+
+use libswifft::buffer::{Input, Output};
+use libswifft::commit::{commit, open, open_sum, Commitment, MESSAGE_SIZE, RANDOMNESS_SIZE};
+use libswifft::hash::compute;
+use proptest::prelude::*;
+
+fn randomness() -> impl Strategy<Value = [u8; RANDOMNESS_SIZE]> {
+    any::<[u8; RANDOMNESS_SIZE]>()
+}
+
+fn message() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 0..=MESSAGE_SIZE)
+}
+
+#[test]
+fn layout_is_documented() {
+    let (message, randomness) = (b"message", [9; RANDOMNESS_SIZE]);
+    let mut input = Input::default();
+    input.0[0][..7].copy_from_slice(message);
+    input.0[0][223..255].copy_from_slice(&randomness);
+    input.0[0][255] = 4;
+    let mut expected = Output::default();
+    compute(&input, &mut expected);
+    assert_eq!(commit(message, &randomness).as_output().0, expected.0);
+    assert_eq!(commit(message, &randomness), Commitment::from(expected));
+}
+
+#[test]
+fn wrong_openings_are_rejected() {
+    let (randomness, other) = ([1; RANDOMNESS_SIZE], [2; RANDOMNESS_SIZE]);
+    let commitment = commit(b"message", &randomness);
+    assert!(open(&commitment, b"message", &randomness));
+    assert!(!open(&commitment, b"massage", &randomness));
+    assert!(!open(&commitment, b"message", &other));
+    assert!(!open(&commitment, &[0; MESSAGE_SIZE + 1], &randomness));
+    // the randomness is not interchangeable with the end of the message
+    assert!(!open(&commitment, &[&b"message"[..], &[0; 216], &[1]].concat(), &[0; RANDOMNESS_SIZE]));
+    // messages are zero padded, as documented
+    assert!(open(&commitment, b"message\0\0", &randomness));
+}
+
+#[test]
+fn congruent_commitments_open() {
+    let randomness = [1; RANDOMNESS_SIZE];
+    let mut coefficients = commit(b"message", &randomness).as_output().to_coefficients();
+    coefficients[0] += 257;
+    let unreduced = Commitment::from(Output::from_coefficients(coefficients));
+    assert_ne!(unreduced, commit(b"message", &randomness));
+    assert!(open(&unreduced, b"message", &randomness));
+    coefficients[0] += 1;
+    assert!(!open(&Commitment::from(Output::from_coefficients(coefficients)), b"message", &randomness));
+}
+
+#[test]
+#[should_panic]
+fn committing_to_long_messages_panics() {
+    commit(&[0; MESSAGE_SIZE + 1], &[0; RANDOMNESS_SIZE]);
+}
+
+proptest! {
+    #[test]
+    fn sums_open_to_both_openings(m1 in message(), r1 in randomness(), m2 in message(), r2 in randomness()) {
+        let (c1, c2) = (commit(&m1, &r1), commit(&m2, &r2));
+        let sum = c1.clone() + c2.clone();
+        prop_assert!(open_sum(&sum, &[(&m1, &r1), (&m2, &r2)]));
+        prop_assert!(open_sum(&sum, &[(&m2, &r2), (&m1, &r1)]));
+        prop_assert!(!open_sum(&sum, &[(&m1, &r1)]));
+        prop_assert_eq!(sum.clone() - c2, c1.clone());
+
+        let mut accumulated = c1.clone();
+        accumulated += &commit(&m2, &r2);
+        prop_assert_eq!(&accumulated, &sum);
+        accumulated -= &c1;
+        prop_assert!(open(&accumulated, &m2, &r2));
+    }
+
+    #[test]
+    fn flipping_any_bit_of_an_opening_fails(m in message(), r in randomness(), bit in 0..8 * (MESSAGE_SIZE + RANDOMNESS_SIZE)) {
+        let commitment = commit(&m, &r);
+        let (mut m, mut r) = (m, r);
+        if bit < 8 * MESSAGE_SIZE {
+            m.resize(MESSAGE_SIZE, 0);
+            m[bit / 8] ^= 1 << (bit % 8);
+        } else {
+            let bit = bit - 8 * MESSAGE_SIZE;
+            r[bit / 8] ^= 1 << (bit % 8);
+        }
+        prop_assert!(!open(&commitment, &m, &r));
+    }
+}
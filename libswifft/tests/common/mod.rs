@@ -0,0 +1,6 @@
+//! Fixtures shared by the integration tests of hashing messages
+
+/// A message of `len` bytes, repeating with a period of `253` bytes, which is not a multiple of any block size
+pub fn message(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 31 % 253) as u8).collect()
+}
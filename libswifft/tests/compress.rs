@@ -0,0 +1,51 @@
+//! The 2-to-1 compression of compact hash values, and its frozen layout
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Digest512, Input, Output};
+use libswifft::hash::{compact, compress, compress_compact, compute, hash_bytes};
+use libswifft::merkle::MerkleTree;
+use proptest::prelude::*;
+
+fn compact_output() -> impl Strategy<Value = CompactOutput> {
+    prop::collection::vec(any::<u8>(), 64).prop_map(|bytes| AlignedBuffer([bytes.try_into().unwrap()]))
+}
+
+#[test]
+fn layout_is_documented() {
+    let (left, right) = (CompactOutput::from(hash_bytes(b"left")), CompactOutput::from(hash_bytes(b"right")));
+    let mut input = Input::default();
+    input.0[0][..64].copy_from_slice(&left.0[0]);
+    input.0[0][64..128].copy_from_slice(&right.0[0]);
+    input.0[0][255] = 1;
+    let mut expected = Output::default();
+    compute(&input, &mut expected);
+    assert_eq!(compress(&left, &right).0, expected.0);
+
+    let mut compacted = CompactOutput::default();
+    compact(&expected, &mut compacted);
+    assert_eq!(compress_compact(&left, &right).0, compacted.0);
+}
+
+#[test]
+fn merkle_nodes_are_compressions() {
+    let tree = MerkleTree::build(&["left", "right"]);
+    let (left, right) = (hash_bytes(b"left"), hash_bytes(b"right"));
+    let node = compress_compact(left.as_compact_output(), right.as_compact_output());
+    assert_eq!(tree.root(), Digest512::from(node));
+}
+
+proptest! {
+    #[test]
+    fn order_of_children_matters(a in compact_output(), b in compact_output()) {
+        prop_assume!(a.0 != b.0);
+        prop_assert_ne!(compress(&a, &b).0, compress(&b, &a).0);
+        prop_assert_ne!(compress_compact(&a, &b).0, compress_compact(&b, &a).0);
+    }
+
+    #[test]
+    fn each_child_is_bound(a in compact_output(), b in compact_output(), byte in 0..64usize, bit in 0..8u8) {
+        let mut flipped = a.clone();
+        flipped.0[0][byte] ^= 1 << bit;
+        prop_assert_ne!(compress(&a, &b).0, compress(&flipped, &b).0);
+        prop_assert_ne!(compress(&b, &a).0, compress(&b, &flipped).0);
+    }
+}
@@ -0,0 +1,75 @@
+//! Per-block constants taken from slices and iterators, against the single-block functions
+
+use libswifft::arithmetic::*;
+use libswifft::buffer::{AlignedBuffer, Inputs, Output, Outputs};
+use libswifft::error::LenMismatch;
+use libswifft::hash::compute_multiple;
+
+const NUM_BLOCKS: usize = 5;
+const OPERANDS: [i16; NUM_BLOCKS] = [0, 1, -1, 256, 100];
+
+type Batch = fn(&mut Outputs<NUM_BLOCKS>, &[i16]) -> Result<(), LenMismatch>;
+type Single = fn(&mut Output, i16);
+
+fn outputs() -> Outputs<NUM_BLOCKS> {
+    let mut outputs = Outputs::default();
+    compute_multiple(&Inputs::<NUM_BLOCKS>::from_seed(5), &mut outputs);
+    outputs
+}
+
+fn per_block(single: Single) -> Outputs<NUM_BLOCKS> {
+    AlignedBuffer(std::array::from_fn(|i| {
+        let mut block = AlignedBuffer([outputs().0[i]]);
+        single(&mut block, OPERANDS[i]);
+        block.0[0]
+    }))
+}
+
+const OPERATIONS: [(Batch, Single, &str); 4] = [
+    (const_set_batch, const_set, "set"),
+    (const_add_batch, const_add, "add"),
+    (const_sub_batch, const_sub, "sub"),
+    (const_mul_batch, const_mul, "mul"),
+];
+
+#[test]
+fn batches_match_the_single_block_functions() {
+    for (batch, single, name) in OPERATIONS {
+        let mut output = outputs();
+        batch(&mut output, &OPERANDS).unwrap();
+        assert_eq!(output, per_block(single), "const_{}_batch", name);
+    }
+}
+
+#[test]
+fn iterators_match_the_single_block_functions() {
+    let mut output = outputs();
+    const_set_iter(&mut output, OPERANDS).unwrap();
+    assert_eq!(output, per_block(const_set));
+    let mut output = outputs();
+    const_add_iter(&mut output, OPERANDS.iter().copied()).unwrap();
+    assert_eq!(output, per_block(const_add));
+    let mut output = outputs();
+    const_sub_iter(&mut output, OPERANDS.to_vec()).unwrap();
+    assert_eq!(output, per_block(const_sub));
+    let mut output = outputs();
+    const_mul_iter(&mut output, (0..NUM_BLOCKS).map(|i| OPERANDS[i])).unwrap();
+    assert_eq!(output, per_block(const_mul));
+}
+
+#[test]
+fn length_mismatches_are_rejected_without_writing() {
+    for (batch, _, name) in OPERATIONS {
+        let mut output = outputs();
+        assert_eq!(batch(&mut output, &OPERANDS[..4]), Err(LenMismatch { expected: 5, actual: 4 }), "const_{}_batch", name);
+        assert_eq!(batch(&mut output, &[1; 7]), Err(LenMismatch { expected: 5, actual: 7 }), "const_{}_batch", name);
+        assert_eq!(output, outputs());
+    }
+    let mut output = outputs();
+    assert_eq!(const_mul_iter(&mut output, std::iter::empty()), Err(LenMismatch { expected: 5, actual: 0 }));
+    assert_eq!(const_add_iter(&mut output, std::iter::repeat(1).take(6)), Err(LenMismatch { expected: 5, actual: 6 }));
+    // only the first surplus constant is taken, so endless iterators are rejected too
+    assert_eq!(const_sub_iter(&mut output, std::iter::repeat(1).take(9)), Err(LenMismatch { expected: 5, actual: 6 }));
+    assert_eq!(const_set_iter(&mut output, std::iter::repeat(1)), Err(LenMismatch { expected: 5, actual: 6 }));
+    assert_eq!(output, outputs());
+}
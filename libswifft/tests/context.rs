@@ -0,0 +1,180 @@
+//! Hashing through reusable scratch buffers gives the results of the stateless functions, without allocating
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::{self, Read};
+
+use libswifft::buffer::CompactOutput;
+use libswifft::context::SwifftContext;
+use libswifft::hash::{compress, compress_compact, hash_bytes, hash_reader, verify, verify_reader, MESSAGE_BYTES_PER_BLOCK};
+
+use common::message;
+
+/// Forwards to the system allocator, counting the allocations of each thread
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+const LENGTHS: [usize; 10] = [0, 1, 254, 255, 256, 510, 64 * 255 - 1, 64 * 255, 64 * 255 + 1, 3 * 64 * 255 + 77];
+
+#[test]
+fn matches_the_stateless_functions() {
+    for batch_blocks in [1, 2, 7, 64, 100] {
+        let mut ctx = SwifftContext::with_batch_blocks(batch_blocks);
+        assert_eq!(ctx.batch_blocks(), batch_blocks);
+        for len in LENGTHS {
+            let data = message(len);
+            assert_eq!(ctx.hash_bytes(&data), hash_bytes(&data), "{} bytes in batches of {}", len, batch_blocks);
+        }
+    }
+
+    let (left, right) = (CompactOutput::from(hash_bytes(b"left")), CompactOutput::from(hash_bytes(b"right")));
+    let mut ctx = SwifftContext::new();
+    assert_eq!(ctx.compress(&left, &right).0, compress(&left, &right).0);
+    assert_eq!(ctx.compress_compact(&left, &right).0, compress_compact(&left, &right).0);
+}
+
+/// Reads at most `max_read` bytes at a time, failing once at least `fail_at` bytes were read if given
+struct Trickle<'a> {
+    data: &'a [u8],
+    max_read: usize,
+    fail_at: Option<usize>,
+    read: usize,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.fail_at.is_some_and(|fail_at| self.read >= fail_at) {
+            return Err(io::Error::other("disk on fire"))
+        }
+        let len = buf.len().min(self.max_read).min(self.data.len());
+        buf[..len].copy_from_slice(&self.data[..len]);
+        self.data = &self.data[len..];
+        self.read += len;
+        Ok(len)
+    }
+}
+
+#[test]
+fn reading_matches_the_stateless_functions() {
+    for batch_blocks in [1, 2, 7, 64] {
+        let mut ctx = SwifftContext::with_batch_blocks(batch_blocks);
+        for len in LENGTHS {
+            let data = message(len);
+            assert_eq!(ctx.hash_reader(&data[..]).unwrap(), hash_bytes(&data), "{} bytes in batches of {}", len, batch_blocks);
+            let trickle = Trickle { data: &data, max_read: 100, fail_at: None, read: 0 };
+            assert_eq!(ctx.hash_reader(trickle).unwrap(), hash_reader(&data[..]).unwrap(), "{} bytes trickled", len);
+        }
+    }
+}
+
+#[test]
+fn a_failed_read_does_not_leak_into_the_next_call() {
+    let data = message(10_000);
+    let mut ctx = SwifftContext::with_batch_blocks(3);
+    let failing = Trickle { data: &data, max_read: 1000, fail_at: Some(5000), read: 0 };
+    assert_eq!(ctx.hash_reader(failing).unwrap_err().to_string(), "disk on fire");
+    assert_eq!(ctx.hash_reader(&data[..]).unwrap(), hash_bytes(&data));
+    assert_eq!(ctx.hash_bytes(b"short"), hash_bytes(b"short"));
+}
+
+#[test]
+fn calls_do_not_depend_on_earlier_calls() {
+    let mut ctx = SwifftContext::with_batch_blocks(3);
+    // a long message leaves stale leaf blocks behind, which must not leak into a short one
+    ctx.hash_bytes(&message(10_000));
+    assert_eq!(ctx.hash_bytes(b"short"), hash_bytes(b"short"));
+    ctx.hash_bytes(&[0xff; 2000]);
+    assert_eq!(ctx.hash_bytes(b""), hash_bytes(b""));
+
+    let (left, right) = (CompactOutput::new(0xff), CompactOutput::new(0x01));
+    ctx.compress(&left, &right);
+    let zero = CompactOutput::default();
+    assert_eq!(ctx.compress(&zero, &zero).0, compress(&zero, &zero).0);
+}
+
+#[test]
+fn hashing_never_allocates_after_construction() {
+    let messages: Vec<Vec<u8>> = LENGTHS.iter().map(|&len| message(len)).chain([message(100 * MESSAGE_BYTES_PER_BLOCK)]).collect();
+    let (left, right) = (CompactOutput::new(1), CompactOutput::new(2));
+    let mut ctx = SwifftContext::with_batch_blocks(4);
+    let before = allocations();
+    for data in &messages {
+        std::hint::black_box(ctx.hash_bytes(data));
+    }
+    for data in &messages {
+        std::hint::black_box(ctx.hash_reader(&data[..]).unwrap());
+    }
+    std::hint::black_box(ctx.compress(&left, &right));
+    std::hint::black_box(ctx.compress_compact(&left, &right));
+    assert_eq!(allocations() - before, 0);
+}
+
+#[test]
+fn clones_never_allocate_after_cloning() {
+    let ctx = SwifftContext::with_batch_blocks(1);
+    let data = message(1000 * MESSAGE_BYTES_PER_BLOCK - 1);
+    let mut clone = ctx.clone();
+    assert_eq!(clone.batch_blocks(), 1);
+    let before = allocations();
+    // one leaf per block, so the tree holds a root per bit set in the number of leaves
+    let digest = clone.hash_bytes(&data);
+    assert_eq!(allocations() - before, 0);
+    assert_eq!(digest, hash_bytes(&data));
+}
+
+#[test]
+fn verifying_allocates_the_same_whatever_the_length() {
+    let (short, long) = (message(1), message(100 * MESSAGE_BYTES_PER_BLOCK));
+    let (short_digest, long_digest) = (hash_bytes(&short), hash_bytes(&long));
+    let allocations_of = |verify: &dyn Fn() -> bool| {
+        let before = allocations();
+        assert!(verify());
+        allocations() - before
+    };
+    let fixed = allocations_of(&|| verify(&short, &short_digest));
+    assert_eq!(allocations_of(&|| verify(&long, &long_digest)), fixed);
+    assert_eq!(allocations_of(&|| verify_reader(&short[..], &short_digest).unwrap()), fixed);
+    assert_eq!(allocations_of(&|| verify_reader(&long[..], &long_digest).unwrap()), fixed);
+}
+
+#[test]
+fn one_context_per_worker_thread() {
+    fn assert_send<T: Send>() {}
+    assert_send::<SwifftContext>();
+
+    let workers: Vec<_> = (0..4)
+        .map(|worker| std::thread::spawn(move || {
+            let mut ctx = SwifftContext::new();
+            (0..20).all(|i| {
+                let data = message(worker * 1000 + i * 37);
+                ctx.hash_bytes(&data) == hash_bytes(&data)
+            })
+        }))
+        .collect();
+    for worker in workers {
+        assert!(worker.join().unwrap());
+    }
+}
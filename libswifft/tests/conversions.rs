@@ -0,0 +1,73 @@
+//! Conversions between buffers and byte slices and arrays
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs};
+use libswifft::error::{ConversionError, LenMismatch, OutOfRange};
+
+fn bytes(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 7 + 3) as u8).collect()
+}
+
+/// The little endian encoding of `len / 2` canonical elements
+fn canonical_bytes(len: usize) -> Vec<u8> {
+    (0..len / 2).flat_map(|i| ((i * 7 + 3) as u16 % 257).to_le_bytes()).collect()
+}
+
+#[test]
+fn single_blocks_round_trip_through_slices_and_arrays() {
+    let input = Input::try_from(&bytes(256)[..]).unwrap();
+    assert_eq!(input.to_array().to_vec(), bytes(256));
+    assert_eq!(Input::from(input.to_array()), input);
+
+    let output = Output::try_from(&canonical_bytes(128)[..]).unwrap();
+    assert_eq!(output.to_array().to_vec(), canonical_bytes(128));
+    assert_eq!(Output::from(output.to_array()), output);
+
+    let compact_output = CompactOutput::try_from(&bytes(64)[..]).unwrap();
+    assert_eq!(compact_output.to_array().to_vec(), bytes(64));
+    assert_eq!(CompactOutput::from(compact_output.to_array()), compact_output);
+    assert!(CompactOutput::verify_alignment(&compact_output));
+}
+
+#[test]
+fn multiple_blocks_round_trip_through_slices_and_blocks() {
+    let inputs = Inputs::<3>::try_from(&bytes(3 * 256)[..]).unwrap();
+    assert_eq!(inputs.0.as_flattened(), &bytes(3 * 256)[..]);
+    assert_eq!(Inputs::<3>::try_from(&inputs.0[..]).unwrap(), inputs);
+
+    let outputs = Outputs::<2>::try_from(&canonical_bytes(2 * 128)[..]).unwrap();
+    assert_eq!(outputs.0[1].to_vec(), canonical_bytes(256)[128..]);
+    assert_eq!(Outputs::<2>::try_from(&outputs.0[..]).unwrap(), outputs);
+
+    let compact_outputs = CompactOutputs::<4>::try_from(&bytes(4 * 64)[..]).unwrap();
+    assert_eq!(CompactOutputs::<4>::try_from(&compact_outputs.0[..]).unwrap(), compact_outputs);
+    assert_eq!(CompactOutputs::<0>::try_from(&[0u8; 0][..]).unwrap(), AlignedBuffer([]));
+}
+
+#[test]
+fn wrong_lengths_are_rejected() {
+    let mismatch = |expected, actual| ConversionError::LenMismatch(LenMismatch { expected, actual });
+    assert_eq!(Input::try_from(&bytes(255)[..]).unwrap_err(), mismatch(256, 255));
+    assert_eq!(Output::try_from(&bytes(256)[..]).unwrap_err(), mismatch(128, 256));
+    assert_eq!(CompactOutput::try_from(&[0u8; 0][..]).unwrap_err(), mismatch(64, 0));
+    assert_eq!(Outputs::<2>::try_from(&bytes(3 * 128)[..]).unwrap_err(), mismatch(256, 384));
+
+    let blocks = [[0u8; 64]; 3];
+    assert_eq!(CompactOutputs::<2>::try_from(&blocks[..]).unwrap_err(), mismatch(2, 3));
+    assert_eq!(CompactOutputs::<4>::try_from(&blocks[..]).unwrap_err().to_string(), "length mismatch: expected 4 elements, got 3");
+}
+
+#[test]
+fn non_canonical_hash_values_are_rejected() {
+    let mut bytes = canonical_bytes(2 * 128);
+    bytes[128 + 2 * 5..][..2].copy_from_slice(&257u16.to_le_bytes());
+    let out_of_range = ConversionError::OutOfRange(OutOfRange { index: 64 + 5, value: 257 });
+    assert_eq!(Outputs::<2>::try_from(&bytes[..]).unwrap_err(), out_of_range);
+    let blocks: Vec<[u8; 128]> = bytes.chunks_exact(128).map(|block| block.try_into().unwrap()).collect();
+    assert_eq!(Outputs::<2>::try_from(&blocks[..]).unwrap_err(), out_of_range);
+
+    bytes[..2].copy_from_slice(&(-1i16).to_le_bytes());
+    assert_eq!(Output::try_from(&bytes[..128]).unwrap_err(), ConversionError::OutOfRange(OutOfRange { index: 0, value: -1 }));
+    // only the elements of hash values are checked, and the unchecked conversion from an array keeps them as they are
+    assert!(Input::try_from(&[0xff; 256][..]).is_ok());
+    assert_eq!(Output::from([0xff; 128]).to_array(), [0xff; 128]);
+}
@@ -0,0 +1,47 @@
+//! The parallel conversions give identical results for any number of threads,
+//! as if run with `RAYON_NUM_THREADS` set to `1`, `2` and the number of CPUs
+#![cfg(feature = "parallel")]
+
+use libswifft::buffer::Inputs;
+use libswifft::error::InteropError;
+use libswifft::interop::{inputs_to_swifft, swifft_to_inputs};
+use rayon::ThreadPoolBuilder;
+use swifft::polynomial::Polynomial;
+
+const NUM_BLOCKS: usize = 100;
+
+/// Runs `f` on thread pools of `1`, `2`, `3` and at least `4` threads
+fn with_thread_counts<T: Send>(f: impl Fn() -> T + Sync) -> Vec<T> {
+    let max_threads = std::thread::available_parallelism().map_or(4, |threads| threads.get()).max(4);
+    [1, 2, 3, max_threads].into_iter()
+        .map(|num_threads| ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap().install(&f))
+        .collect()
+}
+
+#[test]
+fn conversions_are_independent_of_thread_count() {
+    let inputs = Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64);
+    let runs = with_thread_counts(|| {
+        let swifft_inputs = inputs_to_swifft(&inputs);
+        let mut round_trip = Box::<Inputs<NUM_BLOCKS>>::default();
+        swifft_to_inputs(&swifft_inputs, &mut round_trip).unwrap();
+        (swifft_inputs, round_trip.0)
+    });
+    for run in &runs {
+        assert_eq!(run, &runs[0]);
+        assert_eq!(run.1, inputs.0);
+    }
+}
+
+#[test]
+fn first_error_is_independent_of_thread_count() {
+    let mut swifft_inputs = inputs_to_swifft(&Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64));
+    // non-binary coefficients in many blocks, so that several threads find one
+    for input in swifft_inputs.iter_mut().skip(17).step_by(13) {
+        input[3] = Polynomial::from_coefficients(&[2; Polynomial::N]);
+    }
+    let runs = with_thread_counts(|| swifft_to_inputs(&swifft_inputs, &mut Box::<Inputs<NUM_BLOCKS>>::default()).unwrap_err());
+    for error in runs {
+        assert_eq!(error, InteropError::NonBinaryCoefficient { input: 17, polynomial: 3, coefficient: 0 });
+    }
+}
@@ -0,0 +1,54 @@
+//! Differential hashing against a baseline of previous hash values
+
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::{compute_diff, compute_diff_update, compute_multiple};
+
+const NUM_BLOCKS: usize = 150;
+
+fn baseline(inputs: &Inputs<NUM_BLOCKS>) -> Outputs<NUM_BLOCKS> {
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple(inputs, &mut outputs);
+    outputs
+}
+
+fn assert_diff(inputs: &Inputs<NUM_BLOCKS>, baseline: &Outputs<NUM_BLOCKS>, expected: &[usize]) {
+    let current = self::baseline(inputs);
+    let changed = compute_diff(inputs, baseline);
+    assert_eq!(changed.iter().map(|(i, _)| *i).collect::<Vec<_>>(), expected);
+    for (i, output) in &changed {
+        assert_eq!(output.0[0], current.0[*i]);
+    }
+
+    let mut updated = Outputs::<NUM_BLOCKS>::default();
+    updated.0.copy_from_slice(&baseline.0);
+    assert_eq!(compute_diff_update(inputs, &mut updated), expected);
+    assert_eq!(updated.0, current.0);
+}
+
+#[test]
+fn no_blocks_differ() {
+    let inputs = Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64);
+    assert_diff(&inputs, &baseline(&inputs), &[]);
+}
+
+#[test]
+fn some_blocks_differ() {
+    let mut inputs = Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64);
+    let baseline = baseline(&inputs);
+    for i in [0, 63, 64, 65, 149] {
+        inputs.0[i][17] ^= 0x01;
+    }
+    assert_diff(&inputs, &baseline, &[0, 63, 64, 65, 149]);
+    with_max_ffi_blocks(7, || assert_diff(&inputs, &baseline, &[0, 63, 64, 65, 149]));
+}
+
+#[test]
+fn all_blocks_differ() {
+    let inputs = Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64);
+    let mut baseline = baseline(&inputs);
+    for block in baseline.0.iter_mut() {
+        block[0] ^= 0x01;
+    }
+    assert_diff(&inputs, &baseline, &(0..NUM_BLOCKS).collect::<Vec<_>>());
+}
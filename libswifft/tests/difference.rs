@@ -0,0 +1,64 @@
+//! Single-pass differences of hash values for delta encoding
+
+use libswifft::arithmetic::{add, sub};
+use libswifft::buffer::{AlignedBuffer, Input, InputBuilder, Output};
+use libswifft::hash::{compute, compute_difference};
+use proptest::prelude::*;
+
+fn input(bytes: &[u8]) -> Input {
+    bytes.iter().enumerate()
+        .fold(InputBuilder::new(), |builder, (i, &byte)| builder.set_byte(i, byte))
+        .build()
+}
+
+fn hash(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+fn block() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 256)
+}
+
+proptest! {
+    #[test]
+    fn difference_matches_separate_hashing(x in block(), y in block()) {
+        let mut expected = hash(&input(&x));
+        sub(&mut expected, &hash(&input(&y)));
+        prop_assert_eq!(compute_difference(&input(&x), &input(&y)).0, expected.0);
+    }
+
+    #[test]
+    fn difference_of_identical_inputs_is_zero(x in block()) {
+        let difference = compute_difference(&input(&x), &input(&x));
+        prop_assert!(difference.is_difference_zero());
+        prop_assert_eq!(difference.0, Output::default().0);
+    }
+
+    #[test]
+    fn difference_is_antisymmetric(x in block(), y in block()) {
+        let mut sum = compute_difference(&input(&x), &input(&y));
+        add(&mut sum, &compute_difference(&input(&y), &input(&x)));
+        prop_assert!(sum.is_difference_zero());
+    }
+
+    #[test]
+    fn difference_of_distinct_inputs_is_nonzero(x in block(), bit in 0usize..2048) {
+        let mut y = x.clone();
+        y[bit / 8] ^= 1 << (bit % 8);
+        prop_assert!(!compute_difference(&input(&x), &input(&y)).is_difference_zero());
+    }
+}
+
+#[test]
+fn is_difference_zero_accepts_any_multiple_of_q() {
+    let mut bytes = [0u8; 128];
+    bytes[..2].copy_from_slice(&257i16.to_le_bytes());
+    bytes[2..4].copy_from_slice(&(-514i16).to_le_bytes());
+    assert!(Output::from_bytes_reducing(&bytes).is_difference_zero());
+    assert!(AlignedBuffer([bytes]).is_difference_zero());
+
+    bytes[4] = 1;
+    assert!(!AlignedBuffer([bytes]).is_difference_zero());
+}
@@ -0,0 +1,67 @@
+//! Digests of messages, their conversions, ordering and hex
+
+use std::collections::{BTreeSet, HashSet};
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Digest512, Input, Output};
+use libswifft::hash::{compact, compute, hash_bytes};
+
+#[test]
+fn compact_matches_the_low_level_compaction() {
+    let mut input = Input::default();
+    input.0[0] = std::array::from_fn(|i| (i * 7) as u8);
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+    assert_eq!(output.compact().as_bytes(), &compact_output.0[0]);
+    assert_eq!(output.compact(), Digest512::from(compact_output));
+}
+
+#[test]
+fn conversions_round_trip() {
+    let bytes: [u8; 64] = std::array::from_fn(|i| i as u8);
+    let digest = Digest512::from_bytes(bytes);
+    assert_eq!(Digest512::from(bytes), digest);
+    assert_eq!(<[u8; 64]>::from(digest.clone()), bytes);
+    assert_eq!(digest.as_compact_output().0[0], bytes);
+    let compact_output: CompactOutput = digest.clone().into();
+    assert_eq!(Digest512::from(compact_output), digest);
+}
+
+#[test]
+fn hex_is_lowercase_and_complete() {
+    let digest = hash_bytes(b"Hello, world!");
+    let expected = "b06fe99dd23bc7bed250ca33f8e7d50eeb92b0f91239753880d44a5efccf673d\
+                    669271a930a3deb87525a2764060f3f0bb9c6eff380a95b1a6dbea5ab9db163c";
+    assert_eq!(digest.to_hex(), expected);
+    assert_eq!(format!("{:x}", digest), expected);
+    assert_eq!(digest.write_hex(&mut [0; 128]), expected);
+    assert_eq!(format!("{:?}", digest), format!("Digest512({})", expected));
+}
+
+#[test]
+fn digests_are_ordered_by_their_bytes() {
+    let digest = |first: u8, last: u8| {
+        let mut bytes = [0; 64];
+        bytes[0] = first;
+        bytes[63] = last;
+        Digest512::from(bytes)
+    };
+    assert!(digest(0, 255) < digest(1, 0));
+    assert!(digest(1, 0) < digest(1, 1));
+    assert_eq!(digest(2, 2).cmp(&digest(2, 2)), std::cmp::Ordering::Equal);
+
+    let digests: Vec<Digest512> = (0..20u8).map(|i| hash_bytes(&[i % 10])).collect();
+    let ordered: BTreeSet<_> = digests.iter().cloned().collect();
+    let hashed: HashSet<_> = digests.iter().cloned().collect();
+    assert_eq!((ordered.len(), hashed.len()), (10, 10));
+    assert!(ordered.iter().zip(ordered.iter().skip(1)).all(|(a, b)| a.as_bytes() < b.as_bytes()));
+    assert!(hashed.contains(&hash_bytes(&[3])));
+}
+
+#[test]
+fn equality_is_by_value() {
+    assert_eq!(hash_bytes(b"message"), hash_bytes(b"message"));
+    assert_ne!(hash_bytes(b"message"), hash_bytes(b"massage"));
+    assert_eq!(Digest512::from(AlignedBuffer([[0; 64]])), Digest512::from([0; 64]));
+}
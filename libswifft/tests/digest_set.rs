@@ -0,0 +1,90 @@
+//! Sorted, deduplicated sets of compact hash values, checked against a `HashSet` model
+
+use std::collections::HashSet;
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput};
+use libswifft::digest_set::{compare, DigestSet};
+use proptest::prelude::*;
+
+/// Digests drawn from few distinct values, so that duplicates and overlaps between sets are common
+fn digest() -> impl Strategy<Value = CompactOutput> {
+    (0u8..4, 0usize..64, any::<u8>()).prop_map(|(fill, i, byte)| {
+        let mut digest = AlignedBuffer::new(fill);
+        digest.0[0][i] = byte % 4;
+        digest
+    })
+}
+
+fn digests() -> impl Strategy<Value = Vec<CompactOutput>> {
+    prop::collection::vec(digest(), 0..64)
+}
+
+fn model(digests: &[CompactOutput]) -> HashSet<[u8; 64]> {
+    digests.iter().map(|digest| digest.0[0]).collect()
+}
+
+/// The contents of `set`, checking on the way that they are strictly ascending
+fn contents<'a>(set: impl IntoIterator<Item = &'a CompactOutput>) -> Vec<[u8; 64]> {
+    let contents: Vec<[u8; 64]> = set.into_iter().map(|digest| digest.0[0]).collect();
+    assert!(contents.windows(2).all(|pair| pair[0] < pair[1]), "not strictly ascending");
+    contents
+}
+
+fn sorted(model: HashSet<[u8; 64]>) -> Vec<[u8; 64]> {
+    let mut sorted: Vec<[u8; 64]> = model.into_iter().collect();
+    sorted.sort();
+    sorted
+}
+
+proptest! {
+    #[test]
+    fn from_unsorted_collapses_duplicates(digests in digests()) {
+        let set = DigestSet::from_unsorted(digests.clone());
+        prop_assert_eq!(contents(&set), sorted(model(&digests)));
+        for digest in &digests {
+            prop_assert!(set.contains(digest));
+        }
+    }
+
+    #[test]
+    fn insert_matches_model(digests in digests(), absent in digest()) {
+        let mut set = DigestSet::new();
+        let mut model = HashSet::new();
+        for digest in &digests {
+            prop_assert_eq!(set.insert(digest.clone()), model.insert(digest.0[0]));
+        }
+        prop_assert_eq!(contents(&set), sorted(model.clone()));
+        prop_assert_eq!(set.contains(&absent), model.contains(&absent.0[0]));
+    }
+
+    #[test]
+    fn merge_is_union(a in digests(), b in digests()) {
+        let mut set = DigestSet::from_unsorted(a.clone());
+        set.merge(&DigestSet::from_unsorted(b.clone()));
+        prop_assert_eq!(contents(&set), sorted(&model(&a) | &model(&b)));
+    }
+
+    #[test]
+    fn set_operations_match_model(a in digests(), b in digests()) {
+        let (set_a, set_b) = (DigestSet::from_unsorted(a.clone()), DigestSet::from_unsorted(b.clone()));
+        prop_assert_eq!(contents(set_a.intersection(&set_b)), sorted(&model(&a) & &model(&b)));
+        prop_assert_eq!(contents(set_a.difference(&set_b)), sorted(&model(&a) - &model(&b)));
+        prop_assert_eq!(contents(set_b.difference(&set_a)), sorted(&model(&b) - &model(&a)));
+    }
+
+    #[test]
+    fn compare_is_lexicographic(a in digest(), b in digest()) {
+        prop_assert_eq!(compare(&a, &b), a.0[0].cmp(&b.0[0]));
+    }
+}
+
+#[test]
+fn empty_sets() {
+    let empty = DigestSet::new();
+    let set: DigestSet = [AlignedBuffer::new(1), AlignedBuffer::new(1)].into_iter().collect();
+    assert_eq!(set.len(), 1);
+    assert!(empty.is_empty() && !empty.contains(&AlignedBuffer::new(1)));
+    assert_eq!(set.intersection(&empty).count(), 0);
+    assert_eq!(set.difference(&empty).count(), 1);
+    assert_eq!(empty.difference(&set).count(), 0);
+}
@@ -0,0 +1,95 @@
+//! Distances between hash values and between compact hash values
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Output};
+use proptest::prelude::*;
+
+fn output(elements: &[(usize, u16)]) -> Output {
+    let mut output = Output::default();
+    for &(index, value) in elements {
+        output.0[0][2 * index..2 * index + 2].copy_from_slice(&value.to_le_bytes());
+    }
+    output
+}
+
+fn canonical_output() -> impl Strategy<Value = Output> {
+    prop::array::uniform32(0..257u16).prop_flat_map(|low| prop::array::uniform32(0..257u16).prop_map(move |high| {
+        let elements: Vec<_> = low.iter().chain(high.iter()).copied().enumerate().collect();
+        output(&elements)
+    }))
+}
+
+fn compact_output() -> impl Strategy<Value = CompactOutput> {
+    prop::collection::vec(any::<u8>(), 64).prop_map(|bytes| AlignedBuffer([bytes.try_into().unwrap()]))
+}
+
+#[test]
+fn elements_are_read_little_endian() {
+    let elements = output(&[(0, 1), (1, 256), (63, 0x0102)]).to_coefficients();
+    assert_eq!((elements[0], elements[1], elements[2], elements[63]), (1, 256, 0, 0x0102));
+    assert_eq!(output(&[(1, 256)]).to_centered()[1], -1);
+}
+
+#[test]
+fn small_output_examples() {
+    let zero = Output::default();
+    // 1 and 256 are 2 apart modulo 257, through 0
+    let a = output(&[(0, 1), (5, 100)]);
+    let b = output(&[(0, 256), (5, 110), (9, 128)]);
+    assert_eq!(a.hamming_distance(&b), 3);
+    assert_eq!(a.l2_distance_squared(&b), 2 * 2 + 10 * 10 + 128 * 128);
+    assert_eq!(a.linf_distance(&b), 128);
+    // 129 is -128 centered, the farthest from 0 in either direction
+    assert_eq!(output(&[(3, 129)]).linf_distance(&zero), 128);
+    assert_eq!(output(&[(3, 128)]).l2_distance_squared(&output(&[(3, 129)])), 1);
+    // a non-canonical element equal to 0 modulo 257 is no distance from 0
+    assert_eq!(output(&[(2, 257)]).hamming_distance(&zero), 0);
+}
+
+#[test]
+fn small_compact_output_examples() {
+    let mut a = CompactOutput::default();
+    let mut b = CompactOutput::default();
+    a.0[0][0] = 1;
+    b.0[0][0] = 255;
+    b.0[0][7] = 0x80;
+    b.0[0][8] = 0x03;
+    assert_eq!(a.byte_hamming_distance(&b), 3);
+    assert_eq!(a.hamming_distance(&b), 7 + 1 + 2);
+    assert_eq!(a.l2_distance_squared(&b), 2 * 2 + 128 * 128 + 3 * 3);
+    assert_eq!(a.linf_distance(&b), 128);
+    assert_eq!(CompactOutput::new(0xff).linf_distance(&CompactOutput::new(0)), 1);
+}
+
+proptest! {
+    #[test]
+    fn output_distances_are_metrics(a in canonical_output(), b in canonical_output()) {
+        prop_assert_eq!(a.hamming_distance(&a), 0);
+        prop_assert_eq!(a.l2_distance_squared(&a), 0);
+        prop_assert_eq!(a.linf_distance(&a), 0);
+        prop_assert_eq!(a.hamming_distance(&b), b.hamming_distance(&a));
+        prop_assert_eq!(a.l2_distance_squared(&b), b.l2_distance_squared(&a));
+        prop_assert_eq!(a.linf_distance(&b), b.linf_distance(&a));
+        prop_assert!(a.linf_distance(&b) <= 128);
+        prop_assert_eq!(a.hamming_distance(&b) == 0, a.0 == b.0);
+        prop_assert!(a.l2_distance_squared(&b) <= a.hamming_distance(&b) * a.linf_distance(&b).pow(2));
+    }
+
+    #[test]
+    fn compact_output_distances_are_metrics(a in compact_output(), b in compact_output()) {
+        prop_assert_eq!(a.byte_hamming_distance(&a), 0);
+        prop_assert_eq!(a.l2_distance_squared(&a), 0);
+        prop_assert_eq!(a.linf_distance(&a), 0);
+        prop_assert_eq!(a.byte_hamming_distance(&b), b.byte_hamming_distance(&a));
+        prop_assert_eq!(a.l2_distance_squared(&b), b.l2_distance_squared(&a));
+        prop_assert_eq!(a.linf_distance(&b), b.linf_distance(&a));
+        prop_assert!(a.byte_hamming_distance(&b) <= a.hamming_distance(&b));
+        prop_assert_eq!(a.byte_hamming_distance(&b) == 0, a.0 == b.0);
+    }
+    #[test]
+    fn compact_output_hamming_distance_counts_bits(a in compact_output(), b in compact_output(), c in compact_output()) {
+        prop_assert_eq!(a.hamming_distance(&a), 0);
+        prop_assert_eq!(a.hamming_distance(&b), b.hamming_distance(&a));
+        prop_assert!(a.hamming_distance(&b) <= 512);
+        prop_assert!(a.hamming_distance(&c) <= a.hamming_distance(&b) + b.hamming_distance(&c));
+    }
+}
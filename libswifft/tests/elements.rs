@@ -0,0 +1,92 @@
+//! Typed element access to batches of hash values
+
+use libswifft::arithmetic::add_multiple;
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::compute_multiple;
+use proptest::prelude::*;
+
+const NUM_BLOCKS: usize = 3;
+
+fn outputs(bytes: &[u8]) -> Outputs<NUM_BLOCKS> {
+    let mut inputs = Inputs::<NUM_BLOCKS>::default();
+    inputs.0.iter_mut().flatten().zip(bytes).for_each(|(byte, &value)| *byte = value);
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple(&inputs, &mut outputs);
+    outputs
+}
+
+fn input_bytes() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), NUM_BLOCKS * 256)
+}
+
+proptest! {
+    #[test]
+    fn to_element_matrix_decodes_little_endian(x in input_bytes()) {
+        let outputs = outputs(&x);
+        let matrix = outputs.to_element_matrix();
+        prop_assert_eq!(matrix.len(), NUM_BLOCKS);
+        for (row, block) in matrix.iter().zip(&outputs.0) {
+            for (&value, element) in row.iter().zip(block.chunks_exact(2)) {
+                prop_assert_eq!(value, u16::from_le_bytes([element[0], element[1]]));
+            }
+        }
+    }
+
+    #[test]
+    fn copy_from_element_matrix_round_trips(x in input_bytes()) {
+        let outputs = outputs(&x);
+        let mut copy = Outputs::<NUM_BLOCKS>::default();
+        copy.copy_from_element_matrix(&outputs.to_element_matrix().try_into().unwrap());
+        prop_assert_eq!(copy.0, outputs.0);
+    }
+
+    #[test]
+    fn element_additions_match_composition(x in input_bytes(), y in input_bytes()) {
+        let mut expected = outputs(&x);
+        add_multiple(&mut expected, &outputs(&y));
+
+        let mut sum = outputs(&x).to_element_matrix();
+        for (row, operand) in sum.iter_mut().zip(outputs(&y).to_element_matrix()) {
+            for (value, operand) in row.iter_mut().zip(operand) {
+                *value = (*value + operand) % 257;
+            }
+        }
+        prop_assert_eq!(sum, expected.to_element_matrix());
+    }
+}
+
+#[cfg(target_endian = "little")]
+mod in_place {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn element_matrix_views_the_bytes(x in input_bytes()) {
+            let outputs = outputs(&x);
+            prop_assert_eq!(outputs.as_element_matrix().to_vec(), outputs.to_element_matrix());
+        }
+
+        #[test]
+        fn mutations_through_the_view_are_observed(x in input_bytes(), y in input_bytes()) {
+            let mut expected = outputs(&x);
+            add_multiple(&mut expected, &outputs(&y));
+
+            let mut sum = outputs(&x);
+            let operand = outputs(&y);
+            for (row, operand) in sum.as_element_matrix_mut().iter_mut().zip(operand.as_element_matrix()) {
+                for (value, operand) in row.iter_mut().zip(operand) {
+                    *value = (*value + operand) % 257;
+                }
+            }
+            prop_assert_eq!(sum.0, expected.0);
+        }
+    }
+
+    #[test]
+    fn element_writes_land_in_the_bytes() {
+        let mut outputs = Outputs::<NUM_BLOCKS>::default();
+        outputs.as_element_matrix_mut()[1][2] = 0x0102;
+        assert_eq!(outputs.0[1][4..6], [0x02, 0x01]);
+        assert!(outputs.0.iter().flatten().filter(|&&byte| byte != 0).count() == 2);
+    }
+}
@@ -0,0 +1,70 @@
+//! Comparing, ordering and hashing buffers by their bytes
+
+use std::collections::{BTreeSet, HashSet};
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Input, Inputs, Output};
+use libswifft::hash::{compact, compact_multiple, compute, compute_multiple};
+
+#[test]
+fn equal_contents_of_different_provenance_are_equal() {
+    let mut inputs = Inputs::<3>::default();
+    inputs.0[1] = [0x5a; 256];
+    inputs.0[2] = [0x5a; 256];
+    let mut outputs = Default::default();
+    compute_multiple(&inputs, &mut outputs);
+    let mut compact_outputs = CompactOutputs::<3>::default();
+    compact_multiple(&outputs, &mut compact_outputs);
+
+    // a single block hashed on its own equals the same block hashed in a batch
+    let mut output = Output::default();
+    compute(&Input::new(0x5a), &mut output);
+    assert_eq!(output, AlignedBuffer([outputs.0[1]]));
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+    assert_eq!(compact_output, AlignedBuffer([compact_outputs.0[2]]));
+    assert_ne!(compact_output, AlignedBuffer([compact_outputs.0[0]]));
+    assert_eq!(Input::new(0x5a), AlignedBuffer([[0x5a; 256]]));
+}
+
+#[test]
+fn hash_sets_deduplicate() {
+    let digests: Vec<CompactOutput> = (0..30u8)
+        .map(|i| {
+            let mut output = Output::default();
+            compute(&Input::new(i % 7), &mut output);
+            let mut compact_output = CompactOutput::default();
+            compact(&output, &mut compact_output);
+            compact_output
+        })
+        .collect();
+    let unique: HashSet<_> = digests.iter().cloned().collect();
+    assert_eq!(unique.len(), 7);
+    assert!(digests.iter().all(|digest| unique.contains(digest)));
+}
+
+#[test]
+fn ordering_is_lexicographic_over_all_bytes() {
+    let buffer = |first: u8, last: u8| {
+        let mut buffer = CompactOutputs::<2>::default();
+        buffer.0[0][0] = first;
+        buffer.0[1][63] = last;
+        buffer
+    };
+    assert!(buffer(0, 255) < buffer(1, 0));
+    assert!(buffer(1, 0) < buffer(1, 1));
+    assert_eq!(buffer(1, 1).cmp(&buffer(1, 1)), std::cmp::Ordering::Equal);
+
+    let mut sorted: Vec<_> = (0..=255u8).rev().map(CompactOutput::new).collect();
+    sorted.sort();
+    assert!(sorted.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    let ordered: BTreeSet<_> = sorted.iter().chain(&sorted).cloned().collect();
+    assert_eq!(ordered.len(), 256);
+}
+
+#[test]
+fn equality_is_by_bytes_not_modulo_257() {
+    let mut zero = Output::default();
+    zero.0[0][..2].copy_from_slice(&257u16.to_le_bytes());
+    assert!(zero.is_difference_zero());
+    assert_ne!(zero, Output::default());
+}
@@ -0,0 +1,69 @@
+//! Runs every example against the fixture data under `tests/fixtures/examples`, so that the examples cannot rot.
+//!
+//! Each example must succeed and print exactly its `<example>.stdout` fixture;
+//! if a change to an example's output is deliberate, update the fixture to match.
+
+use std::process::Command;
+
+const FIXTURES: &str = "tests/fixtures/examples";
+
+fn run_example(name: &str, args: &[&str]) -> String {
+    let example = escargot::CargoBuild::new()
+        .manifest_path(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"))
+        .example(name)
+        .current_release()
+        .run()
+        .unwrap_or_else(|err| panic!("building example {} failed: {}", name, err));
+    let output = Command::new(example.path())
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(args)
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(output.status.success(), "example {} failed: {}{}",
+        name, stdout, String::from_utf8_lossy(&output.stderr));
+    stdout
+}
+
+fn expected_stdout(name: &str) -> String {
+    let path = format!("{}/{}/{}.stdout", env!("CARGO_MANIFEST_DIR"), FIXTURES, name);
+    std::fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path, err))
+}
+
+fn lines() -> String {
+    format!("{}/lines.txt", FIXTURES)
+}
+
+#[test]
+fn hash_file() {
+    assert_eq!(run_example("hash_file", &[&lines()]), expected_stdout("hash_file"));
+}
+
+#[test]
+fn hash_file_agrees_with_hash_bytes() {
+    let contents = std::fs::read(format!("{}/{}", env!("CARGO_MANIFEST_DIR"), lines())).unwrap();
+    let digest = libswifft::hash::hash_bytes(&contents);
+    assert_eq!(run_example("hash_file", &[&lines()]), format!("{:x}  {}\n", digest, lines()));
+}
+
+#[test]
+fn merkle() {
+    assert_eq!(run_example("merkle", &[&lines(), "4"]), expected_stdout("merkle"));
+}
+
+#[test]
+fn merkle_proves_every_line() {
+    for index in ["0", "9", "10"] {
+        assert!(run_example("merkle", &[&lines(), index]).ends_with("proof verified\ntampered proofs rejected\n"));
+    }
+}
+
+#[test]
+fn accumulator() {
+    assert_eq!(run_example("accumulator", &[&lines()]), expected_stdout("accumulator"));
+}
+
+#[test]
+fn cross_check() {
+    assert_eq!(run_example("cross_check", &[&lines()]), expected_stdout("cross_check"));
+}
@@ -0,0 +1,53 @@
+//! The SWIFFT functions returning their results by value, against their out-parameter counterparts
+
+use libswifft::buffer::{CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs, SignInput};
+use libswifft::hash::{compact, compact_multiple, compute, compute_multiple, compute_signed};
+use libswifft::Swifft;
+
+fn is_aligned<T>(value: &T) -> bool {
+    (value as *const T as usize) % 64 == 0
+}
+
+#[test]
+fn single_blocks_match_out_parameters() {
+    for seed in [0, 1, 0x5a, 0xff] {
+        let input = Input::new(seed);
+        let mut output = Output::default();
+        compute(&input, &mut output);
+        assert_eq!(Swifft::compute(&input).0, output.0);
+
+        let sign_input = SignInput::new(seed.rotate_left(3));
+        let mut signed = Output::default();
+        compute_signed(&input, &sign_input, &mut signed);
+        assert_eq!(Swifft::compute_signed(&input, &sign_input).0, signed.0);
+
+        let mut compact_output = CompactOutput::default();
+        compact(&output, &mut compact_output);
+        assert_eq!(Swifft::compact(&Swifft::compute(&input)).0, compact_output.0);
+    }
+}
+
+#[test]
+fn multiple_blocks_match_out_parameters() {
+    let inputs = Inputs::<5>::from_seed(5);
+    let mut outputs = Outputs::<5>::default();
+    compute_multiple(&inputs, &mut outputs);
+    assert_eq!(Swifft::compute_multiple(&inputs).0, outputs.0);
+
+    let mut compact_outputs = CompactOutputs::<5>::default();
+    compact_multiple(&outputs, &mut compact_outputs);
+    assert_eq!(Swifft::compact_multiple(&Swifft::compute_multiple(&inputs)).0, compact_outputs.0);
+}
+
+#[test]
+fn returned_values_stay_aligned() {
+    let output = Swifft::compute(&Input::new(1));
+    assert!(is_aligned(&output));
+    // moved into a heap allocation of the vector
+    let moved: Vec<Output> = [Swifft::compute(&Input::new(2)), output].into_iter().collect();
+    assert!(moved.iter().all(is_aligned));
+    let boxed = Box::new(Swifft::compute_multiple(&Inputs::<5>::from_seed(5)));
+    assert!(is_aligned(&*boxed));
+    let compact_output = Swifft::compact(&moved[1]);
+    assert!(is_aligned(&compact_output));
+}
@@ -0,0 +1,58 @@
+//! Filling buffers from slices, blocks and bytes, checking their lengths
+
+use libswifft::buffer::{CompactOutputs, Input, Inputs, Output};
+use libswifft::error::{BlockIndexOutOfRange, LenMismatch, TooLong};
+use libswifft::hash::compute;
+
+fn bytes(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i * 13 + 1) as u8).collect()
+}
+
+#[test]
+fn copying_checks_the_exact_length() {
+    let mut inputs = Inputs::<2>::default();
+    assert_eq!(inputs.copy_from_slice(&bytes(512)), Ok(()));
+    assert_eq!(inputs.as_bytes(), &bytes(512)[..]);
+
+    let before = inputs.clone();
+    assert_eq!(inputs.copy_from_slice(&bytes(511)), Err(LenMismatch { expected: 512, actual: 511 }));
+    assert_eq!(inputs.copy_from_slice(&bytes(513)), Err(LenMismatch { expected: 512, actual: 513 }));
+    assert_eq!(inputs, before);
+}
+
+#[test]
+fn writing_blocks_checks_the_index() {
+    let mut compact_outputs = CompactOutputs::<3>::default();
+    assert_eq!(compact_outputs.write_block(2, &[7; 64]), Ok(()));
+    assert_eq!((compact_outputs.0[1], compact_outputs.0[2]), ([0; 64], [7; 64]));
+    assert_eq!(compact_outputs.write_block(3, &[9; 64]), Err(BlockIndexOutOfRange { index: 3, num_blocks: 3 }));
+    assert!(compact_outputs.as_bytes().iter().all(|&byte| byte != 9));
+}
+
+#[test]
+fn filling_overwrites_every_byte() {
+    let mut output = Output::from_coefficients([3; 64]);
+    output.fill(0);
+    assert_eq!(output, Output::default());
+    let mut inputs = Inputs::<3>::default();
+    inputs.fill(0x5a);
+    assert_eq!(inputs, Inputs::new(0x5a));
+}
+
+#[test]
+fn padding_fills_short_slices_with_zeros() {
+    let message = b"the last block of a message";
+    let input = Input::from_slice_padded(message).unwrap();
+    assert_eq!(&input.0[0][..message.len()], message);
+    assert!(input.0[0][message.len()..].iter().all(|&byte| byte == 0));
+    assert_eq!(Input::from_slice_padded(&[]).unwrap(), Input::default());
+    assert_eq!(Input::from_slice_padded(&bytes(256)).unwrap(), Input::try_from(&bytes(256)[..]).unwrap());
+    assert_eq!(Inputs::<2>::from_slice_padded(&bytes(300)).unwrap().0[1][..44], bytes(300)[256..]);
+    assert_eq!(Input::from_slice_padded(&bytes(257)), Err(TooLong { max: 256, actual: 257 }));
+
+    let mut expected = Output::default();
+    compute(&Input::try_from(&[&message[..], &[0; 256 - 27]].concat()[..]).unwrap(), &mut expected);
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    assert_eq!(output, expected);
+}
@@ -0,0 +1,78 @@
+//! Frozen fingerprints of compact hash values, and the shards they assign
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Input, Output};
+use libswifft::hash::{compact, compute};
+
+fn compact_digest(byte: u8) -> CompactOutput {
+    let mut output = Output::default();
+    compute(&Input::new(byte), &mut output);
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+    compact_output
+}
+
+/// Pseudorandom compact hash values, from the `splitmix64` generator
+fn corpus(len: usize) -> impl Iterator<Item = CompactOutput> {
+    let mut state = 0x5157_4946_4654u64;
+    let mut next = move || {
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    };
+    (0..len).map(move |_| {
+        let mut digest = CompactOutput::default();
+        for word in digest.0[0].chunks_exact_mut(8) {
+            word.copy_from_slice(&next().to_le_bytes());
+        }
+        digest
+    })
+}
+
+#[test]
+fn fingerprints_are_frozen() {
+    let digest = AlignedBuffer([std::array::from_fn(|i| (i * 37 + 11) as u8)]);
+    assert_eq!(digest.fingerprint128(), 0x40c0800080c04040008000c0c0404000);
+    assert_eq!(digest.fingerprint64(), 0x404080c040800040);
+
+    assert_eq!(compact_digest(0x00).fingerprint128(), 0);
+    assert_eq!(compact_digest(0x5a).fingerprint128(), 0x72b2c5cf7e689f6007002ea661db12c0);
+    assert_eq!(compact_digest(0x5a).fingerprint64(), 0x75b2eb691fb38da0);
+    assert_eq!(compact_digest(0xff).fingerprint128(), 0x066ab52823affdcdd60831ccba88d49f);
+    assert_eq!(compact_digest(0xff).fingerprint64(), 0xd06284e499272952);
+    assert_eq!([7, 1000].map(|n| compact_digest(0xff).shard(n)), [5, 836]);
+}
+
+#[test]
+fn shards_are_in_range() {
+    for num_shards in [1, 2, 3, 7, 10, 1000, 1 << 31, (1 << 31) + 1, u32::MAX] {
+        for digest in corpus(1000).chain([compact_digest(0x00), AlignedBuffer([[0xff; 64]])]) {
+            let shard = digest.shard(num_shards);
+            assert!(shard < num_shards, "shard {shard} of {num_shards}");
+        }
+    }
+    assert!(corpus(1000).all(|digest| digest.shard(1) == 0));
+}
+
+#[test]
+fn shards_are_uniform() {
+    const LEN: usize = 100_000;
+    for num_shards in [7, 16, 1000] {
+        let mut counts = vec![0usize; num_shards as usize];
+        for digest in corpus(LEN) {
+            counts[digest.shard(num_shards) as usize] += 1;
+        }
+        // chi-squared statistic, with mean `num_shards - 1` and standard deviation about `sqrt(2 * num_shards)`
+        let expected = LEN as f64 / num_shards as f64;
+        let chi_squared: f64 = counts.iter().map(|&count| (count as f64 - expected).powi(2) / expected).sum();
+        let bound = num_shards as f64 + 6.0 * (2.0 * num_shards as f64).sqrt();
+        assert!(chi_squared < bound, "chi-squared {chi_squared} exceeds {bound} for {num_shards} shards");
+    }
+}
+
+#[test]
+#[should_panic = "cannot assign to zero shards"]
+fn zero_shards_panics() {
+    compact_digest(0x5a).shard(0);
+}
@@ -0,0 +1,90 @@
+//! One-shot hashing of messages of any length
+
+mod common;
+
+use libswifft::buffer::{CompactOutput, Digest512, Input, Output};
+use libswifft::hash::{compact, compute, hash_bytes, MESSAGE_BYTES_PER_BLOCK};
+
+use common::message;
+
+fn compact_hash(block: &[u8; 256]) -> CompactOutput {
+    let mut output = Output::default();
+    let mut input = Input::default();
+    input.0[0] = *block;
+    compute(&input, &mut output);
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+    compact_output
+}
+
+/// The tree of `hash_bytes` as documented, recursively over the compact hash values of the leaves
+fn root(leaves: &[CompactOutput]) -> CompactOutput {
+    if leaves.len() == 1 {
+        return leaves[0].clone()
+    }
+    // the largest power of two below the number of leaves
+    let k = 1 << (leaves.len() - 1).ilog2();
+    let mut block = [0; 256];
+    block[..64].copy_from_slice(&root(&leaves[..k]).0[0]);
+    block[64..128].copy_from_slice(&root(&leaves[k..]).0[0]);
+    block[255] = 1;
+    compact_hash(&block)
+}
+
+fn reference(data: &[u8]) -> Digest512 {
+    let mut leaves: Vec<CompactOutput> = data.chunks(MESSAGE_BYTES_PER_BLOCK)
+        .map(|chunk| {
+            let mut block = [0; 256];
+            block[..chunk.len()].copy_from_slice(chunk);
+            compact_hash(&block)
+        })
+        .collect();
+    if leaves.is_empty() {
+        leaves.push(compact_hash(&[0; 256]));
+    }
+    let mut block = [0; 256];
+    block[..64].copy_from_slice(&root(&leaves).0[0]);
+    block[64..72].copy_from_slice(&(data.len() as u64).to_le_bytes());
+    block[255] = 2;
+    compact_hash(&block).into()
+}
+
+#[test]
+fn matches_the_documented_construction() {
+    for len in [0, 1, 254, 255, 256, 510, 765, 766, 1020, 255 * 64, 255 * 64 + 1, 255 * 129 + 7] {
+        assert_eq!(hash_bytes(&message(len)), reference(&message(len)), "{} bytes", len);
+    }
+}
+
+#[test]
+fn construction_is_frozen() {
+    let mut hex = [0; 128];
+    assert_eq!(hash_bytes(b"Hello, world!").write_hex(&mut hex),
+        "b06fe99dd23bc7bed250ca33f8e7d50eeb92b0f91239753880d44a5efccf673d\
+         669271a930a3deb87525a2764060f3f0bb9c6eff380a95b1a6dbea5ab9db163c");
+}
+
+#[test]
+fn lengths_around_block_boundaries_are_distinct() {
+    let mut digests: Vec<Vec<u8>> = Vec::new();
+    for len in [0, 1, 2, 254, 255, 256, 509, 510, 511] {
+        let digest = hash_bytes(&vec![0; len]).as_bytes().to_vec();
+        assert!(!digests.contains(&digest), "{} zero bytes collide", len);
+        digests.push(digest);
+    }
+}
+
+#[test]
+fn chunks_are_bound_to_their_positions() {
+    let a = message(2 * MESSAGE_BYTES_PER_BLOCK);
+    let mut swapped = a[MESSAGE_BYTES_PER_BLOCK..].to_vec();
+    swapped.extend_from_slice(&a[..MESSAGE_BYTES_PER_BLOCK]);
+    assert_ne!(hash_bytes(&a), hash_bytes(&swapped));
+
+    // moving a bit to the same offset of another chunk would leave a sum of the hash values of the blocks unchanged
+    let mut first = vec![0; 2 * MESSAGE_BYTES_PER_BLOCK];
+    first[0] = 1;
+    let mut second = vec![0; 2 * MESSAGE_BYTES_PER_BLOCK];
+    second[MESSAGE_BYTES_PER_BLOCK] = 1;
+    assert_ne!(hash_bytes(&first), hash_bytes(&second));
+}
@@ -0,0 +1,51 @@
+//! Hashing large messages on the `rayon` thread pool gives the digest of the serial and streaming hashers:
+//! `cargo test -p libswifft --test hash_bytes_parallel`
+#![cfg(feature = "parallel")]
+
+mod common;
+
+use libswifft::buffer::Digest512;
+use libswifft::hash::{hash_bytes, hash_bytes_parallel, Hasher, MESSAGE_BYTES_PER_BLOCK};
+use rayon::ThreadPoolBuilder;
+
+use common::message;
+
+/// One batch of leaves, as the serial hashers hash them at a time
+const BATCH: usize = 64 * MESSAGE_BYTES_PER_BLOCK;
+
+fn streamed(data: &[u8]) -> Digest512 {
+    let mut hasher = Hasher::new();
+    for piece in data.chunks(1000) {
+        hasher.update(piece);
+    }
+    hasher.finalize().compact()
+}
+
+#[test]
+fn parallel_matches_serial_for_awkward_lengths() {
+    let lengths = [
+        0, 1, MESSAGE_BYTES_PER_BLOCK - 1, MESSAGE_BYTES_PER_BLOCK, MESSAGE_BYTES_PER_BLOCK + 1,
+        BATCH - 1, BATCH, BATCH + 1, 3 * BATCH + 17, 255 * 255, 1 << 20, (1 << 20) + 12345,
+    ];
+    for num_threads in [1, 3, 8] {
+        let pool = ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap();
+        for len in lengths {
+            let data = message(len);
+            let digest = pool.install(|| hash_bytes_parallel(&data));
+            assert_eq!(digest, hash_bytes(&data), "{} bytes on {} threads", len, num_threads);
+            assert_eq!(digest, streamed(&data), "{} bytes on {} threads", len, num_threads);
+        }
+    }
+}
+
+#[test]
+fn parallel_binds_every_byte() {
+    let data = message(5 * BATCH + 3);
+    let digest = hash_bytes_parallel(&data);
+    for position in [0, BATCH - 1, BATCH, 4 * BATCH + 200, data.len() - 1] {
+        let mut tampered = data.clone();
+        tampered[position] ^= 1;
+        assert_ne!(hash_bytes_parallel(&tampered), digest, "byte {}", position);
+    }
+    assert_ne!(hash_bytes_parallel(&data[..data.len() - 1]), digest);
+}
@@ -0,0 +1,67 @@
+//! Salts and domains that separate the hash values of messages
+
+mod common;
+
+use libswifft::hash::{hash_bytes, hash_bytes_with, hash_reader, hash_reader_with, HashOptions, Hasher};
+
+use common::message;
+
+#[test]
+fn no_options_are_byte_identical_to_hash_bytes() {
+    for len in [0, 1, 255, 10_000] {
+        let data = message(len);
+        assert_eq!(hash_bytes_with(&data, &HashOptions::new()), hash_bytes(&data));
+        assert_eq!(hash_reader_with(data.as_slice(), &HashOptions::new()).unwrap(), hash_reader(data.as_slice()).unwrap());
+    }
+}
+
+#[test]
+fn salts_and_domains_separate_hash_values() {
+    let data = message(1000);
+    let long_domain = "d".repeat(1000);
+    let options = [
+        HashOptions::new(),
+        HashOptions::new().with_salt([0; 32]),
+        HashOptions::new().with_salt([1; 32]),
+        HashOptions::new().with_domain(""),
+        HashOptions::new().with_domain("a"),
+        HashOptions::new().with_domain("b"),
+        HashOptions::new().with_domain("ab"),
+        HashOptions::new().with_domain(&long_domain),
+        HashOptions::new().with_salt([0; 32]).with_domain(""),
+        HashOptions::new().with_salt([1; 32]).with_domain("a"),
+    ];
+    let digests: Vec<_> = options.iter().map(|options| hash_bytes_with(&data, options)).collect();
+    for i in 0..digests.len() {
+        for j in 0..i {
+            assert_ne!(digests[i], digests[j], "{:?} and {:?}", options[i], options[j]);
+        }
+    }
+}
+
+#[test]
+fn prefixes_are_not_messages() {
+    // the prefix of a domain does not hash like the same bytes at the start of the message
+    let options = HashOptions::new().with_domain("a");
+    let mut prefixed = vec![2];
+    prefixed.extend_from_slice(&1u64.to_le_bytes());
+    prefixed.push(b'a');
+    prefixed.resize(255, 0);
+    prefixed.extend_from_slice(b"message");
+    assert_ne!(hash_bytes_with(b"message", &options), hash_bytes(&prefixed));
+}
+
+#[test]
+fn all_entry_points_agree() {
+    let options = HashOptions::new().with_salt([7; 32]).with_domain("entry points");
+    for len in [0, 300, 70_000] {
+        let data = message(len);
+        let expected = hash_bytes_with(&data, &options);
+        let mut hasher = Hasher::with_options(&options);
+        for chunk in data.chunks(1000) {
+            hasher.update(chunk);
+        }
+        assert_eq!(hasher.finalize().compact(), expected);
+        assert_eq!(hash_reader_with(data.as_slice(), &options).unwrap(), expected);
+    }
+}
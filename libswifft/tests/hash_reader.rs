@@ -0,0 +1,69 @@
+//! Hashing messages read from `std::io::Read` sources
+
+use std::io::{Cursor, Error, ErrorKind, Read};
+
+use libswifft::buffer::fill_from_seed;
+use libswifft::hash::{hash_bytes, hash_reader};
+
+fn message(len: usize) -> Vec<u8> {
+    let mut message = vec![0; len];
+    fill_from_seed(len as u64, &mut message);
+    message
+}
+
+/// A reader that returns at most `max` bytes per read, interrupting every other read
+struct Trickle<'a> {
+    data: &'a [u8],
+    max: usize,
+    interrupt: bool,
+}
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt {
+            return Err(Error::from(ErrorKind::Interrupted))
+        }
+        let len = buf.len().min(self.max);
+        self.data.read(&mut buf[..len])
+    }
+}
+
+/// A reader that fails after `len` bytes
+struct Failing(usize);
+
+impl Read for Failing {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.0 == 0 {
+            return Err(Error::new(ErrorKind::BrokenPipe, "gone"))
+        }
+        let len = buf.len().min(self.0);
+        buf[..len].fill(1);
+        self.0 -= len;
+        Ok(len)
+    }
+}
+
+#[test]
+fn matches_hash_bytes() {
+    for len in [0, 1, 255, 256 * 17 + 3, 64 * 255, 64 * 255 + 1, 1 << 20] {
+        let data = message(len);
+        assert_eq!(hash_reader(Cursor::new(&data)).unwrap(), hash_bytes(&data), "{} bytes", len);
+    }
+}
+
+#[test]
+fn short_and_interrupted_reads_are_continued() {
+    let data = message(256 * 17 + 3);
+    for max in [1, 100, 255, 4096] {
+        let reader = Trickle { data: &data, max, interrupt: false };
+        assert_eq!(hash_reader(reader).unwrap(), hash_bytes(&data), "reads of at most {} bytes", max);
+    }
+}
+
+#[test]
+fn errors_are_propagated() {
+    for len in [0, 10, 100_000] {
+        assert_eq!(hash_reader(Failing(len)).unwrap_err().kind(), ErrorKind::BrokenPipe);
+    }
+}
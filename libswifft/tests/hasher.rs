@@ -0,0 +1,156 @@
+//! Streaming hashing of messages that arrive in pieces
+
+mod common;
+
+use std::io::Write;
+
+use libswifft::backend::NativeBackend;
+use libswifft::buffer::Digest512;
+use libswifft::hash::{hash_bytes, hash_reader_with_progress, BlocksConsumed, Hasher, MESSAGE_BYTES_PER_BLOCK};
+use proptest::prelude::*;
+
+use common::message;
+
+fn finalize_compact(hasher: Hasher) -> Digest512 {
+    hasher.finalize().compact()
+}
+
+fn streamed(data: &[u8], piece: usize) -> Digest512 {
+    let mut hasher = Hasher::new();
+    for chunk in data.chunks(piece) {
+        hasher.update(chunk);
+    }
+    finalize_compact(hasher)
+}
+
+#[test]
+fn pieces_of_any_size_match_one_shot() {
+    // lengths around the leaf blocks, and around the batches of 64 leaf blocks that are buffered
+    for len in [0, 1, 254, 255, 256, 64 * 255 - 1, 64 * 255, 64 * 255 + 1, 2 * 64 * 255, 3 * 64 * 255 + 77] {
+        let data = message(len);
+        let expected = hash_bytes(&data);
+        for piece in [1, 2, 3, 254, 255, 256, 1000, 64 * 255, 1 << 20] {
+            assert_eq!(streamed(&data, piece), expected, "{} bytes in pieces of {}", len, piece);
+        }
+    }
+}
+
+#[test]
+fn megabytes_at_once_match_one_shot() {
+    let data = message(3 << 20);
+    let mut hasher = Hasher::new();
+    hasher.update(&data);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+}
+
+#[test]
+fn empty_updates_change_nothing() {
+    let mut hasher = Hasher::new();
+    hasher.update(&[]);
+    assert_eq!(finalize_compact(hasher.clone()), hash_bytes(&[]));
+    hasher.update(b"abc");
+    hasher.update(&[]);
+    assert_eq!(finalize_compact(hasher), hash_bytes(b"abc"));
+}
+
+#[test]
+fn trailing_zeros_are_not_padding() {
+    let data = message(MESSAGE_BYTES_PER_BLOCK - 3);
+    let mut padded = data.clone();
+    padded.extend_from_slice(&[0; 3]);
+    assert_ne!(streamed(&data, 10), streamed(&padded, 10));
+}
+
+#[test]
+fn writing_matches_one_shot() {
+    let data = message(10 << 20);
+    let mut hasher = Hasher::new();
+    for chunk in data.chunks(8 << 10) {
+        hasher.write_all(chunk).unwrap();
+    }
+    hasher.flush().unwrap();
+    assert_eq!(hasher.bytes_written(), data.len() as u64);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+}
+
+#[test]
+fn copying_from_a_reader_matches_one_shot() {
+    let data = message(100_000);
+    let mut hasher = Hasher::new();
+    assert_eq!(std::io::copy(&mut data.as_slice(), &mut hasher).unwrap(), data.len() as u64);
+    assert_eq!(hasher.write(&data[..10]).unwrap(), 10);
+    assert_eq!(hasher.bytes_written(), data.len() as u64 + 10);
+
+    let mut expected = data.clone();
+    expected.extend_from_slice(&data[..10]);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&expected));
+}
+
+#[test]
+fn updates_ending_on_a_block_boundary_complete_their_blocks() {
+    let data = message(3 * MESSAGE_BYTES_PER_BLOCK);
+    let mut hasher = Hasher::new();
+    assert_eq!(hasher.remaining_in_block(), MESSAGE_BYTES_PER_BLOCK);
+    assert_eq!(hasher.update_counted(&data[..MESSAGE_BYTES_PER_BLOCK]), BlocksConsumed { blocks: 1, remainder: 0 });
+    assert_eq!(hasher.update_counted(&data[MESSAGE_BYTES_PER_BLOCK..]), BlocksConsumed { blocks: 2, remainder: 0 });
+    assert_eq!(hasher.position(), data.len() as u64);
+    assert_eq!(hasher.remaining_in_block(), MESSAGE_BYTES_PER_BLOCK);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+}
+
+#[test]
+fn updates_ending_mid_block_buffer_the_remainder() {
+    let data = message(MESSAGE_BYTES_PER_BLOCK + 10);
+    let mut hasher = Hasher::new();
+    assert_eq!(hasher.update_counted(&data[..7]), BlocksConsumed { blocks: 0, remainder: 7 });
+    assert_eq!(hasher.remaining_in_block(), MESSAGE_BYTES_PER_BLOCK - 7);
+    // completing the partial block counts it, and the bytes past it start the next one
+    assert_eq!(hasher.update_counted(&data[7..]), BlocksConsumed { blocks: 1, remainder: 10 });
+    assert_eq!(hasher.update_counted(&[]), BlocksConsumed { blocks: 0, remainder: 10 });
+    assert_eq!(hasher.position(), data.len() as u64);
+    assert_eq!(hasher.remaining_in_block(), MESSAGE_BYTES_PER_BLOCK - 10);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+}
+
+#[test]
+fn one_update_can_complete_many_blocks() {
+    // past a batch of 64 leaf blocks, so that some are hashed during the update
+    let data = message(150 * MESSAGE_BYTES_PER_BLOCK + 42);
+    let mut hasher = Hasher::new();
+    assert_eq!(hasher.update_counted(&data[..3]), BlocksConsumed { blocks: 0, remainder: 3 });
+    assert_eq!(hasher.update_counted(&data[3..]), BlocksConsumed { blocks: 150, remainder: 42 });
+    assert_eq!(hasher.position(), hasher.bytes_written());
+    assert_eq!(hasher.position(), data.len() as u64);
+    assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+}
+
+#[test]
+fn reading_with_progress_reports_every_read() {
+    let data = message(100_000);
+    let (mut blocks, mut position) = (0, 0);
+    let digest = hash_reader_with_progress(data.as_slice(), &NativeBackend, |hasher, consumed| {
+        assert!(hasher.position() > position);
+        position = hasher.position();
+        blocks += consumed.blocks;
+        assert_eq!(consumed.remainder, MESSAGE_BYTES_PER_BLOCK - hasher.remaining_in_block());
+    }).unwrap();
+    assert_eq!(digest, hash_bytes(&data));
+    assert_eq!(position, data.len() as u64);
+    assert_eq!(blocks, (data.len() / MESSAGE_BYTES_PER_BLOCK) as u64);
+}
+
+proptest! {
+    #[test]
+    fn arbitrary_splits_match_one_shot(data in prop::collection::vec(any::<u8>(), 0..2000),
+                                       splits in prop::collection::vec(any::<prop::sample::Index>(), 0..8)) {
+        let mut splits: Vec<usize> = splits.iter().map(|split| split.index(data.len() + 1)).collect();
+        splits.sort();
+        let mut hasher = Hasher::new();
+        let mut start = 0;
+        for split in splits.into_iter().chain([data.len()]) {
+            hasher.update(&data[start..split]);
+            start = split;
+        }
+        prop_assert_eq!(finalize_compact(hasher), hash_bytes(&data));
+    }
+}
@@ -0,0 +1,91 @@
+//! Heap-backed batches of blocks, hashed through the slice-based APIs
+
+use libswifft::arithmetic::{add_multiple, add_slice};
+use libswifft::buffer::heap::{CompactOutputVec, InputVec, OutputVec};
+use libswifft::buffer::{AlignedBuffer, Input, Inputs, Output, Outputs};
+use libswifft::constant::BUFFER_ALIGNMENT;
+use libswifft::hash::{compact_slice, compute, compute_multiple, compute_slice};
+
+const NUM_BLOCKS: usize = 10_000;
+
+fn block(i: usize) -> [u8; 256] {
+    Input::from_seed(i as u64).to_array()
+}
+
+fn is_aligned<T>(blocks: &[T]) -> bool {
+    blocks.as_ptr() as usize % BUFFER_ALIGNMENT == 0
+}
+
+#[test]
+fn ten_thousand_blocks_hash_as_single_blocks() {
+    let mut inputs = InputVec::new();
+    for i in 0..NUM_BLOCKS {
+        inputs.push_bytes(block(i));
+        // growing reallocates, but never misaligns
+        assert!(is_aligned(&inputs));
+    }
+    assert_eq!(inputs.len(), NUM_BLOCKS);
+
+    let mut outputs = OutputVec::zeroed(NUM_BLOCKS);
+    compute_slice(&inputs, &mut outputs).unwrap();
+    for (input, output) in inputs.iter().zip(&outputs) {
+        let mut expected = Output::default();
+        compute(input, &mut expected);
+        assert_eq!(output.0, expected.0);
+    }
+
+    let mut compact_outputs = CompactOutputVec::zeroed(NUM_BLOCKS);
+    compact_slice(&outputs, &mut compact_outputs).unwrap();
+    assert!(is_aligned(&compact_outputs));
+}
+
+#[test]
+fn batches_match_the_const_generic_path() {
+    let fixed: Box<Inputs<37>> = Box::new(AlignedBuffer(std::array::from_fn(block)));
+    let mut expected = Box::<Outputs<37>>::default();
+    compute_multiple(&fixed, &mut expected);
+
+    let inputs = InputVec::from(&*fixed);
+    let mut outputs = OutputVec::zeroed(inputs.len());
+    compute_slice(&inputs, &mut outputs).unwrap();
+    assert_eq!(Vec::from(outputs.clone()), expected.0.to_vec());
+
+    let mut sum = outputs.clone();
+    add_slice(&mut sum, &outputs).unwrap();
+    let operand = expected.clone();
+    add_multiple(&mut expected, &operand);
+    assert_eq!(Vec::<[u8; 128]>::from(sum), expected.0.to_vec());
+}
+
+#[test]
+fn conversions_round_trip() {
+    let blocks: Vec<[u8; 256]> = (0..5).map(block).collect();
+    let inputs = InputVec::from(blocks.clone());
+    assert_eq!(inputs.len(), 5);
+    assert_eq!(inputs[3].0[0], blocks[3]);
+    assert!(is_aligned(&inputs));
+    assert_eq!(Vec::from(inputs.clone()), blocks);
+
+    let collected: InputVec = inputs.iter().cloned().rev().collect();
+    assert_eq!(collected[0].0[0], blocks[4]);
+    assert_eq!(collected.into_iter().count(), 5);
+}
+
+#[test]
+fn slices_and_capacity() {
+    let mut inputs = InputVec::with_capacity(8);
+    assert!(inputs.is_empty());
+    assert!(inputs.capacity() >= 8);
+    inputs.extend((0..4).map(|i| AlignedBuffer([block(i)])));
+    inputs[1].0[0][0] = 7;
+    for input in &mut inputs {
+        input.0[0][1] = 9;
+    }
+    assert_eq!(inputs.as_slice()[1].0[0][..2], [7, 9]);
+    inputs.as_mut_slice()[2].0[0][0] = 3;
+    assert_eq!(inputs[2].0[0][0], 3);
+    inputs.truncate(2);
+    assert_eq!(inputs.len(), 2);
+    inputs.clear();
+    assert!(inputs.is_empty() && inputs.capacity() >= 8);
+}
@@ -0,0 +1,103 @@
+//! Allocation-free hex encoding of hash values
+
+use std::fmt::Write;
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Input, Output, Outputs};
+use libswifft::hash::{compact, compute};
+
+fn output() -> Output {
+    let mut output = Output::default();
+    compute(&Input::new(0x5a), &mut output);
+    output
+}
+
+/// The lowercase hex of `bytes`, one byte at a time, independently of the encoder under test
+fn reference_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// A `core::fmt::Write` over a fixed-size array, as would be used without an allocator
+struct ArrayWriter<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> Write for ArrayWriter<N> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        let end = self.len + s.len();
+        self.bytes.get_mut(self.len..end).ok_or(std::fmt::Error)?.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+    }
+}
+
+#[test]
+fn write_hex_matches_per_byte_formatting() {
+    let output = output();
+    let mut compact_output = CompactOutput::default();
+    compact(&output, &mut compact_output);
+
+    assert_eq!(output.write_hex(&mut [0; 256]), reference_hex(&output.0[0]));
+    assert_eq!(format!("{:x}", output), reference_hex(&output.0[0]));
+    assert_eq!(compact_output.write_hex(&mut [0; 128]), reference_hex(&compact_output.0[0]));
+    assert_eq!(format!("{:x}", compact_output), reference_hex(&compact_output.0[0]));
+}
+
+#[test]
+fn hex_of_known_bytes() {
+    let counter = AlignedBuffer([std::array::from_fn::<u8, 64, _>(|i| (i * 4) as u8)]);
+    let expected = "0004080c1014181c2024282c3034383c4044484c5054585c6064686c7074787c\
+                    8084888c9094989ca0a4a8acb0b4b8bcc0c4c8ccd0d4d8dce0e4e8ecf0f4f8fc";
+    assert_eq!(counter.write_hex(&mut [0; 128]), expected);
+    assert_eq!(format!("{:x}", counter), expected);
+    assert_eq!(format!("{:X}", counter), expected.to_uppercase());
+    assert_eq!(format!("{:x}", Outputs::<3>::new(0xab)), "ab".repeat(384));
+}
+
+#[test]
+fn write_hex_borrows_the_provided_buffer() {
+    let output = output();
+    let mut out = [0u8; 256];
+    let hex = output.write_hex(&mut out);
+    let (ptr, len) = (hex.as_ptr(), hex.len());
+    assert_eq!(ptr, out.as_ptr());
+    assert_eq!(len, out.len());
+    assert_eq!(&out[..2], format!("{:02x}", output.0[0][0]).as_bytes());
+}
+
+#[test]
+fn write_hex_fmt_matches_per_byte_formatting() {
+    let outputs = Outputs::<3>::new(0xab);
+    let mut hex = String::new();
+    outputs.write_hex_fmt(&mut hex).unwrap();
+    assert_eq!(hex, "ab".repeat(384));
+
+    let output = output();
+    let mut writer = ArrayWriter { bytes: [0; 256], len: 0 };
+    output.write_hex_fmt(&mut writer).unwrap();
+    assert_eq!(std::str::from_utf8(&writer.bytes).unwrap(), reference_hex(&output.0[0]));
+
+    let mut short = ArrayWriter { bytes: [0; 255], len: 0 };
+    assert!(output.write_hex_fmt(&mut short).is_err());
+}
+
+#[test]
+fn upper_and_alternate_hex_agree_with_lower_hex() {
+    let output = output();
+    assert_eq!(format!("{:X}", output), format!("{:x}", output).to_uppercase());
+    assert_eq!(format!("{:#x}", output), format!("{:x}", output));
+
+    let outputs = Outputs::<3>::new(0x5a);
+    let chunks: Vec<_> = format!("{:#X}", outputs).split(' ').map(str::to_owned).collect();
+    assert_eq!(chunks.len(), 3);
+    assert_eq!(chunks.concat(), format!("{:X}", outputs));
+}
+
+#[test]
+fn debug_shows_the_elements_of_a_single_hash_value() {
+    let output = output();
+    let debug = format!("{:?}", output);
+    assert!(debug.ends_with(&format!("elements: {:?} }}", output.to_coefficients())), "{}", debug);
+    assert!(!format!("{:?}", Outputs::<2>::default()).contains("elements"));
+    assert!(!format!("{:?}", CompactOutput::default()).contains("elements"));
+}
@@ -0,0 +1,111 @@
+//! The bytes that `InputBuilder` places, checked against constructing the same inputs by hand
+
+use libswifft::buffer::{Input, InputBuilder, Pattern};
+use proptest::prelude::*;
+
+fn manual(poke: impl FnOnce(&mut [u8; 256])) -> Input {
+    let mut input = Input::default();
+    poke(&mut input.0[0]);
+    input
+}
+
+#[test]
+fn set_byte_places_the_byte_at_its_index() {
+    for i in [0, 1, 7, 8, 127, 255] {
+        let built = InputBuilder::new().set_byte(i, 0xA5).build();
+        assert_eq!(built.0, manual(|bytes| bytes[i] = 0xA5).0, "byte {}", i);
+    }
+}
+
+#[test]
+fn set_byte_overwrites_only_its_byte() {
+    let built = InputBuilder::new().fill_pattern(Pattern::Ones).set_byte(9, 0x00).build();
+    let expected = manual(|bytes| {
+        bytes.fill(0xFF);
+        bytes[9] = 0x00;
+    });
+    assert_eq!(built.0, expected.0);
+}
+
+#[test]
+fn set_vector_places_bit_k_of_vector_j_at_bit_64j_plus_k() {
+    for (j, k) in [(0, 0), (0, 7), (0, 8), (0, 63), (3, 0), (7, 9), (31, 63)] {
+        let mut bits = [false; 64];
+        bits[k] = true;
+        let built = InputBuilder::new().set_vector(j, &bits).build();
+        let bit = 64 * j + k;
+        assert_eq!(built.0, manual(|bytes| bytes[bit / 8] = 1 << (bit % 8)).0, "vector {} bit {}", j, k);
+    }
+}
+
+#[test]
+fn set_vector_clears_bits_that_were_set() {
+    let built = InputBuilder::new().fill_pattern(Pattern::Ones).set_vector(2, &[false; 64]).build();
+    let expected = manual(|bytes| {
+        bytes.fill(0xFF);
+        bytes[16..24].fill(0x00);
+    });
+    assert_eq!(built.0, expected.0);
+}
+
+#[test]
+fn set_byte_msb_first_reverses_the_bits() {
+    let built = InputBuilder::new().set_byte_msb_first(5, 0b1000_0001 | 0b0100_0000).build();
+    assert_eq!(built.0, manual(|bytes| bytes[5] = 0b1000_0011).0);
+}
+
+#[test]
+fn fill_pattern_matches_the_manual_patterns() {
+    let cases = [
+        (Pattern::Zeros, manual(|_| {})),
+        (Pattern::Ones, manual(|bytes| bytes.fill(0xFF))),
+        (Pattern::Alternating, manual(|bytes| bytes.fill(0x55))),
+        (Pattern::Repeat(0x3C), manual(|bytes| bytes.fill(0x3C))),
+        (Pattern::Counter, manual(|bytes| bytes.iter_mut().enumerate().for_each(|(i, byte)| *byte = i as u8))),
+    ];
+    for (pattern, expected) in cases {
+        assert_eq!(InputBuilder::new().fill_pattern(pattern).build().0, expected.0, "{:?}", pattern);
+    }
+}
+
+#[test]
+fn into_signed_builds_both_buffers() {
+    let (input, signs) = InputBuilder::new().set_byte(0, 0x0F).into_signed(InputBuilder::new().set_byte(0, 0x03));
+    assert_eq!(input.0, manual(|bytes| bytes[0] = 0x0F).0);
+    assert_eq!(signs.0, manual(|bytes| bytes[0] = 0x03).0);
+}
+
+#[test]
+#[should_panic(expected = "input byte index 256 out of range 0..256")]
+fn set_byte_rejects_indices_past_the_input() {
+    InputBuilder::new().set_byte(256, 0);
+}
+
+#[test]
+#[should_panic(expected = "input vector index 32 out of range 0..32")]
+fn set_vector_rejects_indices_past_the_input() {
+    InputBuilder::new().set_vector(32, &[false; 64]);
+}
+
+#[test]
+#[should_panic(expected = "input vector index 32 out of range 0..32")]
+fn set_vector_from_u64_rejects_indices_past_the_input() {
+    InputBuilder::new().set_vector_from_u64(32, 0);
+}
+
+proptest! {
+    #[test]
+    fn set_vector_from_u64_matches_set_vector(j in 0usize..32, bits in any::<u64>()) {
+        let vector = std::array::from_fn(|k| bits >> k & 1 == 1);
+        prop_assert_eq!(
+            InputBuilder::new().set_vector_from_u64(j, bits).build().0,
+            InputBuilder::new().set_vector(j, &vector).build().0,
+        );
+    }
+
+    #[test]
+    fn set_vector_from_u64_places_the_bytes_little_endian(j in 0usize..32, bits in any::<u64>()) {
+        let expected = manual(|bytes| bytes[8 * j..8 * j + 8].copy_from_slice(&bits.to_le_bytes()));
+        prop_assert_eq!(InputBuilder::new().set_vector_from_u64(j, bits).build().0, expected.0);
+    }
+}
@@ -0,0 +1,72 @@
+//! Bulk conversion between input blocks and pure Rust SWIFFT inputs
+
+use libswifft::buffer::{InputBuilder, Inputs};
+use libswifft::error::{InteropError, LenMismatch};
+use libswifft::interop::{inputs_to_swifft, swifft_to_inputs, SWIFFT_INPUTS_PER_BLOCK};
+use proptest::prelude::*;
+use swifft::hash::{
+    parse_input_block, parse_input_block_base4, BASE4_INPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE
+};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+fn inputs<const NUM_BLOCKS: usize>(bytes: &[u8]) -> Inputs<NUM_BLOCKS> {
+    let mut inputs = Inputs::<NUM_BLOCKS>::default();
+    inputs.0.iter_mut().flatten().zip(bytes).for_each(|(byte, &value)| *byte = value);
+    inputs
+}
+
+proptest! {
+    #[test]
+    fn inputs_round_trip(bytes in prop::collection::vec(any::<u8>(), 4 * 256)) {
+        let inputs = inputs::<4>(&bytes);
+        let swifft_inputs = inputs_to_swifft(&inputs);
+        prop_assert_eq!(swifft_inputs.len(), 4 * SWIFFT_INPUTS_PER_BLOCK);
+        // stale contents are overwritten
+        let mut round_trip = Inputs::<4>::new(0xA5);
+        swifft_to_inputs(&swifft_inputs, &mut round_trip).unwrap();
+        prop_assert_eq!(round_trip.0, inputs.0);
+    }
+
+    #[test]
+    fn conversion_matches_pure_parsing(bytes in prop::collection::vec(any::<u8>(), 2 * 256)) {
+        let swifft_inputs = inputs_to_swifft(&inputs::<2>(&bytes));
+        for (swifft_input, half) in swifft_inputs.iter().zip(bytes.chunks_exact(SWIFFT_INPUT_BLOCK_SIZE)) {
+            prop_assert_eq!(swifft_input, &parse_input_block(half.try_into().unwrap()));
+        }
+    }
+
+    #[test]
+    fn base4_packing_combines_bit_planes(low in prop::collection::vec(any::<u8>(), 256), high in prop::collection::vec(any::<u8>(), 256)) {
+        let builder = |bytes: &[u8]| bytes.iter().enumerate()
+            .fold(InputBuilder::new(), |builder, (i, &byte)| builder.set_byte(i, byte));
+        let block = builder(&low).into_base4(builder(&high));
+        let halves = low.chunks_exact(SWIFFT_INPUT_BLOCK_SIZE).zip(high.chunks_exact(SWIFFT_INPUT_BLOCK_SIZE));
+        for (base4, (low, high)) in block.chunks_exact(BASE4_INPUT_BLOCK_SIZE).zip(halves) {
+            let low = parse_input_block(low.try_into().unwrap());
+            let high = parse_input_block(high.try_into().unwrap());
+            let expected: Vec<Polynomial> = low.iter().zip(high).map(|(&low, high)| low + high * Z257::TWO).collect();
+            prop_assert_eq!(parse_input_block_base4(base4.try_into().unwrap()).to_vec(), expected);
+        }
+    }
+}
+
+#[test]
+fn rejects_wrong_number_of_inputs() {
+    let swifft_inputs = inputs_to_swifft(&Inputs::<3>::default());
+    let mut output = Inputs::<2>::new(0xA5);
+    assert_eq!(swifft_to_inputs(&swifft_inputs, &mut output).unwrap_err(),
+        InteropError::LenMismatch(LenMismatch { expected: 4, actual: 6 }));
+    assert_eq!(output.0, Inputs::<2>::new(0xA5).0);
+}
+
+#[test]
+fn rejects_first_non_binary_coefficient() {
+    let mut swifft_inputs = inputs_to_swifft(&Inputs::<3>::default());
+    let mut coefficients = *swifft_inputs[3][5].coefficients();
+    coefficients[17] = Z257::TWO;
+    swifft_inputs[3][5] = Polynomial::new(coefficients);
+    swifft_inputs[5][0] = Polynomial::new([Z257::MAX; Polynomial::N]);
+    assert_eq!(swifft_to_inputs(&swifft_inputs, &mut Inputs::<3>::default()).unwrap_err(),
+        InteropError::NonBinaryCoefficient { input: 3, polynomial: 5, coefficient: 17 });
+}
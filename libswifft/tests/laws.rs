@@ -0,0 +1,140 @@
+//! The algebraic laws that the composability of SWIFFT hash values relies on,
+//! checked against the native library over random inputs
+
+use libswifft::arithmetic::{add, const_mul, mul, set, sub};
+use libswifft::buffer::{CompactOutput, Input, InputBuilder, Output, Pattern, SignInput};
+use libswifft::hash::{compact, compose, compute, compute_signed};
+use proptest::prelude::*;
+
+fn input(bytes: &[u8]) -> Input {
+    bytes.iter().enumerate()
+        .fold(InputBuilder::new(), |builder, (i, &byte)| builder.set_byte(i, byte))
+        .build()
+}
+
+fn hash(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+fn copy(output: &Output) -> Output {
+    let mut copy = Output::default();
+    set(&mut copy, output);
+    copy
+}
+
+fn block() -> impl Strategy<Value = Vec<u8>> {
+    prop::collection::vec(any::<u8>(), 256)
+}
+
+proptest! {
+    /// Hashing inputs with disjoint bits and composing equals hashing their union
+    #[test]
+    fn compose_of_disjoint_inputs_is_hash_of_union(x in block(), y in block()) {
+        let x_only: Vec<u8> = x.iter().zip(&y).map(|(x, y)| x & !y).collect();
+        let union: Vec<u8> = x_only.iter().zip(&y).map(|(x, y)| x | y).collect();
+        let composed = compose(&hash(&input(&x_only)), &hash(&input(&y)));
+        prop_assert_eq!(composed.0, hash(&input(&union)).0);
+    }
+
+    /// `compute(x) - compute(y)` is the signed hash of `x - y` in `{-1, 0, 1}`
+    #[test]
+    fn difference_of_hashes_is_signed_hash(x in block(), y in block()) {
+        let magnitude: Vec<u8> = x.iter().zip(&y).map(|(x, y)| x ^ y).collect();
+        let signs: Vec<u8> = x.iter().zip(&y).map(|(x, y)| y & !x).collect();
+        let mut difference = hash(&input(&x));
+        sub(&mut difference, &hash(&input(&y)));
+        let mut signed = Output::default();
+        compute_signed(&input(&magnitude), &input(&signs), &mut signed);
+        prop_assert_eq!(difference.0, signed.0);
+    }
+
+    /// Composition is commutative
+    #[test]
+    fn compose_is_commutative(x in block(), y in block()) {
+        let (a, b) = (hash(&input(&x)), hash(&input(&y)));
+        prop_assert_eq!(compose(&a, &b).0, compose(&b, &a).0);
+    }
+
+    /// Subtraction undoes composition
+    #[test]
+    fn sub_undoes_compose(x in block(), y in block()) {
+        let (a, b) = (hash(&input(&x)), hash(&input(&y)));
+        let mut composed = compose(&a, &b);
+        sub(&mut composed, &b);
+        prop_assert_eq!(composed.0, a.0);
+    }
+
+    /// Multiplying by a constant distributes over addition
+    #[test]
+    fn const_mul_distributes_over_add(x in block(), y in block(), c in any::<i16>()) {
+        let (a, b) = (hash(&input(&x)), hash(&input(&y)));
+        let mut lhs = compose(&a, &b);
+        const_mul(&mut lhs, c);
+        let (mut a_c, mut b_c) = (copy(&a), copy(&b));
+        const_mul(&mut a_c, c);
+        const_mul(&mut b_c, c);
+        add(&mut a_c, &b_c);
+        prop_assert_eq!(lhs.0, a_c.0);
+    }
+
+    /// Element-wise multiplication distributes over addition
+    #[test]
+    fn mul_distributes_over_add(x in block(), y in block(), z in block()) {
+        let (a, b, c) = (hash(&input(&x)), hash(&input(&y)), hash(&input(&z)));
+        let mut lhs = compose(&b, &c);
+        mul(&mut lhs, &a);
+        let (mut b_a, mut c_a) = (copy(&b), copy(&c));
+        mul(&mut b_a, &a);
+        mul(&mut c_a, &a);
+        add(&mut b_a, &c_a);
+        prop_assert_eq!(lhs.0, b_a.0);
+    }
+
+    /// Constants are interpreted modulo 257
+    #[test]
+    fn const_mul_reduces_operand(x in block(), c in any::<i16>()) {
+        let (mut a, mut b) = (hash(&input(&x)), hash(&input(&x)));
+        const_mul(&mut a, c);
+        const_mul(&mut b, c.rem_euclid(257));
+        prop_assert_eq!(a.0, b.0);
+    }
+
+    /// Sign bits at positions whose input bit is zero have no effect
+    #[test]
+    fn signs_without_magnitude_are_ignored(x in block(), signs in block()) {
+        let stray_signs: Vec<u8> = x.iter().zip(&signs).map(|(x, s)| s & !x).collect();
+        let mut signed = Output::default();
+        compute_signed(&input(&x), &input(&stray_signs), &mut signed);
+        prop_assert_eq!(signed.0, hash(&input(&x)).0);
+    }
+}
+
+/// Non-law: compacting does not commute with composition,
+/// so compacted hash values cannot be composed byte-wise
+#[test]
+fn compact_is_not_composable() {
+    let x = InputBuilder::new().set_vector_from_u64(0, 0b01).build();
+    let y = InputBuilder::new().set_vector_from_u64(0, 0b10).build();
+    let (a, b) = (hash(&x), hash(&y));
+
+    let (mut compact_a, mut compact_b, mut compact_composed) =
+        (CompactOutput::default(), CompactOutput::default(), CompactOutput::default());
+    compact(&a, &mut compact_a);
+    compact(&b, &mut compact_b);
+    compact(&compose(&a, &b), &mut compact_composed);
+
+    let byte_wise_sum: Vec<u8> = compact_a.0[0].iter().zip(&compact_b.0[0])
+        .map(|(a, b)| a.wrapping_add(*b)).collect();
+    assert_ne!(compact_composed.0[0].to_vec(), byte_wise_sum);
+}
+
+/// Sign inputs are plain input buffers, so unsigned hashing is signed hashing with no signs
+#[test]
+fn unsigned_is_signed_with_no_signs() {
+    let x = InputBuilder::new().fill_pattern(Pattern::Repeat(0xA5)).build();
+    let mut signed = Output::default();
+    compute_signed(&x, &SignInput::default(), &mut signed);
+    assert_eq!(signed.0, hash(&x).0);
+}
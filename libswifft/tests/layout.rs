@@ -0,0 +1,76 @@
+//! Conversion between the planar and interleaved input layouts
+
+use libswifft::buffer::layout::{convert, convert_layout, Interleaved, Layout, Planar};
+use libswifft::buffer::{AlignedBuffer, Input, InputBuilder, Output};
+use libswifft::hash::compute;
+use proptest::prelude::*;
+
+fn hash(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+fn bytes() -> impl Strategy<Value = [u8; 256]> {
+    prop::collection::vec(any::<u8>(), 256).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+/// The `32` input vectors of `64` coefficients, as logical bits
+fn vectors() -> impl Strategy<Value = Vec<u64>> {
+    prop::collection::vec(any::<u64>(), 32)
+}
+
+/// Lays out the logical bits bit-major, as hardware producing interleaved data would
+fn interleaved(vectors: &[u64]) -> [u8; 256] {
+    let mut bytes = [0u8; 256];
+    for k in 0..64 {
+        for (j, vector) in vectors.iter().enumerate() {
+            let position = 32 * k + j;
+            bytes[position / 8] |= (((vector >> k) & 1) as u8) << (position % 8);
+        }
+    }
+    bytes
+}
+
+fn planar(vectors: &[u64]) -> Input {
+    vectors.iter().enumerate()
+        .fold(InputBuilder::new(), |builder, (j, &vector)| builder.set_vector_from_u64(j, vector))
+        .build()
+}
+
+#[test]
+fn identity_conversions_copy() {
+    let bytes: [u8; 256] = std::array::from_fn(|i| i as u8);
+    assert_eq!(convert_layout(&bytes, Layout::Planar, Layout::Planar), bytes);
+    assert_eq!(convert_layout(&bytes, Layout::Interleaved, Layout::Interleaved), bytes);
+}
+
+#[test]
+fn first_coefficient_of_second_vector_moves() {
+    let mut planar = [0u8; 256];
+    planar[8] = 0b0000_0001;
+    let mut interleaved = [0u8; 256];
+    interleaved[0] = 0b0000_0010;
+    assert_eq!(convert::<Planar, Interleaved>(&planar), interleaved);
+    assert_eq!(convert::<Interleaved, Planar>(&interleaved), planar);
+}
+
+proptest! {
+    #[test]
+    fn conversions_round_trip(bytes in bytes()) {
+        let interleaved = convert_layout(&bytes, Layout::Planar, Layout::Interleaved);
+        prop_assert_eq!(convert_layout(&interleaved, Layout::Interleaved, Layout::Planar), bytes);
+        let planar = convert_layout(&bytes, Layout::Interleaved, Layout::Planar);
+        prop_assert_eq!(convert_layout(&planar, Layout::Planar, Layout::Interleaved), bytes);
+    }
+
+    #[test]
+    fn converted_interleaved_input_hashes_like_planar_input(vectors in vectors()) {
+        let expected = planar(&vectors);
+        let converted = Input::from_layout(&interleaved(&vectors), Layout::Interleaved);
+        prop_assert_eq!(converted.0, expected.0);
+        prop_assert_eq!(hash(&converted).0, hash(&expected).0);
+        prop_assert_eq!(hash(&Input::from_layout(&expected.0[0], Layout::Planar)).0, hash(&expected).0);
+        prop_assert_eq!(hash(&AlignedBuffer([convert::<Interleaved, Planar>(&interleaved(&vectors))])).0, hash(&expected).0);
+    }
+}
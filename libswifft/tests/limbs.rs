@@ -0,0 +1,68 @@
+//! Compact hash values as 512-bit little endian integers
+
+use std::cmp::Ordering;
+
+use libswifft::buffer::{AlignedBuffer, CompactOutput};
+use num_bigint::BigUint;
+use proptest::prelude::*;
+
+fn compact_output() -> impl Strategy<Value = CompactOutput> {
+    // mostly values with leading zero bytes, so that thresholds near them are exercised
+    (prop::collection::vec(any::<u8>(), 64), 0..=64usize).prop_map(|(mut bytes, zeros)| {
+        bytes[64 - zeros..].fill(0);
+        AlignedBuffer([bytes.try_into().unwrap()])
+    })
+}
+
+fn to_uint(compact_output: &CompactOutput) -> BigUint {
+    BigUint::from_bytes_le(&compact_output.0[0])
+}
+
+#[test]
+fn limbs_are_little_endian() {
+    let mut compact_output = CompactOutput::default();
+    compact_output.0[0][0] = 1;
+    compact_output.0[0][63] = 0x80;
+    let limbs = compact_output.as_limbs();
+    assert_eq!((limbs[0], limbs[7]), (1, 0x80 << 56));
+    assert_eq!(limbs[1..7], [0; 6]);
+    assert_eq!(compact_output.leading_zero_bits(), 0);
+}
+
+#[test]
+fn leading_zero_bits_examples() {
+    assert_eq!(CompactOutput::default().leading_zero_bits(), 512);
+    let mut compact_output = CompactOutput::default();
+    compact_output.0[0][0] = 1;
+    assert_eq!(compact_output.leading_zero_bits(), 511);
+    compact_output.0[0][62] = 0x10;
+    assert_eq!(compact_output.leading_zero_bits(), 8 + 3);
+}
+
+#[test]
+fn integer_order_is_not_byte_order() {
+    let (mut low, mut high) = (CompactOutput::default(), CompactOutput::default());
+    low.0[0][0] = 2;
+    high.0[0][1] = 1;
+    assert_eq!(low.cmp(&high), Ordering::Greater);
+    assert_eq!(low.cmp_as_uint(&high), Ordering::Less);
+    assert!(low.meets_target(&high.0[0]));
+    assert!(!high.meets_target(&low.0[0]));
+    assert!(high.meets_target(&high.0[0]));
+    assert!(CompactOutput::new(0xff).meets_target(&[0xff; 64]));
+}
+
+proptest! {
+    #[test]
+    fn agrees_with_big_integers(a in compact_output(), b in compact_output()) {
+        let (x, y) = (to_uint(&a), to_uint(&b));
+        prop_assert_eq!(a.as_limbs().to_vec(), {
+            let mut digits = x.to_u64_digits();
+            digits.resize(8, 0);
+            digits
+        });
+        prop_assert_eq!(a.leading_zero_bits() as u64, 512 - x.bits());
+        prop_assert_eq!(a.cmp_as_uint(&b), x.cmp(&y));
+        prop_assert_eq!(a.meets_target(&b.0[0]), x <= y);
+    }
+}
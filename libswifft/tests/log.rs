@@ -0,0 +1,79 @@
+//! Position-binding digests of append-only logs
+
+use libswifft::arithmetic::{add, const_mul};
+use libswifft::buffer::Output;
+use libswifft::log::{LogDigest, ENTRY_CHUNK_SIZE};
+
+fn entries(count: usize) -> Vec<Vec<u8>> {
+    (0..count).map(|i| vec![i as u8; i % (3 * ENTRY_CHUNK_SIZE)]).collect()
+}
+
+fn log_digest(entries: &[Vec<u8>]) -> LogDigest {
+    let mut log = LogDigest::new();
+    for entry in entries {
+        log.append(entry);
+    }
+    log
+}
+
+#[test]
+fn appending_matches_recomputation() {
+    let entries = entries(300);
+    let log = log_digest(&entries);
+    assert_eq!(log.len(), entries.len());
+
+    let mut expected = Output::default();
+    let mut weight = 1i32;
+    for (i, entry) in entries.iter().enumerate() {
+        let mut term = LogDigest::entry_hash(i as u64, entry);
+        const_mul(&mut term, weight as i16);
+        add(&mut expected, &term);
+        weight = weight * 3 % 257;
+    }
+    assert_eq!(log.digest().0, expected.0);
+}
+
+#[test]
+fn permuted_logs_differ() {
+    let entries = entries(300);
+    let digest = log_digest(&entries).digest();
+    for (i, j) in [(0, 1), (5, 133), (0, 256), (10, 266)] {
+        let mut permuted = entries.clone();
+        permuted.swap(i, j);
+        assert_ne!(log_digest(&permuted).digest().0, digest.0, "swapping entries {} and {}", i, j);
+    }
+}
+
+#[test]
+fn default_is_an_empty_log() {
+    let mut log = LogDigest::default();
+    assert!(log.is_empty());
+    log.append(&[1]);
+    assert_eq!(log.digest().0, log_digest(&[vec![1]]).digest().0);
+}
+
+#[test]
+fn empty_entries_and_padding_are_distinguished() {
+    assert_eq!(LogDigest::new().digest().0, Output::default().0);
+    assert_ne!(log_digest(&[vec![]]).digest().0, Output::default().0);
+    assert_ne!(log_digest(&[vec![1]]).digest().0, log_digest(&[vec![1, 0]]).digest().0);
+    assert_ne!(LogDigest::entry_hash(0, &[7; 10]).0, LogDigest::entry_hash(1, &[7; 10]).0);
+}
+
+#[test]
+fn prefix_verification_accepts_prefixes_and_rejects_forks() {
+    let entries = entries(20);
+    let log = log_digest(&entries);
+    for len in 0..=entries.len() {
+        assert!(log.verify_prefix(&log_digest(&entries[..len])), "prefix of {} entries", len);
+    }
+
+    let mut longer = entries.clone();
+    longer.push(vec![1, 2, 3]);
+    assert!(!log.verify_prefix(&log_digest(&longer)));
+    assert!(log_digest(&longer).verify_prefix(&log));
+
+    let mut fork = entries[..10].to_vec();
+    fork[9].push(0xff);
+    assert!(!log.verify_prefix(&log_digest(&fork)));
+}
@@ -0,0 +1,99 @@
+//! Message authentication codes, their construction and their sensitivity to the key and the message
+
+mod common;
+
+use std::io::Write;
+
+use libswifft::buffer::Digest512;
+use libswifft::hash::{hash_bytes, MESSAGE_BYTES_PER_BLOCK};
+use libswifft::error::TagError;
+use libswifft::mac::{SwifftMac, KEY_SIZE};
+use libswifft::tagged::{Mode, TaggedDigest};
+
+use common::message;
+
+const KEY: [u8; KEY_SIZE] = *b"an example key of thirty-two b.!";
+
+fn tag(key: &[u8; KEY_SIZE], message: &[u8]) -> Digest512 {
+    let mut mac = SwifftMac::new(key);
+    mac.update(message);
+    mac.finalize()
+}
+
+#[test]
+fn matches_the_documented_construction() {
+    for len in [0, 1, 300, 5000] {
+        let mut sandwich = KEY.to_vec();
+        sandwich.resize(MESSAGE_BYTES_PER_BLOCK, 0);
+        sandwich.extend_from_slice(&message(len));
+        sandwich.extend_from_slice(&KEY);
+        assert_eq!(tag(&KEY, &message(len)), hash_bytes(&sandwich), "{} bytes", len);
+    }
+}
+
+#[test]
+fn every_key_bit_changes_the_tag() {
+    let expected = tag(&KEY, b"message");
+    for bit in 0..8 * KEY_SIZE {
+        let mut key = KEY;
+        key[bit / 8] ^= 1 << (bit % 8);
+        assert_ne!(tag(&key, b"message"), expected, "key bit {}", bit);
+    }
+}
+
+#[test]
+fn every_message_bit_changes_the_tag() {
+    let message = message(600);
+    let expected = tag(&KEY, &message);
+    for bit in (0..8 * message.len()).step_by(7) {
+        let mut flipped = message.clone();
+        flipped[bit / 8] ^= 1 << (bit % 8);
+        assert_ne!(tag(&KEY, &flipped), expected, "message bit {}", bit);
+    }
+}
+
+#[test]
+fn streaming_and_writing_match_one_shot() {
+    let message = message(40_000);
+    let expected = tag(&KEY, &message);
+    let mut mac = SwifftMac::new(&KEY);
+    for chunk in message.chunks(1000) {
+        mac.write_all(chunk).unwrap();
+    }
+    assert_eq!(mac.bytes_written(), message.len() as u64);
+    assert_eq!(mac.finalize(), expected);
+
+    let mut mac = SwifftMac::new(&KEY);
+    std::io::copy(&mut message.as_slice(), &mut mac).unwrap();
+    assert!(mac.verify(&expected));
+}
+
+#[test]
+fn verify_rejects_other_tags() {
+    let expected = tag(&KEY, b"message");
+    for byte in [0, 31, 63] {
+        let mut bytes = *expected.as_bytes();
+        bytes[byte] ^= 0x80;
+        let tampered = Digest512::from(bytes);
+        let mut mac = SwifftMac::new(&KEY);
+        mac.update(b"message");
+        assert!(!mac.verify(&tampered));
+    }
+    // the tag is not the plain hash of the message
+    let mut mac = SwifftMac::new(&KEY);
+    mac.update(b"message");
+    assert!(!mac.verify(&hash_bytes(b"message")));
+}
+
+#[test]
+fn tagged_tags_are_not_digests_of_other_modes() {
+    let mut mac = SwifftMac::new(&KEY);
+    mac.update(b"message");
+    let tagged = mac.finalize_tagged();
+    assert_eq!(tagged.mode(), Mode::Keyed);
+    assert_eq!(*tagged.digest(), tag(&KEY, b"message"));
+
+    let plain = TaggedDigest::new(Mode::Plain, tag(&KEY, b"message"));
+    assert_eq!(tagged.verify(&plain), Err(TagError::ModeMismatch { expected: Mode::Keyed, actual: Mode::Plain }));
+    assert!(tagged.to_string().starts_with("swifft:v1:keyed:"));
+}
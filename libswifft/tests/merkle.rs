@@ -0,0 +1,133 @@
+//! Merkle trees, their roots and their inclusion proofs
+
+use libswifft::buffer::{CompactOutput, Digest512};
+use libswifft::hash::hash_bytes;
+use libswifft::merkle::{MerkleProof, MerkleTree, StreamingBuilder};
+
+fn leaves(count: usize) -> Vec<Vec<u8>> {
+    (0..count).map(|i| format!("leaf {}", i).repeat(i % 7 + 1).into_bytes()).collect()
+}
+
+#[test]
+fn every_leaf_is_proven_for_every_size() {
+    for count in 1..=33 {
+        let leaves = leaves(count);
+        let tree = MerkleTree::build(&leaves);
+        assert_eq!(tree.len(), count);
+        for (index, leaf) in leaves.iter().enumerate() {
+            let proof = tree.prove(index);
+            assert_eq!((proof.index(), proof.num_leaves()), (index, count));
+            assert!(proof.verify(&tree.root(), leaf), "leaf {} of {}", index, count);
+            assert_eq!(proof.root(leaf), Some(tree.root()));
+            assert!(!proof.verify(&tree.root(), b"another leaf"));
+        }
+    }
+}
+
+#[test]
+fn small_trees() {
+    let no_leaves: [&[u8]; 0] = [];
+    let empty = MerkleTree::build(&no_leaves);
+    assert!(empty.is_empty());
+    assert_eq!(empty.root(), Digest512::from(CompactOutput::default()));
+    assert_eq!(MerkleTree::default().root(), empty.root());
+
+    // the root of a single leaf is its hash value, and its proof has no siblings
+    let single = MerkleTree::build(&[b"only"]);
+    assert_eq!(single.root(), hash_bytes(b"only"));
+    assert!(single.prove(0).siblings().is_empty());
+    assert!(single.prove(0).verify(&single.root(), b"only"));
+}
+
+#[test]
+fn odd_nodes_are_promoted_not_duplicated() {
+    let three = MerkleTree::build(&leaves(3));
+    let mut four = leaves(3);
+    four.push(four[2].clone());
+    assert_ne!(three.root(), MerkleTree::build(&four).root());
+    // the last leaf of three is promoted past the first level, so its proof has a single sibling
+    assert_eq!(three.prove(2).siblings().len(), 1);
+    assert_eq!(three.prove(0).siblings().len(), 2);
+}
+
+#[test]
+#[should_panic]
+fn proving_past_the_leaves_panics() {
+    MerkleTree::build(&leaves(5)).prove(5);
+}
+
+#[test]
+fn tampered_proofs_fail() {
+    let leaves = leaves(13);
+    let tree = MerkleTree::build(&leaves);
+    let root = tree.root();
+    let proof = tree.prove(6);
+
+    // a proof for one index does not prove another leaf at that index, or the same leaf at another index
+    assert!(!proof.verify(&root, &leaves[7]));
+    assert!(!tree.prove(7).verify(&root, &leaves[6]));
+    // siblings that do not fit the path of the leaf prove no root at all
+    let mut siblings = proof.siblings().to_vec();
+    assert_eq!(MerkleProof::new(6, 13, siblings[1..].to_vec()).root(&leaves[6]), None);
+    siblings.push(root.clone());
+    assert_eq!(MerkleProof::new(6, 13, siblings).root(&leaves[6]), None);
+    assert_eq!(MerkleProof::new(13, 13, Vec::new()).root(&leaves[6]), None);
+    // nor does it verify under another root
+    assert!(!proof.verify(&MerkleTree::build(&leaves[..12]).root(), &leaves[6]));
+    assert!(!proof.verify(&MerkleTree::build(&leaves[1..]).root(), &leaves[6]));
+
+    // every sibling is bound: flipping any of its bits fails, as does dropping, adding or reordering siblings
+    let with_siblings = |siblings: Vec<Digest512>| MerkleProof::new(6, 13, siblings);
+    assert!(with_siblings(proof.siblings().to_vec()).verify(&root, &leaves[6]));
+    for i in 0..proof.siblings().len() {
+        for byte in [0, 31, 63] {
+            let mut siblings = proof.siblings().to_vec();
+            let mut bytes = *siblings[i].as_bytes();
+            bytes[byte] ^= 1;
+            siblings[i] = Digest512::from(bytes);
+            assert!(!with_siblings(siblings).verify(&root, &leaves[6]), "sibling {} byte {}", i, byte);
+        }
+    }
+    let siblings = proof.siblings();
+    assert!(!with_siblings(siblings[1..].to_vec()).verify(&root, &leaves[6]));
+    let mut extra = siblings.to_vec();
+    extra.push(root.clone());
+    assert!(!with_siblings(extra).verify(&root, &leaves[6]));
+    let mut swapped = siblings.to_vec();
+    swapped.swap(0, 1);
+    assert!(!with_siblings(swapped).verify(&root, &leaves[6]));
+
+    // as is the position of the leaf, and the shape of its path
+    for (index, num_leaves) in [(7, 13), (4, 13), (6, 7), (6, 8), (6, 40), (13, 13)] {
+        assert!(!MerkleProof::new(index, num_leaves, siblings.to_vec()).verify(&root, &leaves[6]), "{} of {}", index, num_leaves);
+    }
+}
+
+fn streamed_root(leaves: &[Vec<u8>]) -> StreamingBuilder {
+    let mut builder = StreamingBuilder::new();
+    for leaf in leaves {
+        builder.push_leaf(leaf);
+    }
+    builder
+}
+
+#[test]
+fn streaming_matches_the_in_memory_root() {
+    for count in (0..=70).chain([127, 128, 129, 1000]) {
+        let leaves = leaves(count);
+        let builder = streamed_root(&leaves);
+        assert_eq!(builder.num_leaves(), count as u64);
+        assert_eq!(builder.finalize(), MerkleTree::build(&leaves).root(), "{} leaves", count);
+    }
+}
+
+#[test]
+fn streaming_builders_can_be_forked() {
+    let builder = streamed_root(&leaves(13));
+    let mut extended = builder.clone();
+    extended.push_leaf(b"one more");
+    let mut all = leaves(13);
+    all.push(b"one more".to_vec());
+    assert_eq!(extended.finalize(), MerkleTree::build(&all).root());
+    assert_eq!(builder.finalize(), MerkleTree::build(&leaves(13)).root());
+}
@@ -0,0 +1,60 @@
+//! Link-time check that the hashing hot path has no reachable panic.
+//!
+//! Every function instantiated here is annotated with `no_panic`, which fails to link
+//! if the optimizer cannot prove that it never panics. Only meaningful in release builds,
+//! run with `cargo xtask check-no-panic`
+#![cfg(all(feature = "no-panic", not(debug_assertions)))]
+
+use libswifft::arithmetic::*;
+use libswifft::buffer::{Inputs, Output, Outputs};
+use libswifft::hash::{compute, compute_multiple, compute_multiple_signed, compute_signed};
+use swifft::z257::Z257;
+
+const NUM_BLOCKS: usize = 5;
+
+#[test]
+fn hashing_does_not_panic() {
+    let input = Inputs::<NUM_BLOCKS>::default();
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple(&input, &mut outputs);
+    compute_multiple_signed(&input, &input, &mut outputs);
+
+    let input = Inputs::<1>::default();
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    compute_signed(&input, &input, &mut output);
+}
+
+#[test]
+fn arithmetic_does_not_panic() {
+    let operand = Output::default();
+    let mut output = Output::default();
+    set(&mut output, &operand);
+    add(&mut output, &operand);
+    sub(&mut output, &operand);
+    mul(&mut output, &operand);
+    const_set(&mut output, 1);
+    const_add(&mut output, 1);
+    const_sub(&mut output, 1);
+    const_mul(&mut output, 1);
+    scale(&mut output, Z257::ONE);
+
+    let operands = Outputs::<NUM_BLOCKS>::default();
+    let constants = [1i16; NUM_BLOCKS];
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    set_multiple(&mut outputs, &operands);
+    add_multiple(&mut outputs, &operands);
+    sub_multiple(&mut outputs, &operands);
+    mul_multiple(&mut outputs, &operands);
+    const_set_multiple(&mut outputs, &constants);
+    const_add_multiple(&mut outputs, &constants);
+    const_sub_multiple(&mut outputs, &constants);
+    const_mul_multiple(&mut outputs, &constants);
+    scale_multiple(&mut outputs, &[Z257::ONE; NUM_BLOCKS]);
+}
+
+#[test]
+fn pure_rust_hashing_does_not_panic() {
+    #[cfg(not(feature = "parallel"))]
+    swifft::hash::swifft_hash(&[swifft::polynomial::Polynomial::ZERO; swifft::hash::M]);
+}
@@ -0,0 +1,97 @@
+//! The owned variants of the hash and arithmetic functions, against their in-place counterparts
+
+use libswifft::arithmetic::*;
+use libswifft::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs, SignInput, SignInputs};
+use libswifft::hash::{
+    compact, compact_multiple, compact_multiple_owned, compact_owned, compute, compute_multiple,
+    compute_multiple_owned, compute_multiple_signed, compute_multiple_signed_owned, compute_owned, compute_signed,
+    compute_signed_owned,
+};
+
+fn inputs(seed: u64) -> Inputs<3> {
+    Inputs::from_seed(seed)
+}
+
+fn outputs(seed: u64) -> Outputs<3> {
+    let mut outputs = Outputs::<3>::default();
+    compute_multiple(&inputs(seed), &mut outputs);
+    outputs
+}
+
+fn output(seed: u8) -> Output {
+    let mut output = Output::default();
+    compute(&Input::new(seed), &mut output);
+    output
+}
+
+/// Applies an in-place operation to a copy of `a`
+fn in_place<const N: usize>(a: &AlignedBuffer<128, N>, op: impl FnOnce(&mut AlignedBuffer<128, N>)) -> AlignedBuffer<128, N> {
+    let mut output = AlignedBuffer(a.0);
+    op(&mut output);
+    output
+}
+
+#[test]
+fn hash_functions_match_in_place() {
+    let (input, sign_input) = (Input::new(0x5a), SignInput::new(0x0f));
+    assert_eq!(compute_owned(&input).0, output(0x5a).0);
+    let mut signed = Output::default();
+    compute_signed(&input, &sign_input, &mut signed);
+    assert_eq!(compute_signed_owned(&input, &sign_input).0, signed.0);
+    let mut compact_output = CompactOutput::default();
+    compact(&signed, &mut compact_output);
+    assert_eq!(compact_owned(&signed).0, compact_output.0);
+
+    assert_eq!(compute_multiple_owned(&inputs(0)).0, outputs(0).0);
+    let sign_inputs: SignInputs<3> = AlignedBuffer(inputs(7).0);
+    let mut signed = Outputs::<3>::default();
+    compute_multiple_signed(&inputs(0), &sign_inputs, &mut signed);
+    assert_eq!(compute_multiple_signed_owned(&inputs(0), &sign_inputs).0, signed.0);
+    let mut compact_outputs = CompactOutputs::<3>::default();
+    compact_multiple(&signed, &mut compact_outputs);
+    assert_eq!(compact_multiple_owned(&signed).0, compact_outputs.0);
+}
+
+#[test]
+fn arithmetic_matches_in_place() {
+    let (a, b) = (output(1), output(0xa7));
+    assert_eq!(set_owned(&b).0, in_place(&a, |o| set(o, &b)).0);
+    assert_eq!(add_owned(&a, &b).0, in_place(&a, |o| add(o, &b)).0);
+    assert_eq!(sub_owned(&a, &b).0, in_place(&a, |o| sub(o, &b)).0);
+    assert_eq!(mul_owned(&a, &b).0, in_place(&a, |o| mul(o, &b)).0);
+    for operand in [-300, -1, 0, 5, 256, 1000] {
+        assert_eq!(const_set_owned(operand).0, in_place(&a, |o| const_set(o, operand)).0);
+        assert_eq!(const_add_owned(&a, operand).0, in_place(&a, |o| const_add(o, operand)).0);
+        assert_eq!(const_sub_owned(&a, operand).0, in_place(&a, |o| const_sub(o, operand)).0);
+        assert_eq!(const_mul_owned(&a, operand).0, in_place(&a, |o| const_mul(o, operand)).0);
+    }
+    // the operands are left as they were
+    assert_eq!(a.0, output(1).0);
+    assert_eq!(b.0, output(0xa7).0);
+}
+
+#[test]
+fn multiple_arithmetic_matches_in_place() {
+    let (a, b) = (outputs(1), outputs(2));
+    assert_eq!(set_multiple_owned(&b).0, in_place(&a, |o| set_multiple(o, &b)).0);
+    assert_eq!(add_multiple_owned(&a, &b).0, in_place(&a, |o| add_multiple(o, &b)).0);
+    assert_eq!(sub_multiple_owned(&a, &b).0, in_place(&a, |o| sub_multiple(o, &b)).0);
+    assert_eq!(mul_multiple_owned(&a, &b).0, in_place(&a, |o| mul_multiple(o, &b)).0);
+    let operand = [-3, 100, 256];
+    assert_eq!(const_set_multiple_owned(&operand).0, in_place(&a, |o| const_set_multiple(o, &operand)).0);
+    assert_eq!(const_add_multiple_owned(&a, &operand).0, in_place(&a, |o| const_add_multiple(o, &operand)).0);
+    assert_eq!(const_sub_multiple_owned(&a, &operand).0, in_place(&a, |o| const_sub_multiple(o, &operand)).0);
+    assert_eq!(const_mul_multiple_owned(&a, &operand).0, in_place(&a, |o| const_mul_multiple(o, &operand)).0);
+}
+
+#[test]
+fn owned_calls_chain() {
+    let input = Input::new(3);
+    let chained = compact_owned(&add_owned(&compute_owned(&input), &const_set_owned(1)));
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    const_add(&mut output, 1);
+    let mut expected = CompactOutput::default();
+    compact(&output, &mut expected);
+    assert_eq!(chained.0, expected.0);
+}
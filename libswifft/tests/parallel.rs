@@ -0,0 +1,67 @@
+//! Batches hashed on the `rayon` thread pool are identical to batches hashed by a single FFI call:
+//! `cargo test -p libswifft --test parallel`
+#![cfg(feature = "parallel")]
+
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::{compute_multiple, compute_multiple_parallel, compute_multiple_parallel_chunked, parallel_chunk_blocks};
+use rayon::ThreadPoolBuilder;
+
+/// Not divisible by the number of threads, nor by the chunk sizes below
+const NUM_BLOCKS: usize = 1001;
+
+fn sequential(inputs: &Inputs<NUM_BLOCKS>) -> Box<Outputs<NUM_BLOCKS>> {
+    let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+    compute_multiple(inputs, &mut outputs);
+    outputs
+}
+
+#[test]
+fn parallel_matches_sequential_on_eight_threads() {
+    let inputs = Box::new(Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64));
+    let expected = sequential(&inputs);
+    let pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+    pool.install(|| {
+        assert_eq!(parallel_chunk_blocks(NUM_BLOCKS), 126);
+        let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+        compute_multiple_parallel(&inputs, &mut outputs);
+        assert_eq!(outputs.0, expected.0);
+    });
+}
+
+#[test]
+fn every_chunk_size_gives_the_same_result() {
+    let inputs = Box::new(Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64));
+    let expected = sequential(&inputs);
+    for chunk_blocks in [0, 1, 7, 64, 1000, NUM_BLOCKS, 4096] {
+        let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+        compute_multiple_parallel_chunked(&inputs, &mut outputs, chunk_blocks);
+        assert_eq!(outputs.0, expected.0, "chunks of {} blocks", chunk_blocks);
+    }
+}
+
+#[test]
+fn chunks_larger_than_an_ffi_call_are_split_again() {
+    let inputs = Box::new(Inputs::<NUM_BLOCKS>::from_seed(NUM_BLOCKS as u64));
+    let expected = sequential(&inputs);
+    // the override is thread-local, so a single-threaded pool makes the workers see it
+    let pool = ThreadPoolBuilder::new().num_threads(1).use_current_thread().build().unwrap();
+    let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+    with_max_ffi_blocks(3, || pool.install(|| compute_multiple_parallel_chunked(&inputs, &mut outputs, 100)));
+    assert_eq!(outputs.0, expected.0);
+}
+
+#[test]
+fn chunk_sizes_cover_the_blocks() {
+    let pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+    pool.install(|| {
+        assert_eq!(parallel_chunk_blocks(0), 1);
+        assert_eq!(parallel_chunk_blocks(1), 1);
+        assert_eq!(parallel_chunk_blocks(8), 1);
+        assert_eq!(parallel_chunk_blocks(9), 2);
+        for num_blocks in 1..100 {
+            let chunk_blocks = parallel_chunk_blocks(num_blocks);
+            assert!(num_blocks.div_ceil(chunk_blocks) <= 8);
+        }
+    });
+}
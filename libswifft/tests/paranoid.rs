@@ -0,0 +1,48 @@
+//! The native library only emits canonical elements, which the `paranoid` feature asserts,
+//! blaming the caller instead when it passes a non-canonical operand to a copy:
+//! `cargo test -p libswifft --features paranoid --test paranoid`
+#![cfg(all(feature = "paranoid", debug_assertions))]
+
+use libswifft::arithmetic::{add, const_mul, mul, set, set_multiple, set_slice, sub};
+use libswifft::buffer::{Input, Output, Outputs};
+use libswifft::hash::compute;
+
+fn crafted() -> Output {
+    let mut output = Output::default();
+    output.0[0][..2].copy_from_slice(&300i16.to_le_bytes());
+    output.0[0][2..4].copy_from_slice(&(-5i16).to_le_bytes());
+    output
+}
+
+#[test]
+fn reducing_operations_accept_crafted_buffers() {
+    let mut output = Output::default();
+    compute(&Input::new(0xa5), &mut output);
+    add(&mut output, &crafted());
+    sub(&mut output, &crafted());
+    mul(&mut output, &crafted());
+    const_mul(&mut output, 300);
+    assert!(output.is_canonical());
+}
+
+#[test]
+#[should_panic(expected = "`operand` passed to the native library holds a non-canonical element")]
+fn copying_crafted_buffers_blames_the_caller() {
+    let mut output = Output::default();
+    set(&mut output, &crafted());
+}
+
+#[test]
+#[should_panic(expected = "`operand` passed to the native library holds a non-canonical element")]
+fn copying_crafted_batches_blames_the_caller() {
+    let mut outputs = Outputs::<2>::default();
+    let mut operand = Outputs::<2>::default();
+    operand.0[1] = crafted().0[0];
+    set_multiple(&mut outputs, &operand);
+}
+
+#[test]
+#[should_panic(expected = "`operand` passed to the native library holds a non-canonical element")]
+fn copying_crafted_slices_blames_the_caller() {
+    set_slice(&mut [Output::default(), Output::default()], &[Output::default(), crafted()]).unwrap();
+}
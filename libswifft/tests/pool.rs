@@ -0,0 +1,84 @@
+//! Recycling of boxed buffers through an `AlignedBufferPool`
+
+use std::collections::HashSet;
+
+use libswifft::buffer::{CompactOutputs, Inputs, Outputs};
+use libswifft::hash::compute_multiple;
+use libswifft::pool::AlignedBufferPool;
+
+#[test]
+fn put_buffer_is_handed_out_again() {
+    let pool = AlignedBufferPool::new(4);
+    let mut input = pool.get::<Inputs<64>>();
+    input.0[3][5] = 7;
+    let address = &*input as *const Inputs<64>;
+
+    pool.put(input);
+    assert_eq!(pool.len(), 1);
+    let input = pool.get::<Inputs<64>>();
+    assert_eq!(&*input as *const Inputs<64>, address);
+    assert_eq!(input.0[3][5], 7);
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn buffers_of_the_same_size_share_a_class() {
+    let pool = AlignedBufferPool::new(4);
+    // 64 input blocks and 128 output blocks are both 16 KiB
+    let input = pool.get::<Inputs<64>>();
+    let address = &*input as *const Inputs<64> as usize;
+    pool.put(input);
+
+    let output = pool.get::<Outputs<128>>();
+    assert_eq!(&*output as *const Outputs<128> as usize, address);
+    assert!(Outputs::<128>::verify_alignment(&*output));
+
+    pool.put(output);
+    let compact_output = pool.get::<CompactOutputs<64>>();
+    assert_ne!(&*compact_output as *const CompactOutputs<64> as usize, address);
+    assert_eq!(pool.len(), 1);
+}
+
+#[test]
+fn fresh_buffers_are_zeroed() {
+    let pool = AlignedBufferPool::new(4);
+    assert_eq!(pool.get::<Outputs<16>>().0, Outputs::<16>::default().0);
+    assert_eq!(pool.get::<Inputs<0>>().0.len(), 0);
+    pool.put(Box::<Inputs<0>>::default());
+    assert!(pool.is_empty());
+}
+
+#[test]
+fn full_classes_free_returned_buffers() {
+    let pool = AlignedBufferPool::new(2);
+    let buffers: Vec<_> = (0..3).map(|_| pool.get::<Inputs<8>>()).collect();
+    for buffer in buffers {
+        pool.put(buffer);
+    }
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn pool_is_shared_between_threads() {
+    let pool = AlignedBufferPool::new(8);
+    let addresses = std::sync::Mutex::new(HashSet::new());
+    std::thread::scope(|scope| {
+        for thread in 0..8 {
+            let (pool, addresses) = (&pool, &addresses);
+            scope.spawn(move || {
+                for round in 0..50 {
+                    let mut input = pool.get::<Inputs<32>>();
+                    let mut output = pool.get::<Outputs<32>>();
+                    input.0[0][0] = (thread * 50 + round) as u8;
+                    compute_multiple(&input, &mut output);
+                    addresses.lock().unwrap().insert(&*input as *const Inputs<32> as usize);
+                    pool.put(input);
+                    pool.put(output);
+                }
+            });
+        }
+    });
+    // every thread holds at most two buffers at once, so the pool never needs more than that many per class
+    assert!(addresses.into_inner().unwrap().len() <= 2 * 8);
+    assert!(pool.len() <= 2 * 8);
+}
@@ -0,0 +1,44 @@
+//! Random buffers drawn from a `rand` generator:
+//! `cargo test -p libswifft --features rand --test rand`
+#![cfg(feature = "rand")]
+
+use libswifft::buffer::{CompactOutputs, Input, Output, SignInput};
+use rand::rngs::SmallRng;
+use rand::{Rng, RngCore, SeedableRng};
+
+#[test]
+fn random_buffers_follow_the_generator() {
+    let mut rng = SmallRng::seed_from_u64(1);
+    let compact_outputs = CompactOutputs::<3>::random(&mut rng);
+    let mut expected = [0u8; 3 * 64];
+    SmallRng::seed_from_u64(1).fill_bytes(&mut expected);
+    assert_eq!(compact_outputs.as_bytes(), &expected[..]);
+    assert_ne!(CompactOutputs::<3>::random(&mut rng), compact_outputs);
+    // through a trait object too
+    let dyn_rng: &mut dyn RngCore = &mut rng;
+    assert_ne!(Input::random(dyn_rng), Input::default());
+}
+
+#[test]
+fn sampled_hash_values_are_canonical_and_cover_every_element() {
+    let mut rng = SmallRng::seed_from_u64(2);
+    let mut seen = [false; 257];
+    for _ in 0..200 {
+        let output: Output = rng.gen();
+        assert!(output.is_canonical());
+        for element in output.to_coefficients() {
+            seen[element as usize] = true;
+        }
+    }
+    assert!(seen.iter().all(|&seen| seen));
+}
+
+#[test]
+fn sampled_inputs_have_about_half_their_bits_set() {
+    let mut rng = SmallRng::seed_from_u64(3);
+    let (input, sign_input): (Input, SignInput) = (rng.gen(), rng.gen());
+    assert_ne!(input, sign_input);
+    let ones: u32 = input.as_bytes().iter().map(|byte| byte.count_ones()).sum();
+    // the standard deviation is about 23 bits
+    assert!((1024 - 150..=1024 + 150).contains(&ones), "{} bits set", ones);
+}
@@ -0,0 +1,60 @@
+//! Unchecked constructors and the checked views built on them, which make no FFI calls,
+//! so they can be run under Miri: `cargo +nightly miri test -p libswifft --test raw`
+
+use libswifft::buffer::{Input, Inputs, Output, Outputs};
+use libswifft::view::{InputRef, InputsRef, OutputMut, OutputsMut};
+
+#[test]
+fn raw_inputs_alias_their_bytes() {
+    let inputs = Box::new(Inputs::<3>::from_seed(3));
+    let ptr = inputs.0.as_flattened().as_ptr();
+    // SAFETY: `inputs` is aligned, 3 * 256 bytes long, and outlives the views, during which it is not written
+    let (input, slice) = unsafe { (Input::from_raw_parts(ptr), Input::slice_from_raw_parts(ptr, 3)) };
+    assert_eq!(input.0[0], inputs.0[0]);
+    assert!(std::ptr::eq(input, &slice[0]));
+    assert_eq!(slice.iter().map(|input| input.0[0]).collect::<Vec<_>>(), inputs.0);
+    // SAFETY: as above, for no blocks at all
+    assert!(unsafe { Input::slice_from_raw_parts(ptr, 0) }.is_empty());
+}
+
+#[test]
+fn raw_outputs_write_through() {
+    let mut outputs = Outputs::<2>::default();
+    let ptr = outputs.0.as_flattened_mut().as_mut_ptr();
+    {
+        // SAFETY: `outputs` is aligned, 2 * 128 bytes long, and only accessed through this view while it lives
+        let slice = unsafe { Output::slice_from_raw_parts_mut(ptr, 2) };
+        slice[1].0[0][5] = 7;
+    }
+    {
+        // SAFETY: as above, for the first block only
+        let output = unsafe { Output::from_raw_parts_mut(ptr) };
+        output.0[0][0] = 1;
+    }
+    assert_eq!((outputs.0[0][0], outputs.0[1][5]), (1, 7));
+}
+
+#[test]
+fn assume_canonical_agrees_with_the_checked_constructor() {
+    let mut bytes = [0u8; 128];
+    for (i, element) in bytes.chunks_exact_mut(2).enumerate() {
+        element.copy_from_slice(&((i * 5 % 257) as i16).to_le_bytes());
+    }
+    // SAFETY: every element lies in `[0, 256]`
+    let unchecked = unsafe { Output::assume_canonical(&bytes) };
+    assert_eq!(unchecked.0, Output::from_bytes_canonical(&bytes).unwrap().0);
+    assert!(unchecked.is_canonical());
+}
+
+#[test]
+fn checked_views_go_through_the_raw_constructors() {
+    let inputs = Box::new(Inputs::<3>::from_seed(3));
+    let bytes = inputs.0.as_flattened();
+    assert_eq!(InputRef::new(&bytes[..256]).unwrap().as_input().0, [inputs.0[0]]);
+    assert_eq!(InputsRef::new(bytes).unwrap().as_inputs().len(), 3);
+
+    let mut outputs = Outputs::<2>::default();
+    OutputMut::new(&mut outputs.0[1]).unwrap().as_output_mut().0[0][3] = 9;
+    OutputsMut::new(outputs.0.as_flattened_mut()).unwrap().as_outputs_mut()[0].0[0][4] = 8;
+    assert_eq!((outputs.0[1][3], outputs.0[0][4]), (9, 8));
+}
@@ -0,0 +1,140 @@
+//! The example registry composes tagged digests, compact hash values and their serialization:
+//! `cargo test -p libswifft --features registry --test registry`
+#![cfg(feature = "registry")]
+
+use libswifft::buffer::Input;
+use libswifft::error::{ProofError, TagError};
+use libswifft::registry::{Auditor, Registry, UpdateProof};
+use libswifft::tagged::{Mode, TaggedDigest};
+
+/// Inserts the keys `key 0`, `key 1`, ... with their values, returning every root and proof in order
+fn populate(registry: &mut Registry, count: usize) -> Vec<(UpdateProof, TaggedDigest)> {
+    (0..count)
+        .map(|i| {
+            let proof = registry.insert(format!("key {}", i).as_bytes(), format!("value {}", i).as_bytes());
+            (proof, registry.root())
+        })
+        .collect()
+}
+
+#[test]
+fn auditor_follows_every_update() {
+    let mut registry = Registry::new();
+    let mut auditor = Auditor::new(registry.root());
+    for (proof, root) in populate(&mut registry, 20) {
+        assert!(proof.old_value().is_none());
+        auditor.replay(&proof, &root).unwrap();
+    }
+    assert_eq!(registry.len(), 20);
+    assert_eq!(auditor.root().verify(&registry.root()), Ok(true));
+
+    // overwriting a key proves its old value
+    let old_value = registry.get(b"key 3").unwrap().clone();
+    let proof = registry.insert(b"key 3", b"another value");
+    assert_eq!(*proof.old_value().unwrap(), old_value);
+    assert_ne!(*registry.get(b"key 3").unwrap(), old_value);
+    auditor.replay(&proof, &registry.root()).unwrap();
+    assert_eq!(registry.len(), 20);
+}
+
+#[test]
+fn roots_commit_to_the_contents_and_their_order() {
+    let mut forwards = Registry::new();
+    let mut again = Registry::new();
+    let mut backwards = Registry::new();
+    for i in 0..10 {
+        forwards.insert(&[i], &[i, i]);
+        again.insert(&[i], &[i, i]);
+        backwards.insert(&[9 - i], &[9 - i, 9 - i]);
+    }
+    assert_eq!(forwards.root().verify(&again.root()), Ok(true));
+    // keys keep the leaf of their first insertion, which the root commits to
+    assert_eq!(forwards.root().verify(&backwards.root()), Ok(false));
+
+    again.insert(&[0], &[1]);
+    assert_eq!(forwards.root().verify(&again.root()), Ok(false));
+    again.insert(&[0], &[0, 0]);
+    assert_eq!(forwards.root().verify(&again.root()), Ok(true));
+    assert_eq!(Registry::new().root().verify(&forwards.root()), Ok(false));
+    assert!(Registry::new().is_empty());
+    assert!(forwards.get(&[10]).is_none());
+}
+
+#[test]
+fn tampered_proofs_are_rejected() {
+    let mut registry = Registry::new();
+    let updates = populate(&mut registry, 5);
+    let (proof, root) = &updates[3];
+    let (_, old_root) = &updates[2];
+    proof.verify(old_root, root).unwrap();
+
+    // every byte of the encoding is covered: flipping any bit of it fails to decode or to verify
+    let encoded = proof.encode();
+    for i in 0..encoded.len() {
+        let mut tampered = encoded.clone();
+        tampered[i] ^= 0x10;
+        if let Ok(tampered) = UpdateProof::decode(&tampered) {
+            assert!(tampered.verify(old_root, root).is_err(), "byte {} is not covered", i);
+        }
+    }
+
+    // the proof of a different update does not verify this transition
+    let (other, _) = &updates[4];
+    assert_eq!(other.verify(old_root, root), Err(ProofError::OldRootMismatch));
+    // nor does this proof verify a transition to a different root
+    assert_eq!(proof.verify(old_root, &updates[4].1), Err(ProofError::NewRootMismatch));
+
+    // a rejected update leaves the auditor where it was
+    let mut auditor = Auditor::new(old_root.clone());
+    assert!(auditor.replay(other, root).is_err());
+    assert_eq!(auditor.root().verify(old_root), Ok(true));
+}
+
+#[test]
+fn roots_of_other_modes_are_refused() {
+    let mut registry = Registry::new();
+    let old_root = registry.root();
+    let proof = registry.insert(b"key", b"value");
+
+    let retagged = TaggedDigest::new(Mode::Plain, registry.root().digest().clone());
+    assert_eq!(proof.verify(&old_root, &retagged),
+        Err(ProofError::Tag(TagError::ModeMismatch { expected: Mode::Registry, actual: Mode::Plain })));
+    let plain = TaggedDigest::plain(&Input::new(0));
+    assert!(matches!(proof.verify(&plain, &registry.root()), Err(ProofError::Tag(_))));
+}
+
+#[test]
+fn proofs_and_roots_round_trip_through_their_encodings() {
+    let mut registry = Registry::new();
+    let updates = populate(&mut registry, 8);
+    let mut auditor = Auditor::new(Registry::new().root());
+    for (proof, root) in &updates {
+        let encoded = proof.encode();
+        let decoded = UpdateProof::decode(&encoded).unwrap();
+        assert_eq!(decoded.encode(), encoded);
+        assert_eq!(decoded.key(), proof.key());
+
+        let root: TaggedDigest = root.to_string().parse().unwrap();
+        assert_eq!(root.mode(), Mode::Registry);
+        auditor.replay(&decoded, &TaggedDigest::decode(&root.encode()).unwrap()).unwrap();
+    }
+
+    // proofs hold at most one sibling per level of the tree, which has 3 levels above its 8 leaves
+    assert!(updates.iter().all(|(proof, _)| proof.encode().len() <= 3 * 64 + 17 + 3 * 64));
+}
+
+#[test]
+fn malformed_proofs_are_rejected() {
+    let mut registry = Registry::new();
+    registry.insert(b"first", b"value");
+    let encoded = registry.insert(b"first", b"second value").encode();
+    for len in [0, 1, 64, 65, 129, encoded.len() - 1] {
+        assert_eq!(UpdateProof::decode(&encoded[..len]).unwrap_err(), ProofError::Malformed);
+    }
+    let mut trailing = encoded.clone();
+    trailing.push(0);
+    assert_eq!(UpdateProof::decode(&trailing).unwrap_err(), ProofError::Malformed);
+    let mut flag = encoded;
+    flag[64] = 2;
+    assert_eq!(UpdateProof::decode(&flag).unwrap_err(), ProofError::Malformed);
+}
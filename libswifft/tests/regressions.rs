@@ -0,0 +1,108 @@
+//! Regression corpus of inputs found by fuzzing, run through every hashing path.
+//!
+//! Every file dropped into `tests/regressions/` is picked up without code changes. Its bytes are the input,
+//! zero-padded to whole blocks. Metadata is optional, and lives in a sidecar named after the input with `.meta`
+//! appended (*as written by `cargo xtask add-regression`*), so any input, including one beginning with `#`, is kept verbatim.
+
+use std::fs;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::buffer::{AlignedBuffer, CompactOutput, Inputs, Output, Outputs};
+use libswifft::hash::{compact, compute, compute_multiple};
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_const, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE};
+
+/// The number of blocks hashed together by `compute_multiple`
+const BATCH_BLOCKS: usize = 4;
+
+/// The extension of metadata sidecars, the only files of the corpus that are not inputs
+const METADATA_EXTENSION: &str = "meta";
+
+fn corpus_files() -> Vec<PathBuf> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/regressions");
+    let mut files: Vec<_> = fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+    files
+}
+
+fn is_metadata(path: &Path) -> bool {
+    path.extension().is_some_and(|extension| extension == METADATA_EXTENSION)
+}
+
+fn regression_files() -> Vec<PathBuf> {
+    corpus_files().into_iter().filter(|path| !is_metadata(path)).collect()
+}
+
+/// Splits `bytes` into at least one block, zero-padding the last
+fn blocks<const BLOCK_SIZE: usize>(bytes: &[u8]) -> Vec<[u8; BLOCK_SIZE]> {
+    let mut blocks: Vec<_> = bytes.chunks(BLOCK_SIZE).map(|chunk| {
+        let mut block = [0u8; BLOCK_SIZE];
+        block[..chunk.len()].copy_from_slice(chunk);
+        block
+    }).collect();
+    if blocks.is_empty() {
+        blocks.push([0u8; BLOCK_SIZE]);
+    }
+    blocks
+}
+
+/// The FFT and naive pure Rust implementations agree
+fn check_pure(bytes: &[u8]) {
+    for block in blocks::<SWIFFT_INPUT_BLOCK_SIZE>(bytes) {
+        assert_eq!(swifft_hash(&parse_input_block(&block)), swifft_hash_const(&block), "FFT and naive hashes differ");
+    }
+}
+
+/// Single and batched FFI calls agree, whatever the chunking, and emit canonical hash values
+fn check_ffi(bytes: &[u8]) {
+    let blocks = blocks::<{ BATCH_BLOCKS * 256 }>(bytes);
+    for batch in &blocks {
+        let mut inputs = Inputs::<BATCH_BLOCKS>::default();
+        for (input, chunk) in inputs.0.iter_mut().zip(batch.chunks_exact(256)) {
+            input.copy_from_slice(chunk);
+        }
+        let mut outputs = Outputs::<BATCH_BLOCKS>::default();
+        compute_multiple(&inputs, &mut outputs);
+        assert!(outputs.is_canonical(), "non-canonical hash value");
+
+        let mut chunked = Outputs::<BATCH_BLOCKS>::default();
+        with_max_ffi_blocks(1, || compute_multiple(&inputs, &mut chunked));
+        assert_eq!(chunked.0, outputs.0, "chunked and batched hashes differ");
+
+        for (input, expected) in inputs.0.iter().zip(&outputs.0) {
+            let mut output = Output::default();
+            compute(&AlignedBuffer([*input]), &mut output);
+            assert_eq!(&output.0[0], expected, "single and batched hashes differ");
+            compact(&output, &mut CompactOutput::default());
+        }
+    }
+}
+
+#[test]
+fn regressions_agree_across_hashing_paths() {
+    let files = regression_files();
+    assert!(!files.is_empty(), "the regression corpus is empty");
+
+    let failures: Vec<_> = files.iter().filter(|path| {
+        let bytes = fs::read(path).unwrap();
+        catch_unwind(AssertUnwindSafe(|| {
+            check_pure(&bytes);
+            check_ffi(&bytes);
+        })).is_err()
+    }).collect();
+    assert!(failures.is_empty(), "regressions failed: {:?}", failures);
+}
+
+#[test]
+fn metadata_describes_an_input() {
+    for path in corpus_files().into_iter().filter(|path| is_metadata(path)) {
+        // the sidecar of `name` is `name.meta`, so removing its extension names its input
+        assert!(path.with_extension("").is_file(), "{}: metadata of no input", path.display());
+        let metadata = fs::read_to_string(&path).unwrap();
+        assert!(!metadata.trim().is_empty(), "{}: empty metadata", path.display());
+    }
+}
@@ -0,0 +1,54 @@
+//! Detection of whether the running CPU can run the native library, and the checked entry points built on it
+
+use libswifft::buffer::{AlignedBuffer, Input, Output, Outputs};
+use libswifft::capabilities::{swifft_runtime_support, Isa, IsetSupport};
+use libswifft::error::UnsupportedIsa;
+use libswifft::hash::{compute, compute_multiple, try_compute, try_compute_multiple};
+
+#[test]
+fn support_reflects_the_running_cpu() {
+    let support = swifft_runtime_support();
+    assert_eq!(support.detected, Isa::detect());
+    assert_eq!(support.required, Isa::native());
+    assert_ne!(support.required, Isa::Scalar);
+    // these tests run on the machine that built the native library
+    assert!(support.is_supported());
+    assert!(support.backend().is_ok());
+}
+
+#[test]
+fn instruction_sets_are_ordered_by_width() {
+    let isas = [Isa::Scalar, Isa::Avx, Isa::Avx2, Isa::Avx512];
+    for (i, a) in isas.iter().enumerate() {
+        for (j, b) in isas.iter().enumerate() {
+            assert_eq!(a.includes(*b), i >= j, "{} includes {}", a, b);
+        }
+    }
+}
+
+#[test]
+fn unsupported_cpus_are_reported_not_run() {
+    let support = IsetSupport { detected: Isa::Avx, required: Isa::Avx2 };
+    assert!(!support.is_supported());
+    let err = support.backend().unwrap_err();
+    assert_eq!(err, UnsupportedIsa { required: Isa::Avx2, detected: Isa::Avx });
+    assert_eq!(err.to_string(), "the native SWIFFT library requires avx2, but the CPU only supports avx");
+    assert!(IsetSupport { detected: Isa::Scalar, required: Isa::Avx }.backend().is_err());
+    assert!(IsetSupport { detected: Isa::Avx512, required: Isa::Avx2 }.backend().is_ok());
+}
+
+#[test]
+fn checked_entry_points_match_the_unchecked_ones() {
+    let mut input = Input::default();
+    input.0[0].iter_mut().enumerate().for_each(|(i, byte)| *byte = i as u8);
+    let (mut expected, mut actual) = (Output::default(), Output::default());
+    compute(&input, &mut expected);
+    try_compute(&input, &mut actual).unwrap();
+    assert_eq!(actual.0, expected.0);
+
+    let inputs = AlignedBuffer([input.0[0], [3; 256], [0; 256]]);
+    let (mut expected, mut actual): (Outputs<3>, Outputs<3>) = Default::default();
+    compute_multiple(&inputs, &mut expected);
+    try_compute_multiple(&inputs, &mut actual).unwrap();
+    assert_eq!(actual.0, expected.0);
+}
@@ -0,0 +1,50 @@
+//! The RustCrypto `digest` traits, streaming and one-shot:
+//! `cargo test -p libswifft --features digest --test rustcrypto`
+#![cfg(feature = "digest")]
+
+mod common;
+
+use digest::{Digest, FixedOutputReset, Update};
+use libswifft::hash::{hash_bytes, MESSAGE_BYTES_PER_BLOCK};
+use libswifft::rustcrypto::Swifft512;
+
+use common::message;
+
+/// Hashes `data` generically, as code that only knows `D: Digest` would
+fn generic_digest<D: Digest>(data: &[u8]) -> Vec<u8> {
+    D::digest(data).to_vec()
+}
+
+#[test]
+fn one_shot_matches_hash_bytes() {
+    assert_eq!(Swifft512::digest(b"abc")[..], hash_bytes(b"abc").as_bytes()[..]);
+    assert_eq!(<Swifft512 as Digest>::output_size(), 64);
+    for len in [0, 1, MESSAGE_BYTES_PER_BLOCK, 64 * MESSAGE_BYTES_PER_BLOCK, 64 * MESSAGE_BYTES_PER_BLOCK + 1, 20_000] {
+        assert_eq!(generic_digest::<Swifft512>(&message(len)), *hash_bytes(&message(len)).as_bytes(), "{} bytes", len);
+    }
+}
+
+#[test]
+fn streaming_matches_one_shot() {
+    let data = message(3 * 64 * MESSAGE_BYTES_PER_BLOCK + 100);
+    let expected = Swifft512::digest(&data);
+    for piece in [1, 7, MESSAGE_BYTES_PER_BLOCK - 1, MESSAGE_BYTES_PER_BLOCK, 256, 64 * MESSAGE_BYTES_PER_BLOCK, 100_000] {
+        let mut hasher = Swifft512::new();
+        for chunk in data.chunks(piece) {
+            Digest::update(&mut hasher, chunk);
+        }
+        assert_eq!(hasher.finalize(), expected, "pieces of {} bytes", piece);
+    }
+}
+
+#[test]
+fn reset_starts_a_new_message() {
+    let mut hasher = Swifft512::new();
+    Update::update(&mut hasher, &message(1000));
+    assert_eq!(hasher.finalize_fixed_reset(), Swifft512::digest(message(1000)));
+    Update::update(&mut hasher, b"abc");
+    let clone = hasher.clone();
+    assert_eq!(hasher.finalize_reset(), Swifft512::digest(b"abc"));
+    assert_eq!(clone.finalize(), Swifft512::digest(b"abc"));
+    assert_eq!(hasher.finalize(), Swifft512::digest(b""));
+}
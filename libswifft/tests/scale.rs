@@ -0,0 +1,67 @@
+//! Scaling hash values by elements of `Z_{257}`, checked against the other arithmetic of the native library
+
+use libswifft::arithmetic::{scale, scale_multiple, sub};
+use libswifft::buffer::{Output, Outputs};
+use proptest::prelude::*;
+use swifft::z257::Z257;
+
+const NUM_BLOCKS: usize = 3;
+
+fn reduced(output: &Output) -> [u16; 64] {
+    output.to_coefficients().map(|coefficient| coefficient % 257)
+}
+
+fn scaled(output: &Output, scalar: Z257) -> Output {
+    let mut scaled = Output::from_coefficients(output.to_coefficients());
+    scale(&mut scaled, scalar);
+    scaled
+}
+
+fn output() -> impl Strategy<Value = Output> {
+    prop::array::uniform32(0u16..257)
+        .prop_flat_map(|low| prop::array::uniform32(0u16..257).prop_map(move |high| {
+            Output::from_coefficients(std::array::from_fn(|i| if i < 32 { low[i] } else { high[i - 32] }))
+        }))
+}
+
+proptest! {
+    #[test]
+    fn scaling_by_one_is_the_identity(output in output()) {
+        prop_assert_eq!(reduced(&scaled(&output, Z257::ONE)), reduced(&output));
+    }
+
+    #[test]
+    fn scaling_by_zero_zeroes_the_output(output in output()) {
+        prop_assert_eq!(reduced(&scaled(&output, Z257::ZERO)), [0; 64]);
+    }
+
+    #[test]
+    fn scaling_by_256_negates(output in output()) {
+        let mut negated = Output::default();
+        sub(&mut negated, &output);
+        prop_assert_eq!(reduced(&scaled(&output, Z257::new(256))), reduced(&negated));
+    }
+
+    #[test]
+    fn scaling_matches_the_elementwise_product(output in output(), scalar in 0u16..257) {
+        let expected = output.to_coefficients().map(|coefficient| (coefficient as u32 * scalar as u32 % 257) as u16);
+        prop_assert_eq!(reduced(&scaled(&output, Z257::new(scalar))), expected);
+        prop_assert_eq!(reduced(&(output * Z257::new(scalar))), expected);
+    }
+
+    #[test]
+    fn batches_scale_each_block_by_its_own_scalar(
+        blocks in prop::array::uniform3(output()),
+        scalars in prop::array::uniform3(0u16..257),
+    ) {
+        let scalars = scalars.map(Z257::new);
+        let mut outputs: Outputs<NUM_BLOCKS> = Outputs::default();
+        for (block, output) in outputs.0.iter_mut().zip(&blocks) {
+            *block = output.0[0];
+        }
+        scale_multiple(&mut outputs, &scalars);
+        for ((block, output), scalar) in outputs.0.iter().zip(&blocks).zip(scalars) {
+            prop_assert_eq!(block, &scaled(output, scalar).0[0]);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+//! Pseudorandom buffers reproducible from a seed
+
+use libswifft::buffer::{fill_from_seed, CompactOutput, Input, Inputs, Outputs};
+use libswifft::testvectors::VECTORS_CSV;
+
+#[test]
+fn seeds_give_the_pseudorandom_test_vectors() {
+    for seed in 1..=4 {
+        let line = format!("compute,{:x},,", Input::from_seed(seed));
+        assert!(VECTORS_CSV.lines().any(|vector| vector.starts_with(&line)), "seed {}", seed);
+    }
+}
+
+#[test]
+fn seeds_are_reproducible_and_distinct() {
+    assert_eq!(Inputs::<3>::from_seed(7), Inputs::<3>::from_seed(7));
+    assert_ne!(Input::from_seed(0), Input::from_seed(1));
+    assert_ne!(Input::from_seed(0), Input::default());
+    // the stream continues across blocks, rather than restarting with each block
+    let inputs = Inputs::<2>::from_seed(9);
+    assert_eq!(inputs.0[0], Input::from_seed(9).0[0]);
+    assert_ne!(inputs.0[1], inputs.0[0]);
+    // a shorter buffer is a prefix of a longer one
+    assert_eq!(CompactOutput::from_seed(3).as_bytes(), &Outputs::<1>::from_seed(3).as_bytes()[..64]);
+}
+
+/// The seed that the mix sends to the zero state shares its bytes with the seed that is mixed to the replacement state,
+/// while the seeds that used to collide in pairs, before the seed was mixed, no longer do
+#[test]
+fn seeds_collide_only_at_the_zero_state() {
+    assert_ne!(Input::from_seed(0), Input::from_seed(17428512612931826493));
+    assert_eq!(Input::from_seed(7046029254386353131), Input::from_seed(14787246121499254518));
+    assert_ne!(Input::from_seed(7046029254386353131), Input::default());
+    let (mut a, mut b) = ([0u8; 100], [0u8; 100]);
+    fill_from_seed(2, &mut a);
+    fill_from_seed(2 + 17428512612931826493, &mut b);
+    assert_ne!(a, b);
+}
+
+#[test]
+fn seeds_fill_any_number_of_bytes_as_one_stream() {
+    let inputs = Inputs::<4>::from_seed(11);
+    let mut bytes = vec![0u8; 4 * 256 + 100];
+    fill_from_seed(11, &mut bytes);
+    assert_eq!(&bytes[..4 * 256], inputs.as_bytes());
+    let mut block = [0u8; 256];
+    fill_from_seed(11, &mut block);
+    assert_eq!(block, Input::from_seed(11).0[0]);
+}
@@ -0,0 +1,103 @@
+//! Serializing buffers as hex in human-readable formats and as raw bytes otherwise:
+//! `cargo test -p libswifft --features serde --test serde`
+#![cfg(feature = "serde")]
+
+use libswifft::buffer::{CompactOutput, Input, Output, Outputs};
+use libswifft::hash::{compact, compute};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn output() -> Output {
+    let mut output = Output::default();
+    compute(&Input::new(0x5a), &mut output);
+    output
+}
+
+/// Hash values of distinct inputs, all canonical
+fn canonical_outputs<const NUM_OUTPUTS: usize>() -> Outputs<NUM_OUTPUTS> {
+    let mut outputs = Outputs::<NUM_OUTPUTS>::default();
+    for (seed, block) in outputs.0.iter_mut().enumerate() {
+        let mut output = Output::default();
+        compute(&Input::from_seed(seed as u64), &mut output);
+        *block = output.0[0];
+    }
+    outputs
+}
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) {
+    let json = serde_json::to_string(value).unwrap();
+    assert_eq!(&serde_json::from_str::<T>(&json).unwrap(), value);
+    let bytes = bincode::serialize(value).unwrap();
+    assert_eq!(&bincode::deserialize::<T>(&bytes).unwrap(), value);
+}
+
+#[test]
+fn buffers_round_trip() {
+    let mut compact_output = CompactOutput::default();
+    compact(&output(), &mut compact_output);
+    let mut outputs = Outputs::<4>::default();
+    outputs.0[3] = output().0[0];
+
+    round_trip(&Input::from_seed(1));
+    round_trip(&canonical_outputs::<3>());
+    round_trip(&output());
+    round_trip(&outputs);
+    round_trip(&compact_output);
+}
+
+#[test]
+fn human_readable_formats_use_hex() {
+    let json = serde_json::to_string(&output()).unwrap();
+    assert_eq!(json, format!("\"{:x}\"", output()));
+
+    let mut manifest = std::collections::BTreeMap::new();
+    manifest.insert("file", CompactOutput::new(0xab));
+    assert_eq!(serde_json::to_string(&manifest).unwrap(), format!("{{\"file\":\"{}\"}}", "ab".repeat(64)));
+}
+
+#[test]
+fn binary_formats_use_raw_bytes() {
+    let bytes = bincode::serialize(&output()).unwrap();
+    // bincode prefixes byte strings with their length, as a `u64`
+    assert_eq!(bytes[..8], 128u64.to_le_bytes());
+    assert_eq!(bytes[8..], output().0[0]);
+}
+
+#[test]
+fn deserialized_buffers_are_aligned() {
+    let bytes = bincode::serialize(&output()).unwrap();
+    let outputs: Vec<Output> = (0..5).map(|_| bincode::deserialize(&bytes).unwrap()).collect();
+    assert!(outputs.iter().all(|output| Output::verify_alignment(output)));
+    let boxed: Box<Outputs<4>> = serde_json::from_str(&serde_json::to_string(&canonical_outputs::<4>()).unwrap()).unwrap();
+    assert!(Outputs::<4>::verify_alignment(&*boxed));
+}
+
+#[test]
+fn wrong_lengths_and_digits_are_rejected() {
+    let short = format!("\"{}\"", "ab".repeat(63));
+    let error = serde_json::from_str::<CompactOutput>(&short).unwrap_err().to_string();
+    assert!(error.contains("invalid length 126, expected 64 bytes, or 128 lowercase hex digits"), "{}", error);
+    assert!(serde_json::from_str::<CompactOutput>(&format!("\"{}\"", "AB".repeat(64))).is_err());
+    assert!(serde_json::from_str::<CompactOutput>(&format!("\"{}\"", "zz".repeat(64))).is_err());
+    assert!(serde_json::from_str::<CompactOutput>(&format!("\"{}\"", "ab".repeat(65))).is_err());
+
+    let bytes = bincode::serialize(&output()).unwrap();
+    assert!(bincode::deserialize::<CompactOutput>(&bytes).is_err());
+    assert!(bincode::deserialize::<Outputs<2>>(&bytes).is_err());
+    assert!(bincode::deserialize::<Output>(&bytes[..100]).is_err());
+}
+
+#[test]
+fn non_canonical_hash_values_are_rejected() {
+    let json = format!("\"{}{}\"", "0101", "00".repeat(126));
+    let error = serde_json::from_str::<Output>(&json).unwrap_err().to_string();
+    assert!(error.contains("value 257 at index 0 is out of range"), "{}", error);
+    assert!(serde_json::from_str::<Outputs<2>>(&format!("\"{}\"", "ff".repeat(256))).is_err());
+
+    let mut bytes = bincode::serialize(&output()).unwrap();
+    bytes[8 + 2 * 5..8 + 2 * 5 + 2].copy_from_slice(&257u16.to_le_bytes());
+    let error = bincode::deserialize::<Output>(&bytes).unwrap_err().to_string();
+    assert!(error.contains("value 257 at index 5 is out of range"), "{}", error);
+    // only hash values have elements: other buffers take any bytes
+    assert!(serde_json::from_str::<CompactOutput>(&format!("\"{}\"", "ff".repeat(64))).is_ok());
+}
@@ -0,0 +1,92 @@
+//! Multiset hashes, independent of the order of insertion
+
+use libswifft::buffer::Output;
+use libswifft::sethash::SetDigest;
+use proptest::prelude::*;
+
+fn items() -> impl Strategy<Value = Vec<Vec<u8>>> {
+    prop::collection::vec(prop::collection::vec(any::<u8>(), 0..600), 0..12)
+}
+
+fn set_digest<'a>(items: impl IntoIterator<Item = &'a Vec<u8>>) -> SetDigest {
+    let mut digest = SetDigest::new();
+    for item in items {
+        digest.insert(item);
+    }
+    digest
+}
+
+#[test]
+fn multiplicities_count() {
+    let mut once = SetDigest::new();
+    once.insert(b"item");
+    let mut twice = once.clone();
+    twice.insert(b"item");
+    assert_ne!(once, twice);
+    assert_ne!(once, SetDigest::new());
+    twice.remove(b"item");
+    assert_eq!(once, twice);
+    twice.remove(b"item");
+    assert!(twice.is_empty_digest());
+    assert!(SetDigest::new().is_empty_digest());
+    assert!(!once.is_empty_digest());
+
+    // items are hashed by their bytes, so an empty item is an item too
+    let mut empty_item = SetDigest::new();
+    empty_item.insert(b"");
+    assert!(!empty_item.is_empty_digest());
+}
+
+#[test]
+fn removing_an_absent_item_is_undone_by_inserting_it() {
+    let mut digest = SetDigest::new();
+    digest.remove(b"absent");
+    assert!(!digest.is_empty_digest());
+    digest.insert(b"absent");
+    assert!(digest.is_empty_digest());
+}
+
+#[test]
+fn malformed_states_are_rejected() {
+    let mut bytes = SetDigest::new().encode();
+    bytes[6..8].copy_from_slice(&257i16.to_le_bytes());
+    let err = SetDigest::decode(&bytes).unwrap_err();
+    assert_eq!((err.index, err.value), (3, 257));
+}
+
+proptest! {
+    #[test]
+    fn order_of_insertion_does_not_matter(items in items(), seed in any::<u64>()) {
+        let mut shuffled = items.clone();
+        // a deterministic permutation driven by the seed
+        for i in (1..shuffled.len()).rev() {
+            shuffled.swap(i, (seed.rotate_left(i as u32) % (i as u64 + 1)) as usize);
+        }
+        prop_assert_eq!(set_digest(&items), set_digest(&shuffled));
+    }
+
+    #[test]
+    fn insert_then_remove_restores_the_digest(items in items(), item in prop::collection::vec(any::<u8>(), 0..600)) {
+        let before = set_digest(&items);
+        let mut digest = before.clone();
+        digest.insert(&item);
+        prop_assert_ne!(&digest, &before);
+        digest.remove(&item);
+        prop_assert_eq!(digest, before);
+    }
+
+    #[test]
+    fn union_adds_the_items(a in items(), b in items()) {
+        let mut union = set_digest(&a);
+        union.union(&set_digest(&b));
+        prop_assert_eq!(union, set_digest(a.iter().chain(&b)));
+    }
+
+    #[test]
+    fn state_round_trips(items in items()) {
+        let digest = set_digest(&items);
+        let decoded = SetDigest::decode(&digest.encode()).unwrap();
+        prop_assert_eq!(&decoded, &digest);
+        prop_assert!(Output::from_bytes_canonical(&decoded.encode()).is_ok());
+    }
+}
@@ -0,0 +1,104 @@
+//! The signed hashing of the pure Rust implementation, checked against `compute_signed` of the native library.
+//!
+//! The two use different multiplier polynomials and numbers of input vectors, so their digests of the same bytes differ;
+//! what they must share is how they read sign bits. Over a corpus of seeded blocks, both must hash `(input, sign)`
+//! to the hash of the positive bits minus the hash of the negative bits, `input & !sign` and `input & sign`,
+//! and every signed variant of the pure Rust implementation must agree with the others
+
+use libswifft::arithmetic::sub;
+use libswifft::buffer::{Input, Output};
+use libswifft::hash::{compute, compute_signed};
+use libswifft::interop::SWIFFT_INPUTS_PER_BLOCK;
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_signed, swifft_hash_signed_multiple,
+                   swifft_hash_signed_multiple_bytes, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE};
+
+const CORPUS_SIZE: u64 = 64;
+
+/// The seeded `(input, sign)` blocks, together with the blocks of their positive and negative bits
+fn corpus() -> impl Iterator<Item = (Input, Input, Input, Input)> {
+    (0..CORPUS_SIZE).map(|seed| {
+        let (input, sign) = (Input::from_seed(2 * seed), Input::from_seed(2 * seed + 1));
+        let (mut positive, mut negative) = (Input::default(), Input::default());
+        for (i, (&input, &sign)) in input.0[0].iter().zip(&sign.0[0]).enumerate() {
+            positive.0[0][i] = input & !sign;
+            negative.0[0][i] = input & sign;
+        }
+        (input, sign, positive, negative)
+    })
+}
+
+fn native(input: &Input) -> Output {
+    let mut output = Output::default();
+    compute(input, &mut output);
+    output
+}
+
+fn pure(input: &Input) -> Vec<Digest> {
+    input.0[0].chunks_exact(SWIFFT_INPUT_BLOCK_SIZE)
+        .map(|half| swifft_hash(&parse_input_block(half.try_into().unwrap())))
+        .collect()
+}
+
+fn reduced(output: &Output) -> [u16; 64] {
+    output.to_coefficients().map(|coefficient| coefficient % 257)
+}
+
+#[test]
+fn native_signed_hash_is_positive_minus_negative_bits() {
+    for (input, sign, positive, negative) in corpus() {
+        let mut signed = Output::default();
+        compute_signed(&input, &sign, &mut signed);
+        let mut difference = native(&positive);
+        sub(&mut difference, &native(&negative));
+        assert_eq!(reduced(&signed), reduced(&difference));
+    }
+}
+
+#[test]
+fn pure_signed_hash_is_positive_minus_negative_bits() {
+    for (input, sign, positive, negative) in corpus() {
+        let signed = swifft_hash_signed_multiple_bytes(&input.0[0], &sign.0[0]).unwrap();
+        let difference: Vec<Digest> = pure(&positive).into_iter().zip(pure(&negative))
+            .map(|(positive, negative)| Digest::new(positive.into_polynomial() - negative.into_polynomial()))
+            .collect();
+        assert_eq!(signed, difference);
+    }
+}
+
+#[test]
+fn pure_signed_variants_agree() {
+    let mut blocks = (Vec::new(), Vec::new());
+    let mut pairs = Vec::new();
+    let mut single = Vec::new();
+    for (input, sign, _, _) in corpus() {
+        blocks.0.extend_from_slice(&input.0[0]);
+        blocks.1.extend_from_slice(&sign.0[0]);
+        for (input, sign) in input.0[0].chunks_exact(SWIFFT_INPUT_BLOCK_SIZE).zip(sign.0[0].chunks_exact(SWIFFT_INPUT_BLOCK_SIZE)) {
+            let pair = (parse_input_block(input.try_into().unwrap()), sign.try_into().unwrap());
+            single.push(swifft_hash_signed(&pair.0, &pair.1));
+            pairs.push(pair);
+        }
+    }
+    assert_eq!(single.len(), CORPUS_SIZE as usize * SWIFFT_INPUTS_PER_BLOCK);
+    assert_eq!(swifft_hash_signed_multiple(&pairs), single);
+    assert_eq!(swifft_hash_signed_multiple_bytes(&blocks.0, &blocks.1).unwrap(), single);
+}
+
+#[test]
+fn both_ignore_sign_bits_on_clear_input_bits() {
+    for (input, sign, _, _) in corpus() {
+        let mut stray = Input::default();
+        for (i, (&input, &sign)) in input.0[0].iter().zip(&sign.0[0]).enumerate() {
+            stray.0[0][i] = sign | !input;
+        }
+        let (mut signed, mut with_stray) = (Output::default(), Output::default());
+        compute_signed(&input, &sign, &mut signed);
+        compute_signed(&input, &stray, &mut with_stray);
+        assert_eq!(reduced(&signed), reduced(&with_stray));
+        assert_eq!(
+            swifft_hash_signed_multiple_bytes(&input.0[0], &sign.0[0]),
+            swifft_hash_signed_multiple_bytes(&input.0[0], &stray.0[0]),
+        );
+    }
+}
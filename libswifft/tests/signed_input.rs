@@ -0,0 +1,75 @@
+//! Signed inputs whose sign bits are validated, and hashing them
+
+use libswifft::buffer::{Input, Output, SignInput, SignedInput};
+use libswifft::error::{StraySignBit, TritError};
+use libswifft::hash::{compute_signed, compute_signed_checked, compute_trits};
+
+fn trits() -> [i8; 2048] {
+    std::array::from_fn(|i| [0, 1, -1][i * 7 % 3])
+}
+
+#[test]
+fn agrees_with_the_unchecked_functions() {
+    let signed = SignedInput::from_trits(&trits()).unwrap();
+    assert_eq!(signed.to_trits(), trits());
+    assert_eq!(compute_signed_checked(&signed).0, compute_trits(&trits()).unwrap().0);
+
+    let (input, sign_input) = signed.clone().into_parts();
+    let mut expected = Output::default();
+    compute_signed(&input, &sign_input, &mut expected);
+    assert_eq!(compute_signed_checked(&signed).0, expected.0);
+    assert_eq!(SignedInput::from_parts(input, sign_input).unwrap().to_trits(), trits());
+}
+
+#[test]
+fn stray_sign_bits_are_rejected() {
+    for (byte, sign, magnitude, index) in [(0, 0x01, 0x00, 0), (0, 0x06, 0x02, 2), (100, 0x80, 0x7f, 807), (255, 0x80, 0x00, 2047)] {
+        let (mut input, mut sign_input) = (Input::default(), SignInput::default());
+        input.0[0][byte] = magnitude;
+        sign_input.0[0][byte] = sign;
+        assert_eq!(SignedInput::from_parts(input, sign_input).unwrap_err(), StraySignBit { index });
+    }
+
+    // the first stray sign bit is reported
+    let (input, sign_input) = (Input::default(), SignInput::new(0xff));
+    assert_eq!(SignedInput::from_parts(input, sign_input).unwrap_err().index, 0);
+}
+
+#[test]
+fn canonicalizing_clears_stray_sign_bits_without_changing_the_hash_value() {
+    let (input, sign_input) = (Input::new(0x0f), SignInput::new(0x3c));
+    let mut expected = Output::default();
+    compute_signed(&input, &sign_input, &mut expected);
+
+    let signed = SignedInput::from_parts_canonicalizing(input, sign_input);
+    assert_eq!(signed.sign_input().0[0], [0x0c; 256]);
+    assert_eq!(signed.input().0[0], [0x0f; 256]);
+    assert_eq!(compute_signed_checked(&signed).0, expected.0);
+    assert_eq!((signed.trit(0), signed.trit(2), signed.trit(4)), (1, -1, 0));
+}
+
+#[test]
+fn non_trits_are_rejected() {
+    for value in [2, -2, i8::MAX, i8::MIN] {
+        let mut trits = trits();
+        trits[1000] = value;
+        assert_eq!(SignedInput::from_trits(&trits).unwrap_err(), TritError { index: 1000, value });
+        assert_eq!(SignedInput::new().set_trit(5, value).unwrap_err(), TritError { index: 5, value });
+    }
+}
+
+#[test]
+fn setting_trits_overwrites_them() {
+    let signed = SignedInput::new().set_trit(9, -1).unwrap();
+    assert_eq!((signed.input().0[0][1], signed.sign_input().0[0][1]), (0x02, 0x02));
+    let signed = signed.set_trit(9, 1).unwrap();
+    assert_eq!((signed.trit(9), signed.sign_input().0[0][1]), (1, 0));
+    let signed = signed.set_trit(9, 0).unwrap();
+    assert_eq!(signed.to_trits(), [0; 2048]);
+}
+
+#[test]
+#[should_panic]
+fn setting_a_trit_past_the_input_panics() {
+    let _ = SignedInput::new().set_trit(2048, 1);
+}
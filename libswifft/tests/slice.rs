@@ -0,0 +1,174 @@
+//! Slice-based counterparts of the const-generic batch APIs, with the number of blocks known only at runtime
+
+use libswifft::arithmetic::{add_multiple, add_slice, mul_multiple, mul_slice, set_slice, sub_multiple, sub_slice};
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::buffer::{AlignedBuffer, CompactOutput, CompactOutputs, Input, Inputs, Output, Outputs, SignInput, SignInputs};
+use libswifft::error::LenMismatch;
+use libswifft::hash::{
+    compact, compact_multiple, compact_slice, compute, compute_multiple, compute_multiple_signed, compute_signed, compute_signed_slice, compute_slice
+};
+
+const NUM_BLOCKS: usize = 37;
+
+fn inputs(seed: u64) -> Inputs<NUM_BLOCKS> {
+    Inputs::from_seed(seed)
+}
+
+fn outputs(seed: u64) -> Outputs<NUM_BLOCKS> {
+    let mut outputs = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple(&inputs(seed), &mut outputs);
+    outputs
+}
+
+/// Splits a buffer into single-block buffers, as a caller holding a `Vec` of blocks would
+fn blocks<const BLOCK_SIZE: usize>(buffer: &AlignedBuffer<BLOCK_SIZE, NUM_BLOCKS>) -> Vec<AlignedBuffer<BLOCK_SIZE, 1>> {
+    buffer.0.iter().map(|block| AlignedBuffer([*block])).collect()
+}
+
+fn zeroed<T: Default>(num_blocks: usize) -> Vec<T> {
+    (0..num_blocks).map(|_| T::default()).collect()
+}
+
+fn flatten<const BLOCK_SIZE: usize>(blocks: &[AlignedBuffer<BLOCK_SIZE, 1>]) -> Vec<[u8; BLOCK_SIZE]> {
+    blocks.iter().map(|block| block.0[0]).collect()
+}
+
+#[test]
+fn compute_slice_matches_compute_multiple() {
+    let input = inputs(0);
+    let expected = outputs(0);
+
+    let mut output = zeroed(NUM_BLOCKS);
+    with_max_ffi_blocks(5, || compute_slice(&blocks(&input), &mut output)).unwrap();
+    assert_eq!(flatten(&output), expected.0);
+}
+
+#[test]
+fn compute_signed_slice_matches_compute_multiple_signed() {
+    let input = inputs(0);
+    let sign_input: SignInputs<NUM_BLOCKS> = inputs(7);
+    let mut expected = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple_signed(&input, &sign_input, &mut expected);
+
+    let mut output = zeroed(NUM_BLOCKS);
+    let sign_input: Vec<SignInput> = blocks(&sign_input);
+    with_max_ffi_blocks(5, || compute_signed_slice(&blocks(&input), &sign_input, &mut output)).unwrap();
+    assert_eq!(flatten(&output), expected.0);
+}
+
+#[test]
+fn compact_slice_matches_compact_multiple() {
+    let output = outputs(0);
+    let mut expected = CompactOutputs::<NUM_BLOCKS>::default();
+    compact_multiple(&output, &mut expected);
+
+    let mut compact_output = zeroed(NUM_BLOCKS);
+    with_max_ffi_blocks(5, || compact_slice(&blocks(&output), &mut compact_output)).unwrap();
+    assert_eq!(flatten(&compact_output), expected.0);
+}
+
+#[test]
+fn arithmetic_slices_match_multiple() {
+    let operand = outputs(3);
+    type Multiple = fn(&mut Outputs<NUM_BLOCKS>, &Outputs<NUM_BLOCKS>);
+    type Slice = fn(&mut [Output], &[Output]) -> Result<(), LenMismatch>;
+    let operations: [(Multiple, Slice); 3] = [
+        (add_multiple, add_slice),
+        (sub_multiple, sub_slice),
+        (mul_multiple, mul_slice),
+    ];
+    for (multiple, slice) in operations {
+        let mut expected = outputs(0);
+        multiple(&mut expected, &operand);
+
+        let mut output = blocks(&outputs(0));
+        with_max_ffi_blocks(5, || slice(&mut output, &blocks(&operand))).unwrap();
+        assert_eq!(flatten(&output), expected.0);
+    }
+
+    let mut output = zeroed(NUM_BLOCKS);
+    set_slice(&mut output, &blocks(&operand)).unwrap();
+    assert_eq!(flatten(&output), operand.0);
+}
+
+#[test]
+fn mismatched_lengths_are_rejected_without_writing() {
+    let input = blocks(&inputs(0));
+    let mut output = zeroed(NUM_BLOCKS - 1);
+    assert_eq!(compute_slice(&input, &mut output), Err(LenMismatch { expected: NUM_BLOCKS, actual: NUM_BLOCKS - 1 }));
+    assert!(output.iter().all(|block| block.0 == Output::default().0));
+
+    let sign_input: Vec<SignInput> = zeroed(NUM_BLOCKS + 1);
+    let mut output = zeroed(NUM_BLOCKS);
+    assert_eq!(compute_signed_slice(&input, &sign_input, &mut output),
+        Err(LenMismatch { expected: NUM_BLOCKS, actual: NUM_BLOCKS + 1 }));
+
+    let mut compact_output: Vec<CompactOutput> = zeroed(1);
+    assert_eq!(compact_slice(&output, &mut compact_output), Err(LenMismatch { expected: NUM_BLOCKS, actual: 1 }));
+
+    let operand = blocks(&outputs(3));
+    let mut output = blocks(&outputs(0));
+    assert_eq!(add_slice(&mut output[1..], &operand), Err(LenMismatch { expected: NUM_BLOCKS - 1, actual: NUM_BLOCKS }));
+    assert_eq!(flatten(&output), outputs(0).0);
+}
+
+#[test]
+fn empty_slices_are_accepted() {
+    let input: [Input; 0] = [];
+    let mut output: [Output; 0] = [];
+    assert_eq!(compute_slice(&input, &mut output), Ok(()));
+    assert_eq!(compute_signed_slice(&input, &[], &mut output), Ok(()));
+    assert_eq!(compact_slice(&output, &mut []), Ok(()));
+    assert_eq!(mul_slice(&mut output, &[]), Ok(()));
+}
+
+#[test]
+fn single_blocks_match_the_single_block_apis() {
+    let input = blocks(&inputs(0));
+    let sign_input: Vec<SignInput> = blocks(&inputs(7));
+    let mut expected = Output::default();
+    compute(&input[0], &mut expected);
+    let mut output: Vec<Output> = zeroed(1);
+    compute_slice(&input[..1], &mut output).unwrap();
+    assert_eq!(output[0].0, expected.0);
+
+    compute_signed(&input[0], &sign_input[0], &mut expected);
+    compute_signed_slice(&input[..1], &sign_input[..1], &mut output).unwrap();
+    assert_eq!(output[0].0, expected.0);
+
+    let mut expected_compact = CompactOutput::default();
+    compact(&expected, &mut expected_compact);
+    let mut compact_output: Vec<CompactOutput> = zeroed(1);
+    compact_slice(&output, &mut compact_output).unwrap();
+    assert_eq!(compact_output[0].0, expected_compact.0);
+}
+
+#[test]
+fn thousands_of_blocks_hash_alike_in_one_call_or_in_chunks() {
+    const MANY_BLOCKS: usize = 4099;
+    let input: Vec<Input> = (0..MANY_BLOCKS as u64).map(Input::from_seed).collect();
+    let sign_input: Vec<SignInput> = input.iter().rev().cloned().collect();
+
+    let mut expected = zeroed(MANY_BLOCKS);
+    for (input, output) in input.iter().zip(expected.iter_mut()) {
+        compute(input, output);
+    }
+    let mut output = zeroed(MANY_BLOCKS);
+    compute_slice(&input, &mut output).unwrap();
+    assert_eq!(flatten(&output), flatten(&expected));
+    let mut chunked = zeroed(MANY_BLOCKS);
+    with_max_ffi_blocks(1000, || compute_slice(&input, &mut chunked)).unwrap();
+    assert_eq!(flatten(&chunked), flatten(&expected));
+
+    let mut signed = zeroed(MANY_BLOCKS);
+    compute_signed_slice(&input, &sign_input, &mut signed).unwrap();
+    let mut chunked = zeroed(MANY_BLOCKS);
+    with_max_ffi_blocks(1000, || compute_signed_slice(&input, &sign_input, &mut chunked)).unwrap();
+    assert_eq!(flatten(&chunked), flatten(&signed));
+
+    let mut compact_output: Vec<CompactOutput> = zeroed(MANY_BLOCKS);
+    compact_slice(&output, &mut compact_output).unwrap();
+    let mut chunked: Vec<CompactOutput> = zeroed(MANY_BLOCKS);
+    with_max_ffi_blocks(1000, || compact_slice(&output, &mut chunked)).unwrap();
+    assert_eq!(flatten(&chunked), flatten(&compact_output));
+}
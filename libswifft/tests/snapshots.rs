@@ -0,0 +1,70 @@
+//! Snapshot tests of how the buffers of the native wrapper format: the `Debug` preview of an [`Output`],
+//! a [`CompactOutput`] and a batch of [`Outputs`], and their `{:x}`, `{:X}` and alternate hex forms.
+//!
+//! The `swifft` binary prints digests in this hex and checks manifests against it, so a change here must be deliberate.
+//! Update them with `INSTA_UPDATE=always cargo test -p libswifft --test snapshots`, or `cargo insta review`,
+//! and review the changed `.snap` files under `libswifft/tests/snapshots/` before committing them.
+
+use libswifft::buffer::{CompactOutput, InputBuilder, Output, Outputs, Pattern};
+use libswifft::hash::{compact, compute};
+
+fn output() -> Output {
+    let input = InputBuilder::new().fill_pattern(Pattern::Counter).build();
+    let mut output = Output::default();
+    compute(&input, &mut output);
+    output
+}
+
+fn compact_output() -> CompactOutput {
+    let mut compact_output = CompactOutput::default();
+    compact(&output(), &mut compact_output);
+    compact_output
+}
+
+#[test]
+fn output_hex() {
+    insta::assert_snapshot!(format!("{:x}", output()));
+}
+
+#[test]
+fn output_debug() {
+    insta::assert_snapshot!(format!("{:?}", output()));
+}
+
+#[test]
+fn compact_output_hex() {
+    insta::assert_snapshot!(format!("{:x}", compact_output()));
+}
+
+#[test]
+fn compact_output_debug() {
+    insta::assert_snapshot!(format!("{:?}", compact_output()));
+}
+
+#[test]
+fn multiple_outputs_debug() {
+    insta::assert_snapshot!(format!("{:?}", Outputs::<3>::new(0xAB)));
+}
+
+#[test]
+fn output_upper_hex() {
+    insta::assert_snapshot!(format!("{:X}", output()));
+}
+
+#[test]
+fn output_pretty_debug() {
+    insta::assert_snapshot!(format!("{:#?}", output()));
+}
+
+#[test]
+fn multiple_outputs_alternate_hex() {
+    let mut outputs = Outputs::<3>::default();
+    outputs.0[0][0] = 0x01;
+    outputs.0[1][127] = 0xFE;
+    insta::assert_snapshot!(format!("{:#x}", outputs));
+}
+
+#[test]
+fn multiple_outputs_alternate_upper_hex() {
+    insta::assert_snapshot!(format!("{:#X}", Outputs::<2>::new(0xAB)));
+}
@@ -0,0 +1,64 @@
+//! Gathering the errors of several fallible APIs into a single `SwifftError`
+
+use libswifft::buffer::heap::InputVec;
+use libswifft::buffer::{Input, Output};
+use libswifft::constant::INPUT_SIZE;
+use libswifft::capabilities::Isa;
+use libswifft::error::{BlockCountOverflow, BlockIndexOutOfRange, LenMismatch, OutOfRange, StraySignBit, TagError, TooLong, TritError, UnsupportedIsa};
+use libswifft::hash::{compute_slice, pack_trits};
+use libswifft::tagged::TaggedDigest;
+use libswifft::SwifftError;
+
+/// Runs several fallible APIs in turn, as an application would, propagating their errors with `?`
+fn pipeline(num_outputs: usize, trit: i8, output: [u8; 128], digest: &str) -> Result<(), SwifftError> {
+    compute_slice(&[Input::default(), Input::default()], &mut vec![Output::default(); num_outputs])?;
+    let mut trits = [0; INPUT_SIZE];
+    trits[9] = trit;
+    pack_trits(&trits)?;
+    Output::from_bytes_canonical(&output)?;
+    digest.parse::<TaggedDigest>()?;
+    Ok(())
+}
+
+#[test]
+fn errors_of_every_api_convert() {
+    let digest = TaggedDigest::plain(&Input::default()).to_string();
+    assert_eq!(pipeline(2, 1, [0; 128], &digest), Ok(()));
+    assert_eq!(pipeline(3, 1, [0; 128], &digest),
+        Err(SwifftError::LenMismatch(LenMismatch { expected: 2, actual: 3 })));
+    assert_eq!(pipeline(2, 5, [0; 128], &digest), Err(SwifftError::Trit(TritError { index: 9, value: 5 })));
+    assert_eq!(pipeline(2, 1, [0xff; 128], &digest),
+        Err(SwifftError::OutOfRange(OutOfRange { index: 0, value: -1 })));
+    assert_eq!(pipeline(2, 1, [0; 128], "swifft:v2"), Err(SwifftError::Tag(TagError::Malformed)));
+}
+
+#[test]
+fn oversized_block_counts_are_reported() {
+    let max = isize::MAX as usize / 256;
+    assert_eq!(InputVec::try_zeroed(max + 1).map(|_| ()).map_err(SwifftError::from),
+        Err(SwifftError::BlockCountOverflow(BlockCountOverflow { max, actual: max + 1 })));
+    assert_eq!(InputVec::try_zeroed(usize::MAX).unwrap_err(), BlockCountOverflow { max, actual: usize::MAX });
+    assert_eq!(InputVec::try_zeroed(3).unwrap().len(), 3);
+}
+
+#[test]
+fn display_is_that_of_the_source() {
+    let errors: [SwifftError; 7] = [
+        LenMismatch { expected: 2, actual: 3 }.into(),
+        TritError { index: 9, value: 5 }.into(),
+        UnsupportedIsa { required: Isa::Avx2, detected: Isa::Avx }.into(),
+        StraySignBit { index: 12 }.into(),
+        BlockIndexOutOfRange { index: 4, num_blocks: 4 }.into(),
+        TooLong { max: 64, actual: 65 }.into(),
+        BlockCountOverflow { max: 2, actual: 3 }.into(),
+    ];
+    assert_eq!(errors[0].to_string(), LenMismatch { expected: 2, actual: 3 }.to_string());
+    assert_eq!(errors[1].to_string(), "value 5 at index 9 is not a trit");
+    assert_eq!(errors[2].to_string(), "the native SWIFFT library requires avx2, but the CPU only supports avx");
+    assert_eq!(errors[3].to_string(), "sign bit 12 is set on a zero coefficient");
+    assert_eq!(errors[4].to_string(), "block index 4 is out of range for 4 blocks");
+    assert_eq!(errors[5].to_string(), "too long: at most 64 bytes fit, got 65");
+    assert_eq!(errors[6].to_string(), "block count overflow: at most 2 blocks fit, got 3");
+    let boxed: Box<dyn std::error::Error> = Box::new(errors[1]);
+    assert_eq!(boxed.to_string(), errors[1].to_string());
+}
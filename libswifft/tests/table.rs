@@ -0,0 +1,80 @@
+//! Contiguous tables of compact hash values
+
+use std::fs::File;
+
+use libswifft::buffer::{AlignedBuffer, CompactOutputs, Inputs, Outputs};
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::hash::{compact_multiple, compute_and_compact_table, compute_multiple};
+use libswifft::table::DigestTable;
+
+const NUM_BLOCKS: usize = 150;
+
+fn inputs() -> Box<Inputs<NUM_BLOCKS>> {
+    let mut inputs = Box::<Inputs<NUM_BLOCKS>>::default();
+    for (i, block) in inputs.0.iter_mut().enumerate() {
+        block[..8].copy_from_slice(&(i as u64).to_le_bytes());
+    }
+    inputs
+}
+
+fn expected(inputs: &Inputs<NUM_BLOCKS>) -> Box<CompactOutputs<NUM_BLOCKS>> {
+    let mut outputs = Box::<Outputs<NUM_BLOCKS>>::default();
+    compute_multiple(inputs, &mut outputs);
+    let mut compact_outputs = Box::<CompactOutputs<NUM_BLOCKS>>::default();
+    compact_multiple(&outputs, &mut compact_outputs);
+    compact_outputs
+}
+
+#[test]
+fn table_matches_compute_then_compact() {
+    let inputs = inputs();
+    let expected = expected(&inputs);
+    let table = compute_and_compact_table(&inputs);
+    assert_eq!(table.as_flat_bytes(), expected.as_flat_bytes());
+    assert_eq!(table.as_flat_bytes().len(), NUM_BLOCKS * 64);
+    with_max_ffi_blocks(7, || {
+        assert_eq!(compute_and_compact_table(&inputs).as_flat_bytes(), expected.as_flat_bytes());
+    });
+
+    let table = DigestTable::compute(&inputs);
+    assert_eq!(table.len(), NUM_BLOCKS);
+    for (i, block) in expected.0.iter().enumerate() {
+        assert_eq!(&table.get(i).0[0], block);
+    }
+}
+
+#[test]
+fn sorted_table_finds_present_digests_only() {
+    let inputs = inputs();
+    let compact_outputs = compute_and_compact_table(&inputs);
+    let mut table = DigestTable::from_compact_outputs(&compact_outputs);
+    table.sort();
+    assert!((1..table.len()).all(|i| table.get(i - 1).0 <= table.get(i).0));
+
+    for block in compact_outputs.0.iter() {
+        let i = table.binary_search(&AlignedBuffer([*block])).unwrap();
+        assert_eq!(&table.get(i).0[0], block);
+    }
+
+    let mut absent = Inputs::<1>::default();
+    absent.0[0][..8].copy_from_slice(&u64::MAX.to_le_bytes());
+    let absent = DigestTable::compute(&absent);
+    assert!(table.binary_search(absent.get(0)).is_err());
+}
+
+#[test]
+fn table_round_trips_through_a_file() {
+    let mut table = DigestTable::compute(&*inputs());
+    table.sort();
+    let path = std::env::temp_dir().join(format!("libswifft-table-{}.bin", std::process::id()));
+    table.write_to(&mut File::create(&path).unwrap()).unwrap();
+    assert_eq!(std::fs::metadata(&path).unwrap().len(), (NUM_BLOCKS * 64) as u64);
+
+    let read = DigestTable::read_from(&mut File::open(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(read.len(), table.len());
+    assert!((0..table.len()).all(|i| read.get(i).0 == table.get(i).0));
+
+    let error = DigestTable::read_from(&mut &[0u8; 65][..]).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
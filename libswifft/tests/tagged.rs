@@ -0,0 +1,197 @@
+//! Compact hash values tagged with their mode and parameters
+
+use libswifft::arithmetic::{add, const_mul};
+use libswifft::buffer::{CompactOutput, Digest512, Input, Output};
+use libswifft::error::{LenMismatch, TagError};
+use libswifft::hash::{compact, compute, hash_bytes, Hasher};
+use libswifft::log::LogDigest;
+use libswifft::mac::SwifftMac;
+use libswifft::merkle::MerkleTree;
+use libswifft::tagged::{Mode, TaggedDigest, ENCODED_SIZE, PARAMETER_HASH};
+use libswifft::window::SlidingWindowHasher;
+
+fn digests() -> Vec<TaggedDigest> {
+    let mut log = LogDigest::new();
+    log.append(b"entry");
+    let mut window = SlidingWindowHasher::new(2);
+    window.push_block(&[0x5a; 256]);
+    vec![
+        TaggedDigest::plain(&Input::new(0x5a)),
+        TaggedDigest::signed(&Input::new(0x5a), &Input::new(0x0f)),
+        log.tagged_digest(),
+        window.tagged_digest(),
+        MerkleTree::build(&["alice", "bob"]).tagged_root(),
+        SwifftMac::new(&[7; 32]).finalize_tagged(),
+        TaggedDigest::chained(b"message"),
+        TaggedDigest::base4(&Input::new(0x5a), &Input::new(0x0f)),
+    ]
+}
+
+#[test]
+fn constructors_tag_their_mode() {
+    let modes: Vec<Mode> = digests().iter().map(TaggedDigest::mode).collect();
+    assert_eq!(modes, [
+        Mode::Plain, Mode::Signed, Mode::Log, Mode::Window, Mode::MerkleRoot, Mode::Keyed, Mode::Chained, Mode::Base4
+    ]);
+
+    let mut output = Output::default();
+    compute(&Input::new(0x5a), &mut output);
+    let mut expected = CompactOutput::default();
+    compact(&output, &mut expected);
+    assert_eq!(*TaggedDigest::plain(&Input::new(0x5a)).digest(), Digest512::from(expected));
+
+    let mut hasher = Hasher::new();
+    hasher.update(b"mess");
+    hasher.update(b"age");
+    assert_eq!(hasher.finalize_tagged().verify(&TaggedDigest::chained(b"message")), Ok(true));
+    assert_eq!(*TaggedDigest::chained(b"message").digest(), hash_bytes(b"message"));
+}
+
+#[test]
+fn base4_digests_combine_the_bit_planes() {
+    let (low, high) = (Input::new(0x5a), Input::new(0x0f));
+    // without high bits, the coefficients are those of the binary input
+    let base4 = TaggedDigest::base4(&low, &Input::default());
+    assert_eq!(base4.digest(), TaggedDigest::plain(&low).digest());
+    assert_eq!(base4.verify(&TaggedDigest::plain(&low)), Err(TagError::ModeMismatch { expected: Mode::Base4, actual: Mode::Plain }));
+
+    // the native hash is linear, e.g. over the disjoint bits of `0x50` and `0x0a`
+    let (mut sum, mut operand, mut union) = (Output::default(), Output::default(), Output::default());
+    compute(&Input::new(0x50), &mut sum);
+    compute(&Input::new(0x0a), &mut operand);
+    add(&mut sum, &operand);
+    compute(&low, &mut union);
+    assert_eq!(sum, union);
+
+    // coefficients of `1`, `2` and `3` where only the low bit, only the high bit and both bits are set
+    let mut expected = Output::default();
+    for (bits, coefficient) in [(0x5a & !0x0f, 1), (0x0f & !0x5a, 2), (0x5a & 0x0f, 3)] {
+        compute(&Input::new(bits), &mut operand);
+        const_mul(&mut operand, coefficient);
+        add(&mut expected, &operand);
+    }
+    assert_eq!(*TaggedDigest::base4(&low, &high).digest(), expected.compact());
+}
+
+#[test]
+fn binary_encoding_round_trips() {
+    for digest in digests() {
+        let bytes = digest.encode();
+        assert_eq!(bytes[0], digest.mode().byte());
+        assert_eq!(bytes[1..9], PARAMETER_HASH.to_le_bytes());
+        assert_eq!(bytes[9..], *digest.digest().as_bytes());
+
+        let decoded = TaggedDigest::decode(&bytes).unwrap();
+        assert_eq!(decoded.verify(&digest), Ok(true));
+        assert_eq!(decoded.encode(), bytes);
+    }
+}
+
+#[test]
+fn string_encoding_round_trips() {
+    for digest in digests() {
+        let encoded = digest.to_string();
+        let mut hex = [0; 128];
+        assert_eq!(encoded, format!("swifft:v1:{}:{}", digest.mode(), digest.digest().write_hex(&mut hex)));
+
+        let decoded: TaggedDigest = encoded.parse().unwrap();
+        assert_eq!(decoded.verify(&digest), Ok(true));
+        assert_eq!(decoded.to_string(), encoded);
+    }
+}
+
+#[test]
+fn encodings_are_frozen() {
+    assert_eq!(PARAMETER_HASH, 0x3979a7e7bb9043c9);
+    let modes = [
+        Mode::Plain, Mode::Signed, Mode::Log, Mode::Window, Mode::Registry, Mode::MerkleRoot, Mode::Keyed, Mode::Chained,
+        Mode::Base4,
+    ];
+    assert_eq!(modes.map(Mode::byte), [1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    assert_eq!(modes.map(Mode::name), [
+        "plain", "signed", "log", "window", "registry", "merkle-root", "keyed", "chained", "base4"
+    ]);
+    assert!(TaggedDigest::plain(&Input::new(0x5a)).to_string().starts_with("swifft:v1:plain:"));
+}
+
+#[test]
+fn comparing_different_modes_is_refused() {
+    let digests = digests();
+    for a in &digests {
+        for b in &digests {
+            if a.mode() == b.mode() {
+                assert_eq!(a.verify(b), Ok(true));
+            } else {
+                assert_eq!(a.verify(b), Err(TagError::ModeMismatch { expected: a.mode(), actual: b.mode() }));
+            }
+        }
+    }
+
+    // the same compact hash value, retagged, is still refused
+    let plain = TaggedDigest::plain(&Input::new(0x5a));
+    let retagged = TaggedDigest::new(Mode::Log, plain.digest().clone());
+    assert_eq!(plain.verify(&retagged), Err(TagError::ModeMismatch { expected: Mode::Plain, actual: Mode::Log }));
+}
+
+#[test]
+fn roots_are_not_plain_digests_of_the_same_bytes() {
+    // the root of a single leaf is the digest of the leaf, which is all that the untagged values compare
+    let leaf = [0x5a; 256];
+    let tree = MerkleTree::build(&[leaf]);
+    let plain = TaggedDigest::new(Mode::Plain, tree.root());
+    assert_eq!(plain.digest(), tree.tagged_root().digest());
+    assert_eq!(tree.tagged_root().verify(&plain), Err(TagError::ModeMismatch { expected: Mode::MerkleRoot, actual: Mode::Plain }));
+    assert_eq!(TaggedDigest::plain(&Input::new(0x5a)).verify(&tree.tagged_root()).unwrap_err(),
+        TagError::ModeMismatch { expected: Mode::Plain, actual: Mode::MerkleRoot });
+
+    let decoded: TaggedDigest = tree.tagged_root().to_string().parse().unwrap();
+    assert_eq!(decoded.mode(), Mode::MerkleRoot);
+    assert!(tree.tagged_root().to_string().starts_with("swifft:v1:merkle-root:"));
+}
+
+#[test]
+fn different_digests_of_one_mode_do_not_verify() {
+    let a = TaggedDigest::plain(&Input::new(0x5a));
+    let b = TaggedDigest::plain(&Input::new(0x5b));
+    assert_eq!(a.verify(&b), Ok(false));
+}
+
+#[test]
+fn unknown_modes_are_rejected() {
+    let mut bytes = TaggedDigest::plain(&Input::new(0x5a)).encode();
+    for byte in [0, 10, 0x80, 0xff] {
+        bytes[0] = byte;
+        assert_eq!(TaggedDigest::decode(&bytes).unwrap_err(), TagError::UnknownMode(byte));
+        assert_eq!(Mode::from_byte(byte), Err(TagError::UnknownMode(byte)));
+    }
+
+    let encoded = TaggedDigest::plain(&Input::new(0x5a)).to_string();
+    let unknown = encoded.replace(":plain:", ":tree-root:");
+    assert_eq!(unknown.parse::<TaggedDigest>().unwrap_err(), TagError::UnknownModeName);
+}
+
+#[test]
+fn malformed_encodings_are_rejected() {
+    let bytes = TaggedDigest::plain(&Input::new(0x5a)).encode();
+    assert_eq!(TaggedDigest::decode(&bytes[1..]).unwrap_err(),
+        TagError::LenMismatch(LenMismatch { expected: ENCODED_SIZE, actual: ENCODED_SIZE - 1 }));
+
+    let mut other_parameters = bytes;
+    other_parameters[1] ^= 1;
+    let hash = PARAMETER_HASH ^ 1;
+    assert_eq!(TaggedDigest::decode(&other_parameters).unwrap_err(), TagError::UnknownParameters(hash));
+
+    let encoded = TaggedDigest::plain(&Input::new(0x5a)).to_string();
+    let malformed = [
+        encoded.replace("swifft:v1:", "swifft:v2:"),
+        encoded.replace("swifft:", ""),
+        encoded.to_uppercase().replace("SWIFFT:V1:PLAIN:", "swifft:v1:plain:"),
+        encoded[..encoded.len() - 2].to_string(),
+        format!("{}00", encoded),
+        encoded.replace(":plain:", ":plain"),
+        format!("{}g", &encoded[..encoded.len() - 1]),
+    ];
+    for encoded in malformed {
+        assert_eq!(encoded.parse::<TaggedDigest>().unwrap_err(), TagError::Malformed, "{}", encoded);
+    }
+}
@@ -0,0 +1,97 @@
+//! Signed hashing of coefficients in `{-1, 0, 1}`, and their packing into inputs and sign bits
+
+use libswifft::arithmetic::add;
+use libswifft::buffer::{Input, InputBuilder, Output, SignInput};
+use libswifft::constant::INPUT_SIZE;
+use libswifft::error::TritError;
+use libswifft::hash::{compute, compute_signed, compute_trits, pack_trits, unpack_trits};
+use proptest::prelude::*;
+
+fn trits() -> impl Strategy<Value = [i8; INPUT_SIZE]> {
+    prop::collection::vec(-1..=1i8, INPUT_SIZE).prop_map(|trits| trits.try_into().unwrap())
+}
+
+fn unit(index: usize, value: i8) -> [i8; INPUT_SIZE] {
+    let mut trits = [0; INPUT_SIZE];
+    trits[index] = value;
+    trits
+}
+
+#[test]
+fn trits_are_coefficients_of_the_input_vectors() {
+    for (j, k) in [(0, 0), (0, 7), (3, 9), (31, 63)] {
+        let mut bits = [false; 64];
+        bits[k] = true;
+        let (input, sign_input) = pack_trits(&unit(j * 64 + k, 1)).unwrap();
+        assert_eq!(input.0, InputBuilder::new().set_vector(j, &bits).build().0);
+        assert!(sign_input.0[0].iter().all(|&byte| byte == 0));
+    }
+}
+
+#[test]
+fn negative_trits_negate_their_contribution() {
+    for index in [0, 1, 100, 1000, INPUT_SIZE - 1] {
+        let mut sum = compute_trits(&unit(index, 1)).unwrap();
+        add(&mut sum, &compute_trits(&unit(index, -1)).unwrap());
+        assert_eq!(sum.0, Output::default().0, "trit {}", index);
+    }
+}
+
+#[test]
+fn non_trits_are_rejected() {
+    let mut trits = unit(5, -1);
+    trits[17] = 2;
+    trits[18] = -2;
+    assert_eq!(compute_trits(&trits).unwrap_err(), TritError { index: 17, value: 2 });
+    assert_eq!(pack_trits(&unit(2047, i8::MIN)).unwrap_err(), TritError { index: 2047, value: i8::MIN });
+    assert_eq!(TritError { index: 17, value: 2 }.to_string(), "value 2 at index 17 is not a trit");
+}
+
+#[test]
+fn sign_bits_of_zero_trits_are_ignored() {
+    let (input, _) = pack_trits(&unit(9, 1)).unwrap();
+    let (_, sign_input) = pack_trits(&[-1; INPUT_SIZE]).unwrap();
+    assert_eq!(unpack_trits(&input, &sign_input), unit(9, -1));
+    let mut signed = Output::default();
+    compute_signed(&input, &sign_input, &mut signed);
+    assert_eq!(signed.0, compute_trits(&unit(9, -1)).unwrap().0);
+}
+
+#[test]
+fn signs_are_sign_bits() {
+    let mut signs = [false; INPUT_SIZE];
+    signs[3] = true;
+    signs[2047] = true;
+    let sign_input = SignInput::from_signs(&signs);
+    assert_eq!((sign_input.0[0][0], sign_input.0[0][255]), (0x08, 0x80));
+    let (_, packed) = pack_trits(&signs.map(|negative| if negative { -1 } else { 1 })).unwrap();
+    assert_eq!(sign_input, packed);
+
+    // all positive signs hash as plain inputs
+    let input = Input::new(0x96);
+    let (mut signed, mut unsigned) = (Output::default(), Output::default());
+    compute_signed(&input, &SignInput::from_signs(&[false; INPUT_SIZE]), &mut signed);
+    compute(&input, &mut unsigned);
+    assert_eq!(signed, unsigned);
+}
+
+proptest! {
+    #[test]
+    fn pack_and_unpack_round_trip(trits in trits()) {
+        let (input, sign_input) = pack_trits(&trits).unwrap();
+        prop_assert_eq!(unpack_trits(&input, &sign_input), trits);
+        let mut expected = Output::default();
+        compute_signed(&input, &sign_input, &mut expected);
+        prop_assert_eq!(compute_trits(&trits).unwrap().0, expected.0);
+    }
+
+    #[test]
+    fn non_negative_trits_hash_as_unsigned_inputs(trits in trits()) {
+        let trits = trits.map(i8::abs);
+        let (input, sign_input) = pack_trits(&trits).unwrap();
+        prop_assert!(sign_input.0[0].iter().all(|&byte| byte == 0));
+        let mut unsigned = Output::default();
+        compute(&input, &mut unsigned);
+        prop_assert_eq!(compute_trits(&trits).unwrap().0, unsigned.0);
+    }
+}
@@ -0,0 +1,29 @@
+//! Hashing into uninitialized output buffers
+
+use std::mem::MaybeUninit;
+
+use libswifft::batch::with_max_ffi_blocks;
+use libswifft::buffer::{Inputs, Outputs};
+use libswifft::hash::{compute_multiple, compute_multiple_uninit};
+
+#[test]
+fn uninit_matches_initialized_output() {
+    let input = Inputs::<64>::from_seed(64);
+    let mut expected = Outputs::<64>::default();
+    compute_multiple(&input, &mut expected);
+
+    let mut output = MaybeUninit::<Outputs<64>>::uninit();
+    assert_eq!(compute_multiple_uninit(&input, &mut output).0, expected.0);
+}
+
+#[test]
+fn boxed_uninit_is_filled_across_chunks() {
+    let input = Inputs::<64>::from_seed(64);
+    let mut expected = Outputs::<64>::default();
+    compute_multiple(&input, &mut expected);
+
+    let mut output = Outputs::<64>::new_boxed_uninit();
+    with_max_ffi_blocks(5, || {
+        assert_eq!(compute_multiple_uninit(&input, &mut output).0, expected.0);
+    });
+}
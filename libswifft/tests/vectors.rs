@@ -0,0 +1,88 @@
+//! Checks the native backend against the checked-in test vectors under `libswifft/vectors/`.
+//!
+//! A failure here means the output of a hashing mode changed; if that was deliberate,
+//! regenerate the vectors with `cargo xtask gen-vectors --force`.
+
+use libswifft::buffer::{CompactOutput, Input, Output};
+use libswifft::error::SelfTestError;
+use libswifft::hash::{compact, compute, compute_signed};
+use libswifft::testvectors::{known_answers, self_test, Expected, KnownAnswer, VECTORS_CSV};
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+fn input(hex: &str) -> Input {
+    let mut input = Input::default();
+    input.0[0].copy_from_slice(&from_hex(hex));
+    input
+}
+
+#[test]
+fn native_backend_matches_vectors() {
+    let vectors = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/vectors/libswifft.csv")).unwrap();
+    let mut checked = 0;
+    for (line_number, line) in vectors.lines().enumerate().skip(1) {
+        let [mode, input_hex, sign_hex, expected] = line.split(',').collect::<Vec<_>>()[..] else {
+            panic!("malformed vector on line {}", line_number + 1)
+        };
+        let mut output = Output::default();
+        let actual = match mode {
+            "compute" => {
+                compute(&input(input_hex), &mut output);
+                format!("{:x}", output)
+            }
+            "compact" => {
+                compute(&input(input_hex), &mut output);
+                let mut compact_output = CompactOutput::default();
+                compact(&output, &mut compact_output);
+                format!("{:x}", compact_output)
+            }
+            "compute_signed" => {
+                compute_signed(&input(input_hex), &input(sign_hex), &mut output);
+                format!("{:x}", output)
+            }
+            "compact_signed" => {
+                compute_signed(&input(input_hex), &input(sign_hex), &mut output);
+                let mut compact_output = CompactOutput::default();
+                compact(&output, &mut compact_output);
+                format!("{:x}", compact_output)
+            }
+            mode => panic!("unknown mode {} on line {}", mode, line_number + 1),
+        };
+        assert_eq!(actual, expected, "{} vector on line {} changed", mode, line_number + 1);
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
+
+#[test]
+fn embedded_vectors_parse_in_order() {
+    let known_answers = known_answers();
+    assert_eq!(known_answers.len(), VECTORS_CSV.lines().count() - 1);
+    for (vector, line) in known_answers.iter().zip(VECTORS_CSV.lines().skip(1)) {
+        assert!(line.starts_with(&format!("{},", vector.mode())));
+    }
+    for mode in ["compute", "compact", "compute_signed", "compact_signed"] {
+        assert!(known_answers.iter().any(|vector| vector.mode() == mode));
+    }
+}
+
+#[test]
+fn self_test_passes() {
+    assert_eq!(self_test(), Ok(()));
+    assert!(known_answers().iter().all(KnownAnswer::check));
+}
+
+#[test]
+fn tampered_vectors_fail() {
+    for mut vector in known_answers() {
+        match &mut vector.expected {
+            Expected::Output(output) => output.0[0][5] ^= 1,
+            Expected::CompactOutput(compact_output) => compact_output.0[0][5] ^= 1,
+        }
+        assert!(!vector.check(), "{}", vector.mode());
+    }
+    let err = SelfTestError::Mismatch { index: 3, mode: "compact" };
+    assert_eq!(err.to_string(), "the native SWIFFT library failed compact test vector 3");
+}
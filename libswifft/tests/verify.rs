@@ -0,0 +1,54 @@
+//! Checking data against expected digests
+
+mod common;
+
+use std::io::{self, Read};
+
+use libswifft::buffer::Digest512;
+use libswifft::hash::{hash_bytes, verify, verify_reader};
+
+use common::message;
+
+#[test]
+fn matching_digests_verify() {
+    for data in [&b""[..], b"a", &message(1000)] {
+        let digest = hash_bytes(data);
+        assert!(verify(data, &digest));
+        assert!(verify_reader(data, &digest).unwrap());
+    }
+}
+
+#[test]
+fn any_flipped_bit_fails() {
+    let data = message(1000);
+    let digest = hash_bytes(&data);
+    for byte in [0, 254, 255, 999] {
+        for bit in [0, 7] {
+            let mut tampered = data.clone();
+            tampered[byte] ^= 1 << bit;
+            assert!(!verify(&tampered, &digest), "data byte {} bit {}", byte, bit);
+            assert!(!verify_reader(&tampered[..], &digest).unwrap());
+        }
+    }
+    for byte in 0..64 {
+        for bit in [0, 3, 7] {
+            let mut bytes = *digest.as_bytes();
+            bytes[byte] ^= 1 << bit;
+            let tampered = Digest512::from(bytes);
+            assert!(!verify(&data, &tampered), "digest byte {} bit {}", byte, bit);
+            assert!(!verify_reader(&data[..], &tampered).unwrap());
+        }
+    }
+}
+
+#[test]
+fn reader_errors_are_returned() {
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::other("disk on fire"))
+        }
+    }
+    let err = verify_reader(Failing, &hash_bytes(b"")).unwrap_err();
+    assert_eq!(err.to_string(), "disk on fire");
+}
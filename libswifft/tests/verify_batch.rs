@@ -0,0 +1,71 @@
+//! Verifying many input blocks against their expected hash values at once
+
+use libswifft::buffer::{Input, Output};
+use libswifft::hash::{compute, verify_batch};
+
+fn pairs(count: usize) -> Vec<(Input, Output)> {
+    (0..count)
+        .map(|i| {
+            let mut input = Input::default();
+            input.0[0] = std::array::from_fn(|j| (i * 7 + j * 13) as u8);
+            let mut output = Output::default();
+            compute(&input, &mut output);
+            (input, output)
+        })
+        .collect()
+}
+
+/// Flips a bit of the expected hash value of every pair at `indices`
+fn corrupt(pairs: &mut [(Input, Output)], indices: &[usize]) {
+    for &index in indices {
+        pairs[index].1.0[0][index % 128] ^= 1;
+    }
+}
+
+#[test]
+fn intact_batches_verify() {
+    for count in [0, 1, 63, 64, 65, 200] {
+        assert_eq!(verify_batch(&pairs(count)), Ok(()), "{} pairs", count);
+    }
+}
+
+#[test]
+fn a_single_corruption_is_reported() {
+    for index in [0, 63, 64, 199] {
+        let mut pairs = pairs(200);
+        corrupt(&mut pairs, &[index]);
+        assert_eq!(verify_batch(&pairs), Err(vec![index]));
+    }
+}
+
+#[test]
+fn every_corruption_is_reported_in_order() {
+    let corrupted = [0, 1, 62, 63, 64, 127, 128, 150, 199];
+    let mut pairs = pairs(200);
+    corrupt(&mut pairs, &corrupted);
+    assert_eq!(verify_batch(&pairs), Err(corrupted.to_vec()));
+
+    // a corrupted input rather than hash value is reported just the same
+    let mut pairs = self::pairs(70);
+    pairs[66].0.0[0][0] ^= 1;
+    assert_eq!(verify_batch(&pairs), Err(vec![66]));
+}
+
+#[test]
+fn a_single_pair_is_verified_alone() {
+    let mut pairs = pairs(1);
+    assert_eq!(verify_batch(&pairs), Ok(()));
+    corrupt(&mut pairs, &[0]);
+    assert_eq!(verify_batch(&pairs), Err(vec![0]));
+}
+
+#[test]
+fn a_partial_last_chunk_is_verified_alone() {
+    // the last chunk holds a single pair, after a full chunk whose blocks are left in the scratch buffers
+    let mut pairs = pairs(65);
+    assert_eq!(verify_batch(&pairs), Ok(()));
+    corrupt(&mut pairs, &[64]);
+    assert_eq!(verify_batch(&pairs), Err(vec![64]));
+    corrupt(&mut pairs, &[0]);
+    assert_eq!(verify_batch(&pairs), Err(vec![0, 64]));
+}
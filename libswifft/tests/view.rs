@@ -0,0 +1,110 @@
+//! Borrowed views of foreign byte buffers, hashed in place
+
+use libswifft::buffer::{AlignedBuffer, Input, Inputs, Output, Outputs};
+use libswifft::constant::BUFFER_ALIGNMENT;
+use libswifft::error::{LenMismatch, ViewError};
+use libswifft::hash::{compute, compute_multiple, compute_multiple_ref, compute_ref};
+use libswifft::view::{try_cast_input_slice, InputRef, InputsRef, OutputMut, OutputsMut};
+
+const NUM_BLOCKS: usize = 5;
+
+/// An aligned backing buffer standing in for a foreign one, e.g. a network stack's receive buffer
+fn foreign_inputs() -> Box<Inputs<{ NUM_BLOCKS + 1 }>> {
+    Box::new(Inputs::from_seed(NUM_BLOCKS as u64))
+}
+
+#[test]
+fn compute_ref_matches_compute() {
+    let foreign = foreign_inputs();
+    let mut foreign_output = Outputs::<1>::default();
+    let bytes = &foreign.0.as_flattened()[..256];
+    compute_ref(InputRef::new(bytes).unwrap(), OutputMut::new(foreign_output.0.as_flattened_mut()).unwrap());
+
+    let mut expected = Output::default();
+    compute(&AlignedBuffer([bytes.try_into().unwrap()]), &mut expected);
+    assert_eq!(foreign_output.0, expected.0);
+}
+
+#[test]
+fn compute_multiple_ref_matches_compute_multiple() {
+    let foreign = foreign_inputs();
+    let mut foreign_output = Outputs::<NUM_BLOCKS>::default();
+    let bytes = &foreign.0.as_flattened()[..NUM_BLOCKS * 256];
+    let input = InputsRef::new(bytes).unwrap();
+    let output = OutputsMut::new(foreign_output.0.as_flattened_mut()).unwrap();
+    assert_eq!((input.len(), output.len()), (NUM_BLOCKS, NUM_BLOCKS));
+    compute_multiple_ref(input, output).unwrap();
+
+    let mut owned = Inputs::<NUM_BLOCKS>::default();
+    owned.0.as_flattened_mut().copy_from_slice(bytes);
+    let mut expected = Outputs::<NUM_BLOCKS>::default();
+    compute_multiple(&owned, &mut expected);
+    assert_eq!(foreign_output.0, expected.0);
+}
+
+#[test]
+fn casting_input_slices_does_not_copy() {
+    let foreign = foreign_inputs();
+    let bytes = &foreign.0.as_flattened()[256..];
+    let inputs = try_cast_input_slice(bytes).unwrap();
+    assert_eq!(inputs.len(), NUM_BLOCKS);
+    assert_eq!(inputs.as_ptr().cast::<u8>(), bytes.as_ptr());
+    assert_eq!(inputs[2].as_bytes(), &bytes[2 * 256..3 * 256]);
+    assert!(try_cast_input_slice(&bytes[..0]).unwrap().is_empty());
+
+    // misaligned sources take the copying path
+    let misaligned = &foreign.0.as_flattened()[7..][..256];
+    assert_eq!(try_cast_input_slice(misaligned).unwrap_err(),
+        ViewError::Misaligned { address: misaligned.as_ptr() as usize, alignment: BUFFER_ALIGNMENT });
+    assert_eq!(Input::try_from(misaligned).unwrap().as_bytes(), misaligned);
+    assert_eq!(try_cast_input_slice(&bytes[..257]).unwrap_err(), ViewError::PartialBlock { block_size: 256, len: 257 });
+}
+
+#[test]
+fn misaligned_buffers_are_rejected() {
+    let foreign = foreign_inputs();
+    let bytes = &foreign.0.as_flattened()[1..];
+    let misaligned = ViewError::Misaligned { address: bytes.as_ptr() as usize, alignment: BUFFER_ALIGNMENT };
+    assert_eq!(InputRef::new(&bytes[..256]).unwrap_err(), misaligned);
+    assert_eq!(InputsRef::new(&bytes[..2 * 256]).unwrap_err(), misaligned);
+
+    let mut foreign_output = Outputs::<2>::default();
+    let bytes = &mut foreign_output.0.as_flattened_mut()[BUFFER_ALIGNMENT / 2..];
+    let misaligned = ViewError::Misaligned { address: bytes.as_ptr() as usize, alignment: BUFFER_ALIGNMENT };
+    assert_eq!(OutputMut::new(&mut bytes[..128]).unwrap_err(), misaligned);
+    assert_eq!(OutputsMut::new(&mut bytes[..128]).unwrap_err(), misaligned);
+}
+
+#[test]
+fn wrongly_sized_buffers_are_rejected() {
+    let foreign = foreign_inputs();
+    let bytes = foreign.0.as_flattened();
+    assert_eq!(InputRef::new(&bytes[..255]).unwrap_err(),
+        ViewError::LenMismatch(LenMismatch { expected: 256, actual: 255 }));
+    assert_eq!(InputsRef::new(&bytes[..300]).unwrap_err(), ViewError::PartialBlock { block_size: 256, len: 300 });
+
+    let mut foreign_output = Outputs::<2>::default();
+    let bytes = foreign_output.0.as_flattened_mut();
+    assert_eq!(OutputMut::new(bytes).unwrap_err(), ViewError::LenMismatch(LenMismatch { expected: 128, actual: 256 }));
+    assert_eq!(OutputsMut::new(&mut bytes[..200]).unwrap_err(), ViewError::PartialBlock { block_size: 128, len: 200 });
+}
+
+#[test]
+fn mismatched_views_are_rejected_without_writing() {
+    let foreign = foreign_inputs();
+    let mut foreign_output = Outputs::<2>::default();
+    let input = InputsRef::new(&foreign.0.as_flattened()[..3 * 256]).unwrap();
+    let output = OutputsMut::new(foreign_output.0.as_flattened_mut()).unwrap();
+    assert_eq!(compute_multiple_ref(input, output), Err(LenMismatch { expected: 3, actual: 2 }));
+    assert_eq!(foreign_output.0, Outputs::<2>::default().0);
+}
+
+#[test]
+fn empty_views_need_no_alignment() {
+    let unaligned = [0u8; 1];
+    let input = InputsRef::new(&unaligned[1..]).unwrap();
+    assert!(input.is_empty());
+    let empty: &[Input] = input.as_inputs();
+    assert!(empty.is_empty());
+    assert_eq!(compute_multiple_ref(input, OutputsMut::new(&mut []).unwrap()), Ok(()));
+}
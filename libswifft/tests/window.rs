@@ -0,0 +1,42 @@
+//! Sliding window digests against recomputing the sum over the current window
+
+use libswifft::arithmetic::add;
+use libswifft::buffer::{AlignedBuffer, Output};
+use libswifft::hash::compute;
+use libswifft::window::SlidingWindowHasher;
+use proptest::prelude::*;
+
+fn window_sum(blocks: &[[u8; 256]]) -> Output {
+    let mut sum = Output::default();
+    for block in blocks {
+        let mut hash = Output::default();
+        compute(&AlignedBuffer([*block]), &mut hash);
+        add(&mut sum, &hash);
+    }
+    sum
+}
+
+fn stream() -> impl Strategy<Value = Vec<[u8; 256]>> {
+    prop::collection::vec(prop::collection::vec(any::<u8>(), 256), 0..16)
+        .prop_map(|blocks| blocks.into_iter().map(|block| block.try_into().unwrap()).collect())
+}
+
+proptest! {
+    #[test]
+    fn sliding_digest_matches_recomputed_window(window_blocks in 1usize..6, stream in stream()) {
+        let mut hasher = SlidingWindowHasher::new(window_blocks);
+        prop_assert_eq!(hasher.digest().to_centered(), Output::default().to_centered());
+        for (pushed, block) in stream.iter().enumerate() {
+            hasher.push_block(block);
+            let window = &stream[(pushed + 1).saturating_sub(window_blocks)..=pushed];
+            prop_assert_eq!(hasher.len(), window.len());
+            prop_assert_eq!(hasher.digest().to_centered(), window_sum(window).to_centered());
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn empty_window_is_rejected() {
+    SlidingWindowHasher::new(0);
+}
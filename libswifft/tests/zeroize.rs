@@ -0,0 +1,69 @@
+//! Wiping buffers that hold secrets:
+//! `cargo test -p libswifft --features zeroize --test zeroize`
+#![cfg(feature = "zeroize")]
+
+use libswifft::buffer::secret::{SecretInput, SecretSignInput};
+use libswifft::buffer::{CompactOutput, Input, Inputs, Output, Outputs};
+use libswifft::commit::commit;
+use libswifft::hash::{compute, compute_signed, hash_bytes, Hasher};
+use libswifft::mac::SwifftMac;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+#[test]
+fn zeroize_wipes_every_chunk() {
+    let mut inputs = Inputs::<3>::new(0xa5);
+    inputs.zeroize();
+    assert_eq!(inputs, Inputs::default());
+
+    let mut outputs = Outputs::<2>::new(0xff);
+    outputs.zeroize();
+    assert!(outputs.as_bytes().iter().all(|&byte| byte == 0));
+
+    let mut compact_output = CompactOutput::new(1);
+    compact_output.zeroize();
+    assert_eq!(compact_output, CompactOutput::default());
+}
+
+#[test]
+fn secret_inputs_hash_as_their_inputs() {
+    fn assert_zeroize_on_drop<T: ZeroizeOnDrop>() {}
+    assert_zeroize_on_drop::<SecretInput>();
+
+    let (input, sign_input) = (SecretInput::from(Input::new(0x0f)), SecretSignInput::from(Input::new(0x05)));
+    let mut expected = Output::default();
+    compute_signed(&Input::new(0x0f), &Input::new(0x05), &mut expected);
+    let mut output = Output::default();
+    compute_signed(&input, &sign_input, &mut output);
+    assert_eq!(output, expected);
+
+    let mut secret = SecretInput::new();
+    secret.0[0][..6].copy_from_slice(b"secret");
+    compute(&secret, &mut output);
+    secret.zeroize();
+    assert_eq!(*secret, Input::default());
+}
+
+#[test]
+fn wrappers_of_secrets_still_agree() {
+    let key = [3; 32];
+    let mut mac = SwifftMac::new(&key);
+    mac.update(b"message");
+    let tag = mac.clone().finalize();
+    assert!(mac.verify(&tag));
+    assert_eq!(commit(b"message", &[1; 32]), commit(b"message", &[1; 32]));
+}
+
+#[test]
+fn wiping_hashers_still_agree() {
+    // spans several batches of buffered bytes, with a clone whose buffer must be reallocated mid-message
+    let message: Vec<u8> = (0..40_000u32).map(|i| (i % 251) as u8).collect();
+    let mut hasher = Hasher::new();
+    hasher.update(&message[..1000]);
+    let mut clone = hasher.clone();
+    for piece in message[1000..].chunks(7000) {
+        hasher.update(piece);
+        clone.update(piece);
+    }
+    assert_eq!(hasher.finalize().compact(), hash_bytes(&message));
+    assert_eq!(clone.finalize().compact(), hash_bytes(&message));
+}
@@ -0,0 +1,23 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use swifft::hash::bitsliced::swifft_hash_bitsliced;
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+
+fn bitsliced(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bitsliced");
+    for num_blocks in [64, 1024, 16384] {
+        let blocks: Vec<[u8; INPUT_BLOCK_SIZE]> = (0..num_blocks)
+            .map(|b| std::array::from_fn(|i| ((b * INPUT_BLOCK_SIZE + i) * 131 % 251) as u8))
+            .collect();
+        group.throughput(Throughput::Elements(num_blocks as u64));
+        group.bench_with_input(BenchmarkId::new("transposed", num_blocks), &blocks, |b, blocks| {
+            b.iter(|| swifft_hash_bitsliced(black_box(blocks)))
+        });
+        group.bench_with_input(BenchmarkId::new("fft", num_blocks), &blocks, |b, blocks| {
+            b.iter(|| black_box(blocks).iter().map(|block| swifft_hash(&parse_input_block(block))).collect::<Vec<_>>())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bitsliced);
+criterion_main!(benches);
@@ -0,0 +1,45 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_owned, swifft_hash_signed, swifft_hash_signed_multiple,
+                   swifft_hash_signed_multiple_bytes, INPUT_BLOCK_SIZE};
+
+fn hash(c: &mut Criterion) {
+    let mut block = [0u8; INPUT_BLOCK_SIZE];
+    for (i, byte) in block.iter_mut().enumerate() {
+        *byte = (i * 131 % 251) as u8;
+    }
+    let input = parse_input_block(&block);
+
+    let mut group = c.benchmark_group("hash");
+    group.bench_function("borrowed", |b| b.iter(|| swifft_hash(black_box(&input))));
+    group.bench_function("owned", |b| b.iter(|| swifft_hash_owned(black_box(input))));
+    // the former borrowing path: copy the whole input, then transform the copy in place
+    group.bench_function("copy_then_owned", |b| b.iter(|| {
+        let copy = *black_box(&input);
+        swifft_hash_owned(black_box(copy))
+    }));
+    group.finish();
+}
+
+fn signed(c: &mut Criterion) {
+    const NUM_BLOCKS: usize = 64;
+    let mut input = vec![0u8; NUM_BLOCKS * INPUT_BLOCK_SIZE];
+    let mut sign = vec![0u8; NUM_BLOCKS * INPUT_BLOCK_SIZE];
+    for (i, (input, sign)) in input.iter_mut().zip(&mut sign).enumerate() {
+        *input = (i * 131 % 251) as u8;
+        *sign = (i * 47 % 241) as u8;
+    }
+    let pairs: Vec<_> = input.chunks_exact(INPUT_BLOCK_SIZE).zip(sign.chunks_exact(INPUT_BLOCK_SIZE))
+        .map(|(input, sign)| (parse_input_block(input.try_into().unwrap()), sign.try_into().unwrap()))
+        .collect();
+
+    let mut group = c.benchmark_group("signed");
+    group.bench_function("single_loop", |b| b.iter(|| {
+        black_box(&pairs).iter().map(|(input, sign)| swifft_hash_signed(input, sign)).collect::<Vec<_>>()
+    }));
+    group.bench_function("multiple", |b| b.iter(|| swifft_hash_signed_multiple(black_box(&pairs))));
+    group.bench_function("multiple_bytes", |b| b.iter(|| swifft_hash_signed_multiple_bytes(black_box(&input), black_box(&sign))));
+    group.finish();
+}
+
+criterion_group!(benches, hash, signed);
+criterion_main!(benches);
@@ -0,0 +1,39 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use swifft::polynomial::Polynomial;
+
+/// Polynomial with `weight` nonzero coefficients spread evenly over its degree
+fn polynomial_with_weight(weight: usize) -> Polynomial {
+    let mut coefficients = [0u16; Polynomial::N];
+    for i in 0..weight {
+        coefficients[i * Polynomial::N / weight] = (i as u16 * 37) % 256 + 1;
+    }
+    Polynomial::from_coefficients(&coefficients)
+}
+
+fn dense_polynomial() -> Polynomial {
+    let mut coefficients = [0u16; Polynomial::N];
+    for (i, coefficient) in coefficients.iter_mut().enumerate() {
+        *coefficient = (i as u16 * 101 + 7) % 256 + 1;
+    }
+    Polynomial::from_coefficients(&coefficients)
+}
+
+fn multiplication(c: &mut Criterion) {
+    let dense = dense_polynomial();
+    let mut group = c.benchmark_group("multiplication");
+    for weight in [1, 2, 4, 8, 12, 16, 24, 32, 48, 64] {
+        let sparse = polynomial_with_weight(weight);
+        group.bench_with_input(BenchmarkId::new("sparse_mul", weight), &sparse,
+            |b, sparse| b.iter(|| black_box(sparse).sparse_mul(black_box(&dense))));
+        group.bench_with_input(BenchmarkId::new("naive_mul", weight), &sparse,
+            |b, sparse| b.iter(|| black_box(sparse).naive_mul(black_box(&dense))));
+        group.bench_with_input(BenchmarkId::new("fft_mul", weight), &sparse,
+            |b, sparse| b.iter(|| black_box(sparse).fft_mul(black_box(&dense))));
+        group.bench_with_input(BenchmarkId::new("auto_mul", weight), &sparse,
+            |b, sparse| b.iter(|| black_box(sparse).auto_mul(black_box(&dense))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, multiplication);
+criterion_main!(benches);
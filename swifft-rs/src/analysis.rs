@@ -0,0 +1,44 @@
+//! Statistics over many digests, for checking experimentally that SWIFFT outputs look uniform
+//!
+//! [`element_histogram`] counts the elements of $\mathbb{Z}_{257}$ at every coefficient of a set of polynomials,
+//! e.g. the digests of many inputs, and [`chi_squared_per_element`] tests each coefficient's counts against
+//! the uniform distribution. These are research aids rather than security checks, behind the `analysis` feature
+
+use crate::polynomial::Polynomial;
+use crate::z257::Z257;
+
+/// Type alias representing, for each of the [`Polynomial::N`] coefficients,
+/// the number of occurrences of each element of $\mathbb{Z}_{257}$
+pub type Histogram = [[u32; Z257::P as usize]; Polynomial::N];
+
+/// Counts how many times each element of $\mathbb{Z}_{257}$ occurs
+/// at each coefficient position, across all `polynomials`
+pub fn element_histogram(polynomials: &[Polynomial]) -> Histogram {
+    let mut histogram: Histogram = [[0; Z257::P as usize]; Polynomial::N];
+    for polynomial in polynomials {
+        for (coefficient, counts) in polynomial.coefficients().iter().zip(histogram.iter_mut()) {
+            counts[coefficient.value() as usize] += 1;
+        }
+    }
+    histogram
+}
+
+/// Computes, for each coefficient position, the chi-squared statistic of its counts
+/// against the uniform distribution over $\mathbb{Z}_{257}$
+///
+/// Each statistic has $256$ degrees of freedom, so values far above $256$
+/// indicate that the corresponding coefficient is biased
+pub fn chi_squared_per_element(histogram: &Histogram) -> [f64; Polynomial::N] {
+    let mut chi_squared = [0f64; Polynomial::N];
+    for (statistic, counts) in chi_squared.iter_mut().zip(histogram.iter()) {
+        let total: u64 = counts.iter().map(|&count| count as u64).sum();
+        if total == 0 {
+            continue
+        }
+        let expected = total as f64 / Z257::P as f64;
+        *statistic = counts.iter()
+            .map(|&count| (count as f64 - expected).powi(2) / expected)
+            .sum();
+    }
+    chi_squared
+}
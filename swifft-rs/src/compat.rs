@@ -0,0 +1,15 @@
+//! Shims that keep code written against the previous release compiling, for one release cycle.
+//!
+//! Every item in this module is deprecated in favour of its replacement, and will be removed in the next release;
+//! the notes of the deprecation warnings say what to migrate to
+
+use crate::hash::{self, SwifftInput};
+use crate::polynomial::Polynomial;
+
+/// Standard SWIFFT hash function with its signature of `0.1`, returning the digest as a bare [`Polynomial`];
+/// the same as [`hash::swifft_hash`] followed by [`Digest::into_polynomial`](crate::digest::Digest::into_polynomial)
+#[deprecated(since = "0.2.0", note = "use `swifft::hash::swifft_hash`, which returns a `Digest`")]
+#[inline]
+pub fn swifft_hash(input: &SwifftInput) -> Polynomial {
+    hash::swifft_hash(input).into_polynomial()
+}
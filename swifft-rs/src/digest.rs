@@ -0,0 +1,170 @@
+use std::fmt::{Display, Formatter, LowerHex};
+
+use crate::polynomial::{Coefficients, Polynomial};
+use crate::z257::Z257;
+
+/// The output of the SWIFFT hash function, which is an element of
+/// $\mathbb{Z}_{257}[\alpha]/(\alpha^{64} + 1)$ that is known to be a hash value
+///
+/// Digests are additively homomorphic, so they may be composed with [`Digest::compose`],
+/// but they are otherwise kept distinct from arbitrary ring elements
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct Digest(Polynomial);
+
+// STRUCT METHODS
+impl Digest {
+    /// The size of the canonical byte representation of a digest,
+    /// where each of the [`Polynomial::N`] coefficients takes `2` little endian bytes
+    pub const BYTES: usize = 2 * Polynomial::N;
+
+    /// Wraps a polynomial that is known to be a hash value
+    #[inline]
+    pub const fn new(polynomial: Polynomial) -> Self {
+        Self(polynomial)
+    }
+
+    /// The underlying polynomial of this digest
+    #[inline]
+    pub const fn polynomial(&self) -> &Polynomial { &self.0 }
+
+    /// Unwraps this digest into its underlying polynomial, for advanced use
+    #[inline]
+    pub const fn into_polynomial(self) -> Polynomial { self.0 }
+
+    /// Composes two digests using the homomorphic addition of SWIFFT,
+    /// i.e. $H(x) + H(y)$
+    #[inline]
+    pub const fn compose(&self, rhs: &Self) -> Self {
+        Self(self.0.cn_add(&rhs.0))
+    }
+
+    /// Encodes this digest in its canonical [`Digest::BYTES`]-byte representation,
+    /// where the `i`th coefficient is stored little endian at bytes `2i` and `2i+1`
+    pub const fn to_bytes(&self) -> [u8; Self::BYTES] {
+        let mut bytes = [0u8; Self::BYTES];
+        let coefficients = self.0.coefficients();
+        let mut i = 0; while i < Polynomial::N {
+            let value = coefficients[i].value().to_le_bytes();
+            bytes[2*i] = value[0];
+            bytes[2*i + 1] = value[1];
+            i += 1
+        }
+        bytes
+    }
+
+    /// Decodes a digest from its canonical [`Digest::BYTES`]-byte representation,
+    /// or returns [`None`] if any coefficient is not an element of $\mathbb{Z}_{257}$
+    pub const fn from_bytes(bytes: &[u8; Self::BYTES]) -> Option<Self> {
+        let mut coefficients: Coefficients = [Z257::ZERO; Polynomial::N];
+        let mut i = 0; while i < Polynomial::N {
+            let value = u16::from_le_bytes([bytes[2*i], bytes[2*i + 1]]);
+            if value >= Z257::P {
+                return None
+            }
+            coefficients[i] = Z257::new(value);
+            i += 1
+        }
+        Some(Self(Polynomial::new(coefficients)))
+    }
+
+    /// Encodes the canonical byte representation of this digest as lowercase hex,
+    /// only compiled with the `alloc` feature since it allocates the returned [`String`]
+    #[cfg(feature = "alloc")]
+    pub fn to_hex(&self) -> String {
+        format!("{:x}", self)
+    }
+
+    /// Decodes a digest from the hex encoding of its canonical byte representation,
+    /// or returns [`None`] if the encoding is malformed or non-canonical
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        if hex.len() != 2 * Self::BYTES {
+            return None
+        }
+        // unlike `u8::from_str_radix`, `to_digit` rejects signs, so every character must be a hex digit
+        let mut digits = hex.chars().map(|char| char.to_digit(16));
+        let mut bytes = [0u8; Self::BYTES];
+        for byte in bytes.iter_mut() {
+            *byte = (digits.next()?? << 4 | digits.next()??) as u8;
+        }
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl Display for Digest {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        LowerHex::fmt(self, f)
+    }
+}
+
+impl LowerHex for Digest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for byte in self.to_bytes() {
+            write!(f, "{:02x}", byte)?
+        }
+        Ok(())
+    }
+}
+
+impl From<Digest> for Polynomial {
+    #[inline]
+    fn from(value: Digest) -> Self {
+        value.into_polynomial()
+    }
+}
+
+// `serde` TRAITS
+#[cfg(feature = "serde")]
+impl serde::Serialize for Digest {
+    /// Human-readable formats get the hex encoding,
+    /// binary formats get the canonical byte representation
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{:x}", self))
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Digest {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DigestVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for DigestVisitor {
+            type Value = Digest;
+
+            fn expecting(&self, f: &mut Formatter) -> std::fmt::Result {
+                write!(f, "a canonical SWIFFT digest of {} bytes", Digest::BYTES)
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Digest::from_hex(v).ok_or_else(|| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                v.try_into().ok()
+                    .and_then(Digest::from_bytes)
+                    .ok_or_else(|| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))
+            }
+
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut bytes = [0u8; Digest::BYTES];
+                for (i, byte) in bytes.iter_mut().enumerate() {
+                    *byte = seq.next_element()?
+                        .ok_or_else(|| serde::de::Error::invalid_length(i, &self))?;
+                }
+                Digest::from_bytes(&bytes)
+                    .ok_or_else(|| serde::de::Error::invalid_value(serde::de::Unexpected::Bytes(&bytes), &self))
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(DigestVisitor)
+        } else {
+            deserializer.deserialize_bytes(DigestVisitor)
+        }
+    }
+}
@@ -1,9 +1,18 @@
+#[cfg(feature = "bitvec")]
+use bitvec::{order::Lsb0, slice::BitSlice, vec::BitVec, view::BitView};
+#[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use crate::digest::Digest;
 use crate::multiplier::MULTIPLIER_POLYNOMIAL_COEFFICIENTS;
+#[cfg(feature = "parallel")]
+use crate::parallel::deterministic_reduce;
 use crate::polynomial::{Coefficients, Polynomial};
 use crate::z257::Z257;
 
+#[cfg(all(feature = "alloc", feature = "experimental"))]
+pub mod bitsliced;
+
 // CONSTANTS
 /// Efficiency and security parameter representing the number of vectors
 /// (*from vector space $\mathbb{Z}_{257}^{64}$*),
@@ -22,9 +31,23 @@ pub const INPUT_SIZE: usize = Polynomial::N * M;
 /// is represented by `1` bit; `8` elements per byte
 pub const INPUT_BLOCK_SIZE: usize = INPUT_SIZE / u8::BITS as usize;
 
+/// The number of bits representing each element of a base-4 input block
+pub const BASE4_BITS: usize = 2;
+
+/// The total size of a base-4 input block that consists of [`u8`] elements,
+/// calculated by multiplying [`INPUT_SIZE`] by [`BASE4_BITS`] and dividing by [`u8::BITS`].
+///
+/// Each of the [`Polynomial::N`] elements *(in each of the [`M`] input vectors)*,
+/// lies in $\{0, 1, 2, 3\}$ and is represented by `2` bits; `4` elements per byte
+pub const BASE4_INPUT_BLOCK_SIZE: usize = INPUT_SIZE * BASE4_BITS / u8::BITS as usize;
+
 
 // HELPER METHODS
 /// Parses input block of $16$ binary polynomials
+///
+/// Bits are read least significant first: bit $b$ of byte $i$ is coefficient $(8i + b) \bmod 64$
+/// of polynomial $\lfloor (8i + b) / 64 \rfloor$. This bit order is a stable contract;
+/// use [`parse_input_block_msb`] for data in most significant bit first order
 pub const fn parse_input_block(input: &[u8; INPUT_BLOCK_SIZE]) -> SwifftInput {
     // parse inputs into binary polynomial coefficients
     let mut input_coefficients: [Coefficients; M] = [[Z257::ZERO; Polynomial::N]; M];
@@ -49,34 +72,314 @@ pub const fn parse_input_block(input: &[u8; INPUT_BLOCK_SIZE]) -> SwifftInput {
     input_polynomials
 }
 
+/// Parses input block of $16$ binary polynomials, whose bits are in most significant bit first order,
+/// by reversing the bits of every byte into the order expected by [`parse_input_block`]
+pub const fn parse_input_block_msb(input: &[u8; INPUT_BLOCK_SIZE]) -> SwifftInput {
+    let mut reversed = [0u8; INPUT_BLOCK_SIZE];
+    let mut i = 0; while i < INPUT_BLOCK_SIZE {
+        reversed[i] = input[i].reverse_bits();
+        i += 1
+    }
+    parse_input_block(&reversed)
+}
+
+/// Parses an input of $16$ binary polynomials from a slice of [`INPUT_SIZE`] bits,
+/// where bit $k$ of the slice is coefficient $k \bmod 64$ of polynomial $\lfloor k / 64 \rfloor$.
+/// With the [`Lsb0`] order, this is the same bit order as [`parse_input_block`] reads bytes in
+///
+/// Returns [`None`] if `bits` is not [`INPUT_SIZE`] bits long
+///
+/// ```
+/// use bitvec::prelude::*;
+/// use swifft::hash::{parse_bitslice, parse_input_block, to_bitvec, INPUT_SIZE};
+///
+/// let mut bits = bitvec![u8, Lsb0; 0; INPUT_SIZE];
+/// bits.set(9, true);
+/// let input = parse_bitslice(&bits).unwrap();
+///
+/// let mut block = [0u8; 128];
+/// block[1] = 0b10;
+/// assert_eq!(input, parse_input_block(&block));
+/// assert_eq!(to_bitvec(&input), Some(bits));
+/// ```
+#[cfg(feature = "bitvec")]
+pub fn parse_bitslice(bits: &BitSlice<u8, Lsb0>) -> Option<SwifftInput> {
+    if bits.len() != INPUT_SIZE {
+        return None
+    }
+    let mut block = [0u8; INPUT_BLOCK_SIZE];
+    block.view_bits_mut::<Lsb0>().copy_from_bitslice(bits);
+    Some(parse_input_block(&block))
+}
+
+/// Packs an input of $16$ binary polynomials into [`INPUT_SIZE`] bits, the inverse of [`parse_bitslice`]
+///
+/// Returns [`None`] if any coefficient is greater than `1`
+#[cfg(feature = "bitvec")]
+pub fn to_bitvec(input: &SwifftInput) -> Option<BitVec<u8, Lsb0>> {
+    input.iter()
+        .flat_map(|polynomial| polynomial.coefficients())
+        .map(|coefficient| match coefficient.value() {
+            0 => Some(false),
+            1 => Some(true),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parses input block of $16$ polynomials with coefficients in $\{0, 1, 2, 3\}$
+///
+/// Coefficients are read least significant first, `2` bits each: bits $2b$ and $2b + 1$ of byte $i$
+/// are coefficient $(4i + b) \bmod 64$ of polynomial $\lfloor (4i + b) / 64 \rfloor$,
+/// with bit $2b$ as the low bit of the coefficient
+pub const fn parse_input_block_base4(input: &[u8; BASE4_INPUT_BLOCK_SIZE]) -> SwifftInput {
+    let elements_per_byte = u8::BITS as usize / BASE4_BITS;
+    let mut input_coefficients: [Coefficients; M] = [[Z257::ZERO; Polynomial::N]; M];
+    let mut byte_index = 0; while byte_index < BASE4_INPUT_BLOCK_SIZE {
+        let mut element = 0; while element < elements_per_byte {
+            let input_position = byte_index * elements_per_byte + element;
+            let value = (input[byte_index] >> (element * BASE4_BITS)) & 0b11;
+            input_coefficients[input_position / Polynomial::N][input_position % Polynomial::N] = Z257::new(value as u16);
+            element += 1
+        }
+        byte_index += 1
+    }
+
+    let mut input_polynomials: [Polynomial; M] = [Polynomial::ZERO; M];
+    let mut i = 0; while i < M {
+        input_polynomials[i] = Polynomial::new(input_coefficients[i]);
+        i += 1
+    }
+    input_polynomials
+}
+
+/// Packs an input of $16$ polynomials with coefficients in $\{0, 1, 2, 3\}$ into a base-4 input block,
+/// the inverse of [`parse_input_block_base4`]
+///
+/// Returns [`None`] if any coefficient is greater than `3`
+pub const fn to_input_block_base4(input: &SwifftInput) -> Option<[u8; BASE4_INPUT_BLOCK_SIZE]> {
+    let elements_per_byte = u8::BITS as usize / BASE4_BITS;
+    let mut block = [0u8; BASE4_INPUT_BLOCK_SIZE];
+    let mut i = 0; while i < M {
+        let coefficients = input[i].coefficients();
+        let mut j = 0; while j < Polynomial::N {
+            let value = coefficients[j].value();
+            if value > 3 {
+                return None
+            }
+            let input_position = i * Polynomial::N + j;
+            block[input_position / elements_per_byte] |= (value as u8) << (input_position % elements_per_byte * BASE4_BITS);
+            j += 1
+        }
+        i += 1
+    }
+    Some(block)
+}
+
+/// Applies the sign bits in `sign` to `input`, negating the coefficient at every position
+/// whose sign bit is set; sign bits at zero coefficients have no effect.
+///
+/// Together with a binary input, this allows for an input domain of $\{-1, 0, 1\}$
+pub const fn apply_sign_pattern(input: &SwifftInput, sign: &SignPattern) -> SwifftInput {
+    let mut signed_input = *input;
+    let mut i = 0; while i < M {
+        let mut coefficients = *input[i].coefficients();
+        let mut j = 0; while j < Polynomial::N {
+            let input_position = i * Polynomial::N + j;
+            let sign_bit = (sign[input_position / u8::BITS as usize] >> (input_position % u8::BITS as usize)) & 1 != 0;
+            if sign_bit {
+                coefficients[j] = coefficients[j].cn_neg();
+            }
+            j += 1
+        }
+        signed_input[i] = Polynomial::new(coefficients);
+        i += 1
+    }
+    signed_input
+}
+
 // SWIFFT HASH FUNCTION
 /// Type alias representing the input to the SWIFFT hash function
 pub type SwifftInput = [Polynomial; M];
 
+/// Type alias representing the sign bits paired with an input block,
+/// laid out the same way as the input block itself, where `1` negates the corresponding coefficient
+pub type SignPattern = [u8; INPUT_BLOCK_SIZE];
+
 /// Standard SWIFFT hash function, processing a single input
-pub fn swifft_hash(input: &SwifftInput) -> Polynomial {
+///
+/// With the `parallel` feature, the products of the input polynomials are computed in parallel
+/// but summed in index order, so the digest does not depend on the number of threads
+///
+/// Performs no heap allocations when the `parallel` feature is disabled
+///
+/// Never panics without the `parallel` feature, which is checked at link time with the `no-panic` feature
+#[cfg_attr(all(feature = "no-panic", not(feature = "parallel"), not(debug_assertions)), no_panic::no_panic)]
+pub fn swifft_hash(input: &SwifftInput) -> Digest {
+    // Compute 16 individual Polynomial products A_i * X_i
+    // in the Fourier coefficients representation, and their linear combination,
+    // reading each input polynomial straight into its transform, so that no part of the input is copied
+    #[cfg(feature = "parallel")]
+    let mut digest = deterministic_reduce::<M, _, _>(
+        input.par_iter().zip(&MULTIPLIER_FOURIER_COEFFICIENTS).map(fourier_product),
+        Polynomial::ZERO, |sum, product| sum + product);
+    #[cfg(not(feature = "parallel"))]
+    let mut digest = input.iter().zip(&MULTIPLIER_FOURIER_COEFFICIENTS)
+        .map(fourier_product)
+        .fold(Polynomial::ZERO, |sum, product| sum + product);
+
+    // interpolate resulting Fourier coefficients,
+    // and return result
+    digest.interpolate_fourier_coefficients_assign();
+    Digest::new(digest)
+}
+
+/// Standard SWIFFT hash function, processing a single input that it takes ownership of,
+/// so that the input is transformed in place rather than copied
+///
+/// With the `parallel` feature, only the transforms run in parallel; the products are summed
+/// on the calling thread in index order, whatever the number of threads
+///
+/// Performs no heap allocations when the `parallel` feature is disabled
+///
+/// Never panics without the `parallel` feature, which is checked at link time with the `no-panic` feature
+#[cfg_attr(all(feature = "no-panic", not(feature = "parallel"), not(debug_assertions)), no_panic::no_panic)]
+pub fn swifft_hash_owned(mut input: SwifftInput) -> Digest {
     // Compute 16 individual Polynomial products A_i * X_i
     // in the Fourier coefficients representation
-    let mut product_fourier_coefficients = input.clone();
-    product_fourier_coefficients.par_iter_mut().enumerate()
-        .for_each(|(i, input)| {
+    #[cfg(feature = "parallel")]
+    let products = input.par_iter_mut();
+    #[cfg(not(feature = "parallel"))]
+    let products = input.iter_mut();
+    products.zip(&MULTIPLIER_FOURIER_COEFFICIENTS)
+        .for_each(|(input, multiplier)| {
             // compute Fourier coefficients of input
             input.fourier_coefficients_assign();
 
             // compute hadamard product of input and multiplier Fourier coefficients
-            input.hadamard_product_assign(&MULTIPLIER_FOURIER_COEFFICIENTS[i]);
+            input.hadamard_product_assign(multiplier);
         });
-    
+
     // Compute linear combination of those products
     let mut digest = Polynomial::ZERO;
-    for product in product_fourier_coefficients {
+    for product in input {
         digest += product
     }
-    
+
     // interpolate resulting Fourier coefficients,
     // and return result
     digest.interpolate_fourier_coefficients_assign();
-    digest
+    Digest::new(digest)
+}
+
+/// The product $A_i * X_i$ of an input polynomial $X_i$ and its multiplier $A_i$,
+/// in the Fourier coefficients representation, transforming $X_i$ without copying it first
+#[inline]
+fn fourier_product((input, multiplier): (&Polynomial, &Polynomial)) -> Polynomial {
+    let mut product = input.fourier_coefficients();
+    product.hadamard_product_assign(multiplier);
+    product
+}
+
+/// SWIFFT hash function over a base-4 input block, whose coefficients lie in $\{0, 1, 2, 3\}$
+/// rather than $\{0, 1\}$, see [`parse_input_block_base4`]. A base-4 input block whose coefficients
+/// are all `0` or `1` hashes to the same digest as the corresponding binary input block.
+///
+/// # Security
+/// Finding a collision between two base-4 inputs yields a nonzero solution of the
+/// underlying lattice problem with coefficients in $\{-3, \dots, 3\}$, i.e. of infinity norm at most `3`
+/// and Euclidean norm at most $3\sqrt{1024} = 96$, where collisions between binary inputs yield
+/// solutions of infinity norm `1` and Euclidean norm at most $\sqrt{1024} = 32$.
+/// Collision resistance therefore rests on a strictly stronger hardness assumption than for binary inputs,
+/// for parameters that were chosen for binary inputs; the security margin is correspondingly smaller.
+pub fn swifft_hash_base4(input: &[u8; BASE4_INPUT_BLOCK_SIZE]) -> Digest {
+    swifft_hash_owned(parse_input_block_base4(input))
+}
+
+/// Standard SWIFFT hash function over a raw input block, evaluated entirely with `const` operations,
+/// so that digests of known inputs can be computed at compile time and embedded in binaries
+///
+/// Uses [`Polynomial::naive_mul`] rather than the FFT, so it is much slower than [`swifft_hash`] at runtime,
+/// but always agrees with it
+///
+/// ```
+/// use swifft::digest::Digest;
+/// use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_const};
+///
+/// const BLOCK: &[u8; 128] = b"SWIFFT is a collection of provably secure hash functions, whose security is based on lattice problems, fixed at compile time!!!!";
+/// const DIGEST: Digest = swifft_hash_const(BLOCK);
+///
+/// assert_eq!(DIGEST, swifft_hash(&parse_input_block(BLOCK)));
+/// ```
+pub const fn swifft_hash_const(input: &[u8; INPUT_BLOCK_SIZE]) -> Digest {
+    let input = parse_input_block(input);
+    let mut digest = Polynomial::ZERO;
+    let mut i = 0; while i < M {
+        digest = digest.cn_add(&MULTIPLIER_POLYNOMIALS[i].naive_mul(&input[i]));
+        i += 1
+    }
+    Digest::new(digest)
+}
+
+/// Standard SWIFFT hash function, processing a single input,
+/// and returning the digest as a bare [`Polynomial`]
+#[deprecated(since = "0.2.0", note = "use `swifft_hash`, which returns a `Digest`")]
+#[inline]
+pub fn swifft_hash_polynomial(input: &SwifftInput) -> Polynomial {
+    swifft_hash(input).into_polynomial()
+}
+
+/// Signed SWIFFT hash function, processing a single input with its sign bits
+///
+/// Performs no heap allocations when the `parallel` feature is disabled
+#[inline]
+pub fn swifft_hash_signed(input: &SwifftInput, sign: &SignPattern) -> Digest {
+    swifft_hash_owned(apply_sign_pattern(input, sign))
+}
+
+/// Signed SWIFFT hash function, processing multiple inputs (*in parallel with the `parallel` feature*),
+/// with the digests returned in the order of the inputs
+///
+/// Each digest is computed from its own input alone, so the result does not depend on the number of threads
+///
+/// Allocates the returned [`Vec`], so is only compiled with the `alloc` feature
+#[cfg(feature = "alloc")]
+pub fn swifft_hash_signed_multiple(inputs: &[(SwifftInput, SignPattern)]) -> Vec<Digest> {
+    #[cfg(feature = "parallel")]
+    let inputs = inputs.par_iter();
+    #[cfg(not(feature = "parallel"))]
+    let inputs = inputs.iter();
+    inputs
+        .map(|(input, sign)| swifft_hash_signed(input, sign))
+        .collect()
+}
+
+/// Signed SWIFFT hash function over raw bytes, mirroring the layout of `SWIFFT_ComputeMultipleSigned`:
+/// `input` and `sign` are consecutive blocks of [`INPUT_BLOCK_SIZE`] bytes each
+///
+/// Returns [`None`] if the lengths of `input` and `sign` differ,
+/// or are not a multiple of [`INPUT_BLOCK_SIZE`]
+///
+/// The digests are in the order of the blocks, and the same for any number of threads
+///
+/// Allocates the returned [`Vec`], so is only compiled with the `alloc` feature
+#[cfg(feature = "alloc")]
+pub fn swifft_hash_signed_multiple_bytes(input: &[u8], sign: &[u8]) -> Option<Vec<Digest>> {
+    if input.len() != sign.len() || input.len() % INPUT_BLOCK_SIZE != 0 {
+        return None
+    }
+    #[cfg(feature = "parallel")]
+    let blocks = input.par_chunks_exact(INPUT_BLOCK_SIZE)
+        .zip(sign.par_chunks_exact(INPUT_BLOCK_SIZE));
+    #[cfg(not(feature = "parallel"))]
+    let blocks = input.chunks_exact(INPUT_BLOCK_SIZE)
+        .zip(sign.chunks_exact(INPUT_BLOCK_SIZE));
+    Some(blocks
+        .map(|(input, sign)| {
+            let input = parse_input_block(input.try_into().unwrap());
+            swifft_hash_signed(&input, sign.try_into().unwrap())
+        })
+        .collect())
 }
 
 // PRECOMPUTED CONSTANTS
@@ -0,0 +1,104 @@
+//! Experimental batch hashing by transposed evaluation, for very large batches of input blocks
+//!
+//! # Stability
+//! This module is only compiled with the `experimental` and `alloc` features, and is exempt from semantic versioning:
+//! its interface and performance characteristics may change, or it may be removed, in any release.
+//! The digests it returns are not experimental, they always agree with [`swifft_hash`](super::swifft_hash).
+//!
+//! # Strategy
+//! Rather than transforming each block into the Fourier domain, the digest is evaluated directly as
+//! $\sum_{i,j} x_{i,j} \cdot \alpha^j A_i$, the sum of the rows of a precomputed table of rotated multipliers
+//! selected by the set bits $x_{i,j}$ of the block. The blocks of a batch are transposed into one [`u64`] mask
+//! per bit position, covering [`LANES`] blocks at a time, so that each row of the table is loaded once
+//! per [`LANES`] blocks rather than once per block. Sums are accumulated in [`u32`]s and reduced once at the end,
+//! since there are at most [`INPUT_SIZE`] rows of coefficients less than $257$.
+//!
+//! This is the scalar version of the strategy, which later versions may replace with bit-sliced arithmetic.
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use crate::digest::Digest;
+use crate::hash::{INPUT_BLOCK_SIZE, INPUT_SIZE, M, MULTIPLIER_POLYNOMIALS};
+use crate::polynomial::{Coefficients, Polynomial};
+use crate::z257::Z257;
+
+/// The number of blocks that are transposed and evaluated together, one per bit of a [`u64`] mask
+pub const LANES: usize = u64::BITS as usize;
+
+/// SWIFFT hash function over a batch of raw input blocks, evaluated by transposed evaluation
+/// (*in parallel over groups of [`LANES`] blocks with the `parallel` feature*),
+/// with the digests returned in the order of the blocks
+///
+/// Each digest only depends on its own block, so it is the same regardless of the size or order of the batch,
+/// or of the number of threads
+///
+/// Allocates the returned [`Vec`]
+///
+/// # Arguments
+/// * `blocks` - the input blocks, whose bits are read as by [`parse_input_block`](super::parse_input_block)
+pub fn swifft_hash_bitsliced(blocks: &[[u8; INPUT_BLOCK_SIZE]]) -> Vec<Digest> {
+    #[cfg(feature = "parallel")]
+    let digests = blocks.par_chunks(LANES).flat_map_iter(hash_group).collect();
+    #[cfg(not(feature = "parallel"))]
+    let digests = blocks.chunks(LANES).flat_map(hash_group).collect();
+    digests
+}
+
+/// Hashes a group of at most [`LANES`] blocks
+fn hash_group(blocks: &[[u8; INPUT_BLOCK_SIZE]]) -> Vec<Digest> {
+    // transpose the blocks, so that bit `b` of mask `p` is bit position `p` of block `b`
+    let mut masks = [0u64; INPUT_SIZE];
+    for (lane, block) in blocks.iter().enumerate() {
+        for (byte_index, byte) in block.iter().enumerate() {
+            for bit_position in 0..u8::BITS as usize {
+                let bit = (byte >> bit_position) as u64 & 1;
+                masks[byte_index * u8::BITS as usize + bit_position] |= bit << lane;
+            }
+        }
+    }
+
+    // add every row of the table to the sums of the blocks that have its bit set
+    let mut sums = vec![[0u32; Polynomial::N]; blocks.len()];
+    for (mask, row) in masks.iter().zip(&ROTATED_MULTIPLIERS) {
+        let mut mask = *mask;
+        while mask != 0 {
+            let sum = &mut sums[mask.trailing_zeros() as usize];
+            for (sum, coefficient) in sum.iter_mut().zip(row) {
+                *sum += *coefficient as u32;
+            }
+            mask &= mask - 1
+        }
+    }
+
+    // reduce the sums once, into digests
+    sums.iter()
+        .map(|sum| {
+            let mut coefficients: Coefficients = [Z257::ZERO; Polynomial::N];
+            for (coefficient, sum) in coefficients.iter_mut().zip(sum) {
+                *coefficient = Z257::from_u64(*sum as u64);
+            }
+            Digest::new(Polynomial::new(coefficients))
+        })
+        .collect()
+}
+
+// PRECOMPUTED CONSTANTS
+/// The rows $\alpha^j A_i$ of the table of rotated multipliers, as the values of their coefficients,
+/// where row $64i + j$ is selected by the bit at that position of an input block
+static ROTATED_MULTIPLIERS: [[u16; Polynomial::N]; INPUT_SIZE] = compute_rotated_multipliers(); const fn compute_rotated_multipliers() -> [[u16; Polynomial::N]; INPUT_SIZE] {
+    let mut rows = [[0u16; Polynomial::N]; INPUT_SIZE];
+    let mut i = 0; while i < M {
+        let mut rotated = MULTIPLIER_POLYNOMIALS[i];
+        let mut j = 0; while j < Polynomial::N {
+            let mut k = 0; while k < Polynomial::N {
+                rows[i * Polynomial::N + j][k] = rotated.coefficients()[k].value();
+                k += 1
+            }
+            rotated = rotated.increment_power();
+            j += 1
+        }
+        i += 1
+    }
+    rows
+}
@@ -1,4 +1,17 @@
+pub mod compat;
+pub mod digest;
 pub mod multiplier;
 pub mod hash;
+#[cfg(feature = "parallel")]
+mod parallel;
 pub mod polynomial;
-pub mod z257;
\ No newline at end of file
+pub mod rerandomize;
+pub mod z257;
+#[cfg(feature = "analysis")]
+pub mod analysis;
+
+/// Whether this build of the crate has the `ct` feature, for dependents reporting their capabilities
+pub const CT_ENABLED: bool = cfg!(feature = "ct");
+
+/// Whether this build of the crate has the `serde` feature, for dependents reporting their capabilities
+pub const SERDE_ENABLED: bool = cfg!(feature = "serde");
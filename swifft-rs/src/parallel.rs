@@ -0,0 +1,24 @@
+//! Deterministic combination of results computed in parallel
+//!
+//! `rayon`'s own `reduce` combines results in a tree whose shape depends on how the work was split between threads,
+//! so its result only stays the same for every number of threads if the combining step is associative.
+//! The parallel paths of this crate instead compute their results in parallel,
+//! and combine them on the calling thread in index order, which holds for any combining step
+
+use rayon::prelude::*;
+
+/// Computes the `LEN` items of `items` in parallel, then folds them into `identity` with `combine`
+/// in index order on the calling thread, without allocating
+///
+/// `items` must yield exactly `LEN` items; any beyond those are ignored,
+/// and any missing ones are taken to be `identity`
+pub(crate) fn deterministic_reduce<const LEN: usize, T, F>(items: impl IndexedParallelIterator<Item = T>,
+                                                           identity: T, combine: F) -> T
+where
+    T: Copy + Send,
+    F: Fn(T, T) -> T,
+{
+    let mut results = [identity; LEN];
+    results.par_iter_mut().zip(items).for_each(|(result, item)| *result = item);
+    results.into_iter().fold(identity, combine)
+}
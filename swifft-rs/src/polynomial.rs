@@ -2,7 +2,7 @@ use std::fmt::{Debug, Display, Formatter};
 use std::iter::Sum;
 use std::ops::{Add, AddAssign, Index, Mul, MulAssign, Neg, Sub, SubAssign};
 
-use crate::z257::Z257;
+use crate::z257::{NonZeroZ257, Z257};
 
 /// Element of polynomial quotient ring $\mathbb{Z}_{257}[\alpha]/(\alpha^{64} + 1)$
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -12,10 +12,20 @@ pub struct Polynomial(Coefficients);
 /// Type alias representing the coefficients of a polynomial
 pub type Coefficients = [Z257; Polynomial::N];
 
+/// Type alias representing half of the coefficients of a polynomial,
+/// see [`Polynomial::from_halves`] and [`Polynomial::interleave`]
+pub type HalfCoefficients = [Z257; Polynomial::N / 2];
+
 /// Type alias representing a $64 \times 64$ matrix,
 /// where each polynomial is interpreted a column
 pub type Matrix = [Polynomial; Polynomial::N];
 
+/// An odd exponent $k$ modulo $2N = 128$, indexing the automorphism $\sigma_k : \alpha \mapsto \alpha^k$
+/// of $\mathbb{Z}_{257}[\alpha]/(\alpha^{64} + 1)$, see [`Polynomial::automorphism`]
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct OddExponent(u8);
+
 // STRUCT METHODS
 impl Polynomial {
     // CONSTRUCTOR METHODS
@@ -123,6 +133,106 @@ impl Polynomial {
         reduced_product
     }
 
+    /// Decrements the power of every $\alpha$ in this polynomial by $1$,
+    /// and reduces it modulo $\alpha^{64} + 1$, returning the result
+    ///
+    /// This is equivalent to multiplying the polynomial by $\alpha^{-1} = -\alpha^{63}$, or performing
+    /// the inverse negacyclic rotation on the coefficient vector
+    pub const fn decrement_power(&self) -> Self {
+        let mut reduced_product = Polynomial::ZERO;
+        reduced_product.0[Self::N - 1] = self.0[0].cn_neg();
+        let mut i = 0; while i < Self::N - 1 {
+            reduced_product.0[i] = self.0[i+1];
+            i += 1
+        }
+        reduced_product
+    }
+
+    /// Applies the ring automorphism $\sigma_k : \alpha \mapsto \alpha^k$, returning the result
+    ///
+    /// This permutes the coefficients, negating those whose power wraps past $\alpha^{64} = -1$,
+    /// and respects both addition and multiplication, i.e. $\sigma_k(PQ) = \sigma_k(P)\sigma_k(Q)$.
+    /// Its inverse is the automorphism of [`OddExponent::inverse`]
+    pub const fn automorphism(&self, k: OddExponent) -> Self {
+        let mut result = Polynomial::ZERO;
+        let mut i = 0; while i < Self::N {
+            // $\alpha^{ik}$, with $ik$ reduced modulo $128$ as $\alpha$ has order $128$
+            let power = (i * k.0 as usize) % (2 * Self::N);
+            if power < Self::N {
+                result.0[power] = self.0[i]
+            } else {
+                result.0[power - Self::N] = self.0[i].cn_neg()
+            }
+            i += 1
+        }
+        result
+    }
+
+    /// Divides every coefficient by the nonzero `scalar`, undoing [`Polynomial::scalar_mul`]
+    #[inline]
+    pub const fn scalar_div(&self, scalar: &NonZeroZ257) -> Self {
+        self.scalar_mul(&scalar.cn_inv().get())
+    }
+
+    /// Creates a polynomial from two halves of coefficients, as $lo + \alpha^{32} \cdot hi$
+    ///
+    /// The embedding is $\mathbb{Z}_{257}$-linear, so the halves of a linear combination of such polynomials,
+    /// e.g. of digests, are the same linear combinations of the halves, see [`Polynomial::split_halves`].
+    /// Since $\alpha^{64} = -1$, multiplying by $\alpha^{32}$ maps the halves $(lo, hi)$ to $(-hi, lo)$;
+    /// other products mix the halves, for which see [`Polynomial::interleave`]
+    pub const fn from_halves(lo: &HalfCoefficients, hi: &HalfCoefficients) -> Self {
+        let mut result = Polynomial::ZERO;
+        let mut i = 0; while i < Self::N / 2 {
+            result.0[i] = lo[i];
+            result.0[Self::N / 2 + i] = hi[i];
+            i += 1
+        }
+        result
+    }
+
+    /// Splits this polynomial into the halves $(lo, hi)$ of its coefficients,
+    /// the inverse of [`Polynomial::from_halves`]
+    pub const fn split_halves(&self) -> (HalfCoefficients, HalfCoefficients) {
+        let mut lo = [Z257::ZERO; Self::N / 2];
+        let mut hi = [Z257::ZERO; Self::N / 2];
+        let mut i = 0; while i < Self::N / 2 {
+            lo[i] = self.0[i];
+            hi[i] = self.0[Self::N / 2 + i];
+            i += 1
+        }
+        (lo, hi)
+    }
+
+    /// Creates a polynomial from two halves of coefficients interleaved, as $E(\alpha^2) + \alpha \cdot O(\alpha^2)$,
+    /// so that `even` are the coefficients of even powers and `odd` those of odd powers
+    ///
+    /// The polynomials supported on even powers form a subring isomorphic to $\mathbb{Z}_{257}[\beta]/(\beta^{32} + 1)$,
+    /// by $\beta = \alpha^2$. Multiplying by a polynomial $C(\alpha^2)$ supported on even powers keeps the halves separable,
+    /// mapping them to $(C E, C O)$ with the products taken in that subring. Multiplying by a polynomial
+    /// $\alpha \cdot C(\alpha^2)$ supported on odd powers swaps them, mapping them to $(\beta C O, C E)$
+    pub const fn interleave(even: &HalfCoefficients, odd: &HalfCoefficients) -> Self {
+        let mut result = Polynomial::ZERO;
+        let mut i = 0; while i < Self::N / 2 {
+            result.0[2 * i] = even[i];
+            result.0[2 * i + 1] = odd[i];
+            i += 1
+        }
+        result
+    }
+
+    /// Splits this polynomial into the coefficients of its even and odd powers $(E, O)$,
+    /// the inverse of [`Polynomial::interleave`]
+    pub const fn deinterleave(&self) -> (HalfCoefficients, HalfCoefficients) {
+        let mut even = [Z257::ZERO; Self::N / 2];
+        let mut odd = [Z257::ZERO; Self::N / 2];
+        let mut i = 0; while i < Self::N / 2 {
+            even[i] = self.0[2 * i];
+            odd[i] = self.0[2 * i + 1];
+            i += 1
+        }
+        (even, odd)
+    }
+
     /// Evaluates this polynomial at some point
     ///
     /// This is equivalent to computing the dot product of the polynomial coefficient vector
@@ -189,6 +299,18 @@ impl Polynomial {
         Self::matrix_mul_col_vec(&self.toeplitz_matrix(), rhs)
     }
 
+    /// Number of nonzero coefficients of the polynomial
+    pub const fn weight(&self) -> usize {
+        let mut weight = 0;
+        let mut i = 0; while i < Self::N {
+            if !self.0[i].cn_is_zero() {
+                weight += 1
+            }
+            i += 1
+        }
+        weight
+    }
+
     // NON-CONSTANT OPERATIONS
     pub fn neg_assign(&mut self) {
         for i in 0..Self::N {
@@ -216,12 +338,25 @@ impl Polynomial {
     /// a negacyclic rotation on the coefficient vector
     pub fn increment_power_assign(&mut self) {
         let rotated_coefficient = -self[Self::N - 1];
-        for i in 1..Self::N {
+        for i in (1..Self::N).rev() {
             self.0[i] = self[i-1]
         }
         self.0[0] = rotated_coefficient
     }
 
+    /// Decrements the power of every $\alpha$ in this polynomial by $1$,
+    /// and reduces it modulo $\alpha^{64} + 1$
+    ///
+    /// This is equivalent to multiplying the polynomial by $\alpha^{-1} = -\alpha^{63}$, or performing
+    /// the inverse negacyclic rotation on the coefficient vector
+    pub fn decrement_power_assign(&mut self) {
+        let rotated_coefficient = -self[0];
+        for i in 0..Self::N - 1 {
+            self.0[i] = self[i+1]
+        }
+        self.0[Self::N - 1] = rotated_coefficient
+    }
+
 
     /// Evaluates the polynomial at [`Polynomial::N`] ascending odd powers of [`Z257::OMEGA_ORDER_128`],
     /// which is $\omega_{128}, \omega_{128}^3, \dots, \omega_{128}^{127}$,
@@ -231,8 +366,16 @@ impl Polynomial {
     /// $$\left(\mathbb{Z}\_{257}\[\alpha\]/(\alpha^{64}+1), +, * \right) \cong \left(\mathbb{Z}_{257}^{64}, +, \circ \right)$$
     #[inline]
     pub fn fourier_coefficients(&self) -> Self {
-        let mut fourier_coefficients = self.clone();
-        fourier_coefficients.fourier_coefficients_assign();
+        // multiply point-wise by [`OMEGA_ORDER_128_POWERS`] while gathering the coefficients into
+        // bit-reversed order, so the polynomial is only read rather than copied and then permuted,
+        // and compute the butterflies of the [`N`]-dimensional FFT of the result
+        let mut fourier_coefficients = Self::ZERO;
+        let mut k = 0; while k < Self::N {
+            let rk = Self::bit_reversed(k);
+            fourier_coefficients.0[k] = self.0[rk] * Self::OMEGA_ORDER_128_POWERS.0[rk];
+            k += 1
+        }
+        fourier_coefficients.butterflies_assign(&Self::OMEGA_ORDER_64_TWIDDLES);
         fourier_coefficients
     }
 
@@ -242,12 +385,12 @@ impl Polynomial {
     ///
     /// Equivalent to performing the isomorphism
     /// $$\left(\mathbb{Z}\_{257}\[\alpha\]/(\alpha^{64}+1), +, * \right) \cong \left(\mathbb{Z}_{257}^{64}, +, \circ \right)$$
+    #[inline]
     pub fn fourier_coefficients_assign(&mut self) {
         // multiply point-wise by [`OMEGA_ORDER_128_POWERS`]
         // and compute [`N`]-dimensional FFT of the result
         self.hadamard_product_assign(&Self::OMEGA_ORDER_128_POWERS);
-        halo2_proofs::arithmetic::best_fft::<Z257, Z257>(
-            &mut self.0, Z257::OMEGA_ORDER_64, Self::LOG2_N);
+        self.fft_assign(&Self::OMEGA_ORDER_64_TWIDDLES);
     }
 
     /// Interpolates the Fourier coefficients back into a polynomial
@@ -265,15 +408,52 @@ impl Polynomial {
     ///
     /// Equivalent to undoing the isomorphism
     /// $$\left(\mathbb{Z}\_{257}\[\alpha\]/(\alpha^{64}+1), +, * \right) \cong \left(\mathbb{Z}_{257}^{64}, +, \circ \right)$$
+    #[inline]
     pub fn interpolate_fourier_coefficients_assign(&mut self) {
         // and compute [`N`]-dimensional inverse FFT of the result
-        halo2_proofs::arithmetic::best_fft::<Z257, Z257>(
-            &mut self.0, Self::OMEGA_ORDER_64_INV, Self::LOG2_N);
+        self.fft_assign(&Self::OMEGA_ORDER_64_INV_TWIDDLES);
 
         // normalise the result, to get back the original polynomial
         self.hadamard_product_assign(&Self::FOURIER_NORMALISATION_COEFFICIENTS);
     }
 
+    /// Performs the schoolbook algorithm for multiplying polynomials,
+    /// skipping over the zero coefficients of the sparser operand
+    pub fn sparse_mul(&self, rhs: &Self) -> Self {
+        let (sparse, dense) = if self.weight() <= rhs.weight() { (self, rhs) } else { (rhs, self) };
+        let mut product = Self::ZERO;
+        for (i, coefficient) in sparse.0.iter().enumerate() {
+            if coefficient.cn_is_zero() {
+                continue
+            }
+            // multiply `dense` by $c \alpha^i$, reducing by $\alpha^{64} = -1$
+            for (j, term) in dense.0.iter().enumerate() {
+                let term = *coefficient * *term;
+                if i + j < Self::N {
+                    product.0[i + j] += term
+                } else {
+                    product.0[i + j - Self::N] -= term
+                }
+            }
+        }
+        product
+    }
+
+    /// Multiplies polynomials with whichever algorithm is fastest for the sparsity of the operands:
+    /// [`Polynomial::sparse_mul`] when the sparser operand has at most [`Polynomial::SPARSE_MUL_THRESHOLD`]
+    /// nonzero coefficients, [`Polynomial::naive_mul`] when it has at most [`Polynomial::TOEPLITZ_MUL_THRESHOLD`],
+    /// and [`Polynomial::fft_mul`] otherwise
+    pub fn auto_mul(&self, rhs: &Self) -> Self {
+        let weight = self.weight().min(rhs.weight());
+        if weight <= Self::SPARSE_MUL_THRESHOLD {
+            self.sparse_mul(rhs)
+        } else if weight <= Self::TOEPLITZ_MUL_THRESHOLD {
+            self.naive_mul(rhs)
+        } else {
+            self.fft_mul(rhs)
+        }
+    }
+
     /// Performs the FFT algorithm for multiplying polynomials
     #[inline]
     pub fn fft_mul(&self, rhs: &Self) -> Self {
@@ -294,6 +474,48 @@ impl Polynomial {
         // interpolate the result back into a polynomial
         self.interpolate_fourier_coefficients_assign();
     }
+
+    /// Performs an in-place radix-2 FFT over the coefficients, using the precomputed `twiddles`,
+    /// so that no heap allocations take place
+    #[inline]
+    fn fft_assign(&mut self, twiddles: &[Z257; Self::N / 2]) {
+        // permute the coefficients into bit-reversed order
+        let mut k = 0; while k < Self::N {
+            let rk = Self::bit_reversed(k);
+            if k < rk {
+                self.0.swap(k, rk)
+            }
+            k += 1
+        }
+        self.butterflies_assign(twiddles);
+    }
+
+    /// The index whose [`Polynomial::LOG2_N`] bits are those of `k` in reverse order
+    #[inline]
+    const fn bit_reversed(k: usize) -> usize {
+        k.reverse_bits() >> (usize::BITS - Self::LOG2_N)
+    }
+
+    /// Performs the butterflies of an in-place radix-2 FFT over coefficients already in bit-reversed order,
+    /// using the precomputed `twiddles`
+    #[inline]
+    fn butterflies_assign(&mut self, twiddles: &[Z257; Self::N / 2]) {
+        // perform butterflies of increasing size
+        let mut chunk = 2;
+        let mut twiddle_stride = Self::N / 2;
+        while chunk <= Self::N {
+            for coefficients in self.0.chunks_exact_mut(chunk) {
+                let (left, right) = coefficients.split_at_mut(chunk / 2);
+                for (i, (a, b)) in left.iter_mut().zip(right).enumerate() {
+                    let t = *b * twiddles[i * twiddle_stride];
+                    *b = *a - t;
+                    *a += t;
+                }
+            }
+            chunk *= 2;
+            twiddle_stride /= 2;
+        }
+    }
 }
 
 impl Polynomial {
@@ -324,6 +546,15 @@ impl Polynomial {
         0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
     ]);
 
+    /// The largest operand weight for which [`Polynomial::auto_mul`] uses [`Polynomial::sparse_mul`],
+    /// chosen using the `multiplication` benchmarks
+    pub const SPARSE_MUL_THRESHOLD: usize = 10;
+
+    /// The largest operand weight for which [`Polynomial::auto_mul`] uses [`Polynomial::naive_mul`],
+    /// chosen using the `multiplication` benchmarks; since building the Toeplitz matrix makes it slower than
+    /// [`Polynomial::fft_mul`] at every weight, this range is currently empty
+    pub const TOEPLITZ_MUL_THRESHOLD: usize = Self::SPARSE_MUL_THRESHOLD;
+
     /// The inverse element of [`Z257::OMEGA_ORDER_64`]
     pub const OMEGA_ORDER_64_INV: Z257 = Z257::OMEGA_ORDER_64.cn_inv();
 
@@ -337,6 +568,45 @@ impl Polynomial {
     /// and scaling it by the inverse of [`Z257::OMEGA_ORDER_64`]
     pub const FOURIER_NORMALISATION_COEFFICIENTS: Self = Self::from_point_powers(
         &Z257::OMEGA_ORDER_128.cn_inv()).scalar_mul(&Z257::new(Self::N as u16).cn_inv());
+
+    // PRIVATE CONSTANTS
+    const OMEGA_ORDER_64_TWIDDLES: [Z257; Self::N / 2] = Self::compute_twiddles(&Z257::OMEGA_ORDER_64);
+    const OMEGA_ORDER_64_INV_TWIDDLES: [Z257; Self::N / 2] = Self::compute_twiddles(&Self::OMEGA_ORDER_64_INV);
+    const fn compute_twiddles(omega: &Z257) -> [Z257; Self::N / 2] {
+        let mut twiddles = [Z257::ONE; Self::N / 2];
+        let mut i = 1; while i < Self::N / 2 {
+            twiddles[i] = twiddles[i-1].cn_mul(omega);
+            i += 1
+        }
+        twiddles
+    }
+}
+
+impl OddExponent {
+    /// The exponent $1$, whose automorphism is the identity
+    pub const ONE: Self = Self(1);
+
+    /// Creates an odd exponent from `k`, reduced modulo $128$, or returns [`None`] if `k` is even
+    pub const fn new(k: u8) -> Option<Self> {
+        if k % 2 == 1 { Some(Self(k % (2 * Polynomial::N as u8))) } else { None }
+    }
+
+    /// The exponent, in the range $1..128$
+    #[inline]
+    pub const fn value(&self) -> u8 {
+        self.0
+    }
+
+    /// The inverse of this exponent modulo $128$, so that $\sigma_{k^{-1}}$ undoes $\sigma_k$
+    pub const fn inverse(&self) -> Self {
+        // the odd residues modulo $128$ form a group of order $64$, so $k^{-1} = k^{63}$
+        let mut inverse = 1u32;
+        let mut i = 0; while i < Polynomial::N - 1 {
+            inverse = inverse * self.0 as u32 % (2 * Polynomial::N as u32);
+            i += 1
+        }
+        Self(inverse as u8)
+    }
 }
 
 impl Display for Polynomial {
@@ -442,12 +712,12 @@ impl Mul<&Polynomial> for &Matrix {
 impl<T: Into<Self>> Mul<T> for Polynomial {
     type Output = Polynomial;
     fn mul(self, rhs: T) -> Self::Output {
-        self.fft_mul(&rhs.into())
+        self.auto_mul(&rhs.into())
     }
 }
 
 impl<T: Into<Self>> MulAssign<T> for Polynomial {
     fn mul_assign(&mut self, rhs: T) {
-        self.fft_mul_assign(&rhs.into())
+        *self = self.auto_mul(&rhs.into())
     }
 }
\ No newline at end of file
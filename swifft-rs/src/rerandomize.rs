@@ -0,0 +1,35 @@
+//! Rerandomization of digests by the Galois action of $\mathbb{Z}_{257}[\alpha]/(\alpha^{64} + 1)$,
+//! for deriving identifiers that cannot be compared directly without knowing the preimage
+//!
+//! [`rerandomize`] maps a digest $d$ to $s \cdot \sigma_k(d)$, for an automorphism $\sigma_k$
+//! and a nonzero scalar $s$, and [`derandomize`] applies the inverses to get $d$ back.
+//!
+//! This is a structural transformation, not a security claim: there are only $64 \cdot 256$ choices of $(k, s)$,
+//! so anyone holding two digests can link them by trying every choice.
+//! What it preserves is the algebra: rerandomizing with a fixed $(k, s)$ is a $\mathbb{Z}_{257}$-linear bijection,
+//! so equal digests stay equal, distinct digests stay distinct, and [`Digest::compose`](crate::digest::Digest::compose)
+//! commutes with it. Since $\sigma_k$ is also multiplicative, the rerandomized digest of an input under some multipliers
+//! is the digest of the permuted input under the permuted multipliers, rather than a digest under the original ones
+
+use crate::polynomial::{OddExponent, Polynomial};
+use crate::z257::NonZeroZ257;
+
+/// Rerandomizes a digest as $s \cdot \sigma_k(d)$
+///
+/// # Arguments
+/// * `digest` - the digest $d$ to rerandomize
+/// * `k` - the exponent of the automorphism $\sigma_k : \alpha \mapsto \alpha^k$
+/// * `s` - the nonzero scalar
+pub const fn rerandomize(digest: &Polynomial, k: OddExponent, s: NonZeroZ257) -> Polynomial {
+    digest.automorphism(k).scalar_mul(&s.get())
+}
+
+/// Undoes [`rerandomize`] with the same `k` and `s`, as $\sigma_{k^{-1}}(s^{-1} \cdot d)$
+///
+/// # Arguments
+/// * `digest` - the rerandomized digest $d$
+/// * `k` - the exponent that `digest` was rerandomized with
+/// * `s` - the nonzero scalar that `digest` was rerandomized with
+pub const fn derandomize(digest: &Polynomial, k: OddExponent, s: NonZeroZ257) -> Polynomial {
+    digest.scalar_div(&s).automorphism(k.inverse())
+}
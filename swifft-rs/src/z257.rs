@@ -31,7 +31,8 @@ impl Z257 {
     /// from the provided byte value
     #[inline]
     pub const fn from_u8(value: u8) -> Self {
-        Self((value % (Self::P as u8)) as u16)
+        // every byte value is already less than `P`, which does not fit in a `u8`
+        Self(value as u16)
     }
 
     /// Creates a new element of $\mathbb{Z}_{257}$,
@@ -67,6 +68,13 @@ impl Z257 {
         }
     }
     
+    #[cfg(feature = "ct")]
+    #[inline]
+    pub const fn cn_add(&self, rhs: &Self) -> Self {
+        self.ct_add(rhs)
+    }
+
+    #[cfg(not(feature = "ct"))]
     #[inline]
     pub const fn cn_add(&self, rhs: &Self) -> Self {
         let result = self.0 + rhs.0;
@@ -77,6 +85,13 @@ impl Z257 {
         }
     }
 
+    #[cfg(feature = "ct")]
+    #[inline]
+    pub const fn cn_sub(&self, rhs: &Self) -> Self {
+        self.ct_sub(rhs)
+    }
+
+    #[cfg(not(feature = "ct"))]
     #[inline]
     pub const fn cn_sub(&self, rhs: &Self) -> Self {
         if self.0 >= rhs.0 {
@@ -86,6 +101,13 @@ impl Z257 {
         }
     }
 
+    #[cfg(feature = "ct")]
+    #[inline]
+    pub const fn cn_mul(&self, rhs: &Self) -> Self {
+        self.ct_mul(rhs)
+    }
+
+    #[cfg(not(feature = "ct"))]
     #[inline]
     pub const fn cn_mul(&self, rhs: &Self) -> Self {
         Self(((self.0 as u32 * rhs.0 as u32) % Self::P as u32) as u16)
@@ -178,6 +200,80 @@ impl Z257 {
     }
 }
 
+// CONSTANT-TIME OPS
+/// Field operations for the `ct` feature, for use on secret values.
+///
+/// They are written without branches, divisions or table lookups, so that neither their control flow
+/// nor the memory they access depends on the values operated on. This is a property of the source only:
+/// it is not checked mechanically, and the compiler is free to undo it, so it should be confirmed
+/// by measuring the final binary, e.g. with a dudect harness.
+///
+/// With the feature, the operations that have a constant-time counterpart use it: [`cn_add`](Self::cn_add),
+/// [`cn_sub`](Self::cn_sub) and [`cn_mul`](Self::cn_mul), and so the arithmetic operators the hash path is
+/// built on, as well as [`double`](Field::double), [`square`](Field::square) and [`invert`](Field::invert)
+#[cfg(feature = "ct")]
+impl Z257 {
+    /// Barrett reduction of `value` modulo $257$, by multiplying with $\lceil 2^{40} / 257 \rceil$.
+    /// Since $257 \cdot \lceil 2^{40} / 257 \rceil = 2^{40} + 1$, the quotient is exact for every `u32`,
+    /// and no correction step is needed
+    #[inline]
+    pub const fn reduce_u32(value: u32) -> Self {
+        let quotient = ((value as u64 * 0xFF00FF01) >> 40) as u32;
+        Self((value - quotient * Self::P as u32) as u16)
+    }
+
+    /// Subtracts $257$ from `value`, which must be less than $2 \cdot 257$, unless that would underflow,
+    /// by adding it back under a mask of the sign bit
+    #[inline]
+    const fn ct_reduce_once(value: u32) -> Self {
+        let difference = value.wrapping_sub(Self::P as u32);
+        let mask = 0u32.wrapping_sub(difference >> 31);
+        Self(difference.wrapping_add(Self::P as u32 & mask) as u16)
+    }
+
+    /// Adds two elements, reducing the sum with a mask rather than a branch
+    ///
+    /// # Arguments
+    /// * `rhs` - the element to add
+    #[inline]
+    pub const fn ct_add(&self, rhs: &Self) -> Self {
+        Self::ct_reduce_once(self.0 as u32 + rhs.0 as u32)
+    }
+
+    /// Subtracts two elements, adding $257$ first so that the difference never underflows,
+    /// and reducing it with a mask rather than a branch
+    ///
+    /// # Arguments
+    /// * `rhs` - the element to subtract
+    #[inline]
+    pub const fn ct_sub(&self, rhs: &Self) -> Self {
+        Self::ct_reduce_once(self.0 as u32 + Self::P as u32 - rhs.0 as u32)
+    }
+
+    /// Multiplies two elements, reducing the product with [`reduce_u32`](Self::reduce_u32) rather than a division
+    /// or a lookup into the multiplication table
+    ///
+    /// # Arguments
+    /// * `rhs` - the element to multiply by
+    #[inline]
+    pub const fn ct_mul(&self, rhs: &Self) -> Self {
+        Self::reduce_u32(self.0 as u32 * rhs.0 as u32)
+    }
+
+    /// Inverts by Fermat's little theorem, as $x^{255}$ with the same `7` squarings and multiplications
+    /// for every $x$, where zero inverts to zero rather than panicking
+    #[inline]
+    pub const fn ct_inv(&self) -> Self {
+        // $x^{2^k - 1}$ for $k = 1, \dots, 8$
+        let mut power = *self;
+        let mut k = 1; while k < 8 {
+            power = power.ct_mul(&power).ct_mul(self);
+            k += 1
+        }
+        power
+    }
+}
+
 // STRUCT CONSTS
 impl Z257 {
     // PUBLIC CONSTANTS
@@ -374,11 +470,7 @@ impl<T: Into<Self>> Add<T> for Z257 {
 impl<T: Into<Self>> AddAssign<T> for Z257 {
     #[inline]
     fn add_assign(&mut self, rhs: T) {
-        let rhs = rhs.into();
-        self.0 += rhs.0;
-        if self.0 >= Self::P {
-            self.0 -= Self::P
-        }
+        *self = self.cn_add(&rhs.into())
     }
 }
 
@@ -393,12 +485,7 @@ impl<T: Into<Self>> Sub<T> for Z257 {
 impl<T: Into<Self>> SubAssign<T> for Z257 {
     #[inline]
     fn sub_assign(&mut self, rhs: T) {
-        let rhs = rhs.into();
-        if self.0 >= rhs.0 {
-            self.0 -= rhs.0
-        } else {
-            self.0 += Self::P - rhs.0
-        }
+        *self = self.cn_sub(&rhs.into())
     }
 }
 
@@ -420,7 +507,7 @@ impl<T: Into<Self>> Mul<T> for Z257 {
 impl<T: Into<Self>> MulAssign<T> for Z257 {
     #[inline]
     fn mul_assign(&mut self, rhs: T) {
-        self.0 = ((self.0 as u32 * rhs.into().0 as u32) % Self::P as u32) as u16
+        *self = self.cn_mul(&rhs.into())
     }
 }
 
@@ -590,16 +677,39 @@ impl Field for Z257 {
         Self((rng.next_u32() % Self::P as u32) as u16)
     }
 
+    #[cfg(not(feature = "ct"))]
     #[inline]
     fn square(&self) -> Self {
         Self(Self::POW[self.0 as usize][2])
     }
 
+    #[cfg(feature = "ct")]
+    #[inline]
+    fn square(&self) -> Self {
+        self.ct_mul(self)
+    }
+
+    #[cfg(not(feature = "ct"))]
     #[inline]
     fn double(&self) -> Self {
         *self * Self::TWO
     }
 
+    #[cfg(feature = "ct")]
+    #[inline]
+    fn double(&self) -> Self {
+        self.ct_add(self)
+    }
+
+    #[cfg(feature = "ct")]
+    #[inline]
+    fn invert(&self) -> ff::derive::subtle::CtOption<Self> {
+        // `self - 1` wraps around to set the sign bit only for zero
+        let is_nonzero = 1 ^ ((self.0 as u32).wrapping_sub(1) >> 31) as u8;
+        ff::derive::subtle::CtOption::new(self.ct_inv(), ff::derive::subtle::Choice::from(is_nonzero))
+    }
+
+    #[cfg(not(feature = "ct"))]
     #[inline]
     fn invert(&self) -> ff::derive::subtle::CtOption<Self> {
         match self.cn_inv_checked() {
@@ -691,4 +801,36 @@ impl WithSmallOrderMulGroup<4> for Z257 {
 
 impl WithSmallOrderMulGroup<2> for Z257 {
     const ZETA: Self = Self::OMEGA_ORDER_2;
-}
\ No newline at end of file
+}
+
+/// A nonzero element of $\mathbb{Z}_{257}$, which is always invertible
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[repr(transparent)]
+pub struct NonZeroZ257(Z257);
+
+impl NonZeroZ257 {
+    /// Wraps `value`, or returns [`None`] if it is zero
+    #[inline]
+    pub const fn new(value: Z257) -> Option<Self> {
+        if value.cn_is_zero() { None } else { Some(Self(value)) }
+    }
+
+    /// The underlying element of $\mathbb{Z}_{257}$
+    #[inline]
+    pub const fn get(&self) -> Z257 {
+        self.0
+    }
+
+    /// The multiplicative inverse, which is nonzero as well
+    #[inline]
+    pub const fn cn_inv(&self) -> Self {
+        Self(Z257(Z257::INV[self.0.0 as usize]))
+    }
+}
+
+impl From<NonZeroZ257> for Z257 {
+    #[inline]
+    fn from(value: NonZeroZ257) -> Self {
+        value.0
+    }
+}
@@ -0,0 +1,89 @@
+//! Enforces that the core hash path performs no heap allocations.
+//!
+//! Parsing, signing, the transforms and the digests never allocate, and are checked with any features;
+//! the hash functions themselves allocate for the threads of the `parallel` feature, so they are only checked without it:
+//! `cargo test -p swifft --no-default-features --test allocations`
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use swifft::digest::Digest;
+use swifft::hash::{apply_sign_pattern, parse_input_block, swifft_hash_const, INPUT_BLOCK_SIZE};
+#[cfg(not(feature = "parallel"))]
+use swifft::hash::{swifft_hash, swifft_hash_owned, swifft_hash_signed};
+use swifft::polynomial::Polynomial;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const BLOCKS: usize = 1000;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+/// Calls `f` on [`BLOCKS`] pseudorandom blocks, returning the number of allocations it made
+fn count_allocations(mut f: impl FnMut(&[u8; INPUT_BLOCK_SIZE])) -> usize {
+    let mut block = [0u8; INPUT_BLOCK_SIZE];
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let before = allocations();
+    for _ in 0..BLOCKS {
+        for byte in block.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *byte = state as u8;
+        }
+        f(&block);
+    }
+    allocations() - before
+}
+
+#[test]
+fn parsing_transforms_and_digests_do_not_allocate() {
+    let allocations = count_allocations(|block| {
+        let input = parse_input_block(block);
+        let signed = apply_sign_pattern(&input, block);
+        let transformed = signed[0].fourier_coefficients();
+        std::hint::black_box(transformed.fft_mul(&input[1]));
+        std::hint::black_box(transformed.interpolate_fourier_coefficients());
+        let digest = swifft_hash_const(block);
+        let composed = digest.compose(&Digest::new(Polynomial::ZERO));
+        std::hint::black_box(Digest::from_bytes(&composed.to_bytes()));
+    });
+    assert_eq!(allocations, 0);
+}
+
+#[cfg(not(feature = "parallel"))]
+#[test]
+fn hashing_does_not_allocate() {
+    let allocations = count_allocations(|block| {
+        let input = parse_input_block(block);
+        std::hint::black_box(swifft_hash(&input));
+        std::hint::black_box(swifft_hash_owned(input));
+        std::hint::black_box(swifft_hash_signed(&input, block));
+    });
+    assert_eq!(allocations, 0);
+}
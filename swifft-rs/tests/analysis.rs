@@ -0,0 +1,40 @@
+//! Element histograms of polynomials, and their chi-squared statistics against the uniform distribution:
+//! `cargo test -p swifft --features analysis --test analysis`
+#![cfg(feature = "analysis")]
+
+use swifft::analysis::{chi_squared_per_element, element_histogram};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+/// The polynomial whose coefficient `i` is `coefficient(i)`
+fn polynomial(coefficient: impl Fn(usize) -> u16) -> Polynomial {
+    Polynomial::new(std::array::from_fn(|i| Z257::new(coefficient(i))))
+}
+
+#[test]
+fn histograms_count_every_coefficient() {
+    let histogram = element_histogram(&[polynomial(|i| i as u16), polynomial(|_| 256), polynomial(|i| i as u16)]);
+    for (i, counts) in histogram.iter().enumerate() {
+        assert_eq!(counts[i], 2, "coefficient {}", i);
+        assert_eq!(counts[256], 1, "coefficient {}", i);
+        assert_eq!(counts.iter().sum::<u32>(), 3, "coefficient {}", i);
+    }
+    assert!(element_histogram(&[]).iter().flatten().all(|&count| count == 0));
+}
+
+#[test]
+fn uniform_counts_have_no_chi_squared() {
+    // every element occurs exactly once at every coefficient
+    let polynomials: Vec<_> = (0..Z257::P).map(|value| polynomial(|i| (value + i as u16) % Z257::P)).collect();
+    assert!(chi_squared_per_element(&element_histogram(&polynomials)).iter().all(|&statistic| statistic == 0.0));
+    // nor do coefficients that were never observed
+    assert_eq!(chi_squared_per_element(&element_histogram(&[])), [0.0; Polynomial::N]);
+}
+
+#[test]
+fn constant_counts_are_maximally_biased() {
+    // 257 observations of a single element: (257 - 1)^2 / 1 for that element, and (0 - 1)^2 / 1 for the other 256
+    let polynomials = vec![polynomial(|i| i as u16); Z257::P as usize];
+    let chi_squared = chi_squared_per_element(&element_histogram(&polynomials));
+    assert!(chi_squared.iter().all(|&statistic| statistic == 256.0 * 256.0 + 256.0));
+}
@@ -0,0 +1,65 @@
+//! Inputs with coefficients in {0, 1, 2, 3}, packed 2 bits per coefficient
+
+use proptest::prelude::*;
+use swifft::hash::{
+    parse_input_block, parse_input_block_base4, swifft_hash, swifft_hash_base4, to_input_block_base4,
+    BASE4_INPUT_BLOCK_SIZE, INPUT_BLOCK_SIZE
+};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+fn base4_block() -> impl Strategy<Value = [u8; BASE4_INPUT_BLOCK_SIZE]> {
+    prop::collection::vec(any::<u8>(), BASE4_INPUT_BLOCK_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+fn binary_block() -> impl Strategy<Value = [u8; INPUT_BLOCK_SIZE]> {
+    prop::collection::vec(any::<u8>(), INPUT_BLOCK_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+/// Spreads every bit of a binary block into a coefficient of `2` bits
+fn widen(binary: &[u8; INPUT_BLOCK_SIZE]) -> [u8; BASE4_INPUT_BLOCK_SIZE] {
+    let mut block = [0u8; BASE4_INPUT_BLOCK_SIZE];
+    for k in 0..8 * INPUT_BLOCK_SIZE {
+        block[k / 4] |= ((binary[k / 8] >> (k % 8)) & 1) << (2 * (k % 4));
+    }
+    block
+}
+
+proptest! {
+    #[test]
+    fn packing_round_trips(block in base4_block()) {
+        prop_assert_eq!(to_input_block_base4(&parse_input_block_base4(&block)), Some(block));
+    }
+
+    #[test]
+    fn binary_coefficients_hash_identically(binary in binary_block()) {
+        prop_assert_eq!(parse_input_block_base4(&widen(&binary)), parse_input_block(&binary));
+        prop_assert_eq!(swifft_hash_base4(&widen(&binary)), swifft_hash(&parse_input_block(&binary)));
+    }
+
+    /// A base-4 input is its low bits plus twice its high bits, so by linearity so is its digest
+    #[test]
+    fn hash_is_linear_in_the_bit_planes(low in binary_block(), high in binary_block()) {
+        let block: Vec<u8> = widen(&low).iter().zip(widen(&high)).map(|(low, high)| low | high << 1).collect();
+        let high_digest = *swifft_hash(&parse_input_block(&high)).polynomial();
+        let expected = *swifft_hash(&parse_input_block(&low)).polynomial() + high_digest * Z257::new(2);
+        prop_assert_eq!(*swifft_hash_base4(&block.try_into().unwrap()).polynomial(), expected);
+    }
+
+    #[test]
+    fn sums_of_inputs_hash_to_sums_of_digests(x in binary_block(), y in binary_block()) {
+        let sum: Vec<u8> = widen(&x).iter().zip(widen(&y)).map(|(x, y)| x + y).collect();
+        let expected = swifft_hash(&parse_input_block(&x)).compose(&swifft_hash(&parse_input_block(&y)));
+        prop_assert_eq!(swifft_hash_base4(&sum.try_into().unwrap()), expected);
+    }
+}
+
+#[test]
+fn coefficients_above_three_cannot_be_packed() {
+    let mut input = parse_input_block_base4(&[0xff; BASE4_INPUT_BLOCK_SIZE]);
+    assert!(input.iter().flat_map(Polynomial::coefficients).all(|coefficient| coefficient.value() == 3));
+    let mut coefficients = *input[5].coefficients();
+    coefficients[17] = Z257::new(4);
+    input[5] = Polynomial::new(coefficients);
+    assert_eq!(to_input_block_base4(&input), None);
+}
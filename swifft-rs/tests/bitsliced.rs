@@ -0,0 +1,47 @@
+//! Batch hashing by transposed evaluation, which is experimental:
+//! `cargo test -p swifft --features experimental --test bitsliced`
+#![cfg(feature = "experimental")]
+
+use proptest::prelude::*;
+use swifft::digest::Digest;
+use swifft::hash::bitsliced::{swifft_hash_bitsliced, LANES};
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_const, INPUT_BLOCK_SIZE};
+
+fn blocks(max_len: usize) -> impl Strategy<Value = Vec<[u8; INPUT_BLOCK_SIZE]>> {
+    prop::collection::vec(prop::array::uniform32(any::<u8>()), 0..4 * max_len).prop_map(|quarters| {
+        quarters.chunks_exact(4).map(|quarters| quarters.concat().try_into().unwrap()).collect()
+    })
+}
+
+fn hash_each(blocks: &[[u8; INPUT_BLOCK_SIZE]]) -> Vec<Digest> {
+    blocks.iter().map(|block| swifft_hash(&parse_input_block(block))).collect()
+}
+
+proptest! {
+    #[test]
+    fn agrees_with_swifft_hash(blocks in blocks(2 * LANES)) {
+        prop_assert_eq!(swifft_hash_bitsliced(&blocks), hash_each(&blocks));
+    }
+
+    #[test]
+    fn independent_of_batch_size(blocks in blocks(LANES + LANES / 2), batch_size in 1..2 * LANES) {
+        let batched: Vec<Digest> = blocks.chunks(batch_size).flat_map(swifft_hash_bitsliced).collect();
+        prop_assert_eq!(batched, swifft_hash_bitsliced(&blocks));
+    }
+
+    #[test]
+    fn independent_of_order(mut blocks in blocks(LANES + LANES / 2)) {
+        let mut digests = swifft_hash_bitsliced(&blocks);
+        blocks.reverse();
+        digests.reverse();
+        prop_assert_eq!(swifft_hash_bitsliced(&blocks), digests);
+    }
+}
+
+#[test]
+fn extreme_blocks_agree_with_swifft_hash_const() {
+    let blocks = [[0x00; INPUT_BLOCK_SIZE], [0xFF; INPUT_BLOCK_SIZE], [0x55; INPUT_BLOCK_SIZE]];
+    let expected: Vec<Digest> = blocks.iter().map(swifft_hash_const).collect();
+    assert_eq!(swifft_hash_bitsliced(&blocks), expected);
+    assert!(swifft_hash_bitsliced(&[]).is_empty());
+}
@@ -0,0 +1,42 @@
+//! Inputs parsed from `bitvec` bit slices:
+//! `cargo test -p swifft --features bitvec --test bitvec`
+#![cfg(feature = "bitvec")]
+
+use bitvec::prelude::*;
+use proptest::prelude::*;
+use swifft::hash::{parse_bitslice, parse_input_block, parse_input_block_base4, swifft_hash, to_bitvec,
+                   INPUT_BLOCK_SIZE, INPUT_SIZE};
+
+fn block() -> impl Strategy<Value = [u8; INPUT_BLOCK_SIZE]> {
+    prop::collection::vec(any::<u8>(), INPUT_BLOCK_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+proptest! {
+    #[test]
+    fn parse_bitslice_round_trips(block in block()) {
+        let bits = BitVec::<u8, Lsb0>::from_slice(&block);
+        let input = parse_bitslice(&bits).unwrap();
+        prop_assert_eq!(input, parse_input_block(&block));
+        prop_assert_eq!(to_bitvec(&input), Some(bits));
+    }
+
+    #[test]
+    fn digests_match_the_byte_based_input(block in block()) {
+        let input = parse_bitslice(block.view_bits::<Lsb0>()).unwrap();
+        prop_assert_eq!(swifft_hash(&input), swifft_hash(&parse_input_block(&block)));
+    }
+}
+
+#[test]
+fn wrongly_sized_bit_slices_are_rejected() {
+    let bits = bitvec![u8, Lsb0; 0; INPUT_SIZE + 1];
+    assert_eq!(parse_bitslice(&bits[..INPUT_SIZE - 1]), None);
+    assert_eq!(parse_bitslice(&bits), None);
+}
+
+#[test]
+fn non_binary_inputs_have_no_bits() {
+    let mut base4 = [0u8; 2 * INPUT_BLOCK_SIZE];
+    base4[0] = 0b10;
+    assert_eq!(to_bitvec(&parse_input_block_base4(&base4)), None);
+}
@@ -0,0 +1,7 @@
+//! Code written against the previous release still compiles through the shims of `swifft::compat`
+
+#[test]
+fn old_style_code_compiles() {
+    let t = trybuild::TestCases::new();
+    t.pass("tests/compat/*.rs");
+}
@@ -0,0 +1,14 @@
+//! Code that still works on polynomials can convert new digests into them
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+use swifft::polynomial::Polynomial;
+
+fn takes_polynomial(polynomial: impl Into<Polynomial>) -> Polynomial {
+    polynomial.into()
+}
+
+fn main() {
+    let input = parse_input_block(&[0x5a; INPUT_BLOCK_SIZE]);
+    let digest = swifft_hash(&input);
+    assert_eq!(Polynomial::from(digest), *digest.polynomial());
+    assert_eq!(takes_polynomial(digest), digest.into_polynomial());
+}
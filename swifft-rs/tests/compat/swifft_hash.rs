@@ -0,0 +1,14 @@
+//! `swifft_hash` returned a bare `Polynomial` before `Digest` was introduced
+#![allow(deprecated)]
+
+use swifft::compat::swifft_hash;
+use swifft::hash::{parse_input_block, INPUT_BLOCK_SIZE};
+use swifft::polynomial::Polynomial;
+
+fn main() {
+    let input = parse_input_block(&[0x5a; INPUT_BLOCK_SIZE]);
+    let digest: Polynomial = swifft_hash(&input);
+    let composed = digest + swifft_hash(&input);
+    assert_eq!(composed, digest.cn_add(&digest));
+    assert_eq!(digest, swifft::hash::swifft_hash(&input).into_polynomial());
+}
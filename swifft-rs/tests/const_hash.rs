@@ -0,0 +1,28 @@
+//! Compile-time hashing agrees with the FFT-based hash function
+
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_const, INPUT_BLOCK_SIZE};
+
+const ZEROS: Digest = swifft_hash_const(&[0x00; INPUT_BLOCK_SIZE]);
+const ONES: Digest = swifft_hash_const(&[0xff; INPUT_BLOCK_SIZE]);
+
+#[test]
+fn const_digests_match_runtime_digests() {
+    assert_eq!(ZEROS, swifft_hash(&parse_input_block(&[0x00; INPUT_BLOCK_SIZE])));
+    assert_eq!(ONES, swifft_hash(&parse_input_block(&[0xff; INPUT_BLOCK_SIZE])));
+}
+
+#[test]
+fn const_hash_matches_runtime_hash_on_pseudorandom_blocks() {
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for _ in 0..8 {
+        let mut block = [0u8; INPUT_BLOCK_SIZE];
+        for byte in block.iter_mut() {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            *byte = state as u8;
+        }
+        assert_eq!(swifft_hash_const(&block), swifft_hash(&parse_input_block(&block)));
+    }
+}
@@ -0,0 +1,82 @@
+//! Constant-time field operations, which agree with the table-based ones over the whole field:
+//! `cargo test -p swifft --features ct --test ct`
+#![cfg(feature = "ct")]
+
+use ff::Field;
+use proptest::prelude::*;
+use swifft::z257::Z257;
+
+fn field() -> impl Iterator<Item = Z257> {
+    (0..Z257::P).map(Z257::new)
+}
+
+/// The sum, difference and product of `a` and `b`, reduced with a remainder rather than any of the field operations
+fn reference(a: Z257, b: Z257) -> (Z257, Z257, Z257) {
+    let (a, b) = (a.value() as u64, b.value() as u64);
+    (Z257::from_u64(a + b), Z257::from_u64(a + Z257::P as u64 - b), Z257::from_u64(a * b))
+}
+
+#[test]
+fn binary_operations_agree_exhaustively() {
+    for a in field() {
+        for b in field() {
+            let (sum, difference, product) = reference(a, b);
+            assert_eq!(a.ct_add(&b), sum, "{a:?} + {b:?}");
+            assert_eq!(a.ct_sub(&b), difference, "{a:?} - {b:?}");
+            assert_eq!(a.ct_mul(&b), product, "{a:?} * {b:?}");
+        }
+    }
+}
+
+#[test]
+fn operators_use_the_constant_time_operations_exhaustively() {
+    for a in field() {
+        for b in field() {
+            let (sum, difference, product) = reference(a, b);
+            assert_eq!((a.cn_add(&b), a + b), (sum, sum), "{a:?} + {b:?}");
+            assert_eq!((a.cn_sub(&b), a - b), (difference, difference), "{a:?} - {b:?}");
+            assert_eq!((a.cn_mul(&b), a * b), (product, product), "{a:?} * {b:?}");
+
+            let (mut x, mut y, mut z) = (a, a, a);
+            x += b;
+            y -= b;
+            z *= b;
+            assert_eq!((x, y, z), (sum, difference, product), "{a:?} op= {b:?}");
+        }
+    }
+}
+
+#[test]
+fn inverse_agrees_exhaustively() {
+    assert_eq!(Z257::ZERO.ct_inv(), Z257::ZERO);
+    for a in field().skip(1) {
+        assert_eq!(a.ct_inv(), a.cn_inv(), "{a:?}^-1");
+    }
+}
+
+#[test]
+fn field_operations_use_the_constant_time_operations() {
+    assert!(bool::from(Z257::ZERO.invert().is_none()));
+    for a in field() {
+        assert_eq!(a.double(), a.ct_add(&a), "2 * {a:?}");
+        assert_eq!(a.square(), a.ct_mul(&a), "{a:?}^2");
+        assert_eq!(a.square(), a.cn_mul(&a), "{a:?}^2");
+        if a != Z257::ZERO {
+            assert_eq!(a.invert().unwrap(), a.cn_inv(), "{a:?}^-1");
+        }
+    }
+}
+
+#[test]
+fn reduction_is_exact_at_the_extremes() {
+    for value in [0, 1, 256, 257, 258, 256 * 256, u32::MAX / 257 * 257, u32::MAX - 1, u32::MAX] {
+        assert_eq!(Z257::reduce_u32(value), Z257::from_u64(value as u64), "{value}");
+    }
+}
+
+proptest! {
+    #[test]
+    fn reduction_agrees_with_remainder(value in any::<u32>()) {
+        prop_assert_eq!(Z257::reduce_u32(value), Z257::from_u64(value as u64));
+    }
+}
@@ -0,0 +1,44 @@
+//! The parallel paths give identical digests for any number of threads,
+//! as if run with `RAYON_NUM_THREADS` set to `1`, `2` and the number of CPUs
+#![cfg(feature = "parallel")]
+
+use rayon::ThreadPoolBuilder;
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_owned, swifft_hash_signed_multiple,
+                   swifft_hash_signed_multiple_bytes, INPUT_BLOCK_SIZE};
+
+const NUM_BLOCKS: usize = 100;
+
+fn blocks(seed: usize) -> Vec<u8> {
+    (0..NUM_BLOCKS * INPUT_BLOCK_SIZE).map(|i| ((i + seed) * 131 % 251) as u8).collect()
+}
+
+/// Every parallel path, run on the current thread pool
+fn all_digests() -> Vec<Digest> {
+    let (input, sign) = (blocks(0), blocks(7));
+    let inputs: Vec<_> = input.chunks_exact(INPUT_BLOCK_SIZE)
+        .zip(sign.chunks_exact(INPUT_BLOCK_SIZE))
+        .map(|(input, sign)| (parse_input_block(input.try_into().unwrap()), sign.try_into().unwrap()))
+        .collect();
+
+    let mut digests = Vec::new();
+    digests.extend(inputs.iter().map(|(input, _)| swifft_hash(input)));
+    digests.extend(inputs.iter().map(|(input, _)| swifft_hash_owned(*input)));
+    digests.extend(swifft_hash_signed_multiple(&inputs));
+    digests.extend(swifft_hash_signed_multiple_bytes(&input, &sign).unwrap());
+    #[cfg(feature = "experimental")]
+    digests.extend(swifft::hash::bitsliced::swifft_hash_bitsliced(
+        &input.chunks_exact(INPUT_BLOCK_SIZE).map(|block| block.try_into().unwrap()).collect::<Vec<_>>()));
+    digests
+}
+
+#[test]
+fn digests_are_independent_of_thread_count() {
+    let max_threads = std::thread::available_parallelism().map_or(4, |threads| threads.get()).max(4);
+    let runs: Vec<Vec<Digest>> = [1, 2, 3, max_threads].into_iter()
+        .map(|num_threads| ThreadPoolBuilder::new().num_threads(num_threads).build().unwrap().install(all_digests))
+        .collect();
+    for run in &runs[1..] {
+        assert_eq!(run, &runs[0]);
+    }
+}
@@ -0,0 +1,64 @@
+//! Digests: their homomorphic composition and their canonical byte and hex encodings
+
+use proptest::prelude::*;
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+fn block() -> impl Strategy<Value = [u8; INPUT_BLOCK_SIZE]> {
+    prop::collection::vec(any::<u8>(), INPUT_BLOCK_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+fn digest(block: &[u8; INPUT_BLOCK_SIZE]) -> Digest {
+    swifft_hash(&parse_input_block(block))
+}
+
+fn digest_of(values: impl Fn(usize) -> u16) -> Digest {
+    Digest::new(Polynomial::new(std::array::from_fn(|i| Z257::new(values(i)))))
+}
+
+#[test]
+fn bytes_are_little_endian_coefficients() {
+    let bytes = digest_of(|i| [0, 1, 256, 255][i % 4]).to_bytes();
+    assert_eq!(bytes.len(), Digest::BYTES);
+    assert_eq!(bytes[..8], [0x00, 0x00, 0x01, 0x00, 0x00, 0x01, 0xff, 0x00]);
+    assert_eq!(bytes[126..], [0xff, 0x00]);
+    assert_eq!(digest_of(|_| 0).to_hex(), "0".repeat(256));
+    assert_eq!(&digest_of(|i| i as u16).to_hex()[..12], "000001000200");
+    assert_eq!(format!("{}", digest_of(|i| i as u16)), digest_of(|i| i as u16).to_hex());
+}
+
+#[test]
+fn non_canonical_encodings_are_rejected() {
+    let mut bytes = [0u8; Digest::BYTES];
+    bytes[2..4].copy_from_slice(&257u16.to_le_bytes());
+    assert_eq!(Digest::from_bytes(&bytes), None);
+    bytes[2..4].copy_from_slice(&256u16.to_le_bytes());
+    assert_eq!(Digest::from_bytes(&bytes), Some(digest_of(|i| if i == 1 { 256 } else { 0 })));
+
+    let hex = digest_of(|i| i as u16).to_hex();
+    assert_eq!(Digest::from_hex(&hex[1..]), None);
+    assert_eq!(Digest::from_hex(&format!("{}0", hex)), None);
+    assert_eq!(Digest::from_hex(&hex.replacen('0', "g", 1)), None);
+    assert_eq!(Digest::from_hex(&format!("+{}", &hex[1..])), None);
+    assert_eq!(Digest::from_hex(&format!("0+{}", &hex[2..])), None);
+    assert_eq!(Digest::from_hex(&hex.to_uppercase()), Some(digest_of(|i| i as u16)));
+}
+
+proptest! {
+    #[test]
+    fn compose_agrees_with_polynomial_addition(a in block(), b in block()) {
+        let (a, b) = (digest(&a), digest(&b));
+        prop_assert_eq!(a.compose(&b).into_polynomial(), a.into_polynomial() + b.into_polynomial());
+        prop_assert_eq!(a.compose(&b), b.compose(&a));
+    }
+
+    #[test]
+    fn bytes_and_hex_round_trip(block in block()) {
+        let digest = digest(&block);
+        prop_assert_eq!(Digest::from_bytes(&digest.to_bytes()), Some(digest));
+        prop_assert_eq!(Digest::from_hex(&digest.to_hex()), Some(digest));
+        prop_assert_eq!(Polynomial::from(digest), *digest.polynomial());
+    }
+}
@@ -0,0 +1,36 @@
+//! Serializing digests, as hex in human-readable formats and as their canonical bytes otherwise:
+//! `cargo test -p swifft --features serde --test digest_serde`
+#![cfg(feature = "serde")]
+
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash};
+
+fn digest() -> Digest {
+    swifft_hash(&parse_input_block(&std::array::from_fn(|i| (i * 7) as u8)))
+}
+
+#[test]
+fn human_readable_formats_use_hex() {
+    let json = serde_json::to_string(&digest()).unwrap();
+    assert_eq!(json, format!("\"{}\"", digest().to_hex()));
+    assert_eq!(serde_json::from_str::<Digest>(&json).unwrap(), digest());
+}
+
+#[test]
+fn binary_formats_use_the_canonical_bytes() {
+    let bytes = bincode::serialize(&digest()).unwrap();
+    // bincode prefixes the bytes with their length, as a `u64`
+    assert_eq!(bytes[..8], 128u64.to_le_bytes());
+    assert_eq!(bytes[8..], digest().to_bytes());
+    assert_eq!(bincode::deserialize::<Digest>(&bytes).unwrap(), digest());
+}
+
+#[test]
+fn non_canonical_digests_are_rejected() {
+    let mut bytes = digest().to_bytes();
+    bytes[..2].copy_from_slice(&257u16.to_le_bytes());
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    assert!(serde_json::from_str::<Digest>(&format!("\"{}\"", hex)).is_err());
+    assert!(bincode::deserialize::<Digest>(&bincode::serialize(&bytes.to_vec()).unwrap()).is_err());
+    assert!(serde_json::from_str::<Digest>("\"00\"").is_err());
+}
@@ -0,0 +1,89 @@
+//! Packing two halves of coefficients into one polynomial, by blocks or interleaved
+
+use proptest::prelude::*;
+use swifft::polynomial::{HalfCoefficients, Polynomial};
+use swifft::z257::Z257;
+
+fn half() -> impl Strategy<Value = HalfCoefficients> {
+    prop::array::uniform32((0u16..257).prop_map(Z257::new))
+}
+
+fn scalar() -> impl Strategy<Value = Z257> {
+    (0u16..257).prop_map(Z257::new)
+}
+
+/// The product in $\mathbb{Z}_{257}[\beta]/(\beta^{32} + 1)$
+fn half_mul(a: &HalfCoefficients, b: &HalfCoefficients) -> HalfCoefficients {
+    let mut product = [Z257::ZERO; 32];
+    for (i, a) in a.iter().enumerate() {
+        for (j, b) in b.iter().enumerate() {
+            if i + j < 32 {
+                product[i + j] += *a * *b;
+            } else {
+                product[i + j - 32] -= *a * *b;
+            }
+        }
+    }
+    product
+}
+
+/// The half polynomial $\beta$
+fn beta() -> HalfCoefficients {
+    let mut beta = [Z257::ZERO; 32];
+    beta[1] = Z257::ONE;
+    beta
+}
+
+fn add(a: &HalfCoefficients, b: &HalfCoefficients) -> HalfCoefficients {
+    std::array::from_fn(|i| a[i] + b[i])
+}
+
+fn scale(s: Z257, a: &HalfCoefficients) -> HalfCoefficients {
+    a.map(|a| s * a)
+}
+
+proptest! {
+    #[test]
+    fn halves_round_trip(lo in half(), hi in half()) {
+        let polynomial = Polynomial::from_halves(&lo, &hi);
+        prop_assert_eq!(polynomial.split_halves(), (lo, hi));
+
+        let polynomial = Polynomial::interleave(&lo, &hi);
+        prop_assert_eq!(polynomial.deinterleave(), (lo, hi));
+        prop_assert_eq!(&polynomial.coefficients()[..2], &[lo[0], hi[0]]);
+    }
+
+    #[test]
+    fn linear_combinations_keep_halves_separable(lo1 in half(), hi1 in half(), lo2 in half(), hi2 in half(),
+                                                 s in scalar(), t in scalar()) {
+        let combination = Polynomial::from_halves(&lo1, &hi1).scalar_mul(&s)
+            + Polynomial::from_halves(&lo2, &hi2).scalar_mul(&t);
+        prop_assert_eq!(combination.split_halves(),
+            (add(&scale(s, &lo1), &scale(t, &lo2)), add(&scale(s, &hi1), &scale(t, &hi2))));
+
+        let combination = Polynomial::interleave(&lo1, &hi1).scalar_mul(&s)
+            + Polynomial::interleave(&lo2, &hi2).scalar_mul(&t);
+        prop_assert_eq!(combination.deinterleave(),
+            (add(&scale(s, &lo1), &scale(t, &lo2)), add(&scale(s, &hi1), &scale(t, &hi2))));
+    }
+
+    #[test]
+    fn alpha_32_rotates_halves(lo in half(), hi in half()) {
+        let mut one = [Z257::ZERO; 32];
+        one[0] = Z257::ONE;
+        let alpha_32 = Polynomial::from_halves(&[Z257::ZERO; 32], &one);
+        prop_assert_eq!((Polynomial::from_halves(&lo, &hi) * alpha_32).split_halves(), (hi.map(|c| -c), lo));
+    }
+
+    #[test]
+    fn even_support_products_keep_interleaved_halves_separable(even in half(), odd in half(), c in half()) {
+        let product = Polynomial::interleave(&even, &odd) * Polynomial::interleave(&c, &[Z257::ZERO; 32]);
+        prop_assert_eq!(product.deinterleave(), (half_mul(&c, &even), half_mul(&c, &odd)));
+    }
+
+    #[test]
+    fn odd_support_products_swap_interleaved_halves(even in half(), odd in half(), c in half()) {
+        let product = Polynomial::interleave(&even, &odd) * Polynomial::interleave(&[Z257::ZERO; 32], &c);
+        prop_assert_eq!(product.deinterleave(), (half_mul(&beta(), &half_mul(&c, &odd)), half_mul(&c, &even)));
+    }
+}
@@ -0,0 +1,73 @@
+use proptest::prelude::*;
+use swifft::polynomial::Polynomial;
+
+/// Polynomial with at most `max_weight` nonzero coefficients
+fn polynomial(max_weight: usize) -> impl Strategy<Value = Polynomial> {
+    prop::collection::vec((0..Polynomial::N, 1u16..257), 0..=max_weight).prop_map(|terms| {
+        let mut coefficients = [0u16; Polynomial::N];
+        for (i, coefficient) in terms {
+            coefficients[i] = coefficient;
+        }
+        Polynomial::from_coefficients(&coefficients)
+    })
+}
+
+/// Polynomial with exactly `weight` nonzero coefficients
+fn polynomial_with_weight(weight: usize) -> impl Strategy<Value = Polynomial> {
+    (prop::sample::subsequence((0..Polynomial::N).collect::<Vec<_>>(), weight),
+        prop::collection::vec(1u16..257, weight)).prop_map(|(positions, values)| {
+        let mut coefficients = [0u16; Polynomial::N];
+        for (i, coefficient) in positions.into_iter().zip(values) {
+            coefficients[i] = coefficient;
+        }
+        Polynomial::from_coefficients(&coefficients)
+    })
+}
+
+fn boundary_weights() -> Vec<usize> {
+    [Polynomial::SPARSE_MUL_THRESHOLD, Polynomial::TOEPLITZ_MUL_THRESHOLD].into_iter()
+        .flat_map(|threshold| [threshold.saturating_sub(1), threshold, threshold + 1])
+        .filter(|&weight| weight <= Polynomial::N)
+        .collect()
+}
+
+fn assert_algorithms_agree(a: &Polynomial, b: &Polynomial) -> Result<(), TestCaseError> {
+    let expected = a.naive_mul(b);
+    prop_assert_eq!(a.sparse_mul(b), expected);
+    prop_assert_eq!(a.fft_mul(b), expected);
+    prop_assert_eq!(a.auto_mul(b), expected);
+    prop_assert_eq!(*a * *b, expected);
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn algorithms_agree_on_dense_operands(a in polynomial(Polynomial::N), b in polynomial(Polynomial::N)) {
+        assert_algorithms_agree(&a, &b)?;
+    }
+
+    #[test]
+    fn algorithms_agree_on_sparse_operands(a in polynomial(4), b in polynomial(Polynomial::N)) {
+        assert_algorithms_agree(&a, &b)?;
+        assert_algorithms_agree(&b, &a)?;
+    }
+
+    #[test]
+    fn algorithms_agree_at_dispatch_boundaries(
+        (a, b) in prop::sample::select(boundary_weights())
+            .prop_flat_map(|weight| (polynomial_with_weight(weight), polynomial(Polynomial::N))),
+    ) {
+        assert_algorithms_agree(&a, &b)?;
+        assert_algorithms_agree(&b, &a)?;
+    }
+
+    #[test]
+    fn weight_counts_nonzero_coefficients(weight in 0..=Polynomial::N) {
+        let polynomial = Polynomial::from_point_powers(&swifft::z257::Z257::THREE);
+        let mut coefficients = [0u16; Polynomial::N];
+        for (i, coefficient) in coefficients.iter_mut().enumerate().take(weight) {
+            *coefficient = polynomial[i].value();
+        }
+        prop_assert_eq!(Polynomial::from_coefficients(&coefficients).weight(), weight);
+    }
+}
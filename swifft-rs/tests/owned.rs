@@ -0,0 +1,43 @@
+//! Hashing borrowed and owned inputs
+
+use proptest::prelude::*;
+use swifft::hash::{parse_input_block, swifft_hash, swifft_hash_const, swifft_hash_owned, SwifftInput, INPUT_BLOCK_SIZE, M};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+fn block() -> impl Strategy<Value = [u8; INPUT_BLOCK_SIZE]> {
+    prop::collection::vec(any::<u8>(), INPUT_BLOCK_SIZE).prop_map(|bytes| bytes.try_into().unwrap())
+}
+
+/// Coefficients of every value in `Z_{257}`, not only the `0` and `1` of parsed blocks
+fn coefficients() -> impl Strategy<Value = Vec<u16>> {
+    prop::collection::vec(0u16..257, M * Polynomial::N)
+}
+
+proptest! {
+    #[test]
+    fn borrowed_and_owned_digests_match_reference(block in block()) {
+        let expected = swifft_hash_const(&block);
+        prop_assert_eq!(swifft_hash(&parse_input_block(&block)), expected);
+        prop_assert_eq!(swifft_hash_owned(parse_input_block(&block)), expected);
+    }
+
+    #[test]
+    fn borrowed_input_is_untouched(block in block()) {
+        let input = parse_input_block(&block);
+        let copy = input;
+        swifft_hash(&input);
+        prop_assert_eq!(input, copy);
+    }
+
+    #[test]
+    fn borrowed_digests_match_owned_digests_of_any_coefficients(coefficients in coefficients()) {
+        let input: SwifftInput = std::array::from_fn(|i| {
+            Polynomial::new(std::array::from_fn(|j| Z257::new(coefficients[i * Polynomial::N + j])))
+        });
+        let borrowed = swifft_hash(&input);
+        prop_assert_eq!(borrowed, swifft_hash_owned(input));
+        // hashing again through the borrow gives the same digest, as the borrowed input is left as it was
+        prop_assert_eq!(swifft_hash(&input), borrowed);
+    }
+}
@@ -0,0 +1,62 @@
+//! Rerandomization of digests by automorphisms and nonzero scalars
+
+use proptest::prelude::*;
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+use swifft::polynomial::{OddExponent, Polynomial};
+use swifft::rerandomize::{derandomize, rerandomize};
+use swifft::z257::{NonZeroZ257, Z257};
+
+fn polynomial() -> impl Strategy<Value = Polynomial> {
+    prop::collection::vec(0u16..257, Polynomial::N)
+        .prop_map(|coefficients| Polynomial::from_coefficients(&coefficients.try_into().unwrap()))
+}
+
+fn digest() -> impl Strategy<Value = Polynomial> {
+    prop::collection::vec(any::<u8>(), INPUT_BLOCK_SIZE)
+        .prop_map(|bytes| swifft_hash(&parse_input_block(&bytes.try_into().unwrap())).into_polynomial())
+}
+
+fn exponent() -> impl Strategy<Value = OddExponent> {
+    (0u8..64).prop_map(|k| OddExponent::new(2 * k + 1).unwrap())
+}
+
+fn scalar() -> impl Strategy<Value = NonZeroZ257> {
+    (1u16..257).prop_map(|s| NonZeroZ257::new(Z257::new(s)).unwrap())
+}
+
+proptest! {
+    #[test]
+    fn derandomize_undoes_rerandomize(p in polynomial(), k in exponent(), s in scalar()) {
+        prop_assert_eq!(derandomize(&rerandomize(&p, k, s), k, s), p);
+        prop_assert_eq!(rerandomize(&derandomize(&p, k, s), k, s), p);
+    }
+
+    #[test]
+    fn different_parameters_give_unequal_digests(d in digest(), k1 in exponent(), s1 in scalar(),
+                                                 k2 in exponent(), s2 in scalar()) {
+        prop_assume!((k1, s1) != (k2, s2));
+        prop_assert_ne!(rerandomize(&d, k1, s1), rerandomize(&d, k2, s2));
+    }
+
+    #[test]
+    fn rerandomize_commutes_with_composition(a in digest(), b in digest(), k in exponent(), s in scalar()) {
+        prop_assert_eq!(rerandomize(&(a + b), k, s), rerandomize(&a, k, s) + rerandomize(&b, k, s));
+    }
+
+    #[test]
+    fn automorphism_is_multiplicative(p in polynomial(), q in polynomial(), k in exponent()) {
+        prop_assert_eq!((p * q).automorphism(k), p.automorphism(k) * q.automorphism(k));
+    }
+}
+
+#[test]
+fn odd_exponents_invert_modulo_128() {
+    for k in (1..128).step_by(2) {
+        let k = OddExponent::new(k).unwrap();
+        assert_eq!(k.value() as u32 * k.inverse().value() as u32 % 128, 1);
+    }
+    assert_eq!(OddExponent::new(2), None);
+    assert_eq!(OddExponent::new(129).map(|k| k.value()), Some(1));
+    assert_eq!(Polynomial::ALPHA.automorphism(OddExponent::ONE), Polynomial::ALPHA);
+    assert_eq!(Polynomial::ALPHA.automorphism(OddExponent::new(65).unwrap()), -Polynomial::ALPHA);
+}
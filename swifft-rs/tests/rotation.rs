@@ -0,0 +1,64 @@
+//! Multiplication by $\alpha$ and $\alpha^{-1}$ as negacyclic rotations
+
+use proptest::prelude::*;
+use swifft::polynomial::Polynomial;
+
+fn polynomial() -> impl Strategy<Value = Polynomial> {
+    prop::collection::vec(0u16..257, Polynomial::N)
+        .prop_map(|coefficients| Polynomial::from_coefficients(&coefficients.try_into().unwrap()))
+}
+
+/// $\alpha^{-1} = -\alpha^{63}$
+fn alpha_inverse() -> Polynomial {
+    let mut coefficients = [0u16; Polynomial::N];
+    coefficients[Polynomial::N - 1] = 256;
+    Polynomial::from_coefficients(&coefficients)
+}
+
+fn repeat(polynomial: &Polynomial, times: usize, f: fn(&Polynomial) -> Polynomial) -> Polynomial {
+    (0..times).fold(*polynomial, |p, _| f(&p))
+}
+
+proptest! {
+    #[test]
+    fn increment_power_has_order_128(p in polynomial()) {
+        prop_assert_eq!(repeat(&p, Polynomial::N, Polynomial::increment_power), -p);
+        prop_assert_eq!(repeat(&p, 2 * Polynomial::N, Polynomial::increment_power), p);
+    }
+
+    #[test]
+    fn increment_power_multiplies_by_alpha(p in polynomial()) {
+        prop_assert_eq!(p.increment_power(), p.fft_mul(&Polynomial::ALPHA));
+    }
+
+    #[test]
+    fn increment_power_assign_agrees(p in polynomial()) {
+        let mut assigned = p;
+        assigned.increment_power_assign();
+        prop_assert_eq!(assigned, p.increment_power());
+    }
+
+    #[test]
+    fn decrement_power_has_order_128(p in polynomial()) {
+        prop_assert_eq!(repeat(&p, Polynomial::N, Polynomial::decrement_power), -p);
+        prop_assert_eq!(repeat(&p, 2 * Polynomial::N, Polynomial::decrement_power), p);
+    }
+
+    #[test]
+    fn decrement_power_multiplies_by_alpha_inverse(p in polynomial()) {
+        prop_assert_eq!(p.decrement_power(), p.fft_mul(&alpha_inverse()));
+    }
+
+    #[test]
+    fn decrement_power_assign_agrees(p in polynomial()) {
+        let mut assigned = p;
+        assigned.decrement_power_assign();
+        prop_assert_eq!(assigned, p.decrement_power());
+    }
+
+    #[test]
+    fn decrement_power_inverts_increment_power(p in polynomial()) {
+        prop_assert_eq!(p.increment_power().decrement_power(), p);
+        prop_assert_eq!(p.decrement_power().increment_power(), p);
+    }
+}
@@ -0,0 +1,44 @@
+//! Snapshot tests of how the values of the pure Rust implementation format: the `Display` and `Debug`
+//! of a [`Z257`] element, a [`Polynomial`] and a [`Digest`].
+//!
+//! Debugging sessions and test failures are read through these, so a change here must be deliberate.
+//! Update them with `INSTA_UPDATE=always cargo test -p swifft --test snapshots`, or `cargo insta review`,
+//! and review the changed `.snap` files under `swifft-rs/tests/snapshots/` before committing them.
+
+use swifft::digest::Digest;
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+use swifft::polynomial::Polynomial;
+use swifft::z257::Z257;
+
+fn digest() -> Digest {
+    let mut block = [0u8; INPUT_BLOCK_SIZE];
+    for (i, byte) in block.iter_mut().enumerate() {
+        *byte = i as u8
+    }
+    swifft_hash(&parse_input_block(&block))
+}
+
+#[test]
+fn z257_display_and_debug() {
+    insta::assert_snapshot!(format!("{} {:?}", Z257::new(256), Z257::OMEGA_ORDER_128));
+}
+
+#[test]
+fn polynomial_display() {
+    insta::assert_snapshot!(format!("{}", Polynomial::OMEGA_ORDER_128_POWERS));
+}
+
+#[test]
+fn polynomial_debug() {
+    insta::assert_snapshot!(format!("{:?}", Polynomial::ALPHA));
+}
+
+#[test]
+fn digest_display() {
+    insta::assert_snapshot!(format!("{}", digest()));
+}
+
+#[test]
+fn digest_debug() {
+    insta::assert_snapshot!(format!("{:?}", digest()));
+}
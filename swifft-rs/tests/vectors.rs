@@ -0,0 +1,29 @@
+//! Checks the pure Rust implementation against the checked-in test vectors under `vectors/`.
+//!
+//! A failure here means the output of a hashing mode changed; if that was deliberate,
+//! regenerate the vectors with `cargo xtask gen-vectors --force`.
+
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[test]
+fn pure_backend_matches_vectors() {
+    let vectors = std::fs::read_to_string(concat!(env!("CARGO_MANIFEST_DIR"), "/../vectors/swifft.csv")).unwrap();
+    let mut checked = 0;
+    for (line_number, line) in vectors.lines().enumerate().skip(1) {
+        let [mode, input_hex, _, expected] = line.split(',').collect::<Vec<_>>()[..] else {
+            panic!("malformed vector on line {}", line_number + 1)
+        };
+        let block: [u8; INPUT_BLOCK_SIZE] = from_hex(input_hex).try_into().unwrap();
+        let actual = match mode {
+            "swifft_hash" => swifft_hash(&parse_input_block(&block)).to_hex(),
+            mode => panic!("unknown mode {} on line {}", mode, line_number + 1),
+        };
+        assert_eq!(actual, expected, "{} vector on line {} changed", mode, line_number + 1);
+        checked += 1;
+    }
+    assert!(checked > 0);
+}
@@ -0,0 +1,18 @@
+//! Conversions into elements of `Z_{257}`
+
+use swifft::z257::Z257;
+
+#[test]
+fn bytes_convert_to_their_own_value() {
+    for value in 0..=u8::MAX {
+        assert_eq!(Z257::from_u8(value).value(), value as u16);
+        assert_eq!(Z257::from(value), Z257::new(value as u16));
+    }
+}
+
+#[test]
+fn wider_values_are_reduced() {
+    assert_eq!(Z257::new(257), Z257::ZERO);
+    assert_eq!(Z257::new(u16::MAX).value(), u16::MAX % 257);
+    assert_eq!(Z257::from_u64(u64::MAX).value(), (u64::MAX % 257) as u16);
+}
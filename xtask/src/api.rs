@@ -0,0 +1,358 @@
+//! Snapshots of the public API surface of the library crates under `api/`,
+//! so that signature changes are always deliberate
+//!
+//! The surface is found by scanning the sources rather than by compiling them, starting at `lib.rs`
+//! and following `mod` declarations: one line per public item, with its signature up to its body,
+//! the `cfg`, `deprecated` and `non_exhaustive` attributes on it, and the `impl` block it is declared in.
+//! Enum bodies and trait members are included in full, as are the public fields of structs.
+//! Of private modules only the `impl` blocks are included, as their trait impls and methods still apply to public types,
+//! and `impl` blocks of the private types of the crate are left out.
+//! This catches added, removed and changed items, but not e.g. changes to the bounds of a generic `impl` body,
+//! nor items made public through `pub use` of a private module, which this workspace avoids
+
+use std::path::Path;
+
+/// The library crates whose public API is snapshotted, and their directories
+const CRATES: [(&str, &str); 2] = [("libswifft", "libswifft"), ("swifft", "swifft-rs")];
+
+/// Compares the public API of every library crate to its snapshot, or overwrites the snapshots with `bless`
+pub fn check(root: &Path, bless: bool) -> Result<(), String> {
+    let mut changed = Vec::new();
+    for (name, dir) in CRATES {
+        let api = public_api(&root.join(dir).join("src"), name)?.join("\n") + "\n";
+        let path = root.join("api").join(name).with_extension("txt");
+        if bless {
+            std::fs::create_dir_all(root.join("api")).map_err(|err| err.to_string())?;
+            std::fs::write(&path, api).map_err(|err| err.to_string())?;
+            println!("wrote {}", path.display());
+            continue
+        }
+        let snapshot = std::fs::read_to_string(&path).unwrap_or_default();
+        if snapshot != api {
+            for line in snapshot.lines().filter(|line| !api.lines().any(|item| item == *line)) {
+                eprintln!("- {}", line);
+            }
+            for line in api.lines().filter(|line| !snapshot.lines().any(|item| item == *line)) {
+                eprintln!("+ {}", line);
+            }
+            changed.push(name);
+        }
+    }
+    if changed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("the public API of {} changed; if that is deliberate, run `cargo xtask api-check --bless`",
+            changed.join(" and ")))
+    }
+}
+
+/// The public items of the crate `name` whose sources are in `src`, in declaration order
+pub fn public_api(src: &Path, name: &str) -> Result<Vec<String>, String> {
+    let mut items = Vec::new();
+    let (mut public_types, mut private_types) = (Vec::new(), Vec::new());
+    let mut modules = vec![(src.join("lib.rs"), name.to_string(), true)];
+    while let Some((file, path, public)) = modules.pop() {
+        let source = std::fs::read_to_string(&file).map_err(|err| format!("{}: {}", file.display(), err))?;
+        let mut scanner = Scanner::new(path.clone(), public);
+        for line in source.lines() {
+            scanner.line(line);
+        }
+        // submodules live next to `lib.rs`, and in a directory named after any other module
+        let dir = match file.file_name().and_then(|name| name.to_str()) {
+            Some("lib.rs") => src.to_path_buf(),
+            _ => file.with_extension(""),
+        };
+        for (module, module_public) in scanner.modules.iter().rev() {
+            modules.push((dir.join(module).with_extension("rs"), format!("{}::{}", path, module), public && *module_public));
+        }
+        items.extend(scanner.items);
+        public_types.extend(scanner.public_types);
+        private_types.extend(scanner.private_types);
+    }
+    // a type name declared both publicly and privately in different modules is kept, as either may be meant
+    let is_private = |name: &String| private_types.contains(name) && !public_types.contains(name);
+    Ok(items.into_iter()
+        .filter(|item| !item.self_type.as_ref().is_some_and(is_private))
+        .map(|item| item.line)
+        .collect())
+}
+
+/// A line of the snapshot, with the type of the `impl` block it is declared in, if any
+struct Item {
+    line: String,
+    self_type: Option<String>,
+}
+
+/// What the block at some depth of braces belongs to
+enum Block {
+    /// An `impl` block, with its header and the name of its type
+    Impl(String, String),
+    /// The body of a public struct, with its name
+    Struct(String),
+    /// The body of a public enum, appended in full to the item at the index
+    Enum(usize),
+    /// The body of a public trait, with its name
+    Trait(String),
+    /// Any other block, e.g. a function body
+    Other,
+}
+
+/// A line-based scanner of the public items of a single module
+struct Scanner {
+    module: String,
+    /// Whether the module is reachable from outside the crate, so that its public items other than in `impl` blocks are
+    public: bool,
+    items: Vec<Item>,
+    /// The submodules declared in the module, and whether each is declared `pub`
+    modules: Vec<(String, bool)>,
+    /// The names of the types declared in the module, by whether they are declared `pub`
+    public_types: Vec<String>,
+    private_types: Vec<String>,
+    blocks: Vec<(usize, Block)>,
+    depth: usize,
+    attributes: Vec<String>,
+    /// A signature spanning several lines, gathered until its body or terminator
+    pending: Option<String>,
+}
+
+impl Scanner {
+    fn new(module: String, public: bool) -> Self {
+        Self {
+            module, public, items: Vec::new(), modules: Vec::new(), public_types: Vec::new(), private_types: Vec::new(),
+            blocks: Vec::new(), depth: 0, attributes: Vec::new(), pending: None,
+        }
+    }
+
+    fn line(&mut self, line: &str) {
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            return
+        }
+        if let Some(pending) = self.pending.take() {
+            return self.signature(format!("{} {}", pending, line))
+        }
+        if line.starts_with("#[") || line.starts_with("#![") {
+            if ["#[cfg(", "#[deprecated", "#[non_exhaustive]"].iter().any(|prefix| line.starts_with(prefix)) {
+                self.attributes.push(line.to_string());
+            }
+            return
+        }
+
+        match self.blocks.last() {
+            Some((_, Block::Enum(index))) => {
+                let index = *index;
+                self.items[index].line.push(' ');
+                self.items[index].line.push_str(line);
+                self.attributes.clear();
+                return self.braces(line)
+            }
+            Some((depth, Block::Trait(_))) if *depth == self.depth => {
+                let member = ["fn ", "unsafe fn ", "const ", "type "].iter().any(|prefix| line.starts_with(prefix));
+                if member {
+                    return self.signature(line.to_string())
+                }
+            }
+            _ => {}
+        }
+        let is_impl = line.starts_with("impl") || line.starts_with("unsafe impl");
+        if (line.starts_with("pub ") || is_impl) && self.in_item_scope() {
+            return self.signature(line.to_string())
+        }
+        if self.blocks.is_empty() {
+            // private declarations, including those visible only within the crate, e.g. `pub(crate) struct`
+            let declaration = line.strip_prefix("pub(").and_then(|rest| rest.split_once(") ")).map_or(line, |(_, rest)| rest);
+            if let Some(module) = declaration.strip_prefix("mod ").and_then(|rest| rest.strip_suffix(';')) {
+                self.modules.push((module.to_string(), false));
+            } else if ["struct ", "enum ", "type ", "union "].iter().any(|prefix| declaration.starts_with(prefix)) {
+                self.private_types.push(item_name(declaration));
+            }
+        }
+        self.attributes.clear();
+        self.braces(line)
+    }
+
+    /// Whether items declared at the current depth are part of the API, i.e. not inside a function body
+    fn in_item_scope(&self) -> bool {
+        match self.blocks.last() {
+            None => true,
+            Some((depth, block)) => *depth == self.depth && !matches!(block, Block::Other),
+        }
+    }
+
+    /// Records the item whose signature starts `signature`, once it is complete
+    fn signature(&mut self, signature: String) {
+        let end = find_end(&signature);
+        let field = matches!(self.blocks.last(), Some((_, Block::Struct(_)))) && signature.ends_with(',');
+        let Some(end) = end.or(field.then_some(signature.len() - 1)) else {
+            self.pending = Some(signature);
+            return
+        };
+        let opens = signature[end..].starts_with('{');
+        let mut declaration = normalize(&signature[..end]);
+        if declaration.starts_with("pub const ") || declaration.starts_with("pub static ") {
+            // the value of a constant is not part of its signature
+            declaration = declaration.split(" = ").next().unwrap_or_default().to_string();
+        }
+        let attributes = std::mem::take(&mut self.attributes);
+        let prefix = attributes.iter().map(|attribute| format!("{} ", attribute)).collect::<String>();
+
+        let is_impl = declaration.starts_with("impl") || declaration.starts_with("unsafe impl");
+        let in_impl = matches!(self.blocks.last(), Some((_, Block::Impl(..))));
+        let block = if is_impl {
+            let header = format!("{}{}", prefix, declaration);
+            let self_type = impl_type(&declaration);
+            // inherent impl blocks are not items themselves, only the public items in them are
+            if declaration.contains(" for ") {
+                self.items.push(Item { line: format!("{}: {}", self.module, header), self_type: Some(self_type.clone()) });
+            }
+            Block::Impl(header, self_type)
+        } else if let Some(module) = declaration.strip_prefix("pub mod ").filter(|_| !opens) {
+            self.modules.push((module.to_string(), true));
+            if self.public {
+                self.push(format!("{}{}", prefix, declaration));
+            }
+            Block::Other
+        } else if !self.public && !in_impl {
+            // the public items of a private module are not reachable, as this workspace never re-exports them
+            if ["pub struct ", "pub enum ", "pub type ", "pub union "].iter().any(|kind| declaration.starts_with(kind)) {
+                self.private_types.push(item_name(&declaration));
+            }
+            Block::Other
+        } else {
+            self.push(format!("{}{}", prefix, declaration));
+            let name = item_name(&declaration);
+            if ["pub struct ", "pub enum ", "pub type ", "pub union ", "pub trait ", "pub unsafe trait "].iter()
+                .any(|kind| declaration.starts_with(kind))
+            {
+                self.public_types.push(name.clone());
+            }
+            if declaration.starts_with("pub enum ") {
+                let index = self.items.len() - 1;
+                self.items[index].line.push_str(" {");
+                Block::Enum(index)
+            } else if declaration.starts_with("pub struct ") {
+                Block::Struct(name)
+            } else if declaration.starts_with("pub trait ") || declaration.starts_with("pub unsafe trait ") {
+                Block::Trait(name)
+            } else {
+                Block::Other
+            }
+        };
+        if opens {
+            self.blocks.push((self.depth + 1, block));
+        }
+        // count only the braces after the one that opened the block, e.g. of a one-line body
+        self.depth += usize::from(opens);
+        self.braces(&signature[end + usize::from(opens)..]);
+    }
+
+    /// Records an item, qualified by the module and any block it is declared in
+    fn push(&mut self, item: String) {
+        let (context, self_type) = match self.blocks.last() {
+            Some((_, Block::Impl(header, self_type))) => (format!("{}: ", header), Some(self_type.clone())),
+            Some((_, Block::Struct(name) | Block::Trait(name))) => (format!("{}: ", name), None),
+            _ => (String::new(), None),
+        };
+        self.items.push(Item { line: format!("{}: {}{}", self.module, context, item), self_type });
+    }
+
+    /// Tracks the depth of braces, closing the blocks that end on `line`
+    fn braces(&mut self, line: &str) {
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    self.depth += 1;
+                    if !matches!(self.blocks.last(), Some((depth, Block::Enum(_))) if *depth < self.depth) {
+                        self.blocks.push((self.depth, Block::Other));
+                    }
+                }
+                '}' => {
+                    if let Some((depth, _)) = self.blocks.last() {
+                        if *depth == self.depth {
+                            self.blocks.pop();
+                        }
+                    }
+                    self.depth = self.depth.saturating_sub(1);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The position of the `{` or `;` that ends a signature, outside of any parentheses or brackets,
+/// e.g. of array types such as `[u8; 256]`, and of the braces of `use` groups such as `a::{B, C}`
+fn find_end(signature: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in signature.char_indices() {
+        match c {
+            '(' | '[' => depth += 1,
+            '{' if signature[..i].ends_with("::") => depth += 1,
+            ')' | ']' | '}' => depth = depth.saturating_sub(1),
+            '{' | ';' if depth == 0 => return Some(i),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The line without any trailing `//` comment, outside of string literals
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut previous = ' ';
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' if previous != '\\' => in_string = !in_string,
+            '/' if !in_string && previous == '/' => return &line[..i - 1],
+            _ => {}
+        }
+        previous = c;
+    }
+    line
+}
+
+/// Collapses runs of whitespace, and the whitespace after opening and before closing brackets
+fn normalize(signature: &str) -> String {
+    signature.split_whitespace().collect::<Vec<_>>().join(" ")
+        .replace("( ", "(").replace(" )", ")").replace("< ", "<").replace(" >", ">")
+        .replace(", )", ")").replace(",)", ")").trim().to_string()
+}
+
+/// The name of the type of the `impl` block with the header `declaration`, e.g. `Foo` of `impl<T> Trait<T> for a::Foo<T>`
+fn impl_type(declaration: &str) -> String {
+    let header = declaration.trim_start_matches("unsafe ").trim_start_matches("impl");
+    // skip the generic parameters of the impl, which may themselves contain angle brackets
+    let mut depth = 0usize;
+    let start = header.char_indices().find(|&(_, c)| {
+        match c {
+            '<' => depth += 1,
+            '>' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        depth == 0 && c.is_whitespace()
+    }).map_or(0, |(i, _)| i);
+    let header = header[start..].trim();
+    let header = header.split_once(" for ").map_or(header, |(_, self_type)| self_type);
+    header.split(" where ").next().unwrap_or_default()
+        .trim_start_matches(['&', '(', '['])
+        .trim_start_matches("mut ")
+        .trim_start_matches("dyn ")
+        .split(['<', ' ', ';', ']', ')'])
+        .next()
+        .unwrap_or_default()
+        .rsplit("::")
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// The name of the item declared by `declaration`, e.g. `Foo` of `pub struct Foo<T>(T)`
+fn item_name(declaration: &str) -> String {
+    declaration.split_whitespace()
+        .find(|word| !matches!(*word, "pub" | "struct" | "enum" | "trait" | "unsafe" | "type" | "union"))
+        .unwrap_or_default()
+        .split(['<', '(', ':'])
+        .next()
+        .unwrap_or_default()
+        .to_string()
+}
@@ -0,0 +1,7 @@
+//! The tasks of the workspace automation, kept in a library so that they can be tested
+
+pub mod api;
+pub mod msrv;
+pub mod perf;
+pub mod regressions;
+pub mod vectors;
@@ -0,0 +1,57 @@
+//! Workspace automation, run with `cargo xtask <task>`
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, ExitCode};
+
+use xtask::{api, msrv, perf, regressions, vectors};
+
+const USAGE: &str = "\
+usage: cargo xtask <task>
+
+tasks:
+    gen-vectors [--force]    regenerate the test vectors under `vectors/` and `libswifft/vectors/`
+    check-no-panic           fail if a panic is reachable from the hashing hot path
+    check-msrv               check the workspace with the toolchain of its `rust-version`
+    api-check [--bless]      fail if the public API differs from its snapshot under `api/`;
+                             `--bless` records the current public API instead
+    add-regression <file>    add a fuzzing artifact to the regression corpus
+    perf [--save] [--tolerance <fraction>] [--runs <n>] [--corpus <file>] [--baseline <file>]
+                             fail if there is no baseline, or if the pure Rust throughput fell more than
+                             `--tolerance` (default 0.1) below it; `--save` records a new baseline";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("gen-vectors") => vectors::generate(&workspace_root(), args[1..].iter().any(|arg| arg == "--force")),
+        Some("check-no-panic") => check_no_panic(&workspace_root()),
+        Some("check-msrv") => msrv::check(&workspace_root()),
+        Some("api-check") => api::check(&workspace_root(), args[1..].iter().any(|arg| arg == "--bless")),
+        Some("perf") => perf::run(&workspace_root(), &args[1..]),
+        Some("add-regression") => match args.get(1) {
+            Some(file) => regressions::add(&workspace_root(), Path::new(file)),
+            None => Err(USAGE.to_string()),
+        },
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Builds the `no_panic` test target in release mode, where `no_panic` turns every reachable panic into a link error
+fn check_no_panic(root: &Path) -> Result<(), String> {
+    let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .current_dir(root)
+        .args(["test", "--release", "-p", "libswifft", "--no-default-features", "--features", "no-panic", "--test", "no_panic"])
+        .status()
+        .map_err(|err| err.to_string())?;
+    if status.success() { Ok(()) } else { Err("a panic is reachable from the hashing hot path".to_string()) }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap().to_path_buf()
+}
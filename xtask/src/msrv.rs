@@ -0,0 +1,70 @@
+//! Checking the workspace with the toolchain of its minimum supported Rust version
+
+use std::path::Path;
+use std::process::Command;
+
+/// The comment that marks the next feature of a manifest as requiring a nightly toolchain
+const NIGHTLY_MARKER: &str = "# requires a nightly toolchain";
+
+/// The packages whose features are checked, with the directories of their manifests
+const PACKAGES: [(&str, &str); 2] = [("libswifft", "libswifft"), ("swifft", "swifft-rs")];
+
+/// Checks the workspace, with default and with all stable features, using the toolchain named by the `rust-version`
+/// of `libswifft`, which must be installed with rustup
+pub fn check(root: &Path) -> Result<(), String> {
+    let read = |dir: &str| std::fs::read_to_string(root.join(dir).join("Cargo.toml")).map_err(|err| err.to_string());
+    let manifest = read("libswifft")?;
+    let msrv = manifest.lines()
+        .find_map(|line| line.strip_prefix("rust-version = "))
+        .map(|version| version.trim_matches('"'))
+        .ok_or("`libswifft/Cargo.toml` declares no `rust-version`")?;
+    let mut stable = Vec::new();
+    for (package, dir) in PACKAGES {
+        stable.extend(stable_features(&read(dir)?).into_iter().map(|feature| format!("{}/{}", package, feature)));
+    }
+    let stable = stable.join(",");
+    for features in [&[][..], &["--features", &stable][..]] {
+        // `cargo` from `PATH` is the rustup proxy, which understands the `+toolchain` argument
+        let status = Command::new("cargo")
+            .current_dir(root)
+            .env_remove("RUSTUP_TOOLCHAIN")
+            .arg(format!("+{}", msrv))
+            .args(["check", "--workspace"])
+            .args(features)
+            .status()
+            .map_err(|err| err.to_string())?;
+        if !status.success() {
+            return Err(format!("the workspace does not build with its minimum supported Rust version {}", msrv))
+        }
+    }
+    Ok(())
+}
+
+/// The features declared in the `[features]` table of `manifest`, except `default` and those
+/// directly preceded by the comment `# requires a nightly toolchain`
+///
+/// # Arguments
+/// * `manifest` - the contents of a `Cargo.toml`
+pub fn stable_features(manifest: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut nightly = false;
+    for line in manifest.lines()
+        .skip_while(|line| line.trim() != "[features]")
+        .skip(1)
+        .take_while(|line| !line.trim_start().starts_with('['))
+        .map(str::trim)
+    {
+        if line.starts_with('#') {
+            nightly |= line == NIGHTLY_MARKER;
+            continue
+        }
+        if let Some((name, _)) = line.split_once('=') {
+            let name = name.trim();
+            if !nightly && name != "default" {
+                features.push(name.to_string());
+            }
+            nightly = false;
+        }
+    }
+    features
+}
@@ -0,0 +1,204 @@
+//! Performance regression gate, timing the pure Rust implementation against the native library
+//! over a fixed corpus and comparing the pure Rust throughput with a previously saved baseline
+
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Instant;
+
+use libswifft::buffer::{fill_from_seed, AlignedBuffer, Input, Output};
+use libswifft::hash::compute_slice;
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE as SWIFFT_INPUT_BLOCK_SIZE};
+
+/// The size of a block of the corpus, which both implementations hash
+const BLOCK_SIZE: usize = 256;
+
+/// The number of blocks of the default, pseudorandom corpus
+const DEFAULT_CORPUS_BLOCKS: usize = 4096;
+
+/// The seed of the default, pseudorandom corpus
+const DEFAULT_CORPUS_SEED: u64 = 0x5EED;
+
+/// The arguments of the `perf` task
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// Whether to record the timings as the new baseline rather than compare against it
+    pub save: bool,
+    /// The fraction by which the pure Rust throughput may fall below the baseline
+    pub tolerance: f64,
+    /// The number of timed runs, of which the median is taken
+    pub runs: usize,
+    /// The file of blocks to hash, instead of the default pseudorandom corpus
+    pub corpus: Option<PathBuf>,
+    /// The file of the baseline to compare against or record
+    pub baseline: PathBuf,
+}
+
+/// The median throughput of both implementations, in blocks per second
+#[derive(Debug, Clone, PartialEq)]
+pub struct Timings {
+    /// The number of blocks of the corpus
+    pub corpus_blocks: usize,
+    /// The throughput of the native library
+    pub c_blocks_per_sec: f64,
+    /// The throughput of the pure Rust implementation
+    pub rust_blocks_per_sec: f64,
+}
+
+/// Runs the `perf` task with the arguments following its name
+pub fn run(root: &Path, args: &[String]) -> Result<(), String> {
+    if cfg!(debug_assertions) {
+        // the timings of an unoptimized build are meaningless, so rerun the task from a release build
+        let status = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+            .current_dir(root)
+            .args(["run", "--release", "--package", "xtask", "--", "perf"])
+            .args(args)
+            .status()
+            .map_err(|err| err.to_string())?;
+        return if status.success() { Ok(()) } else { Err("the performance gate failed".to_string()) }
+    }
+
+    let options = parse_options(root, args)?;
+    let corpus = load_corpus(options.corpus.as_deref())?;
+    let timings = measure(&corpus, options.runs);
+    println!("corpus: {} blocks of {} bytes, median of {} runs", timings.corpus_blocks, BLOCK_SIZE, options.runs);
+    println!("native library: {:>12.0} blocks/s", timings.c_blocks_per_sec);
+    println!("pure Rust:      {:>12.0} blocks/s ({:.2}x the native library)",
+        timings.rust_blocks_per_sec, timings.rust_blocks_per_sec / timings.c_blocks_per_sec);
+
+    if options.save {
+        if let Some(directory) = options.baseline.parent() {
+            std::fs::create_dir_all(directory).map_err(|err| err.to_string())?;
+        }
+        std::fs::write(&options.baseline, timings.to_json()).map_err(|err| err.to_string())?;
+        println!("wrote {}", options.baseline.display());
+        return Ok(())
+    }
+
+    check_against_baseline(&options, &timings)
+}
+
+/// Compares `timings` with the baseline named by `options`
+///
+/// # Errors
+/// If there is no valid baseline, if it was recorded over a corpus of a different size,
+/// or if the pure Rust throughput fell more than the tolerance below it
+pub fn check_against_baseline(options: &Options, timings: &Timings) -> Result<(), String> {
+    let json = std::fs::read_to_string(&options.baseline)
+        .map_err(|err| format!("no baseline at {} ({}); pass --save to record one", options.baseline.display(), err))?;
+    let baseline = Timings::from_json(&json)
+        .ok_or_else(|| format!("{} is not a valid baseline", options.baseline.display()))?;
+    if baseline.corpus_blocks != timings.corpus_blocks {
+        return Err(format!("the baseline was recorded over a corpus of {} blocks, not {}; pass --save to record a new one",
+            baseline.corpus_blocks, timings.corpus_blocks))
+    }
+    let change = timings.rust_blocks_per_sec / baseline.rust_blocks_per_sec - 1.0;
+    println!("pure Rust change from baseline: {:+.1}% (tolerance -{:.1}%)", 100.0 * change, 100.0 * options.tolerance);
+    if change < -options.tolerance {
+        return Err(format!("the pure Rust implementation regressed from {:.0} to {:.0} blocks/s",
+            baseline.rust_blocks_per_sec, timings.rust_blocks_per_sec))
+    }
+    Ok(())
+}
+
+/// Parses the arguments following the name of the `perf` task, with the baseline under `root` by default
+///
+/// # Errors
+/// For an unknown argument, a missing or malformed value, or no runs
+pub fn parse_options(root: &Path, args: &[String]) -> Result<Options, String> {
+    let mut options = Options {
+        save: false,
+        tolerance: 0.1,
+        runs: 15,
+        corpus: None,
+        baseline: root.join("target/perf/baseline.json"),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("{} requires a value", arg));
+        match arg.as_str() {
+            "--save" => options.save = true,
+            "--tolerance" => options.tolerance = value()?.parse().map_err(|_| "--tolerance must be a non-negative number")?,
+            "--runs" => options.runs = value()?.parse().map_err(|_| "--runs must be a positive integer")?,
+            "--corpus" => options.corpus = Some(PathBuf::from(value()?)),
+            "--baseline" => options.baseline = PathBuf::from(value()?),
+            _ => return Err(format!("unknown argument to perf: {}", arg)),
+        }
+    }
+    if options.runs == 0 {
+        return Err("--runs must be a positive integer".to_string())
+    }
+    // a NaN tolerance would never compare below the change, silently disabling the gate
+    if options.tolerance.is_nan() || options.tolerance < 0.0 {
+        return Err("--tolerance must be a non-negative number".to_string())
+    }
+    Ok(options)
+}
+
+/// Loads the blocks of the corpus from `file`, or generates the default pseudorandom corpus without one
+fn load_corpus(file: Option<&Path>) -> Result<Vec<[u8; BLOCK_SIZE]>, String> {
+    let Some(file) = file else {
+        let mut corpus = vec![[0u8; BLOCK_SIZE]; DEFAULT_CORPUS_BLOCKS];
+        fill_from_seed(DEFAULT_CORPUS_SEED, corpus.as_flattened_mut());
+        return Ok(corpus)
+    };
+    let bytes = std::fs::read(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+    if bytes.is_empty() || bytes.len() % BLOCK_SIZE != 0 {
+        return Err(format!("{}: a corpus must be a non-empty whole number of {}-byte blocks", file.display(), BLOCK_SIZE))
+    }
+    Ok(bytes.chunks_exact(BLOCK_SIZE).map(|block| block.try_into().unwrap()).collect())
+}
+
+/// Times both implementations over the corpus, after one warm-up run each
+fn measure(corpus: &[[u8; BLOCK_SIZE]], runs: usize) -> Timings {
+    let inputs: Vec<Input> = corpus.iter().map(|block| AlignedBuffer([*block])).collect();
+    let mut outputs: Vec<Output> = (0..corpus.len()).map(|_| Output::default()).collect();
+    let c_blocks_per_sec = median_blocks_per_sec(corpus.len(), runs, || {
+        compute_slice(black_box(&inputs), &mut outputs).unwrap();
+        black_box(&outputs);
+    });
+
+    // the pure Rust implementation takes inputs of half a block
+    let rust_blocks_per_sec = median_blocks_per_sec(corpus.len(), runs, || {
+        for half in black_box(corpus).as_flattened().chunks_exact(SWIFFT_INPUT_BLOCK_SIZE) {
+            black_box(swifft_hash(&parse_input_block(half.try_into().unwrap())));
+        }
+    });
+
+    Timings { corpus_blocks: corpus.len(), c_blocks_per_sec, rust_blocks_per_sec }
+}
+
+/// The median throughput of `runs` runs of `hash`, which hashes `blocks` blocks,
+/// as the median is robust against the outliers of noisy machines
+fn median_blocks_per_sec(blocks: usize, runs: usize, mut hash: impl FnMut()) -> f64 {
+    hash();
+    let mut rates: Vec<f64> = (0..runs).map(|_| {
+        let start = Instant::now();
+        hash();
+        blocks as f64 / start.elapsed().as_secs_f64()
+    }).collect();
+    rates.sort_by(f64::total_cmp);
+    rates[runs / 2]
+}
+
+impl Timings {
+    /// Formats the timings as the JSON of a baseline
+    pub fn to_json(&self) -> String {
+        format!("{{\n  \"corpus_blocks\": {},\n  \"c_blocks_per_sec\": {:.1},\n  \"rust_blocks_per_sec\": {:.1}\n}}\n",
+            self.corpus_blocks, self.c_blocks_per_sec, self.rust_blocks_per_sec)
+    }
+
+    /// Parses a baseline as written by [`Timings::to_json`]
+    pub fn from_json(json: &str) -> Option<Self> {
+        let field = |key: &str| -> Option<&str> {
+            let start = json.find(&format!("\"{}\":", key))? + key.len() + 3;
+            let value = &json[start..];
+            Some(value[..value.find([',', '}', '\n']).unwrap_or(value.len())].trim())
+        };
+        Some(Self {
+            corpus_blocks: field("corpus_blocks")?.parse().ok()?,
+            c_blocks_per_sec: field("c_blocks_per_sec")?.parse().ok()?,
+            rust_blocks_per_sec: field("rust_blocks_per_sec")?.parse().ok()?,
+        })
+    }
+}
@@ -0,0 +1,31 @@
+//! Addition of fuzzing artifacts to the regression corpus under `libswifft/tests/regressions/`
+
+use std::ffi::OsString;
+use std::path::Path;
+
+/// The extension appended to the name of an input for its optional metadata sidecar
+pub const METADATA_EXTENSION: &str = "meta";
+
+/// Copies the fuzzing artifact at `file` verbatim into the regression corpus under its own name,
+/// with its metadata in a sidecar `<name>.meta`, refusing to overwrite an existing regression of the same name
+pub fn add(root: &Path, file: &Path) -> Result<(), String> {
+    let input = std::fs::read(file).map_err(|err| format!("{}: {}", file.display(), err))?;
+    let name = file.file_name().ok_or_else(|| format!("{} is not a file", file.display()))?;
+    if Path::new(name).extension().is_some_and(|extension| extension == METADATA_EXTENSION) {
+        return Err(format!("{}: the corpus reads `.{}` files as metadata", file.display(), METADATA_EXTENSION))
+    }
+    let directory = root.join("libswifft/tests/regressions");
+    // appended rather than set with `with_extension`, which would replace anything after a dot in the name
+    let mut metadata_name = OsString::from(name);
+    metadata_name.push(format!(".{}", METADATA_EXTENSION));
+    let (path, metadata_path) = (directory.join(name), directory.join(metadata_name));
+    if path.exists() || metadata_path.exists() {
+        return Err(format!("{} already exists", path.display()))
+    }
+
+    let metadata = format!("source: {}\nlength: {} bytes\n", name.to_string_lossy(), input.len());
+    std::fs::write(&path, &input).map_err(|err| err.to_string())?;
+    std::fs::write(&metadata_path, metadata).map_err(|err| err.to_string())?;
+    println!("wrote {} and {}", path.display(), metadata_path.display());
+    Ok(())
+}
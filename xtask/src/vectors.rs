@@ -0,0 +1,103 @@
+//! Generation of the test vectors that pin the output of every hashing mode across releases
+
+use std::fmt::Write;
+use std::path::Path;
+
+use libswifft::buffer::{fill_from_seed, CompactOutput, Input, InputBuilder, Output, Pattern};
+use libswifft::hash::{compact, compute, compute_signed};
+use swifft::hash::{parse_input_block, swifft_hash, INPUT_BLOCK_SIZE};
+
+/// The header line of every vectors file
+pub const HEADER: &str = "mode,input,sign,output";
+
+/// Writes the vectors files, refusing to overwrite existing ones unless `force` is set,
+/// so that accidental algorithm changes surface as test failures rather than silent regeneration
+pub fn generate(root: &Path, force: bool) -> Result<(), String> {
+    // the vectors of the native library are embedded in `libswifft`, so they live inside its package
+    for (directory, name, contents) in [
+        ("libswifft/vectors", "libswifft.csv", libswifft_vectors()),
+        ("vectors", "swifft.csv", swifft_vectors()),
+    ] {
+        let directory = root.join(directory);
+        std::fs::create_dir_all(&directory).map_err(|err| err.to_string())?;
+        let path = directory.join(name);
+        if path.exists() && !force {
+            return Err(format!("{} already exists; pass --force to deliberately regenerate it", path.display()))
+        }
+        write_atomically(&path, contents.as_bytes()).map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Writes `contents` to a temporary file next to `path` and renames it over `path`,
+/// so that a failed or interrupted `--force` never leaves a truncated vectors file behind
+fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    let temporary = path.with_file_name(name);
+    let written = std::fs::write(&temporary, contents).and_then(|()| std::fs::rename(&temporary, path));
+    if written.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    written
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::with_capacity(2 * bytes.len()), |mut hex, byte| {
+        write!(hex, "{:02x}", byte).unwrap();
+        hex
+    })
+}
+
+fn libswifft_inputs() -> Vec<Input> {
+    let mut inputs = vec![
+        InputBuilder::new().fill_pattern(Pattern::Zeros).build(),
+        InputBuilder::new().fill_pattern(Pattern::Ones).build(),
+        InputBuilder::new().fill_pattern(Pattern::Alternating).build(),
+        InputBuilder::new().fill_pattern(Pattern::Counter).build(),
+    ];
+    // the single-bit vectors, in an order that `libswifft/tests/bits.rs` relies on
+    for bit in [0, 1, 63, 64, 1000, 2047] {
+        inputs.push(InputBuilder::new().set_byte(bit / 8, 1 << (bit % 8)).build());
+    }
+    inputs.extend((1..=4).map(Input::from_seed));
+    inputs
+}
+
+fn libswifft_vectors() -> String {
+    let mut lines = vec![HEADER.to_string()];
+    let inputs = libswifft_inputs();
+    for input in &inputs {
+        let mut output = Output::default();
+        compute(input, &mut output);
+        let mut compact_output = CompactOutput::default();
+        compact(&output, &mut compact_output);
+        lines.push(format!("compute,{},,{:x}", hex(&input.0[0]), output));
+        lines.push(format!("compact,{},,{:x}", hex(&input.0[0]), compact_output));
+    }
+    for (i, input) in inputs.iter().enumerate() {
+        let sign = &inputs[(i + 1) % inputs.len()];
+        let mut output = Output::default();
+        compute_signed(input, sign, &mut output);
+        let mut compact_output = CompactOutput::default();
+        compact(&output, &mut compact_output);
+        lines.push(format!("compute_signed,{},{},{:x}", hex(&input.0[0]), hex(&sign.0[0]), output));
+        lines.push(format!("compact_signed,{},{},{:x}", hex(&input.0[0]), hex(&sign.0[0]), compact_output));
+    }
+    lines.join("\n") + "\n"
+}
+
+fn swifft_vectors() -> String {
+    let mut lines = vec![HEADER.to_string()];
+    let mut blocks = vec![[0x00; INPUT_BLOCK_SIZE], [0xFF; INPUT_BLOCK_SIZE], [0x55; INPUT_BLOCK_SIZE]];
+    for seed in 1..=4 {
+        let mut block = [0u8; INPUT_BLOCK_SIZE];
+        fill_from_seed(seed, &mut block);
+        blocks.push(block);
+    }
+    for block in &blocks {
+        lines.push(format!("swifft_hash,{},,{}", hex(block), swifft_hash(&parse_input_block(block)).to_hex()));
+    }
+    lines.join("\n") + "\n"
+}
@@ -0,0 +1,71 @@
+//! The public API that the `api-check` task finds by scanning the sources of a crate
+
+use std::path::PathBuf;
+
+use xtask::api::public_api;
+
+/// A crate under the temporary directory, unique to `name`, with the sources `files` relative to its `src`
+fn sources(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let src = std::env::temp_dir().join(format!("xtask-api-{}-{}", std::process::id(), name)).join("src");
+    for (file, source) in files {
+        let path = src.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, source).unwrap();
+    }
+    src
+}
+
+#[test]
+fn impls_in_private_modules_are_included() {
+    let src = sources("private-modules", &[
+        ("lib.rs", "pub mod buffer;\n"),
+        ("buffer.rs", "mod serde;\npub(crate) mod random;\npub struct Buffer;\n"),
+        ("buffer/serde.rs", "\
+use crate::buffer::Buffer;
+
+impl Serialize for Buffer {
+    fn serialize(&self) {}
+}
+
+impl Buffer {
+    pub fn to_hex(&self) -> String {
+        String::new()
+    }
+}
+
+pub fn helper() {}
+"),
+        ("buffer/random.rs", "impl Distribution<Buffer> for Standard {\n}\n"),
+    ]);
+    assert_eq!(public_api(&src, "example").unwrap(), [
+        "example: pub mod buffer",
+        "example::buffer: pub struct Buffer",
+        "example::buffer::serde: impl Serialize for Buffer",
+        "example::buffer::serde: impl Buffer: pub fn to_hex(&self) -> String",
+        "example::buffer::random: impl Distribution<Buffer> for Standard",
+    ]);
+}
+
+#[test]
+fn impls_of_private_types_are_left_out() {
+    let src = sources("private-types", &[("lib.rs", "\
+pub struct Public;
+struct Private<'a>(&'a [u8]);
+pub(crate) enum Internal {}
+
+impl Debug for Public {}
+impl Debug for Private<'_> {}
+impl<'a> Private<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+}
+unsafe impl Send for Internal {}
+impl From<Public> for u32 {}
+")]);
+    assert_eq!(public_api(&src, "example").unwrap(), [
+        "example: pub struct Public",
+        "example: impl Debug for Public",
+        "example: impl From<Public> for u32",
+    ]);
+}
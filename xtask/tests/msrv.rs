@@ -0,0 +1,42 @@
+//! The features that the `check-msrv` task enables, read from the manifests of the workspace
+
+use xtask::msrv::stable_features;
+
+#[test]
+fn every_stable_feature_of_libswifft_is_checked() {
+    let features = stable_features(include_str!("../../libswifft/Cargo.toml"));
+    for feature in ["analysis", "bitvec", "bytemuck", "digest", "rand", "serde", "test-doubles", "zeroize"] {
+        assert!(features.iter().any(|name| name == feature), "{} is not checked", feature);
+    }
+    assert!(!features.iter().any(|name| name == "allocator_api"));
+    assert!(!features.iter().any(|name| name == "default"));
+}
+
+#[test]
+fn every_feature_of_swifft_is_checked() {
+    let features = stable_features(include_str!("../../swifft-rs/Cargo.toml"));
+    for feature in ["analysis", "bitvec", "ct", "experimental", "no-panic", "serde"] {
+        assert!(features.iter().any(|name| name == feature), "{} is not checked", feature);
+    }
+}
+
+#[test]
+fn only_features_marked_nightly_are_skipped() {
+    let manifest = "\
+[package]
+name = \"example\"
+
+[features]
+default = [\"a\"]
+# requires a nightly toolchain
+nightly = []
+# a documented feature
+a = []
+
+b = [\"dep:b\"]
+
+[dependencies]
+c = \"1\"
+";
+    assert_eq!(stable_features(manifest), ["a", "b"]);
+}
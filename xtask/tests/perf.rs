@@ -0,0 +1,96 @@
+//! Parsing of the arguments of the `perf` task, and its baselines
+
+use std::path::{Path, PathBuf};
+
+use xtask::perf::{check_against_baseline, parse_options, Options, Timings};
+
+fn parse(args: &[&str]) -> Result<Options, String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    parse_options(Path::new("/workspace"), &args)
+}
+
+fn timings(rust_blocks_per_sec: f64) -> Timings {
+    Timings { corpus_blocks: 4096, c_blocks_per_sec: 123456.7, rust_blocks_per_sec }
+}
+
+/// A baseline file of `timings` under the temporary directory, unique to `name`
+fn baseline(name: &str, timings: &Timings) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("xtask-perf-{}-{}.json", std::process::id(), name));
+    std::fs::write(&path, timings.to_json()).unwrap();
+    path
+}
+
+fn options(baseline: PathBuf) -> Options {
+    Options { baseline, ..parse(&[]).unwrap() }
+}
+
+#[test]
+fn defaults() {
+    assert_eq!(parse(&[]).unwrap(), Options {
+        save: false,
+        tolerance: 0.1,
+        runs: 15,
+        corpus: None,
+        baseline: PathBuf::from("/workspace/target/perf/baseline.json"),
+    });
+}
+
+#[test]
+fn every_option_is_parsed() {
+    let options = parse(&["--save", "--tolerance", "0.25", "--runs", "3", "--corpus", "blocks.bin", "--baseline", "base.json"]).unwrap();
+    assert_eq!(options, Options {
+        save: true,
+        tolerance: 0.25,
+        runs: 3,
+        corpus: Some(PathBuf::from("blocks.bin")),
+        baseline: PathBuf::from("base.json"),
+    });
+}
+
+#[test]
+fn malformed_arguments_are_rejected() {
+    assert_eq!(parse(&["--fast"]).unwrap_err(), "unknown argument to perf: --fast");
+    assert_eq!(parse(&["--runs"]).unwrap_err(), "--runs requires a value");
+    assert_eq!(parse(&["--runs", "0"]).unwrap_err(), "--runs must be a positive integer");
+    assert_eq!(parse(&["--runs", "-1"]).unwrap_err(), "--runs must be a positive integer");
+    for tolerance in ["ten", "-0.1", "NaN", "-inf"] {
+        assert_eq!(parse(&["--tolerance", tolerance]).unwrap_err(), "--tolerance must be a non-negative number");
+    }
+    assert_eq!(parse(&["--tolerance", "0"]).unwrap().tolerance, 0.0);
+}
+
+#[test]
+fn timings_round_trip_through_json() {
+    let timings = timings(98765.4);
+    assert_eq!(Timings::from_json(&timings.to_json()), Some(timings));
+}
+
+#[test]
+fn malformed_baselines_are_rejected() {
+    assert_eq!(Timings::from_json(""), None);
+    assert_eq!(Timings::from_json("{ \"corpus_blocks\": 4096, \"c_blocks_per_sec\": 1.0 }"), None);
+    assert_eq!(Timings::from_json("{ \"corpus_blocks\": -1, \"c_blocks_per_sec\": 1.0, \"rust_blocks_per_sec\": 1.0 }"), None);
+}
+
+#[test]
+fn a_missing_baseline_fails_the_gate() {
+    let options = options(std::env::temp_dir().join("xtask-perf-missing/baseline.json"));
+    let error = check_against_baseline(&options, &timings(1000.0)).unwrap_err();
+    assert!(error.starts_with("no baseline at "), "{}", error);
+}
+
+#[test]
+fn the_gate_allows_changes_within_the_tolerance() {
+    let path = baseline("tolerance", &timings(1000.0));
+    assert_eq!(check_against_baseline(&options(path.clone()), &timings(950.0)), Ok(()));
+    assert_eq!(check_against_baseline(&options(path.clone()), &timings(1500.0)), Ok(()));
+    assert!(check_against_baseline(&options(path.clone()), &timings(850.0)).unwrap_err().contains("regressed"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn baselines_of_other_corpora_are_rejected() {
+    let path = baseline("corpus", &Timings { corpus_blocks: 16, ..timings(1000.0) });
+    assert!(check_against_baseline(&options(path.clone()), &timings(1000.0)).unwrap_err().contains("corpus of 16 blocks"));
+    std::fs::remove_file(path).unwrap();
+}
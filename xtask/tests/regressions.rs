@@ -0,0 +1,35 @@
+//! Adding fuzzing artifacts to the regression corpus
+
+use std::path::PathBuf;
+
+use xtask::regressions::add;
+
+/// A workspace root under the temporary directory, unique to `name`, with an empty regression corpus
+fn root(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("xtask-regressions-{}-{}", std::process::id(), name));
+    std::fs::create_dir_all(root.join("libswifft/tests/regressions")).unwrap();
+    root
+}
+
+#[test]
+fn artifacts_are_copied_verbatim_under_their_own_name() {
+    let root = root("verbatim");
+    let artifact = root.join("crash-1.2");
+    std::fs::write(&artifact, b"# not a header").unwrap();
+    add(&root, &artifact).unwrap();
+
+    let corpus = root.join("libswifft/tests/regressions");
+    assert_eq!(std::fs::read(corpus.join("crash-1.2")).unwrap(), b"# not a header");
+    let metadata = std::fs::read_to_string(corpus.join("crash-1.2.meta")).unwrap();
+    assert_eq!(metadata, "source: crash-1.2\nlength: 14 bytes\n");
+    assert!(add(&root, &artifact).unwrap_err().ends_with("already exists"));
+}
+
+#[test]
+fn artifacts_named_as_metadata_are_refused() {
+    let root = root("metadata");
+    let artifact = root.join("crash.meta");
+    std::fs::write(&artifact, b"").unwrap();
+    assert!(add(&root, &artifact).is_err());
+    assert!(!root.join("libswifft/tests/regressions/crash.meta").exists());
+}